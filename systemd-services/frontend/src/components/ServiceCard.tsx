@@ -5,7 +5,7 @@ import { Play, Square, RotateCw, ScrollText } from "lucide-react"
 
 interface ServiceCardProps {
   name: string
-  status: "running" | "stopped" | "failed"
+  status: "running" | "stopped" | "failed" | "missing" | "masked"
   uptime?: string
   onStart: () => void
   onStop: () => void
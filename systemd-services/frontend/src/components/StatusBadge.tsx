@@ -1,6 +1,6 @@
 import { Badge } from "@/components/ui/badge"
 
-type Status = "running" | "stopped" | "failed"
+type Status = "running" | "stopped" | "failed" | "missing" | "masked"
 
 const statusConfig: Record<Status, { label: string; className: string }> = {
   running: {
@@ -15,6 +15,14 @@ const statusConfig: Record<Status, { label: string; className: string }> = {
     label: "Failed",
     className: "bg-red-500/10 text-red-600 dark:text-red-400 border-red-500/20",
   },
+  missing: {
+    label: "Unit missing",
+    className: "bg-amber-500/10 text-amber-600 dark:text-amber-400 border-amber-500/20",
+  },
+  masked: {
+    label: "Masked",
+    className: "bg-muted text-muted-foreground",
+  },
 }
 
 interface StatusBadgeProps {
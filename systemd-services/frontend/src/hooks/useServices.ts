@@ -6,7 +6,9 @@ export function useServices(api: PluginApi, refreshInterval = 30000) {
   const [loading, setLoading] = useState(true)
   const [error, setError] = useState<string | null>(null)
 
-  const fetchServices = useCallback(async () => {
+  // Resolves to the delay before the next poll: the server's X-Refresh-After
+  // hint when present, otherwise refreshInterval
+  const fetchServices = useCallback(async (): Promise<number> => {
     try {
       setError(null)
       const response = await api.fetch("/services")
@@ -15,19 +17,32 @@ export function useServices(api: PluginApi, refreshInterval = 30000) {
       }
       const data = await response.json()
       setServices(data)
+      const hint = Number(response.headers.get("X-Refresh-After"))
+      return hint > 0 ? hint * 1000 : refreshInterval
     } catch (err) {
       setError(err instanceof Error ? err.message : "Failed to fetch services")
       console.error("Error fetching services:", err)
+      return refreshInterval
     } finally {
       setLoading(false)
     }
-  }, [api])
+  }, [api, refreshInterval])
 
   useEffect(() => {
-    fetchServices()
-    const interval = setInterval(fetchServices, refreshInterval)
-    return () => clearInterval(interval)
-  }, [fetchServices, refreshInterval])
+    let timeout: ReturnType<typeof setTimeout> | undefined
+    let cancelled = false
+    const poll = async () => {
+      const delay = await fetchServices()
+      if (!cancelled) {
+        timeout = setTimeout(poll, delay)
+      }
+    }
+    poll()
+    return () => {
+      cancelled = true
+      clearTimeout(timeout)
+    }
+  }, [fetchServices])
 
   const controlService = async (
     serviceName: string,
@@ -40,7 +55,15 @@ export function useServices(api: PluginApi, refreshInterval = 30000) {
       if (!response.ok) {
         throw new Error(`Failed to ${action} service: ${response.statusText}`)
       }
-      await fetchServices()
+      // The action response carries the service's new status; fall back to a
+      // full refresh when it couldn't be read
+      const result = await response.json()
+      const status: ServiceInfo | null = result.data?.status ?? null
+      if (status) {
+        setServices((prev) => prev.map((s) => (s.name === status.name ? status : s)))
+      } else {
+        await fetchServices()
+      }
     } catch (err) {
       console.error(`Error ${action}ing service:`, err)
       throw err
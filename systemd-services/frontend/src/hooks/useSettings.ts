@@ -1,5 +1,27 @@
 import { useState, useEffect, useCallback } from "react"
-import type { PluginApi, AvailableService } from "@/types"
+import type { PluginApi, AvailableService, PluginSettings } from "@/types"
+
+// Plugin settings live in one JSON object; older installs only have the
+// per-setting keys, which the backend also falls back to
+const SETTINGS_KEY = "settings"
+const LEGACY_KEYS = ["watched_services", "read_only", "debug_mode", "action_cooldown_ms"] as const
+
+async function loadSettings(api: PluginApi): Promise<PluginSettings> {
+  const stored = await api.kv.get(SETTINGS_KEY)
+  if (stored) {
+    return JSON.parse(stored)
+  }
+
+  // Carry every legacy setting over so the first save doesn't drop any
+  const settings: PluginSettings = {}
+  for (const key of LEGACY_KEYS) {
+    const value = await api.kv.get(key)
+    if (value) {
+      Object.assign(settings, { [key]: JSON.parse(value) })
+    }
+  }
+  return settings
+}
 
 export function useSettings(api: PluginApi) {
   const [availableServices, setAvailableServices] = useState<AvailableService[]>([])
@@ -24,12 +46,8 @@ export function useSettings(api: PluginApi) {
 
   const fetchWatchedServices = useCallback(async () => {
     try {
-      const stored = await api.kv.get("watched_services")
-      if (stored) {
-        setWatchedServices(JSON.parse(stored))
-      } else {
-        setWatchedServices([])
-      }
+      const settings = await loadSettings(api)
+      setWatchedServices(settings.watched_services ?? [])
     } catch (err) {
       console.error("Error fetching watched services:", err)
       setWatchedServices([])
@@ -44,7 +62,8 @@ export function useSettings(api: PluginApi) {
 
   const saveWatchedServices = async (services: string[]) => {
     try {
-      await api.kv.set("watched_services", JSON.stringify(services))
+      const settings = await loadSettings(api)
+      await api.kv.set(SETTINGS_KEY, JSON.stringify({ ...settings, watched_services: services }))
       setWatchedServices(services)
     } catch (err) {
       console.error("Error saving watched services:", err)
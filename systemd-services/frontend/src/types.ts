@@ -8,7 +8,7 @@ export interface PluginApi {
 
 export interface ServiceInfo {
   name: string
-  status: "running" | "stopped" | "failed"
+  status: "running" | "stopped" | "failed" | "missing" | "masked"
   uptime: string | null
   pid: number | null
 }
@@ -17,3 +17,10 @@ export interface AvailableService {
   name: string
   description: string
 }
+
+export interface PluginSettings {
+  watched_services?: string[]
+  read_only?: boolean
+  debug_mode?: boolean
+  action_cooldown_ms?: number
+}
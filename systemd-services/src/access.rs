@@ -0,0 +1,149 @@
+// Unit access policy - restricts which units this plugin may manage
+
+use crate::error::Result;
+use crate::settings::PluginSettings;
+use toru_plugin_api::PluginKvStore;
+
+/// Allowlist/denylist of unit name patterns (`*` and `?` wildcards)
+///
+/// The denylist always takes precedence. An empty allowlist permits every
+/// unit that is not denied.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UnitAccessPolicy {
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+}
+
+impl UnitAccessPolicy {
+    /// Loads the policy from the plugin settings (empty lists mean "no restriction")
+    pub async fn load(kv_store: &dyn PluginKvStore) -> Result<Self> {
+        Ok(Self::from_settings(&PluginSettings::load(kv_store).await?))
+    }
+
+    /// Builds the policy from already loaded settings
+    pub fn from_settings(settings: &PluginSettings) -> Self {
+        Self {
+            allow: settings.allowed_units.clone(),
+            deny: settings.denied_units.clone(),
+        }
+    }
+
+    /// Checks whether a unit may be managed, returning the rejection reason if not
+    pub fn check(&self, unit: &str) -> std::result::Result<(), String> {
+        if let Some(pattern) = self.deny.iter().find(|p| glob_match(p, unit)) {
+            return Err(format!(
+                "Unit {} is denied by the plugin's denylist (pattern '{}')",
+                unit, pattern
+            ));
+        }
+
+        if !self.allow.is_empty() && !self.allow.iter().any(|p| glob_match(p, unit)) {
+            return Err(format!("Unit {} is not in the plugin's allowlist", unit));
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether a manager-wide operation (e.g. daemon-reload) may run
+    ///
+    /// Such operations affect every unit at once, including denied ones, so
+    /// they are only permitted while the policy restricts nothing.
+    pub fn check_system_wide(&self, operation: &str) -> std::result::Result<(), String> {
+        if self.allow.is_empty() && self.deny.is_empty() {
+            return Ok(());
+        }
+
+        Err(format!(
+            "{} affects all units and is disabled while an allowlist or denylist is configured",
+            operation
+        ))
+    }
+}
+
+/// Matches a unit name against a glob pattern supporting `*` and `?`
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` in the pattern and the name index it was tried at
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            // Let the last `*` swallow one more character and retry
+            p = star + 1;
+            n = matched + 1;
+            backtrack = Some((star, n));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("nginx.service", "nginx.service"));
+        assert!(glob_match("*.service", "nginx.service"));
+        assert!(glob_match("chfscraper-*", "chfscraper-scrape-bcp.service"));
+        assert!(glob_match("getty@tty?.service", "getty@tty1.service"));
+        assert!(glob_match("*", "anything.service"));
+
+        assert!(!glob_match("*.timer", "nginx.service"));
+        assert!(!glob_match("nginx", "nginx.service"));
+        assert!(!glob_match("getty@tty?.service", "getty@tty10.service"));
+    }
+
+    #[test]
+    fn test_empty_policy_permits_everything() {
+        let policy = UnitAccessPolicy::default();
+        assert!(policy.check("nginx.service").is_ok());
+    }
+
+    #[test]
+    fn test_denylist_takes_precedence_over_allowlist() {
+        let policy = UnitAccessPolicy {
+            allow: vec!["*.service".to_string()],
+            deny: vec!["sshd.service".to_string()],
+        };
+
+        assert!(policy.check("nginx.service").is_ok());
+        let reason = policy.check("sshd.service").unwrap_err();
+        assert!(reason.contains("denylist"));
+    }
+
+    #[test]
+    fn test_allowlist_rejects_unlisted_units() {
+        let policy = UnitAccessPolicy {
+            allow: vec!["app-*.service".to_string()],
+            deny: Vec::new(),
+        };
+
+        assert!(policy.check("app-web.service").is_ok());
+        let reason = policy.check("postgresql.service").unwrap_err();
+        assert!(reason.contains("allowlist"));
+    }
+
+    #[test]
+    fn test_system_wide_operations_need_unrestricted_policy() {
+        assert!(UnitAccessPolicy::default().check_system_wide("daemon-reload").is_ok());
+
+        let policy = UnitAccessPolicy {
+            allow: Vec::new(),
+            deny: vec!["sshd.service".to_string()],
+        };
+        assert!(policy.check_system_wide("daemon-reload").is_err());
+    }
+}
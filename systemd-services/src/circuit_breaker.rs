@@ -0,0 +1,174 @@
+// Circuit breaker - stops querying a unit whose status keeps failing
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Consecutive failed status queries that open a unit's breaker
+pub const FAILURE_THRESHOLD: u32 = 3;
+
+/// How long an open breaker skips the unit before trying it again
+pub const BREAKER_COOLDOWN: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Default)]
+struct UnitBreaker {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+/// Per-unit circuit breaker for status queries
+///
+/// After `FAILURE_THRESHOLD` consecutive failures (e.g. D-Bus timeouts) the
+/// unit is reported unavailable without being queried until the cooldown
+/// has passed. The next query is then let through: success closes the
+/// breaker, another failure opens it for a further cooldown.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    units: Mutex<HashMap<String, UnitBreaker>>,
+    /// Checks answered from an open breaker, i.e. the cached unavailable status
+    hits: AtomicU64,
+    /// Checks that let the query through to systemd
+    misses: AtomicU64,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::with_settings(FAILURE_THRESHOLD, BREAKER_COOLDOWN)
+    }
+}
+
+impl CircuitBreaker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a breaker with a custom threshold and cooldown
+    pub fn with_settings(threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            threshold: threshold.max(1),
+            cooldown,
+            units: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns true if the unit may be queried now
+    pub fn allow(&self, unit: &str) -> bool {
+        self.allow_at(unit, Instant::now())
+    }
+
+    /// Same as `allow` with an explicit clock reading
+    pub fn allow_at(&self, unit: &str, now: Instant) -> bool {
+        let units = self.units.lock().unwrap_or_else(|e| e.into_inner());
+
+        let allowed = match units.get(unit).and_then(|breaker| breaker.open_until) {
+            Some(open_until) => now >= open_until,
+            None => true,
+        };
+
+        let counter = if allowed { &self.misses } else { &self.hits };
+        counter.fetch_add(1, Ordering::Relaxed);
+        allowed
+    }
+
+    /// Returns `(hits, misses)`: checks answered from an open breaker vs let through
+    pub fn hit_counts(&self) -> (u64, u64) {
+        (self.hits.load(Ordering::Relaxed), self.misses.load(Ordering::Relaxed))
+    }
+
+    /// Records a successful query, closing the unit's breaker
+    pub fn record_success(&self, unit: &str) {
+        let mut units = self.units.lock().unwrap_or_else(|e| e.into_inner());
+        units.remove(unit);
+    }
+
+    /// Records a failed query, opening the breaker once the threshold is reached
+    pub fn record_failure(&self, unit: &str) {
+        self.record_failure_at(unit, Instant::now())
+    }
+
+    /// Same as `record_failure` with an explicit clock reading
+    pub fn record_failure_at(&self, unit: &str, now: Instant) {
+        let mut units = self.units.lock().unwrap_or_else(|e| e.into_inner());
+        let breaker = units.entry(unit.to_string()).or_default();
+
+        breaker.consecutive_failures = breaker.consecutive_failures.saturating_add(1);
+        if breaker.consecutive_failures >= self.threshold {
+            breaker.open_until = Some(now + self.cooldown);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opens_after_threshold() {
+        let breaker = CircuitBreaker::new();
+        let now = Instant::now();
+
+        for _ in 0..FAILURE_THRESHOLD - 1 {
+            breaker.record_failure_at("nginx.service", now);
+            assert!(breaker.allow_at("nginx.service", now));
+        }
+
+        breaker.record_failure_at("nginx.service", now);
+        assert!(!breaker.allow_at("nginx.service", now));
+        assert!(breaker.allow_at("postgresql.service", now));
+    }
+
+    #[test]
+    fn test_retries_after_cooldown() {
+        let breaker = CircuitBreaker::new();
+        let now = Instant::now();
+
+        for _ in 0..FAILURE_THRESHOLD {
+            breaker.record_failure_at("nginx.service", now);
+        }
+        assert!(!breaker.allow_at("nginx.service", now + BREAKER_COOLDOWN - Duration::from_secs(1)));
+        assert!(breaker.allow_at("nginx.service", now + BREAKER_COOLDOWN));
+
+        // A failed retry opens it again straight away
+        let retry = now + BREAKER_COOLDOWN;
+        breaker.record_failure_at("nginx.service", retry);
+        assert!(!breaker.allow_at("nginx.service", retry + Duration::from_secs(1)));
+
+        // A successful one closes it
+        breaker.record_success("nginx.service");
+        assert!(breaker.allow_at("nginx.service", retry + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_success_resets_failure_count() {
+        let breaker = CircuitBreaker::new();
+        let now = Instant::now();
+
+        for _ in 0..FAILURE_THRESHOLD - 1 {
+            breaker.record_failure_at("nginx.service", now);
+        }
+        breaker.record_success("nginx.service");
+        breaker.record_failure_at("nginx.service", now);
+
+        assert!(breaker.allow_at("nginx.service", now));
+    }
+
+    #[test]
+    fn test_hit_counts() {
+        let breaker = CircuitBreaker::new();
+        let now = Instant::now();
+
+        for _ in 0..FAILURE_THRESHOLD {
+            breaker.record_failure_at("nginx.service", now);
+        }
+        assert!(!breaker.allow_at("nginx.service", now));
+        assert!(!breaker.allow_at("nginx.service", now));
+        assert!(breaker.allow_at("postgresql.service", now));
+
+        assert_eq!(breaker.hit_counts(), (2, 1));
+    }
+}
@@ -0,0 +1,63 @@
+// Clock abstraction - lets uptime and other time-dependent logic run against a pinned clock
+
+use chrono::{DateTime, Utc};
+
+/// Source of the current time
+///
+/// Uptimes are computed against both clocks systemd reports timestamps in:
+/// the wall clock and CLOCK_MONOTONIC.
+pub trait Clock: Send + Sync {
+    /// Current wall-clock time
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Current CLOCK_MONOTONIC reading in microseconds, when it is known
+    fn monotonic_usec(&self) -> Option<u64>;
+}
+
+/// The host's clocks
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    /// Reads the monotonic time from `/proc/uptime`, which also counts time
+    /// spent suspended like systemd's `*Monotonic` properties; None when unreadable
+    fn monotonic_usec(&self) -> Option<u64> {
+        let uptime = std::fs::read_to_string("/proc/uptime").ok()?;
+        let seconds: f64 = uptime.split_whitespace().next()?.parse().ok()?;
+        Some((seconds * 1_000_000.0) as u64)
+    }
+}
+
+/// A clock pinned to a given instant, for tests
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock {
+    now: DateTime<Utc>,
+    monotonic_usec: Option<u64>,
+}
+
+impl FixedClock {
+    /// Creates a clock reading `now` with no monotonic reading
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self { now, monotonic_usec: None }
+    }
+
+    /// Sets the CLOCK_MONOTONIC reading, in microseconds
+    pub fn with_monotonic_usec(mut self, usec: u64) -> Self {
+        self.monotonic_usec = Some(usec);
+        self
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.now
+    }
+
+    fn monotonic_usec(&self) -> Option<u64> {
+        self.monotonic_usec
+    }
+}
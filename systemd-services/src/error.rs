@@ -12,15 +12,42 @@ pub enum ServiceError {
     /// Permission denied (need root/sudo)
     PermissionDenied(String),
 
+    /// Unit is masked and can't be started until unmasked
+    Masked(String),
+
+    /// polkit wants interactive authentication (plugin lacks privileges)
+    AuthenticationRequired(String),
+
+    /// The unit's job failed because a unit it depends on failed
+    DependencyFailed(String),
+
     /// Invalid service name (potential injection attack)
     InvalidServiceName(String),
 
+    /// Property name not on the allowlist for `systemctl show --property`
+    InvalidProperty(String),
+
+    /// Journal namespace name that journalctl would reject (or that isn't safe to pass)
+    InvalidNamespace(String),
+
+    /// Unit state systemd doesn't know for `list-units --state=`
+    InvalidState(String),
+
+    /// Malformed journal cursor for `journalctl --after-cursor=`
+    InvalidCursor(String),
+
     /// Failed to parse systemctl/journalctl output
     ParseError(String),
 
+    /// Request body is oversized or not valid JSON (the client's fault)
+    BadRequest(String),
+
     /// Command execution timeout
     Timeout(String),
 
+    /// Command was cancelled before it finished (e.g. the client went away)
+    Cancelled(String),
+
     /// Command execution failed
     CommandFailed {
         command: String,
@@ -44,15 +71,42 @@ impl fmt::Display for ServiceError {
             ServiceError::PermissionDenied(msg) => {
                 write!(f, "Permission denied: {}", msg)
             }
+            ServiceError::Masked(name) => {
+                write!(f, "Unit {} is masked; unmask it before starting it", name)
+            }
+            ServiceError::AuthenticationRequired(msg) => {
+                write!(f, "Authentication required (run the plugin as root or add a polkit rule): {}", msg)
+            }
+            ServiceError::DependencyFailed(name) => {
+                write!(f, "A dependency of {} failed; check the dependency's logs", name)
+            }
             ServiceError::InvalidServiceName(name) => {
                 write!(f, "Invalid service name: {}", name)
             }
+            ServiceError::InvalidProperty(name) => {
+                write!(f, "Invalid property name: {}", name)
+            }
+            ServiceError::InvalidNamespace(name) => {
+                write!(f, "Invalid journal namespace: {}", name)
+            }
+            ServiceError::InvalidState(state) => {
+                write!(f, "Invalid unit state: {}", state)
+            }
+            ServiceError::InvalidCursor(cursor) => {
+                write!(f, "Invalid journal cursor: {}", cursor)
+            }
             ServiceError::ParseError(msg) => {
                 write!(f, "Failed to parse output: {}", msg)
             }
+            ServiceError::BadRequest(msg) => {
+                write!(f, "{}", msg)
+            }
             ServiceError::Timeout(msg) => {
                 write!(f, "Operation timed out: {}", msg)
             }
+            ServiceError::Cancelled(msg) => {
+                write!(f, "Operation cancelled: {}", msg)
+            }
             ServiceError::CommandFailed { command, exit_code, stderr } => {
                 write!(f, "Command '{}' failed with exit code {}: {}", command, exit_code, stderr)
             }
@@ -66,6 +120,19 @@ impl fmt::Display for ServiceError {
     }
 }
 
+impl ServiceError {
+    /// HTTP status for an error that reaches the client unhandled
+    ///
+    /// Only a bad request is the client's fault; a JSON failure anywhere else
+    /// (serializing a response, reading stored settings) is a bug and stays 500.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            ServiceError::BadRequest(_) => 400,
+            _ => 500,
+        }
+    }
+}
+
 impl std::error::Error for ServiceError {}
 
 impl From<std::io::Error> for ServiceError {
@@ -106,6 +173,19 @@ mod tests {
         assert!(err.to_string().contains("exit code 1"));
     }
 
+    #[test]
+    fn test_error_status_code() {
+        let err = ServiceError::BadRequest("Invalid request body: EOF".to_string());
+        assert_eq!(err.status_code(), 400);
+        assert_eq!(err.to_string(), "Invalid request body: EOF");
+
+        // serde_json errors converted implicitly come from the plugin's own data
+        let json_err = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+        let err: ServiceError = json_err.into();
+        assert!(matches!(err, ServiceError::ParseError(_)));
+        assert_eq!(err.status_code(), 500);
+    }
+
     #[test]
     fn test_error_from_io() {
         let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
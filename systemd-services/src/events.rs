@@ -0,0 +1,390 @@
+// Service state snapshots and change detection for the events long-poll
+
+use crate::clock::Clock;
+use crate::systemctl::CommandExecutor;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// Default time to wait for a change when `?wait=` is not given
+pub const DEFAULT_WAIT: Duration = Duration::from_secs(30);
+
+/// Upper bound on `?wait=` so a request can't hold the plugin indefinitely
+pub const MAX_WAIT: Duration = Duration::from_secs(60);
+
+/// Interval between internal state polls while clients are waiting
+pub const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Observed state of one service
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceState {
+    pub active_state: String,
+    pub sub_state: String,
+}
+
+/// State of every watched service at one point in time, keyed by name
+pub type ServiceSnapshot = BTreeMap<String, ServiceState>;
+
+/// A service whose state differs between two snapshots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateChange {
+    pub name: String,
+    /// None if the service was not watched in the earlier snapshot
+    pub previous: Option<ServiceState>,
+    /// None if the service is no longer watched
+    pub current: Option<ServiceState>,
+}
+
+/// Queries the current state of each service
+/// Services that can't be queried are recorded as "unknown", as in GET /services
+pub async fn take_snapshot<E: CommandExecutor>(
+    executor: Arc<E>,
+    clock: &dyn Clock,
+    services: &[String],
+) -> ServiceSnapshot {
+    let mut snapshot = ServiceSnapshot::new();
+
+    for service_name in services {
+        let state = match crate::systemctl::get_service_status(executor.clone(), service_name, clock).await {
+            Ok(status) => ServiceState {
+                active_state: status.active_state,
+                sub_state: status.sub_state,
+            },
+            Err(_) => ServiceState {
+                active_state: "unknown".to_string(),
+                sub_state: "unknown".to_string(),
+            },
+        };
+        snapshot.insert(service_name.clone(), state);
+    }
+
+    snapshot
+}
+
+/// Lists the services whose state differs between two snapshots, sorted by name
+pub fn diff_snapshots(previous: &ServiceSnapshot, current: &ServiceSnapshot) -> Vec<StateChange> {
+    let mut names: Vec<&String> = previous.keys().chain(current.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let before = previous.get(name);
+            let after = current.get(name);
+            (before != after).then(|| StateChange {
+                name: name.clone(),
+                previous: before.cloned(),
+                current: after.cloned(),
+            })
+        })
+        .collect()
+}
+
+/// Snapshots kept so a client's last-seen version can still be diffed against
+pub const HISTORY_LEN: usize = 32;
+
+/// Version identifying a snapshot: FNV-1a of each service's name and state,
+/// as 16 hex digits
+///
+/// Equal states give equal versions, across requests and restarts.
+pub fn snapshot_version(snapshot: &ServiceSnapshot) -> String {
+    const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    let mut hash = FNV_OFFSET;
+    for (name, state) in snapshot {
+        let line = format!("{}={}/{}\n", name, state.active_state, state.sub_state);
+        for byte in line.bytes() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    format!("{:016x}", hash)
+}
+
+/// Result of waiting for changes: the version to send back next time and
+/// what changed since the version the client had
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventsUpdate {
+    pub version: String,
+    pub changes: Vec<StateChange>,
+}
+
+#[derive(Debug, Default)]
+struct HubState {
+    services: Vec<String>,
+    polled_at: Option<Instant>,
+    version: String,
+    snapshot: ServiceSnapshot,
+    /// Recent (version, snapshot) pairs, oldest first
+    history: VecDeque<(String, ServiceSnapshot)>,
+}
+
+/// Shared state poller behind the events long-poll
+///
+/// All waiting clients read the same snapshot: systemd is polled at most once
+/// per poll interval however many are waiting. Recent snapshots are kept by
+/// version so a client that sends the version it last saw gets every change
+/// made since, including those made between its requests.
+#[derive(Debug)]
+pub struct EventHub {
+    poll_interval: Duration,
+    state: Mutex<HubState>,
+}
+
+impl Default for EventHub {
+    fn default() -> Self {
+        Self::with_poll_interval(POLL_INTERVAL)
+    }
+}
+
+impl EventHub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a hub polling at most once per `poll_interval`
+    pub fn with_poll_interval(poll_interval: Duration) -> Self {
+        Self {
+            poll_interval,
+            state: Mutex::new(HubState::default()),
+        }
+    }
+
+    /// Returns the current version and snapshot of `services`
+    ///
+    /// systemd is only queried if the last poll is older than the poll
+    /// interval or the watched services changed; callers arriving during a
+    /// poll wait for it and share its result.
+    pub async fn current<E: CommandExecutor>(
+        &self,
+        executor: Arc<E>,
+        clock: &dyn Clock,
+        services: &[String],
+    ) -> (String, ServiceSnapshot) {
+        let mut state = self.state.lock().await;
+
+        let fresh = state.services == services
+            && state.polled_at.is_some_and(|at| at.elapsed() < self.poll_interval);
+        if !fresh {
+            let snapshot = take_snapshot(executor, clock, services).await;
+            let version = snapshot_version(&snapshot);
+            if version != state.version {
+                state.history.push_back((version.clone(), snapshot.clone()));
+                if state.history.len() > HISTORY_LEN {
+                    state.history.pop_front();
+                }
+            }
+            state.services = services.to_vec();
+            state.polled_at = Some(Instant::now());
+            state.version = version;
+            state.snapshot = snapshot;
+        }
+
+        (state.version.clone(), state.snapshot.clone())
+    }
+
+    /// Snapshot recorded under `version`, if it is still kept
+    async fn snapshot_at(&self, version: &str) -> Option<ServiceSnapshot> {
+        let state = self.state.lock().await;
+        state
+            .history
+            .iter()
+            .rev()
+            .find(|(v, _)| v == version)
+            .map(|(_, snapshot)| snapshot.clone())
+    }
+
+    /// Waits until the state of `services` differs from version `since`, or
+    /// `wait` elapses
+    ///
+    /// Without `since` the state at the time of the call is the baseline.
+    /// A `since` that differs from the current version answers at once; if
+    /// that version is no longer kept every service is reported, with no
+    /// previous state. On timeout the change list is empty.
+    pub async fn wait_for_changes<E: CommandExecutor>(
+        &self,
+        executor: Arc<E>,
+        clock: &dyn Clock,
+        services: &[String],
+        since: Option<&str>,
+        wait: Duration,
+    ) -> EventsUpdate {
+        let deadline = Instant::now() + wait;
+        let (mut version, mut snapshot) = self.current(executor.clone(), clock, services).await;
+        let since = since.map(str::to_string).unwrap_or_else(|| version.clone());
+
+        loop {
+            if version != since {
+                let previous = self.snapshot_at(&since).await.unwrap_or_default();
+                let changes = diff_snapshots(&previous, &snapshot);
+                return EventsUpdate { version, changes };
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return EventsUpdate { version, changes: Vec::new() };
+            }
+
+            tokio::time::sleep(self.poll_interval.min(deadline - now)).await;
+            (version, snapshot) = self.current(executor.clone(), clock, services).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::SystemClock;
+    use crate::error::Result;
+    use crate::systemctl::CommandOutput;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Reports nginx as running for the first `running_polls` calls, then as failed
+    struct FlappingExecutor {
+        calls: AtomicUsize,
+        running_polls: usize,
+    }
+
+    #[async_trait::async_trait]
+    impl CommandExecutor for FlappingExecutor {
+        async fn execute(&self, _cmd: &str, _args: &[&str]) -> Result<CommandOutput> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            let stdout = if call < self.running_polls {
+                "ActiveState=active\nSubState=running\nMainPID=1234\nActiveEnterTimestamp=\n"
+            } else {
+                "ActiveState=failed\nSubState=failed\nMainPID=0\nActiveEnterTimestamp=\n"
+            };
+
+            Ok(CommandOutput {
+                exit_code: 0,
+                stdout: stdout.to_string(),
+                stderr: String::new(),
+            })
+        }
+    }
+
+    fn state(active: &str, sub: &str) -> ServiceState {
+        ServiceState {
+            active_state: active.to_string(),
+            sub_state: sub.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let mut previous = ServiceSnapshot::new();
+        previous.insert("nginx.service".to_string(), state("active", "running"));
+        previous.insert("sshd.service".to_string(), state("active", "running"));
+
+        let mut current = ServiceSnapshot::new();
+        current.insert("nginx.service".to_string(), state("failed", "failed"));
+        current.insert("sshd.service".to_string(), state("active", "running"));
+        current.insert("redis.service".to_string(), state("active", "running"));
+
+        let changes = diff_snapshots(&previous, &current);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].name, "nginx.service");
+        assert_eq!(changes[0].previous, Some(state("active", "running")));
+        assert_eq!(changes[0].current, Some(state("failed", "failed")));
+        assert_eq!(changes[1].name, "redis.service");
+        assert_eq!(changes[1].previous, None);
+    }
+
+    #[tokio::test]
+    async fn test_wait_detects_change_between_polls() {
+        // Baseline plus one quiet poll see "running"; the second poll sees "failed"
+        let executor = Arc::new(FlappingExecutor {
+            calls: AtomicUsize::new(0),
+            running_polls: 2,
+        });
+        let services = vec!["nginx.service".to_string()];
+        let hub = EventHub::with_poll_interval(Duration::from_millis(5));
+
+        let update = hub.wait_for_changes(executor.clone(), &SystemClock, &services, None, Duration::from_secs(5)).await;
+
+        assert_eq!(update.changes.len(), 1);
+        assert_eq!(update.changes[0].previous, Some(state("active", "running")));
+        assert_eq!(update.changes[0].current, Some(state("failed", "failed")));
+        assert_eq!(executor.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_wait_times_out_without_changes() {
+        let executor = Arc::new(FlappingExecutor {
+            calls: AtomicUsize::new(0),
+            running_polls: usize::MAX,
+        });
+        let services = vec!["nginx.service".to_string()];
+        let hub = EventHub::with_poll_interval(Duration::from_millis(5));
+
+        let update = hub.wait_for_changes(executor, &SystemClock, &services, None, Duration::from_millis(30)).await;
+
+        assert!(update.changes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_change_between_requests_is_reported() {
+        let executor = Arc::new(FlappingExecutor {
+            calls: AtomicUsize::new(0),
+            running_polls: 1,
+        });
+        let services = vec!["nginx.service".to_string()];
+        let hub = EventHub::with_poll_interval(Duration::from_millis(5));
+
+        // First request sees "running" and times out straight away
+        let first = hub.wait_for_changes(executor.clone(), &SystemClock, &services, None, Duration::ZERO).await;
+        assert!(first.changes.is_empty());
+
+        // nginx fails while no request is waiting
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        // The next request, sent with the version the client saw, answers at once
+        let second = hub
+            .wait_for_changes(executor.clone(), &SystemClock, &services, Some(&first.version), Duration::from_secs(5))
+            .await;
+        assert_eq!(second.changes.len(), 1);
+        assert_eq!(second.changes[0].previous, Some(state("active", "running")));
+        assert_eq!(second.changes[0].current, Some(state("failed", "failed")));
+        assert_ne!(second.version, first.version);
+        assert_eq!(executor.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_waiters_share_one_poll() {
+        let executor = Arc::new(FlappingExecutor {
+            calls: AtomicUsize::new(0),
+            running_polls: usize::MAX,
+        });
+        let services = vec!["nginx.service".to_string()];
+        let hub = EventHub::with_poll_interval(Duration::from_secs(60));
+
+        let waiters = (0..5).map(|_| hub.wait_for_changes(executor.clone(), &SystemClock, &services, None, Duration::ZERO));
+        futures::future::join_all(waiters).await;
+
+        assert_eq!(executor.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_version_reports_every_service() {
+        let executor = Arc::new(FlappingExecutor {
+            calls: AtomicUsize::new(0),
+            running_polls: usize::MAX,
+        });
+        let services = vec!["nginx.service".to_string()];
+        let hub = EventHub::new();
+
+        let update = hub
+            .wait_for_changes(executor, &SystemClock, &services, Some("0000000000000000"), Duration::from_secs(5))
+            .await;
+
+        assert_eq!(update.changes.len(), 1);
+        assert_eq!(update.changes[0].previous, None);
+        assert_eq!(update.changes[0].current, Some(state("active", "running")));
+    }
+}
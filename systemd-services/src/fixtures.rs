@@ -0,0 +1,154 @@
+// Canned systemd responses for running the plugin without systemd
+
+use crate::settings::PluginSettings;
+use crate::systemctl::{MockCommandExecutor, SHOW_TIMESTAMP_ARG, STATUS_PROPERTIES, SUMMARY_PROPERTIES};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use toru_plugin_api::{PluginKvStore, PluginResult};
+
+/// Environment variable that, set to `1`, runs the plugin against the fixtures
+/// instead of the host's systemd (for frontend development)
+pub const MOCK_ENV_VAR: &str = "TORU_PLUGIN_MOCK";
+
+/// A service known to the fixtures
+pub struct FixtureService {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub active_state: &'static str,
+    pub sub_state: &'static str,
+    pub main_pid: u32,
+}
+
+/// Services the fixtures report, covering the states the dashboard renders
+pub const FIXTURE_SERVICES: &[FixtureService] = &[
+    FixtureService {
+        name: "nginx.service",
+        description: "A high performance web server and a reverse proxy server",
+        active_state: "active",
+        sub_state: "running",
+        main_pid: 1234,
+    },
+    FixtureService {
+        name: "postgresql.service",
+        description: "PostgreSQL RDBMS",
+        active_state: "active",
+        sub_state: "running",
+        main_pid: 2345,
+    },
+    FixtureService {
+        name: "backup.service",
+        description: "Nightly backup",
+        active_state: "failed",
+        sub_state: "failed",
+        main_pid: 0,
+    },
+    FixtureService {
+        name: "redis.service",
+        description: "Advanced key-value store",
+        active_state: "inactive",
+        sub_state: "dead",
+        main_pid: 0,
+    },
+];
+
+/// Returns true when `TORU_PLUGIN_MOCK=1`
+pub fn mock_mode_enabled() -> bool {
+    std::env::var(MOCK_ENV_VAR).is_ok_and(|value| value == "1")
+}
+
+/// Builds a mock executor answering the listing, status and summary queries
+/// for `FIXTURE_SERVICES` and the system state probe
+///
+/// Any other command fails as it would for an unknown mock command.
+pub fn mock_executor() -> MockCommandExecutor {
+    let listing: String = FIXTURE_SERVICES
+        .iter()
+        .map(|service| {
+            format!(
+                "{} loaded {} {} {}\n",
+                service.name, service.active_state, service.sub_state, service.description
+            )
+        })
+        .collect();
+
+    let status_arg = format!("--property={}", STATUS_PROPERTIES);
+    let summary_arg = format!("--property={},{}", STATUS_PROPERTIES, SUMMARY_PROPERTIES);
+
+    let mut executor = MockCommandExecutor::new()
+        .with_stdout(
+            "systemctl",
+            &["list-units", "--type=service", "--all", "--no-pager", "--plain", "--no-legend"],
+            &listing,
+        )
+        .with_stdout("systemctl", &["is-system-running"], "degraded\n");
+
+    for service in FIXTURE_SERVICES {
+        let status = status_output(service);
+        let summary = format!(
+            "{}UnitFileState=enabled\nMemoryCurrent=52428800\nControlGroup=/system.slice/{}\nRestart=on-failure\nRestartUSec=100ms\n",
+            status, service.name
+        );
+        executor = executor
+            .with_stdout("systemctl", &["show", service.name, SHOW_TIMESTAMP_ARG, &status_arg], &status)
+            .with_stdout("systemctl", &["show", service.name, SHOW_TIMESTAMP_ARG, &summary_arg], &summary);
+    }
+
+    executor
+}
+
+fn status_output(service: &FixtureService) -> String {
+    let running = service.active_state == "active";
+    format!(
+        "Description={}\nLoadState=loaded\nActiveState={}\nSubState={}\nMainPID={}\nActiveEnterTimestamp={}\nActiveEnterTimestampMonotonic=0\nTasksCurrent={}\nTasksMax=4915\n",
+        service.description,
+        service.active_state,
+        service.sub_state,
+        service.main_pid,
+        if running { "@1705315845" } else { "" },
+        if running { "4" } else { "[not set]" },
+    )
+}
+
+/// In-memory KV store for mock mode, starting with every fixture service watched
+pub struct FixtureKvStore {
+    data: Mutex<HashMap<String, String>>,
+}
+
+impl FixtureKvStore {
+    pub fn new() -> Self {
+        let settings = PluginSettings {
+            watched_services: FIXTURE_SERVICES.iter().map(|service| service.name.to_string()).collect(),
+            ..PluginSettings::default()
+        };
+
+        let mut data = HashMap::new();
+        if let Ok(json_str) = serde_json::to_string(&settings) {
+            data.insert(crate::settings::SETTINGS_KEY.to_string(), json_str);
+        }
+
+        Self { data: Mutex::new(data) }
+    }
+}
+
+impl Default for FixtureKvStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl PluginKvStore for FixtureKvStore {
+    async fn get(&self, key: &str) -> PluginResult<Option<String>> {
+        Ok(self.data.lock().unwrap_or_else(|e| e.into_inner()).get(key).cloned())
+    }
+
+    async fn set(&self, key: &str, value: &str) -> PluginResult<()> {
+        self.data.lock().unwrap_or_else(|e| e.into_inner()).insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> PluginResult<()> {
+        self.data.lock().unwrap_or_else(|e| e.into_inner()).remove(key);
+        Ok(())
+    }
+}
@@ -7,15 +7,34 @@ mod tests;
 
 pub use services::{
     handle_get_services,
+    handle_get_services_snapshot,
     handle_get_available_services,
     handle_service_action,
+    handle_service_action_dry_run,
+    handle_bulk_action,
+    handle_daemon_reload,
+    handle_get_journal_usage,
+    handle_get_health,
+    handle_get_stats,
     handle_get_logs,
+    handle_get_multi_logs,
+    handle_get_raw_logs,
+    handle_get_properties,
+    handle_get_summary,
+    handle_get_audit,
+    handle_get_events,
+    handle_get_activity,
 };
 
-use crate::error::Result;
+use crate::error::{Result, ServiceError};
+use crate::log_filter::{LogFilter, MAX_GREP_PATTERNS, MAX_GREP_PATTERN_LEN};
+use crate::rate_limit::RateLimiter;
+use crate::settings::PluginSettings;
+use serde::de::DeserializeOwned;
 use serde::Serialize;
 use std::collections::HashMap;
-use toru_plugin_api::HttpResponse;
+use std::time::Instant;
+use toru_plugin_api::{HttpResponse, PluginKvStore};
 
 /// Creates a JSON response with given status and data
 pub fn json_response<T: Serialize>(status: u16, data: T) -> Result<HttpResponse> {
@@ -31,6 +50,73 @@ pub fn json_response<T: Serialize>(status: u16, data: T) -> Result<HttpResponse>
     })
 }
 
+/// Response header carrying how long the plugin took to handle the request
+pub const PROCESSING_TIME_HEADER: &str = "X-Processing-Time-Ms";
+
+/// Sets `PROCESSING_TIME_HEADER` to `elapsed` in milliseconds (one decimal)
+///
+/// Measured around the whole dispatch, systemctl and journalctl calls
+/// included, so a slow dashboard can be told apart from a slow network.
+pub fn set_processing_time(response: &mut HttpResponse, elapsed: std::time::Duration) {
+    let millis = elapsed.as_secs_f64() * 1000.0;
+    response.headers.insert(PROCESSING_TIME_HEADER.to_string(), format!("{:.1}", millis));
+}
+
+/// Methods advertised to CORS preflight requests
+pub const CORS_ALLOWED_METHODS: &str = "GET, HEAD, POST, OPTIONS";
+
+/// Request headers advertised to CORS preflight requests
+pub const CORS_ALLOWED_HEADERS: &str = "Content-Type";
+
+/// Reads the configured CORS origin; None when unset, empty or unreadable
+pub async fn cors_origin(kv_store: &dyn PluginKvStore) -> Option<String> {
+    PluginSettings::load(kv_store)
+        .await
+        .ok()
+        .and_then(|settings| settings.cors_origin)
+        .filter(|origin| !origin.trim().is_empty())
+}
+
+/// Adds the CORS headers allowing `origin` to a response
+pub fn set_cors_headers(response: &mut HttpResponse, origin: &str) {
+    response.headers.insert("Access-Control-Allow-Origin".to_string(), origin.to_string());
+    response.headers.insert("Access-Control-Allow-Methods".to_string(), CORS_ALLOWED_METHODS.to_string());
+    response.headers.insert("Access-Control-Allow-Headers".to_string(), CORS_ALLOWED_HEADERS.to_string());
+    response.headers.insert("Vary".to_string(), "Origin".to_string());
+}
+
+/// Answers an `OPTIONS` preflight request: 204 with the CORS headers
+pub fn preflight_response(origin: &str) -> HttpResponse {
+    let mut response = HttpResponse {
+        status: 204,
+        headers: HashMap::new(),
+        body: None,
+    };
+    set_cors_headers(&mut response, origin);
+    response
+}
+
+/// Largest request body accepted, in bytes
+pub const MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// Parses a JSON request body
+///
+/// Oversized bodies are refused before parsing. Failures are
+/// `ServiceError::BadRequest` rather than going through the `serde_json`
+/// conversion, so a bad payload becomes a 400 instead of a 500.
+pub fn parse_json_body<T: DeserializeOwned>(body: &str) -> Result<T> {
+    if body.len() > MAX_BODY_BYTES {
+        return Err(ServiceError::BadRequest(format!(
+            "Request body too large: {} bytes (max {})",
+            body.len(),
+            MAX_BODY_BYTES
+        )));
+    }
+
+    serde_json::from_str(body)
+        .map_err(|e| ServiceError::BadRequest(format!("Invalid request body: {}", e)))
+}
+
 /// Creates an error response
 pub fn error_response(status: u16, error: &str) -> Result<HttpResponse> {
     let error_obj = serde_json::json!({
@@ -51,6 +137,174 @@ pub fn success_response(message: &str) -> Result<HttpResponse> {
     json_response(200, success_obj)
 }
 
+/// Creates a success response carrying the affected resource under `data`
+pub fn success_response_with<T: Serialize>(message: &str, data: T) -> Result<HttpResponse> {
+    let success_obj = serde_json::json!({
+        "success": true,
+        "message": message,
+        "data": data
+    });
+
+    json_response(200, success_obj)
+}
+
+/// Returns true for HTTP methods that may change system state
+pub fn is_mutating_method(method: &str) -> bool {
+    matches!(method, "POST" | "PUT" | "PATCH" | "DELETE")
+}
+
+/// Blocks mutating requests while the plugin is in read-only mode
+pub async fn check_read_only(
+    kv_store: &dyn PluginKvStore,
+    method: &str,
+) -> Result<Option<HttpResponse>> {
+    if !is_mutating_method(method) {
+        return Ok(None);
+    }
+
+    if !PluginSettings::load(kv_store).await?.read_only {
+        return Ok(None);
+    }
+
+    let error_obj = serde_json::json!({
+        "success": false,
+        "code": "read_only",
+        "error": "Plugin is in read-only mode"
+    });
+
+    json_response(403, error_obj).map(Some)
+}
+
+/// Rejects a repeat of the same unit action within the configured cooldown with a 429
+///
+/// An action let through is recorded at `now`; the handler's response goes to
+/// `release_rejected_action` so a rejected request doesn't start the cooldown.
+pub async fn check_rate_limit(
+    limiter: &RateLimiter,
+    kv_store: &dyn PluginKvStore,
+    service_name: &str,
+    action: &str,
+    now: Instant,
+) -> Result<Option<HttpResponse>> {
+    let cooldown = PluginSettings::load(kv_store).await?.action_cooldown();
+
+    let wait = match limiter.check_at(service_name, action, cooldown, now) {
+        Ok(()) => return Ok(None),
+        Err(wait) => wait,
+    };
+
+    let error_obj = serde_json::json!({
+        "success": false,
+        "error": format!("Service {} was just asked to {}; try again shortly", service_name, action),
+        "retry_after_ms": wait.as_millis() as u64
+    });
+
+    let mut response = json_response(429, error_obj)?;
+    let retry_after_secs = (wait.as_millis() as u64).div_ceil(1000).max(1);
+    response.headers.insert("Retry-After".to_string(), retry_after_secs.to_string());
+    Ok(Some(response))
+}
+
+/// Forgets an action let through by `check_rate_limit` at `now` if its
+/// handler rejected it (4xx) without running it
+///
+/// A bad name, a denied unit or a missing service then doesn't hold back
+/// the corrected retry.
+pub fn release_rejected_action(
+    limiter: &RateLimiter,
+    service_name: &str,
+    action: &str,
+    now: Instant,
+    response: &HttpResponse,
+) {
+    if (400..500).contains(&response.status) {
+        limiter.release(service_name, action, now);
+    }
+}
+
+/// Reads the debug mode flag (off unless set)
+pub async fn is_debug_mode(kv_store: &dyn PluginKvStore) -> Result<bool> {
+    Ok(PluginSettings::load(kv_store).await?.debug_mode)
+}
+
+/// Returns true if the request asks for unprocessed journal lines (`?raw=true`)
+pub fn is_raw(query_params: &HashMap<String, String>) -> bool {
+    query_params.get("raw").is_some_and(|v| v == "true" || v == "1")
+}
+
+/// Returns true if the request asks for a dry run (`?dry_run=true`)
+pub fn is_dry_run(query_params: &HashMap<String, String>) -> bool {
+    query_params.get("dry_run").is_some_and(|v| v == "true" || v == "1")
+}
+
+/// Returns true if a stop may escalate to SIGKILL (`?force=true`)
+pub fn is_force(query_params: &HashMap<String, String>) -> bool {
+    query_params.get("force").is_some_and(|v| v == "true" || v == "1")
+}
+
+/// Splits the comma-separated `units` query parameter into decoded unit names
+pub fn parse_units_param(query_params: &HashMap<String, String>) -> Vec<String> {
+    query_params
+        .get("units")
+        .map(|s| {
+            s.split(',')
+                .map(|unit| decode_path_segment(unit.trim()))
+                .filter(|unit| !unit.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Reads `?grep=` and `?priority=` into a log filter
+///
+/// `grep` is percent-decoded with `+` as a space and split on commas into
+/// patterns, any of which may match; empty or overlong patterns are rejected.
+/// `priority` takes a number (0-7) or a level name and keeps that level and
+/// anything more severe.
+pub fn parse_log_filter(params: &HashMap<String, String>) -> std::result::Result<LogFilter, String> {
+    let grep = match params.get("grep").map(|s| decode_path_segment(&s.replace('+', " "))) {
+        Some(value) if !value.is_empty() => {
+            let patterns: Vec<String> = value.split(',').map(str::to_string).collect();
+            if patterns.len() > MAX_GREP_PATTERNS {
+                return Err(format!("Too many grep patterns: at most {}", MAX_GREP_PATTERNS));
+            }
+            if let Some(pattern) = patterns.iter().find(|p| p.trim().is_empty() || p.len() > MAX_GREP_PATTERN_LEN) {
+                return Err(format!(
+                    "Invalid grep pattern {:?}: expected 1-{} characters",
+                    pattern, MAX_GREP_PATTERN_LEN
+                ));
+            }
+            patterns
+        }
+        _ => Vec::new(),
+    };
+
+    let max_priority = match params.get("priority") {
+        None => None,
+        Some(value) => Some(
+            crate::systemctl::level_to_priority(value)
+                .ok_or_else(|| format!("Invalid priority: {}", value))?,
+        ),
+    };
+
+    Ok(LogFilter { grep, max_priority })
+}
+
+/// Splits `/services/:name/:action` for the service actions (start, stop, restart)
+pub fn parse_action_path(path: &str) -> Option<(&str, &str)> {
+    let rest = path.strip_prefix("/services/")?;
+    let (name, action) = rest.rsplit_once('/')?;
+
+    if name.is_empty() || name.contains('/') {
+        return None;
+    }
+
+    match action {
+        "start" | "stop" | "restart" | "disable-and-stop" | "stop-and-mask" => Some((name, action)),
+        _ => None,
+    }
+}
+
 /// Parses query parameters from a path
 pub fn parse_query_params(path: &str) -> HashMap<String, String> {
     let mut params = HashMap::new();
@@ -73,3 +327,27 @@ pub fn parse_query_params(path: &str) -> HashMap<String, String> {
 pub fn path_without_query(path: &str) -> &str {
     path.split('?').next().unwrap_or(path)
 }
+
+/// Decodes percent-encoded characters in a path segment (e.g. `%40` -> `@`)
+/// Malformed escapes are kept as-is; the result still goes through name validation
+pub fn decode_path_segment(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let high = (bytes[i + 1] as char).to_digit(16);
+            let low = (bytes[i + 2] as char).to_digit(16);
+            if let (Some(high), Some(low)) = (high, low) {
+                decoded.push((high * 16 + low) as u8);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
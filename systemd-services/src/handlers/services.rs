@@ -1,112 +1,734 @@
 // Service-related HTTP handlers
 
 use crate::{
+    access::UnitAccessPolicy,
+    audit::{append_audit, load_audit_log, AuditEntry},
+    circuit_breaker::CircuitBreaker,
+    clock::Clock,
     error::{Result, ServiceError},
+    events::{EventHub, DEFAULT_WAIT, MAX_WAIT},
+    log_filter::{filter_with_context, MAX_CONTEXT_LINES},
+    rate_limit::RateLimiter,
+    refresh_hint::{RefreshAdvisor, REFRESH_AFTER_HEADER},
+    settings::PluginSettings,
+    stats::PluginStats,
     systemctl::CommandExecutor,
 };
-use super::{json_response, error_response, success_response};
+use super::{json_response, error_response, parse_json_body, success_response, success_response_with};
+use futures::future::join_all;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::time::Instant;
 use toru_plugin_api::{HttpResponse, PluginKvStore};
 
+/// Response header on GET /services/:name/logs carrying the newest entry's
+/// journal cursor, to be sent back as `after_cursor` on the next request
+pub const JOURNAL_CURSOR_HEADER: &str = "X-Journal-Cursor";
+
 /// Response format for GET /services
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServiceStatusResponse {
     pub name: String,
-    pub status: String,  // "running", "failed", "inactive"
+    pub description: String,
+    pub status: String,  // "running", "failed", "inactive", "missing", "masked"
+    /// "loaded", "not-found", "masked", ...; "unknown" when the query failed
+    pub load_state: String,
     pub active_state: String,
     pub sub_state: String,
     pub uptime_seconds: u64,
+    /// Time in the current activation (monotonic clock when available)
+    pub current_uptime_seconds: u64,
+    /// When the service was first started this boot; earlier than the current
+    /// activation when it has been restarted since. Only GET /services and
+    /// the summary read it; None when the journal has no record of it
+    pub first_activation_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    pub tasks_current: Option<u64>,
+    /// None when unlimited
+    pub tasks_max: Option<u64>,
+}
+
+impl From<crate::systemctl::ServiceStatus> for ServiceStatusResponse {
+    fn from(status: crate::systemctl::ServiceStatus) -> Self {
+        // A deleted or masked unit reads as inactive; say why instead
+        let simple_status = match (status.load_state.as_str(), status.active_state.as_str()) {
+            ("not-found", _) => "missing",
+            ("masked", _) => "masked",
+            (_, "active") => "running",
+            (_, "failed") => "failed",
+            _ => "inactive",
+        };
+
+        Self {
+            name: status.name,
+            description: status.description,
+            status: simple_status.to_string(),
+            load_state: status.load_state,
+            active_state: status.active_state,
+            sub_state: status.sub_state,
+            uptime_seconds: status.uptime_seconds,
+            current_uptime_seconds: status.current_uptime_seconds,
+            first_activation_timestamp: status.first_activation_timestamp,
+            tasks_current: status.tasks_current,
+            tasks_max: status.tasks_max,
+        }
+    }
 }
 
+/// Response for GET /services/snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServicesSnapshotResponse {
+    /// Changes whenever a watched service's state does; stable otherwise
+    pub hash: String,
+    pub services: usize,
+    /// Number of services per `status` ("running", "failed", ...)
+    pub states: std::collections::BTreeMap<String, usize>,
+}
+
+/// Window of GET /services/activity when `since` is not given
+const DEFAULT_ACTIVITY_SINCE: &str = "1 hour ago";
+
+/// Journal lines attached to a failed start/restart while debug mode is on
+const FAILURE_LOG_LINES: u32 = 20;
+
+/// Status queries made after a successful action while the unit is still changing state
+const POST_ACTION_STATUS_ATTEMPTS: u32 = 5;
+
+/// Delay between post-action status queries
+const POST_ACTION_STATUS_INTERVAL: std::time::Duration = std::time::Duration::from_millis(200);
+
 /// Handle GET /services - return watched services with status
 pub async fn handle_get_services<E: CommandExecutor>(
     executor: Arc<E>,
     kv_store: &dyn PluginKvStore,
+    clock: &dyn Clock,
+    advisor: &RefreshAdvisor,
+    breaker: &CircuitBreaker,
 ) -> Result<HttpResponse> {
     // Get watched services from KV storage
     let watched_services = get_watched_services(kv_store).await?;
+    let (mut results, first_activations) = tokio::join!(
+        gather_statuses(executor.clone(), watched_services.clone(), clock, breaker),
+        crate::systemctl::get_first_activations(executor, &watched_services),
+    );
 
-    let mut results = Vec::new();
+    // One journal query covers every service; without it the field stays unset
+    match first_activations {
+        Ok(first_activations) => {
+            for service in &mut results {
+                service.first_activation_timestamp = first_activations.get(&service.name).copied();
+            }
+        }
+        Err(e) => eprintln!("Failed to read first activations: {}", e),
+    }
+
+    // Hint how soon the dashboard should poll again, based on how much is changing
+    let refresh_after = advisor.observe(results.iter().map(|service: &ServiceStatusResponse| {
+        (service.name.clone(), format!("{}/{}", service.active_state, service.sub_state))
+    }));
+
+    let mut response = json_response(200, results)?;
+    response.headers.insert(REFRESH_AFTER_HEADER.to_string(), refresh_after.as_secs().to_string());
+    Ok(response)
+}
 
-    // Get status for each watched service
-    for service_name in watched_services {
-        match crate::systemctl::get_service_status(executor.clone(), &service_name).await {
-            Ok(status) => {
-                // Map active_state to simple status
-                let simple_status = match status.active_state.as_str() {
-                    "active" => "running",
-                    "failed" => "failed",
-                    _ => "inactive",
-                };
-
-                results.push(ServiceStatusResponse {
-                    name: status.name,
-                    status: simple_status.to_string(),
-                    active_state: status.active_state,
-                    sub_state: status.sub_state,
-                    uptime_seconds: status.uptime_seconds,
-                });
+/// Handle GET /services/snapshot - a hash of the watched services' states
+///
+/// Lets a dashboard poll cheaply and fetch `/services` only when the hash
+/// changes. Only states feed the hash, so uptimes ticking don't change it.
+pub async fn handle_get_services_snapshot<E: CommandExecutor>(
+    executor: Arc<E>,
+    kv_store: &dyn PluginKvStore,
+    clock: &dyn Clock,
+    breaker: &CircuitBreaker,
+) -> Result<HttpResponse> {
+    let watched_services = get_watched_services(kv_store).await?;
+    let statuses = gather_statuses(executor, watched_services, clock, breaker).await;
+
+    let mut states = std::collections::BTreeMap::new();
+    for service in &statuses {
+        *states.entry(service.status.clone()).or_insert(0) += 1;
+    }
+
+    json_response(200, ServicesSnapshotResponse {
+        hash: status_hash(&statuses),
+        services: statuses.len(),
+        states,
+    })
+}
+
+/// Queries the status of each service, in order
+///
+/// Every service is queried at once; the executor bounds how many commands
+/// actually run. Services whose queries keep failing are skipped while their
+/// breaker is open and, like those that fail now, listed as unavailable.
+async fn gather_statuses<E: CommandExecutor>(
+    executor: Arc<E>,
+    service_names: Vec<String>,
+    clock: &dyn Clock,
+    breaker: &CircuitBreaker,
+) -> Vec<ServiceStatusResponse> {
+    let statuses = join_all(service_names.into_iter().map(|service_name| {
+        let executor = executor.clone();
+        async move {
+            if !breaker.allow(&service_name) {
+                return (service_name, None);
             }
-            Err(e) => {
+
+            let status = crate::systemctl::get_service_status(executor, &service_name, clock).await;
+            match &status {
+                Ok(_) => breaker.record_success(&service_name),
+                Err(_) => breaker.record_failure(&service_name),
+            }
+            (service_name, Some(status))
+        }
+    }))
+    .await;
+
+    let mut results = Vec::new();
+
+    for (service_name, status) in statuses {
+        match status {
+            Some(Ok(status)) => results.push(status.into()),
+            Some(Err(e)) => {
                 // Include services that failed to query but mark them as unavailable
                 eprintln!("Failed to get status for {}: {}", service_name, e);
-                results.push(ServiceStatusResponse {
-                    name: service_name.clone(),
-                    status: "unknown".to_string(),
-                    active_state: "unknown".to_string(),
-                    sub_state: "unknown".to_string(),
-                    uptime_seconds: 0,
-                });
+                results.push(unavailable_status(service_name));
             }
+            None => results.push(unavailable_status(service_name)),
         }
     }
 
-    json_response(200, results)
+    results
 }
 
-/// Handle GET /services/available - return all systemd services
+/// FNV-1a hash of each service's name and state, as 16 hex digits
+///
+/// Stable across restarts and builds, unlike `std`'s hasher.
+fn status_hash(statuses: &[ServiceStatusResponse]) -> String {
+    const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    let mut hash = FNV_OFFSET;
+    for service in statuses {
+        let line = format!(
+            "{}={}/{}/{}/{}\n",
+            service.name, service.status, service.load_state, service.active_state, service.sub_state
+        );
+        for byte in line.bytes() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    format!("{:016x}", hash)
+}
+
+/// Placeholder listed for a watched service whose status couldn't be read
+fn unavailable_status(name: String) -> ServiceStatusResponse {
+    ServiceStatusResponse {
+        name,
+        description: String::new(),
+        status: "unknown".to_string(),
+        load_state: "unknown".to_string(),
+        active_state: "unknown".to_string(),
+        sub_state: "unknown".to_string(),
+        uptime_seconds: 0,
+        current_uptime_seconds: 0,
+        first_activation_timestamp: None,
+        tasks_current: None,
+        tasks_max: None,
+    }
+}
+
+/// Handle GET /services/available?state=failed - return all systemd services,
+/// optionally only those in the given (comma-separated) states
 pub async fn handle_get_available_services<E: CommandExecutor>(
     executor: Arc<E>,
+    query_params: &std::collections::HashMap<String, String>,
 ) -> Result<HttpResponse> {
-    let services = crate::systemctl::list_services(executor).await?;
-    json_response(200, services)
+    let result = match query_params.get("state").filter(|state| !state.is_empty()) {
+        Some(state) => crate::systemctl::list_services_in_state(executor, state).await,
+        None => crate::systemctl::list_services(executor).await,
+    };
+
+    match result {
+        Ok(services) => json_response(200, services),
+        Err(e @ ServiceError::InvalidState(_)) => {
+            error_response(400, &e.to_string())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Handle GET /services/events?wait=30&since=<version> - long-poll for watched
+/// service state changes
+///
+/// Responds as soon as a watched service's state differs from version `since`
+/// (the `version` of the previous response; the state when the request
+/// arrived if omitted), or with an empty change list once `wait` seconds
+/// (default 30, max 60) have passed. All waiting requests share one poller.
+pub async fn handle_get_events<E: CommandExecutor>(
+    executor: Arc<E>,
+    kv_store: &dyn PluginKvStore,
+    clock: &dyn Clock,
+    hub: &EventHub,
+    query_params: &std::collections::HashMap<String, String>,
+) -> Result<HttpResponse> {
+    let wait = query_params
+        .get("wait")
+        .and_then(|s| s.parse::<u64>().ok())
+        .map(std::time::Duration::from_secs)
+        .unwrap_or(DEFAULT_WAIT)
+        .min(MAX_WAIT);
+    let since = query_params.get("since").map(String::as_str).filter(|since| !since.is_empty());
+
+    let watched_services = get_watched_services(kv_store).await?;
+    let update = hub.wait_for_changes(executor, clock, &watched_services, since, wait).await;
+
+    json_response(200, serde_json::json!({
+        "changed": !update.changes.is_empty(),
+        "version": update.version,
+        "changes": update.changes
+    }))
 }
 
 /// Handle POST /services/:name/start|stop|restart
 pub async fn handle_service_action<E: CommandExecutor>(
     executor: Arc<E>,
+    kv_store: &dyn PluginKvStore,
+    clock: &dyn Clock,
     service_name: &str,
     action: &str,
+    force: bool,
 ) -> Result<HttpResponse> {
     // Validate service name
     crate::systemctl::validate_service_name(service_name)?;
 
-    // Execute action
+    if force && action != "stop" {
+        return error_response(400, "force is only supported for stop");
+    }
+
+    // Reject units outside the configured allowlist/denylist before shelling out
+    let policy = UnitAccessPolicy::load(kv_store).await?;
+    if let Err(reason) = policy.check(service_name) {
+        return error_response(403, &reason);
+    }
+
+    // A missing unit gets a clean 404 before anything is run or audited
+    if !crate::systemctl::unit_exists(executor.clone(), service_name).await {
+        return error_response(404, "Service not found");
+    }
+
+    if let Some(steps) = crate::systemctl::compound_action_steps(action) {
+        return run_compound_action(executor, kv_store, clock, service_name, action, steps).await;
+    }
+
+    // Execute action; Ok(true) means a forced stop had to escalate to SIGKILL
     let result = match action {
-        "start" => crate::systemctl::start_service(executor, service_name).await,
-        "stop" => crate::systemctl::stop_service(executor, service_name).await,
-        "restart" => crate::systemctl::restart_service(executor, service_name).await,
+        "start" => crate::systemctl::start_service(executor.clone(), service_name).await.map(|()| false),
+        "stop" if force => crate::systemctl::force_stop_service(executor.clone(), service_name).await,
+        "stop" => crate::systemctl::stop_service(executor.clone(), service_name).await.map(|()| false),
+        "restart" => crate::systemctl::restart_service(executor.clone(), service_name).await.map(|()| false),
         _ => {
             return error_response(400, &format!("Invalid action: {}", action));
         }
     };
 
+    let audit_action = if force { "force-stop" } else { action };
+    record_audit(kv_store, clock, service_name, audit_action, &result).await;
+
+    // A failed start usually explains itself in the unit's last journal lines
+    let recent_logs = match &result {
+        Err(ServiceError::ServiceNotFound(_)) | Ok(_) => None,
+        Err(_) if action == "stop" || !super::is_debug_mode(kv_store).await? => None,
+        Err(_) => crate::systemctl::get_logs(executor.clone(), service_name, FAILURE_LOG_LINES).await.ok(),
+    };
+
+    // Report the state the action left the service in so the UI needn't poll for it
+    let status = match &result {
+        Ok(_) => post_action_status(executor, clock, service_name).await,
+        Err(_) => None,
+    };
+
+    let mut response = match result {
+        Ok(escalated) => {
+            let mut data = serde_json::json!({
+                "service": service_name,
+                "action": action,
+                "status": status
+            });
+            if force {
+                data["escalated"] = serde_json::json!(escalated);
+            }
+
+            let message = if escalated {
+                format!("Service {} successful after escalating to SIGKILL on timeout", action)
+            } else {
+                format!("Service {} successful", action)
+            };
+            success_response_with(&message, data)
+        }
+        Err(e) => {
+            let (status, message) = action_error(action, &e);
+            error_response(status, &message)
+        }
+    }?;
+
+    if let Some(logs) = recent_logs {
+        attach_recent_logs(&mut response, &logs)?;
+    }
+
+    Ok(response)
+}
+
+/// Status code and message for a failed service action
+fn action_error(action: &str, error: &ServiceError) -> (u16, String) {
+    match error {
+        ServiceError::ServiceNotFound(_) => (404, "Service not found".to_string()),
+        ServiceError::PermissionDenied(_) => (403, "Permission denied".to_string()),
+        ServiceError::Masked(_) => (409, error.to_string()),
+        ServiceError::AuthenticationRequired(_) => (401, error.to_string()),
+        _ => (500, format!("Failed to {} service: {}", action, error)),
+    }
+}
+
+/// Runs the steps of a compound action in order, stopping at the first failure
+///
+/// A failure after some steps succeeded leaves the service half way (e.g.
+/// stopped but not masked), so the error names the step that failed and the
+/// ones already done. The whole action is audited as one entry.
+async fn run_compound_action<E: CommandExecutor>(
+    executor: Arc<E>,
+    kv_store: &dyn PluginKvStore,
+    clock: &dyn Clock,
+    service_name: &str,
+    action: &str,
+    steps: &[&str],
+) -> Result<HttpResponse> {
+    let mut completed_steps = Vec::new();
+    let mut result = Ok(());
+
+    for step in steps {
+        result = crate::systemctl::run_action_step(executor.clone(), service_name, step).await;
+        if result.is_err() {
+            break;
+        }
+        completed_steps.push(*step);
+    }
+
+    record_audit(kv_store, clock, service_name, action, &result).await;
+
     match result {
-        Ok(_) => success_response(&format!("Service {} successful", action)),
-        Err(ServiceError::ServiceNotFound(_)) => {
-            error_response(404, "Service not found")
+        Ok(()) => {
+            let status = post_action_status(executor, clock, service_name).await;
+            success_response_with(
+                &format!("Service {} successful", action),
+                serde_json::json!({
+                    "service": service_name,
+                    "action": action,
+                    "completed_steps": completed_steps,
+                    "status": status
+                }),
+            )
+        }
+        Err(e) => {
+            let failed_step = steps[completed_steps.len()];
+            let (status, message) = action_error(failed_step, &e);
+            let message = match completed_steps.last() {
+                Some(previous) => format!("{} (after {} succeeded)", message, previous),
+                None => message,
+            };
+
+            json_response(status, serde_json::json!({
+                "success": false,
+                "error": message,
+                "action": action,
+                "failed_step": failed_step,
+                "completed_steps": completed_steps
+            }))
+        }
+    }
+}
+
+/// Queries a service's status after a successful action
+///
+/// A unit that is still activating, deactivating or reloading is queried again
+/// a few times so a restart reports the service running rather than the brief
+/// transition. None when the status can't be read; the action itself succeeded.
+async fn post_action_status<E: CommandExecutor>(
+    executor: Arc<E>,
+    clock: &dyn Clock,
+    service_name: &str,
+) -> Option<ServiceStatusResponse> {
+    let mut attempts = 1;
+    loop {
+        let status = match crate::systemctl::get_service_status(executor.clone(), service_name, clock).await {
+            Ok(status) => status,
+            Err(e) => {
+                eprintln!("Failed to get status for {} after action: {}", service_name, e);
+                return None;
+            }
+        };
+
+        let settling = matches!(status.active_state.as_str(), "activating" | "deactivating" | "reloading");
+        if !settling || attempts >= POST_ACTION_STATUS_ATTEMPTS {
+            return Some(status.into());
+        }
+
+        attempts += 1;
+        tokio::time::sleep(POST_ACTION_STATUS_INTERVAL).await;
+    }
+}
+
+/// Body of POST /services/bulk
+#[derive(Debug, Deserialize)]
+pub struct BulkActionRequest {
+    pub action: String,
+    pub services: Vec<String>,
+    /// Delay between launching consecutive actions; 0 launches all at once
+    #[serde(default)]
+    pub stagger_ms: u64,
+    /// Run one service at a time, dependencies before the services needing them
+    /// (the reverse for stop)
+    #[serde(default)]
+    pub dependency_order: bool,
+}
+
+/// Outcome of one service in a bulk action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkActionResult {
+    pub service: String,
+    /// Status the single-service action responded with
+    pub status: u16,
+    /// Body the single-service action responded with
+    pub response: serde_json::Value,
+}
+
+/// Most services accepted in one bulk action
+pub const MAX_BULK_SERVICES: usize = 50;
+
+/// Largest accepted `stagger_ms`
+pub const MAX_STAGGER_MS: u64 = 10_000;
+
+/// Handle POST /services/bulk - run start|stop|restart on several services
+///
+/// Each service goes through the same access checks, cooldown and audit as a
+/// single action and gets its own result. With `stagger_ms` set the actions
+/// are launched that far apart, in request order, so restarting many services
+/// doesn't spike load; by default they all launch at once.
+///
+/// With `dependency_order` the services are ordered by their dependencies
+/// (falling back to request order if those can't be resolved) and each
+/// action finishes before the next one starts. Results follow the order the
+/// actions ran in.
+pub async fn handle_bulk_action<E: CommandExecutor>(
+    executor: Arc<E>,
+    kv_store: &dyn PluginKvStore,
+    clock: &dyn Clock,
+    limiter: &RateLimiter,
+    body: &str,
+) -> Result<HttpResponse> {
+    let request: BulkActionRequest = match parse_json_body(body) {
+        Ok(request) => request,
+        Err(e) => return error_response(e.status_code(), &e.to_string()),
+    };
+
+    if !matches!(request.action.as_str(), "start" | "stop" | "restart") {
+        return error_response(400, &format!("Invalid action: {}", request.action));
+    }
+    if request.services.is_empty() || request.services.len() > MAX_BULK_SERVICES {
+        return error_response(400, &format!("services must list 1-{} services", MAX_BULK_SERVICES));
+    }
+    if request.stagger_ms > MAX_STAGGER_MS {
+        return error_response(400, &format!("stagger_ms must be at most {}", MAX_STAGGER_MS));
+    }
+
+    let stagger = std::time::Duration::from_millis(request.stagger_ms);
+    let action = request.action.as_str();
+
+    let results = if request.dependency_order {
+        let mut order = crate::systemctl::dependency_order(executor.clone(), &request.services).await;
+        if action == "stop" {
+            order.reverse();
         }
+
+        let mut results = Vec::with_capacity(order.len());
+        for (i, service_name) in order.iter().enumerate() {
+            if i > 0 && !stagger.is_zero() {
+                tokio::time::sleep(stagger).await;
+            }
+            results.push(bulk_item(executor.clone(), kv_store, clock, limiter, service_name, action).await?);
+        }
+        results
+    } else {
+        join_all(request.services.iter().enumerate().map(|(i, service_name)| {
+            let executor = executor.clone();
+            async move {
+                if !stagger.is_zero() {
+                    tokio::time::sleep(stagger * i as u32).await;
+                }
+                bulk_item(executor, kv_store, clock, limiter, service_name, action).await
+            }
+        }))
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>>>()?
+    };
+
+    let succeeded = results.iter().filter(|r| r.status == 200).count();
+    json_response(200, serde_json::json!({
+        "success": succeeded == results.len(),
+        "message": format!("Service {} succeeded for {} of {} services", action, succeeded, results.len()),
+        "results": results
+    }))
+}
+
+/// Runs one service's part of a bulk action, honouring the action cooldown
+async fn bulk_item<E: CommandExecutor>(
+    executor: Arc<E>,
+    kv_store: &dyn PluginKvStore,
+    clock: &dyn Clock,
+    limiter: &RateLimiter,
+    service_name: &str,
+    action: &str,
+) -> Result<BulkActionResult> {
+    let now = Instant::now();
+    let response = match super::check_rate_limit(limiter, kv_store, service_name, action, now).await? {
+        Some(response) => response,
+        None => {
+            let response = handle_service_action(executor, kv_store, clock, service_name, action, false).await?;
+            super::release_rejected_action(limiter, service_name, action, now, &response);
+            response
+        }
+    };
+
+    let body = response.body.as_deref().unwrap_or("null");
+    Ok(BulkActionResult {
+        service: service_name.to_string(),
+        status: response.status,
+        response: serde_json::from_str(body)?,
+    })
+}
+
+/// Handle POST /services/:name/start|stop|restart?dry_run=true
+/// Applies the same validation and access checks as the real action, then
+/// reports the command that would run without executing anything
+pub async fn handle_service_action_dry_run(
+    kv_store: &dyn PluginKvStore,
+    service_name: &str,
+    action: &str,
+) -> Result<HttpResponse> {
+    let command = match crate::systemctl::service_action_command(service_name, action)? {
+        Some(command) => command,
+        None => return error_response(400, &format!("Invalid action: {}", action)),
+    };
+
+    let policy = UnitAccessPolicy::load(kv_store).await?;
+    if let Err(reason) = policy.check(service_name) {
+        return error_response(403, &reason);
+    }
+
+    json_response(200, serde_json::json!({
+        "command": command,
+        "executed": false
+    }))
+}
+
+/// Handle POST /system/daemon-reload
+/// Read-only mode is enforced before routing; the unit access policy is checked here
+pub async fn handle_daemon_reload<E: CommandExecutor>(
+    executor: Arc<E>,
+    kv_store: &dyn PluginKvStore,
+    clock: &dyn Clock,
+) -> Result<HttpResponse> {
+    let policy = UnitAccessPolicy::load(kv_store).await?;
+    if let Err(reason) = policy.check_system_wide("daemon-reload") {
+        return error_response(403, &reason);
+    }
+
+    let result = crate::systemctl::daemon_reload(executor).await;
+    record_audit(kv_store, clock, "systemd", "daemon-reload", &result).await;
+
+    match result {
+        Ok(_) => success_response("Daemon reload successful"),
         Err(ServiceError::PermissionDenied(_)) => {
             error_response(403, "Permission denied")
         }
+        Err(e @ ServiceError::AuthenticationRequired(_)) => {
+            error_response(401, &e.to_string())
+        }
         Err(e) => {
-            error_response(500, &format!("Failed to {} service: {}", action, e))
+            error_response(500, &format!("Failed to reload daemon: {}", e))
         }
     }
 }
 
-/// Handle GET /services/:name/logs?lines=100
+/// Response format for GET /healthz
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthResponse {
+    /// "ok" when systemd answered, "degraded" when it didn't
+    pub status: String,
+    pub systemd_available: bool,
+    /// What `systemctl is-system-running` reported, if anything
+    pub system_state: Option<String>,
+}
+
+/// Handle GET /healthz - whether the plugin process is up and can reach systemd
+///
+/// Answering at all shows the plugin is alive. If systemd can't be queried
+/// within the probe timeout, or reports itself `offline`/`unknown`, the
+/// response is `degraded` with a 503 so supervisors can act on the status code.
+pub async fn handle_get_health<E: CommandExecutor>(executor: Arc<E>) -> Result<HttpResponse> {
+    let system_state = match crate::systemctl::get_system_state(executor).await {
+        Ok(state) => Some(state),
+        Err(e) => {
+            eprintln!("Health probe could not reach systemd: {}", e);
+            None
+        }
+    };
+    let systemd_available = system_state
+        .as_deref()
+        .is_some_and(|state| !matches!(state, "offline" | "unknown"));
+
+    let health = HealthResponse {
+        status: if systemd_available { "ok" } else { "degraded" }.to_string(),
+        systemd_available,
+        system_state,
+    };
+
+    json_response(if systemd_available { 200 } else { 503 }, health)
+}
+
+/// Handle GET /debug/stats - plugin-internal request and command counters
+/// Cache hits are status polls answered from an open circuit breaker.
+pub fn handle_get_stats(stats: &PluginStats, breaker: &CircuitBreaker) -> Result<HttpResponse> {
+    let (cache_hits, cache_misses) = breaker.hit_counts();
+    json_response(200, stats.snapshot(cache_hits, cache_misses))
+}
+
+/// Handle GET /system/journal-usage - journal disk usage
+pub async fn handle_get_journal_usage<E: CommandExecutor>(
+    executor: Arc<E>,
+) -> Result<HttpResponse> {
+    match crate::systemctl::get_journal_usage(executor).await {
+        Ok(usage) => json_response(200, usage),
+        Err(e) => error_response(500, &format!("Failed to get journal usage: {}", e)),
+    }
+}
+
+/// Response format for GET /services/:name/logs when `grep` or `priority` is given
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilteredLogsResponse {
+    /// Matches within the fetched window, plus their context lines
+    pub entries: Vec<crate::systemctl::LogEntry>,
+    /// Matches across the whole journal (or boot range); None if counting failed
+    pub total_matches: Option<usize>,
+    /// True if counting stopped at `MAX_COUNTED_MATCHES`, so `total_matches` is a lower bound
+    #[serde(default)]
+    pub total_matches_capped: bool,
+}
+
+/// Handle GET /services/:name/logs?lines=100&boots=3&grep=timeout&priority=warning&context=2&namespace=foo&after_cursor=c
 pub async fn handle_get_logs<E: CommandExecutor>(
     executor: Arc<E>,
     service_name: &str,
@@ -121,9 +743,170 @@ pub async fn handle_get_logs<E: CommandExecutor>(
         .and_then(|s| s.parse::<u32>().ok())
         .unwrap_or(100);
 
+    // Optional boots parameter: include the last N boots instead of the whole journal
+    let boots = match query_params.get("boots") {
+        None => None,
+        Some(value) => match value.parse::<u32>() {
+            Ok(n) if (1..=crate::systemctl::MAX_LOG_BOOTS).contains(&n) => Some(n),
+            _ => {
+                return error_response(
+                    400,
+                    &format!("Invalid boots: expected 1-{}", crate::systemctl::MAX_LOG_BOOTS),
+                );
+            }
+        },
+    };
+
+    let filter = match super::parse_log_filter(query_params) {
+        Ok(filter) => filter,
+        Err(msg) => return error_response(400, &msg),
+    };
+
+    let context = match query_params.get("context") {
+        None => 0,
+        Some(value) => match value.parse::<usize>() {
+            Ok(n) if n <= MAX_CONTEXT_LINES => n,
+            _ => {
+                return error_response(400, &format!("Invalid context: expected 0-{}", MAX_CONTEXT_LINES));
+            }
+        },
+    };
+
+    let namespace = query_params.get("namespace").map(|s| s.as_str()).filter(|s| !s.is_empty());
+    let after_cursor = query_params
+        .get("after_cursor")
+        .map(|s| super::decode_path_segment(s))
+        .filter(|s| !s.is_empty());
+
+    let since = match boots {
+        Some(boots) => match crate::systemctl::boot_range_since(executor.clone(), boots).await {
+            Ok(since) => since,
+            Err(e) => return error_response(500, &format!("Failed to list boots: {}", e)),
+        },
+        None => None,
+    };
+
     // Get logs
-    match crate::systemctl::get_logs(executor, service_name, lines).await {
+    let result = crate::systemctl::get_logs_since(
+        executor.clone(),
+        service_name,
+        lines,
+        since.as_deref(),
+        after_cursor.as_deref(),
+        namespace,
+    )
+    .await;
+
+    // Nothing new past the cursor: hand the same cursor back so the next tail resumes there
+    let newest_cursor = match &result {
+        Ok(logs) => logs.iter().rev().find_map(|entry| entry.cursor.clone()).or(after_cursor),
+        Err(_) => None,
+    };
+
+    let mut response = match result {
+        Ok(logs) if filter.is_empty() => json_response(200, logs),
+        Ok(logs) => {
+            // Filtering happens over the fetched window so context lines around a match are available;
+            // the total comes from a separate count over the whole journal
+            let matches = crate::systemctl::count_matching_logs(executor, service_name, since.as_deref(), namespace, &filter)
+                .await
+                .map_err(|e| eprintln!("Failed to count matching logs for {}: {}", service_name, e))
+                .ok();
+
+            json_response(200, FilteredLogsResponse {
+                entries: filter_with_context(logs, &filter, context),
+                total_matches: matches.map(|m| m.count),
+                total_matches_capped: matches.is_some_and(|m| m.capped),
+            })
+        }
+        Err(e @ (ServiceError::InvalidNamespace(_) | ServiceError::InvalidCursor(_))) => {
+            error_response(400, &e.to_string())
+        }
+        Err(ServiceError::ServiceNotFound(_)) => {
+            error_response(404, "Service not found")
+        }
+        Err(e) => {
+            error_response(500, &format!("Failed to get logs: {}", e))
+        }
+    }?;
+
+    if let Some(cursor) = newest_cursor {
+        response.headers.insert(JOURNAL_CURSOR_HEADER.to_string(), cursor);
+    }
+
+    Ok(response)
+}
+
+/// Handle GET /services/:name/properties?names=MemoryMax,TasksCurrent
+pub async fn handle_get_properties<E: CommandExecutor>(
+    executor: Arc<E>,
+    service_name: &str,
+    query_params: &std::collections::HashMap<String, String>,
+) -> Result<HttpResponse> {
+    let names: Vec<String> = query_params
+        .get("names")
+        .map(|s| {
+            s.split(',')
+                .map(|name| super::decode_path_segment(name.trim()))
+                .filter(|name| !name.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    match crate::systemctl::get_service_properties(executor, service_name, &names).await {
+        Ok(properties) => json_response(200, properties),
+        Err(ServiceError::InvalidProperty(name)) => {
+            error_response(400, &format!("Property not allowed: {}", name))
+        }
+        Err(ServiceError::InvalidServiceName(msg)) => {
+            error_response(400, &msg)
+        }
+        Err(ServiceError::ServiceNotFound(_)) => {
+            error_response(404, "Service not found")
+        }
+        Err(e) => {
+            error_response(500, &format!("Failed to get properties: {}", e))
+        }
+    }
+}
+
+/// Handle GET /services/:name/summary - `systemctl status`-style detail view
+pub async fn handle_get_summary<E: CommandExecutor>(
+    executor: Arc<E>,
+    clock: &dyn Clock,
+    service_name: &str,
+) -> Result<HttpResponse> {
+    match crate::systemctl::get_service_summary(executor, service_name, clock).await {
+        Ok(summary) => json_response(200, summary),
+        Err(ServiceError::InvalidServiceName(msg)) => {
+            error_response(400, &msg)
+        }
+        Err(ServiceError::ServiceNotFound(_)) => {
+            error_response(404, "Service not found")
+        }
+        Err(e) => {
+            error_response(500, &format!("Failed to get service summary: {}", e))
+        }
+    }
+}
+
+/// Handle GET /services/logs?units=a.service,b.service&lines=200 - interleaved logs
+pub async fn handle_get_multi_logs<E: CommandExecutor>(
+    executor: Arc<E>,
+    query_params: &std::collections::HashMap<String, String>,
+) -> Result<HttpResponse> {
+    let units = super::parse_units_param(query_params);
+
+    let lines = query_params
+        .get("lines")
+        .and_then(|s| s.parse::<u32>().ok())
+        .unwrap_or(100);
+
+    match crate::systemctl::get_logs_for_units(executor, &units, lines).await {
         Ok(logs) => json_response(200, logs),
+        Err(ServiceError::InvalidServiceName(msg)) => {
+            error_response(400, &msg)
+        }
         Err(ServiceError::ServiceNotFound(_)) => {
             error_response(404, "Service not found")
         }
@@ -133,24 +916,140 @@ pub async fn handle_get_logs<E: CommandExecutor>(
     }
 }
 
-/// Helper: Get watched services from KV storage
-async fn get_watched_services(kv_store: &dyn PluginKvStore) -> Result<Vec<String>> {
-    match kv_store.get("watched_services").await? {
-        Some(json_str) => {
-            let services: Vec<String> = serde_json::from_str(&json_str)?;
-            Ok(services)
+/// Handle GET /services/:name/logs?raw=true and GET /services/logs?units=..&raw=true
+///
+/// Passes journald's JSON lines through untouched as NDJSON, for checking which
+/// fields an entry actually carries. Only available while debug mode is on.
+pub async fn handle_get_raw_logs<E: CommandExecutor>(
+    executor: Arc<E>,
+    kv_store: &dyn PluginKvStore,
+    units: &[String],
+    query_params: &std::collections::HashMap<String, String>,
+) -> Result<HttpResponse> {
+    if !super::is_debug_mode(kv_store).await? {
+        let error_obj = serde_json::json!({
+            "success": false,
+            "code": "debug_disabled",
+            "error": "Raw journal output requires debug mode"
+        });
+        return json_response(403, error_obj);
+    }
+
+    let lines = query_params
+        .get("lines")
+        .and_then(|s| s.parse::<u32>().ok())
+        .unwrap_or(100);
+
+    match crate::systemctl::get_raw_logs_for_units(executor, units, lines).await {
+        Ok(raw_lines) => {
+            let mut body = raw_lines.join("\n");
+            if !body.is_empty() {
+                body.push('\n');
+            }
+
+            let mut headers = std::collections::HashMap::new();
+            headers.insert("Content-Type".to_string(), "application/x-ndjson".to_string());
+
+            Ok(HttpResponse {
+                status: 200,
+                headers,
+                body: Some(body),
+            })
+        }
+        Err(ServiceError::InvalidServiceName(msg)) => {
+            error_response(400, &msg)
+        }
+        Err(ServiceError::ServiceNotFound(_)) => {
+            error_response(404, "Service not found")
+        }
+        Err(e) => {
+            error_response(500, &format!("Failed to get logs: {}", e))
         }
-        None => Ok(Vec::new()),
     }
 }
 
-/// Helper: Save watched services to KV storage
+/// Adds a `recent_logs` array to a JSON error response
+fn attach_recent_logs(response: &mut HttpResponse, logs: &[crate::systemctl::LogEntry]) -> Result<()> {
+    let Some(body) = response.body.as_deref() else {
+        return Ok(());
+    };
+
+    let mut value: serde_json::Value = serde_json::from_str(body)?;
+    value["recent_logs"] = serde_json::to_value(logs)?;
+    response.body = Some(serde_json::to_string(&value)?);
+
+    Ok(())
+}
+
+/// Handle GET /services/activity?since=1 hour ago - lifecycle events of watched services
+pub async fn handle_get_activity<E: CommandExecutor>(
+    executor: Arc<E>,
+    kv_store: &dyn PluginKvStore,
+    query_params: &std::collections::HashMap<String, String>,
+) -> Result<HttpResponse> {
+    let since = query_params
+        .get("since")
+        .map(|s| super::decode_path_segment(&s.replace('+', " ")))
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_ACTIVITY_SINCE.to_string());
+
+    // journalctl time specs only need these; anything else is a typo or an attack
+    if !since.chars().all(|c| c.is_ascii_alphanumeric() || " -:+.".contains(c)) {
+        return error_response(400, &format!("Invalid since: {}", since));
+    }
+
+    let watched_services = get_watched_services(kv_store).await?;
+    if watched_services.is_empty() {
+        return json_response(200, Vec::<crate::systemctl::ActivityEvent>::new());
+    }
+
+    match crate::systemctl::get_activity(executor, &watched_services, &since).await {
+        Ok(events) => json_response(200, events),
+        Err(ServiceError::InvalidServiceName(msg)) => {
+            error_response(400, &msg)
+        }
+        Err(e) => {
+            error_response(500, &format!("Failed to get activity: {}", e))
+        }
+    }
+}
+
+/// Handle GET /audit - recent mutating actions, newest first
+pub async fn handle_get_audit(kv_store: &dyn PluginKvStore) -> Result<HttpResponse> {
+    let mut entries = load_audit_log(kv_store).await?;
+    entries.reverse();
+
+    json_response(200, entries)
+}
+
+/// Helper: Record the outcome of a service action in the audit log
+/// A failing audit write is logged but never fails the action itself
+async fn record_audit<T>(
+    kv_store: &dyn PluginKvStore,
+    clock: &dyn Clock,
+    service_name: &str,
+    action: &str,
+    result: &Result<T>,
+) {
+    let entry = AuditEntry::from_result(service_name, action, result, clock);
+
+    if let Err(e) = append_audit(kv_store, entry).await {
+        eprintln!("Failed to write audit entry for {} {}: {}", action, service_name, e);
+    }
+}
+
+/// Helper: Get watched services from the plugin settings
+async fn get_watched_services(kv_store: &dyn PluginKvStore) -> Result<Vec<String>> {
+    Ok(PluginSettings::load(kv_store).await?.watched_services)
+}
+
+/// Helper: Save watched services to the plugin settings
 #[allow(dead_code)]
 pub async fn save_watched_services(
     kv_store: &dyn PluginKvStore,
     services: &[String],
 ) -> Result<()> {
-    let json_str = serde_json::to_string(services)?;
-    kv_store.set("watched_services", &json_str).await?;
-    Ok(())
+    let mut settings = PluginSettings::load(kv_store).await?;
+    settings.watched_services = services.to_vec();
+    settings.save(kv_store).await
 }
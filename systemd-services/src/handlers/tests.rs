@@ -1,6 +1,10 @@
 // Tests for HTTP handlers
 
 use super::*;
+use crate::circuit_breaker::CircuitBreaker;
+use crate::clock::SystemClock;
+use crate::refresh_hint::RefreshAdvisor;
+use crate::settings::PluginSettings;
 use crate::systemctl::{CommandOutput, MockCommandExecutor};
 use std::sync::Arc;
 use toru_plugin_api::{PluginKvStore, PluginResult};
@@ -46,7 +50,7 @@ async fn test_get_services_empty_list() {
     let executor = Arc::new(MockCommandExecutor::new());
     let kv_store = TestKvStore::new();
 
-    let response = services::handle_get_services(executor, &kv_store).await.unwrap();
+    let response = services::handle_get_services(executor, &kv_store, &SystemClock, &RefreshAdvisor::new(), &CircuitBreaker::new()).await.unwrap();
 
     assert_eq!(response.status, 200);
     assert!(response.body.is_some());
@@ -61,10 +65,10 @@ async fn test_get_services_with_watched_services() {
     let executor = MockCommandExecutor::new()
         .with_response(
             "systemctl",
-            &["show", "nginx.service", "--property=ActiveState,SubState,MainPID,ActiveEnterTimestamp"],
+            &["show", "nginx.service", "--timestamp=unix", "--property=Description,LoadState,ActiveState,SubState,MainPID,ActiveEnterTimestamp,ActiveEnterTimestampMonotonic,TasksCurrent,TasksMax"],
             CommandOutput {
                 exit_code: 0,
-                stdout: "ActiveState=active\nSubState=running\nMainPID=1234\nActiveEnterTimestamp=Wed 2024-01-10 10:00:00 UTC\n".to_string(),
+                stdout: "ActiveState=active\nSubState=running\nMainPID=1234\nActiveEnterTimestamp=@1704880800\n".to_string(),
                 stderr: String::new(),
             },
         );
@@ -76,7 +80,7 @@ async fn test_get_services_with_watched_services() {
     data.insert("watched_services".to_string(), r#"["nginx.service"]"#.to_string());
     let kv_store = TestKvStore::with_data(data);
 
-    let response = services::handle_get_services(executor, &kv_store).await.unwrap();
+    let response = services::handle_get_services(executor, &kv_store, &SystemClock, &RefreshAdvisor::new(), &CircuitBreaker::new()).await.unwrap();
 
     assert_eq!(response.status, 200);
     let body: Vec<services::ServiceStatusResponse> =
@@ -88,12 +92,208 @@ async fn test_get_services_with_watched_services() {
     assert_eq!(body[0].active_state, "active");
 }
 
+#[tokio::test]
+async fn test_services_snapshot_hash_tracks_state_changes() {
+    let show = |name: &'static str| {
+        ["show", name, "--timestamp=unix", "--property=Description,LoadState,ActiveState,SubState,MainPID,ActiveEnterTimestamp,ActiveEnterTimestampMonotonic,TasksCurrent,TasksMax"]
+    };
+    let executor_with = |nginx_state: &str| {
+        Arc::new(
+            MockCommandExecutor::new()
+                .with_stdout("systemctl", &show("nginx.service"), nginx_state)
+                .with_stdout("systemctl", &show("redis.service"), "LoadState=loaded\nActiveState=active\nSubState=running\nMainPID=7\n"),
+        )
+    };
+    let mut data = std::collections::HashMap::new();
+    data.insert("watched_services".to_string(), r#"["nginx.service","redis.service"]"#.to_string());
+    let kv_store = TestKvStore::with_data(data);
+    let breaker = CircuitBreaker::new();
+
+    let snapshot = |executor| {
+        let kv_store = &kv_store;
+        let breaker = &breaker;
+        async move {
+            let response = services::handle_get_services_snapshot(executor, kv_store, &SystemClock, breaker).await.unwrap();
+            assert_eq!(response.status, 200);
+            serde_json::from_str::<services::ServicesSnapshotResponse>(&response.body.unwrap()).unwrap()
+        }
+    };
+
+    let running = "LoadState=loaded\nActiveState=active\nSubState=running\nMainPID=1234\n";
+    let first = snapshot(executor_with(running)).await;
+    let second = snapshot(executor_with(running)).await;
+    assert_eq!(first.hash, second.hash);
+    assert_eq!(first.services, 2);
+    assert_eq!(first.states.get("running"), Some(&2));
+
+    let failed = snapshot(executor_with("LoadState=loaded\nActiveState=failed\nSubState=failed\nMainPID=0\n")).await;
+    assert_ne!(failed.hash, first.hash);
+    assert_eq!(failed.states.get("failed"), Some(&1));
+}
+
+#[tokio::test]
+async fn test_get_services_reports_missing_and_masked_units() {
+    let show = |name: &'static str| {
+        ["show", name, "--timestamp=unix", "--property=Description,LoadState,ActiveState,SubState,MainPID,ActiveEnterTimestamp,ActiveEnterTimestampMonotonic,TasksCurrent,TasksMax"]
+    };
+    let executor = Arc::new(
+        MockCommandExecutor::new()
+            .with_stdout("systemctl", &show("deleted.service"), "LoadState=not-found\nActiveState=inactive\nSubState=dead\n")
+            .with_stdout("systemctl", &show("masked.service"), "LoadState=masked\nActiveState=inactive\nSubState=dead\n")
+            .with_stdout("systemctl", &show("stopped.service"), "LoadState=loaded\nActiveState=inactive\nSubState=dead\n"),
+    );
+    let mut data = std::collections::HashMap::new();
+    data.insert(
+        "watched_services".to_string(),
+        r#"["deleted.service","masked.service","stopped.service","broken.service"]"#.to_string(),
+    );
+    let kv_store = TestKvStore::with_data(data);
+
+    let response = services::handle_get_services(executor, &kv_store, &SystemClock, &RefreshAdvisor::new(), &CircuitBreaker::new()).await.unwrap();
+    let body: Vec<services::ServiceStatusResponse> =
+        serde_json::from_str(&response.body.unwrap()).unwrap();
+
+    let statuses: Vec<(&str, &str, &str)> = body
+        .iter()
+        .map(|s| (s.name.as_str(), s.status.as_str(), s.load_state.as_str()))
+        .collect();
+    assert_eq!(statuses, vec![
+        ("deleted.service", "missing", "not-found"),
+        ("masked.service", "masked", "masked"),
+        ("stopped.service", "inactive", "loaded"),
+        // The query itself failed: nothing is known about the unit
+        ("broken.service", "unknown", "unknown"),
+    ]);
+}
+
+#[tokio::test]
+async fn test_get_services_refresh_hint_backs_off() {
+    let executor = Arc::new(MockCommandExecutor::new().with_stdout(
+        "systemctl",
+        &["show", "nginx.service", "--timestamp=unix", "--property=Description,LoadState,ActiveState,SubState,MainPID,ActiveEnterTimestamp,ActiveEnterTimestampMonotonic,TasksCurrent,TasksMax"],
+        "ActiveState=active\nSubState=running\n",
+    ));
+    let mut data = std::collections::HashMap::new();
+    data.insert("watched_services".to_string(), r#"["nginx.service"]"#.to_string());
+    let kv_store = TestKvStore::with_data(data);
+    let advisor = RefreshAdvisor::new();
+
+    let mut hints = Vec::new();
+    for _ in 0..3 {
+        let response = services::handle_get_services(executor.clone(), &kv_store, &SystemClock, &advisor, &CircuitBreaker::new()).await.unwrap();
+        hints.push(response.headers.get("X-Refresh-After").cloned().unwrap());
+    }
+
+    assert_eq!(hints, vec!["10", "20", "40"]);
+}
+
+/// Fails every command like a timed-out D-Bus call, counting the systemctl attempts
+struct FailingExecutor {
+    calls: std::sync::atomic::AtomicUsize,
+}
+
+#[async_trait::async_trait]
+impl crate::systemctl::CommandExecutor for FailingExecutor {
+    async fn execute(&self, cmd: &str, _args: &[&str]) -> crate::error::Result<CommandOutput> {
+        if cmd == "systemctl" {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        }
+        Err(crate::error::ServiceError::Timeout("Connection timed out".to_string()))
+    }
+}
+
+#[tokio::test]
+async fn test_get_services_breaker_stops_querying_failing_unit() {
+    use std::sync::atomic::Ordering;
+
+    let executor = Arc::new(FailingExecutor { calls: std::sync::atomic::AtomicUsize::new(0) });
+    let mut data = std::collections::HashMap::new();
+    data.insert("watched_services".to_string(), r#"["nginx.service"]"#.to_string());
+    let kv_store = TestKvStore::with_data(data);
+    let advisor = RefreshAdvisor::new();
+    let breaker = CircuitBreaker::with_settings(3, std::time::Duration::from_millis(100));
+
+    for _ in 0..5 {
+        let response = services::handle_get_services(executor.clone(), &kv_store, &SystemClock, &advisor, &breaker).await.unwrap();
+        let body: Vec<services::ServiceStatusResponse> = serde_json::from_str(&response.body.unwrap()).unwrap();
+        assert_eq!(body[0].status, "unknown");
+    }
+    // Two polls were answered from the open breaker without shelling out
+    assert_eq!(executor.calls.load(Ordering::SeqCst), 3);
+
+    tokio::time::sleep(std::time::Duration::from_millis(120)).await;
+    services::handle_get_services(executor.clone(), &kv_store, &SystemClock, &advisor, &breaker).await.unwrap();
+    assert_eq!(executor.calls.load(Ordering::SeqCst), 4);
+}
+
+/// Answers every `systemctl show` after a short delay, tracking how many calls overlap
+struct SlowStatusExecutor {
+    queried: std::sync::Mutex<Vec<String>>,
+    in_flight: std::sync::atomic::AtomicUsize,
+    max_in_flight: std::sync::atomic::AtomicUsize,
+}
+
+#[async_trait::async_trait]
+impl crate::systemctl::CommandExecutor for SlowStatusExecutor {
+    async fn execute(&self, cmd: &str, args: &[&str]) -> crate::error::Result<CommandOutput> {
+        use std::sync::atomic::Ordering;
+
+        if cmd != "systemctl" {
+            return Ok(CommandOutput { exit_code: 0, stdout: String::new(), stderr: String::new() });
+        }
+
+        let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(now, Ordering::SeqCst);
+        self.queried.lock().unwrap().push(args[1].to_string());
+
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+        Ok(CommandOutput {
+            exit_code: 0,
+            stdout: "ActiveState=active\nSubState=running\nMainPID=1\nActiveEnterTimestamp=\n".to_string(),
+            stderr: String::new(),
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_get_services_queries_watched_services_concurrently() {
+    let executor = Arc::new(SlowStatusExecutor {
+        queried: std::sync::Mutex::new(Vec::new()),
+        in_flight: std::sync::atomic::AtomicUsize::new(0),
+        max_in_flight: std::sync::atomic::AtomicUsize::new(0),
+    });
+
+    let mut data = std::collections::HashMap::new();
+    data.insert(
+        "watched_services".to_string(),
+        r#"["a.service","b.service","c.service","d.service"]"#.to_string(),
+    );
+    let kv_store = TestKvStore::with_data(data);
+
+    let response = services::handle_get_services(executor.clone(), &kv_store, &SystemClock, &RefreshAdvisor::new(), &CircuitBreaker::new()).await.unwrap();
+
+    assert_eq!(response.status, 200);
+    let body: Vec<services::ServiceStatusResponse> =
+        serde_json::from_str(&response.body.unwrap()).unwrap();
+
+    // Results keep the watched-list order regardless of completion order
+    let names: Vec<&str> = body.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["a.service", "b.service", "c.service", "d.service"]);
+
+    let mut queried = executor.queried.lock().unwrap().clone();
+    queried.sort();
+    assert_eq!(queried, vec!["a.service", "b.service", "c.service", "d.service"]);
+    assert!(executor.max_in_flight.load(std::sync::atomic::Ordering::SeqCst) > 1);
+}
+
 #[tokio::test]
 async fn test_get_services_handles_failures_gracefully() {
     let executor = MockCommandExecutor::new()
         .with_response(
             "systemctl",
-            &["show", "nonexistent.service", "--property=ActiveState,SubState,MainPID,ActiveEnterTimestamp"],
+            &["show", "nonexistent.service", "--timestamp=unix", "--property=Description,LoadState,ActiveState,SubState,MainPID,ActiveEnterTimestamp,ActiveEnterTimestampMonotonic,TasksCurrent,TasksMax"],
             CommandOutput {
                 exit_code: 5,
                 stdout: String::new(),
@@ -107,7 +307,7 @@ async fn test_get_services_handles_failures_gracefully() {
     data.insert("watched_services".to_string(), r#"["nonexistent.service"]"#.to_string());
     let kv_store = TestKvStore::with_data(data);
 
-    let response = services::handle_get_services(executor, &kv_store).await.unwrap();
+    let response = services::handle_get_services(executor, &kv_store, &SystemClock, &RefreshAdvisor::new(), &CircuitBreaker::new()).await.unwrap();
 
     assert_eq!(response.status, 200);
     let body: Vec<services::ServiceStatusResponse> =
@@ -134,7 +334,7 @@ async fn test_get_available_services() {
 
     let executor = Arc::new(executor);
 
-    let response = services::handle_get_available_services(executor).await.unwrap();
+    let response = services::handle_get_available_services(executor, &HashMap::new()).await.unwrap();
 
     assert_eq!(response.status, 200);
     let body: Vec<crate::systemctl::ServiceInfo> =
@@ -144,6 +344,55 @@ async fn test_get_available_services() {
     assert_eq!(body[0].name, "nginx.service");
 }
 
+#[tokio::test]
+async fn test_get_available_services_filters_by_state() {
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_stdout(
+            "systemctl",
+            &["list-units", "--type=service", "--all", "--state=failed", "--no-pager", "--plain", "--no-legend"],
+            "backup.service    loaded failed failed Nightly backup\n",
+        )
+    );
+    let query = HashMap::from([("state".to_string(), "failed".to_string())]);
+
+    let response = services::handle_get_available_services(executor, &query).await.unwrap();
+
+    assert_eq!(response.status, 200);
+    let body: Vec<crate::systemctl::ServiceInfo> = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body.len(), 1);
+    assert_eq!(body[0].active_state, "failed");
+}
+
+#[tokio::test]
+async fn test_get_available_services_rejects_unknown_state() {
+    // No command is mocked: an invalid state must never reach systemctl
+    let executor = Arc::new(MockCommandExecutor::new());
+
+    for state in ["broken", "failed,--all", "--user"] {
+        let query = HashMap::from([("state".to_string(), state.to_string())]);
+        let response = services::handle_get_available_services(executor.clone(), &query).await.unwrap();
+        assert_eq!(response.status, 400, "{}", state);
+    }
+}
+
+#[tokio::test]
+async fn test_get_events_times_out_without_changes() {
+    let executor = Arc::new(MockCommandExecutor::new());
+    let kv_store = TestKvStore::new();
+
+    let mut params = std::collections::HashMap::new();
+    params.insert("wait".to_string(), "0".to_string());
+
+    let hub = crate::events::EventHub::new();
+    let response = services::handle_get_events(executor, &kv_store, &SystemClock, &hub, &params).await.unwrap();
+
+    assert_eq!(response.status, 200);
+    let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body["changed"], false);
+    assert_eq!(body["changes"].as_array().unwrap().len(), 0);
+    assert_eq!(body["version"].as_str().unwrap().len(), 16);
+}
+
 #[tokio::test]
 async fn test_service_action_start() {
     let executor = MockCommandExecutor::new()
@@ -158,8 +407,9 @@ async fn test_service_action_start() {
         );
 
     let executor = Arc::new(executor);
+    let kv_store = TestKvStore::new();
 
-    let response = services::handle_service_action(executor, "nginx.service", "start").await.unwrap();
+    let response = services::handle_service_action(executor, &kv_store, &SystemClock, "nginx.service", "start", false).await.unwrap();
 
     assert_eq!(response.status, 200);
     let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
@@ -167,6 +417,197 @@ async fn test_service_action_start() {
     assert_eq!(body["message"], "Service start successful");
 }
 
+#[tokio::test]
+async fn test_service_action_returns_new_status() {
+    let executor = MockCommandExecutor::new()
+        .with_stdout("systemctl", &["start", "nginx.service"], "")
+        .with_stdout(
+            "systemctl",
+            &["show", "nginx.service", "--timestamp=unix", "--property=Description,LoadState,ActiveState,SubState,MainPID,ActiveEnterTimestamp,ActiveEnterTimestampMonotonic,TasksCurrent,TasksMax"],
+            "Description=nginx\nActiveState=active\nSubState=running\nMainPID=1234\nActiveEnterTimestamp=\n",
+        );
+
+    let executor = Arc::new(executor);
+    let kv_store = TestKvStore::new();
+
+    let response = services::handle_service_action(executor, &kv_store, &SystemClock, "nginx.service", "start", false).await.unwrap();
+
+    assert_eq!(response.status, 200);
+    let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body["data"]["status"]["name"], "nginx.service");
+    assert_eq!(body["data"]["status"]["active_state"], "active");
+    assert_eq!(body["data"]["status"]["status"], "running");
+}
+
+#[tokio::test]
+async fn test_service_action_without_status_still_succeeds() {
+    // The mock has no `systemctl show` response, so the status query fails
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_stdout("systemctl", &["restart", "nginx.service"], "")
+    );
+    let kv_store = TestKvStore::new();
+
+    let response = services::handle_service_action(executor, &kv_store, &SystemClock, "nginx.service", "restart", false).await.unwrap();
+
+    assert_eq!(response.status, 200);
+    let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body["success"], true);
+    assert!(body["data"]["status"].is_null());
+}
+
+/// Records when each non-`show` systemctl command starts; every command succeeds
+struct RecordingExecutor {
+    actions: std::sync::Mutex<Vec<(String, std::time::Instant)>>,
+}
+
+#[async_trait::async_trait]
+impl crate::systemctl::CommandExecutor for RecordingExecutor {
+    async fn execute(&self, _cmd: &str, args: &[&str]) -> crate::error::Result<CommandOutput> {
+        if args[0] != "show" {
+            self.actions.lock().unwrap().push((args[1].to_string(), std::time::Instant::now()));
+        }
+
+        Ok(CommandOutput {
+            exit_code: 0,
+            stdout: "ActiveState=active\nSubState=running\nMainPID=1\nActiveEnterTimestamp=\n".to_string(),
+            stderr: String::new(),
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_bulk_action_staggers_launches() {
+    let executor = Arc::new(RecordingExecutor { actions: std::sync::Mutex::new(Vec::new()) });
+    let kv_store = TestKvStore::new();
+    let limiter = crate::rate_limit::RateLimiter::new();
+    let body = r#"{"action":"restart","services":["a.service","b.service","c.service"],"stagger_ms":40}"#;
+
+    let response = services::handle_bulk_action(executor.clone(), &kv_store, &SystemClock, &limiter, body).await.unwrap();
+
+    assert_eq!(response.status, 200);
+    let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body["success"], true);
+    let results: Vec<services::BulkActionResult> = serde_json::from_value(body["results"].clone()).unwrap();
+    let names: Vec<&str> = results.iter().map(|r| r.service.as_str()).collect();
+    assert_eq!(names, vec!["a.service", "b.service", "c.service"]);
+    assert!(results.iter().all(|r| r.status == 200 && r.response["data"]["action"] == "restart"));
+
+    // Launched in request order, at least the stagger apart
+    let actions = executor.actions.lock().unwrap().clone();
+    let launched: Vec<&str> = actions.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(launched, vec!["a.service", "b.service", "c.service"]);
+    for pair in actions.windows(2) {
+        assert!(pair[1].1 - pair[0].1 >= std::time::Duration::from_millis(35));
+    }
+}
+
+#[tokio::test]
+async fn test_bulk_restart_in_dependency_order() {
+    // web needs api and db, api needs db
+    let list_deps = |unit: &str, deps: &str| {
+        (unit.to_string(), format!("{}\n{}", unit, deps))
+    };
+    let mut executor = MockCommandExecutor::new();
+    for (unit, output) in [
+        list_deps("web.service", "  api.service\n  db.service\n  network.target\n"),
+        list_deps("api.service", "  db.service\n"),
+        list_deps("db.service", "  network.target\n"),
+    ] {
+        executor = executor.with_stdout(
+            "systemctl",
+            &["list-dependencies", &unit, "--plain", "--no-legend", "--no-pager"],
+            &output,
+        );
+    }
+    for unit in ["web.service", "api.service", "db.service"] {
+        executor = executor.with_stdout("systemctl", &["restart", unit], "");
+    }
+
+    let kv_store = TestKvStore::new();
+    let limiter = crate::rate_limit::RateLimiter::new();
+    let body = r#"{"action":"restart","services":["web.service","api.service","db.service"],"dependency_order":true}"#;
+
+    let response = services::handle_bulk_action(Arc::new(executor), &kv_store, &SystemClock, &limiter, body).await.unwrap();
+
+    assert_eq!(response.status, 200);
+    let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body["success"], true);
+    let order: Vec<&str> = body["results"].as_array().unwrap().iter().map(|r| r["service"].as_str().unwrap()).collect();
+    assert_eq!(order, vec!["db.service", "api.service", "web.service"]);
+
+    // The audit log records the restarts in the order they ran
+    let audit = crate::audit::load_audit_log(&kv_store).await.unwrap();
+    let audited: Vec<&str> = audit.iter().map(|e| e.unit.as_str()).collect();
+    assert_eq!(audited, vec!["db.service", "api.service", "web.service"]);
+}
+
+#[tokio::test]
+async fn test_bulk_action_reports_each_service() {
+    let executor = Arc::new(
+        MockCommandExecutor::new()
+            .with_stdout("systemctl", &["stop", "nginx.service"], "")
+            .with_error("systemctl", &["stop", "missing.service"], 5, "Unit missing.service not loaded.")
+    );
+    let mut data = std::collections::HashMap::new();
+    data.insert("denied_units".to_string(), r#"["sshd.service"]"#.to_string());
+    let kv_store = TestKvStore::with_data(data);
+    let limiter = crate::rate_limit::RateLimiter::new();
+    let body = r#"{"action":"stop","services":["nginx.service","missing.service","sshd.service"]}"#;
+
+    let response = services::handle_bulk_action(executor, &kv_store, &SystemClock, &limiter, body).await.unwrap();
+
+    assert_eq!(response.status, 200);
+    let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body["success"], false);
+    let statuses: Vec<u64> = body["results"].as_array().unwrap().iter().map(|r| r["status"].as_u64().unwrap()).collect();
+    assert_eq!(statuses, vec![200, 404, 403]);
+}
+
+#[tokio::test]
+async fn test_bulk_action_rejects_invalid_requests() {
+    let executor = Arc::new(MockCommandExecutor::new());
+    let kv_store = TestKvStore::new();
+    let limiter = crate::rate_limit::RateLimiter::new();
+
+    for body in [
+        "not json",
+        r#"{"action":"reload","services":["nginx.service"]}"#,
+        r#"{"action":"restart","services":[]}"#,
+        r#"{"action":"restart","services":["nginx.service"],"stagger_ms":60000}"#,
+    ] {
+        let response = services::handle_bulk_action(executor.clone(), &kv_store, &SystemClock, &limiter, body).await.unwrap();
+        assert_eq!(response.status, 400, "{}", body);
+    }
+}
+
+#[test]
+fn test_response_serialization_failure_stays_server_error() {
+    // Non-string map keys can't be serialized; that's a plugin bug, not a bad request
+    let mut data = HashMap::new();
+    data.insert((1, 2), "value");
+    let err = json_response(200, data).unwrap_err();
+    assert_eq!(err.status_code(), 500);
+
+    let err = parse_json_body::<serde_json::Value>("{").unwrap_err();
+    assert!(matches!(err, crate::error::ServiceError::BadRequest(_)));
+    assert_eq!(err.status_code(), 400);
+}
+
+#[tokio::test]
+async fn test_bulk_action_rejects_oversized_body() {
+    let executor = Arc::new(MockCommandExecutor::new());
+    let kv_store = TestKvStore::new();
+    let limiter = crate::rate_limit::RateLimiter::new();
+    let services: Vec<String> = (0..super::MAX_BODY_BYTES / 10).map(|i| format!("s{:05}.service", i)).collect();
+    let body = serde_json::json!({ "action": "restart", "services": services }).to_string();
+
+    let response = services::handle_bulk_action(executor, &kv_store, &SystemClock, &limiter, &body).await.unwrap();
+
+    assert_eq!(response.status, 400);
+    let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert!(body["error"].as_str().unwrap().starts_with("Request body too large"));
+}
+
 #[tokio::test]
 async fn test_service_action_stop() {
     let executor = MockCommandExecutor::new()
@@ -181,12 +622,136 @@ async fn test_service_action_stop() {
         );
 
     let executor = Arc::new(executor);
+    let kv_store = TestKvStore::new();
+
+    let response = services::handle_service_action(executor, &kv_store, &SystemClock, "nginx.service", "stop", false).await.unwrap();
+
+    assert_eq!(response.status, 200);
+    let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body["success"], true);
+}
+
+#[tokio::test]
+async fn test_service_action_force_stop_without_escalation() {
+    let executor = Arc::new(MockCommandExecutor::new().with_stdout("systemctl", &["stop", "nginx.service"], ""));
+    let kv_store = TestKvStore::new();
+
+    let response = services::handle_service_action(executor, &kv_store, &SystemClock, "nginx.service", "stop", true).await.unwrap();
+
+    assert_eq!(response.status, 200);
+    let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body["data"]["escalated"], false);
+    assert_eq!(body["message"], "Service stop successful");
+}
+
+#[tokio::test]
+async fn test_service_action_force_stop_escalates_on_timeout() {
+    let executor = Arc::new(
+        MockCommandExecutor::new()
+            .with_timeout("systemctl", &["stop", "stuck.service"])
+            .with_stdout("systemctl", &["kill", "--signal=SIGKILL", "stuck.service"], ""),
+    );
+    let kv_store = TestKvStore::new();
+
+    let response = services::handle_service_action(executor, &kv_store, &SystemClock, "stuck.service", "stop", true).await.unwrap();
 
-    let response = services::handle_service_action(executor, "nginx.service", "stop").await.unwrap();
+    assert_eq!(response.status, 200);
+    let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body["data"]["escalated"], true);
+    assert!(body["message"].as_str().unwrap().contains("SIGKILL"));
+
+    let audit = crate::audit::load_audit_log(&kv_store).await.unwrap();
+    assert_eq!(audit.len(), 1);
+    assert_eq!(audit[0].action, "force-stop");
+}
+
+#[tokio::test]
+async fn test_service_action_stop_timeout_without_force_fails() {
+    let executor = Arc::new(MockCommandExecutor::new().with_timeout("systemctl", &["stop", "stuck.service"]));
+    let kv_store = TestKvStore::new();
+
+    let response = services::handle_service_action(executor, &kv_store, &SystemClock, "stuck.service", "stop", false).await.unwrap();
+
+    assert_eq!(response.status, 500);
+}
+
+#[tokio::test]
+async fn test_service_action_force_only_for_stop() {
+    let executor = Arc::new(MockCommandExecutor::new());
+    let kv_store = TestKvStore::new();
+
+    let response = services::handle_service_action(executor, &kv_store, &SystemClock, "nginx.service", "restart", true).await.unwrap();
+
+    assert_eq!(response.status, 400);
+}
+
+#[tokio::test]
+async fn test_service_action_missing_unit_is_404() {
+    // Only the LoadState query is mocked; the start must not be attempted
+    let executor = Arc::new(MockCommandExecutor::new().with_stdout(
+        "systemctl",
+        &["show", "gone.service", "--property=LoadState"],
+        "LoadState=not-found\n",
+    ));
+    let kv_store = TestKvStore::new();
+
+    let response = services::handle_service_action(executor, &kv_store, &SystemClock, "gone.service", "start", false).await.unwrap();
+
+    assert_eq!(response.status, 404);
+    assert!(crate::audit::load_audit_log(&kv_store).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_stop_and_mask_runs_both_steps() {
+    let executor = Arc::new(
+        MockCommandExecutor::new()
+            .with_stdout("systemctl", &["stop", "nginx.service"], "")
+            .with_stdout("systemctl", &["mask", "nginx.service"], "Created symlink /etc/systemd/system/nginx.service → /dev/null.")
+    );
+    let kv_store = TestKvStore::new();
+
+    let response = services::handle_service_action(executor, &kv_store, &SystemClock, "nginx.service", "stop-and-mask", false).await.unwrap();
 
     assert_eq!(response.status, 200);
     let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
     assert_eq!(body["success"], true);
+    assert_eq!(body["data"]["action"], "stop-and-mask");
+    assert_eq!(body["data"]["completed_steps"], serde_json::json!(["stop", "mask"]));
+}
+
+#[tokio::test]
+async fn test_stop_and_mask_reports_failed_mask_after_stop() {
+    let executor = Arc::new(
+        MockCommandExecutor::new()
+            .with_stdout("systemctl", &["stop", "nginx.service"], "")
+            .with_error("systemctl", &["mask", "nginx.service"], 1, "Failed to mask unit: Read-only file system")
+    );
+    let kv_store = TestKvStore::new();
+
+    let response = services::handle_service_action(executor, &kv_store, &SystemClock, "nginx.service", "stop-and-mask", false).await.unwrap();
+
+    assert_eq!(response.status, 500);
+    let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body["success"], false);
+    assert_eq!(body["failed_step"], "mask");
+    assert_eq!(body["completed_steps"], serde_json::json!(["stop"]));
+    assert!(body["error"].as_str().unwrap().contains("after stop succeeded"));
+
+    let audit = crate::audit::load_audit_log(&kv_store).await.unwrap();
+    assert_eq!(audit.len(), 1);
+    assert_eq!(audit[0].action, "stop-and-mask");
+}
+
+#[tokio::test]
+async fn test_compound_action_honors_unit_policy() {
+    let executor = Arc::new(MockCommandExecutor::new());
+    let mut data = std::collections::HashMap::new();
+    data.insert("denied_units".to_string(), r#"["sshd.service"]"#.to_string());
+    let kv_store = TestKvStore::with_data(data);
+
+    let response = services::handle_service_action(executor, &kv_store, &SystemClock, "sshd.service", "disable-and-stop", false).await.unwrap();
+
+    assert_eq!(response.status, 403);
 }
 
 #[tokio::test]
@@ -203,19 +768,24 @@ async fn test_service_action_restart() {
         );
 
     let executor = Arc::new(executor);
+    let kv_store = TestKvStore::new();
 
-    let response = services::handle_service_action(executor, "nginx.service", "restart").await.unwrap();
+    let response = services::handle_service_action(executor, &kv_store, &SystemClock, "nginx.service", "restart", false).await.unwrap();
 
     assert_eq!(response.status, 200);
     let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
     assert_eq!(body["success"], true);
+    assert_eq!(body["message"], "Service restart successful");
+    assert_eq!(body["data"]["service"], "nginx.service");
+    assert_eq!(body["data"]["action"], "restart");
 }
 
 #[tokio::test]
 async fn test_service_action_invalid() {
     let executor = Arc::new(MockCommandExecutor::new());
+    let kv_store = TestKvStore::new();
 
-    let response = services::handle_service_action(executor, "nginx.service", "invalid").await.unwrap();
+    let response = services::handle_service_action(executor, &kv_store, &SystemClock, "nginx.service", "invalid", false).await.unwrap();
 
     assert_eq!(response.status, 400);
     let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
@@ -237,8 +807,9 @@ async fn test_service_action_not_found() {
         );
 
     let executor = Arc::new(executor);
+    let kv_store = TestKvStore::new();
 
-    let response = services::handle_service_action(executor, "nonexistent.service", "start").await.unwrap();
+    let response = services::handle_service_action(executor, &kv_store, &SystemClock, "nonexistent.service", "start", false).await.unwrap();
 
     assert_eq!(response.status, 404);
     let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
@@ -260,8 +831,9 @@ async fn test_service_action_permission_denied() {
         );
 
     let executor = Arc::new(executor);
+    let kv_store = TestKvStore::new();
 
-    let response = services::handle_service_action(executor, "nginx.service", "start").await.unwrap();
+    let response = services::handle_service_action(executor, &kv_store, &SystemClock, "nginx.service", "start", false).await.unwrap();
 
     assert_eq!(response.status, 403);
     let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
@@ -270,18 +842,199 @@ async fn test_service_action_permission_denied() {
 }
 
 #[tokio::test]
-async fn test_get_logs() {
-    let executor = MockCommandExecutor::new()
-        .with_response(
-            "journalctl",
-            &["-u", "nginx.service", "-n", "50", "--no-pager", "--output=json"],
-            CommandOutput {
-                exit_code: 0,
-                stdout: r#"{"MESSAGE":"Started nginx","PRIORITY":"6","__REALTIME_TIMESTAMP":"1704902400000000"}
-{"MESSAGE":"Stopped nginx","PRIORITY":"6","__REALTIME_TIMESTAMP":"1704902500000000"}"#.to_string(),
-                stderr: String::new(),
-            },
-        );
+async fn test_service_action_denied_unit_rejected_even_if_allowlisted() {
+    // No mock responses: any attempt to shell out would fail with a 500
+    let executor = Arc::new(MockCommandExecutor::new());
+
+    let mut data = std::collections::HashMap::new();
+    data.insert("allowed_units".to_string(), r#"["*.service"]"#.to_string());
+    data.insert("denied_units".to_string(), r#"["sshd.service"]"#.to_string());
+    let kv_store = TestKvStore::with_data(data);
+
+    let response = services::handle_service_action(executor, &kv_store, &SystemClock, "sshd.service", "restart", false).await.unwrap();
+
+    assert_eq!(response.status, 403);
+    let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body["success"], false);
+    assert!(body["error"].as_str().unwrap().contains("denylist"));
+}
+
+#[tokio::test]
+async fn test_service_action_unit_outside_allowlist_rejected() {
+    let executor = Arc::new(MockCommandExecutor::new());
+
+    let mut data = std::collections::HashMap::new();
+    data.insert("allowed_units".to_string(), r#"["app-*.service"]"#.to_string());
+    let kv_store = TestKvStore::with_data(data);
+
+    let response = services::handle_service_action(executor, &kv_store, &SystemClock, "nginx.service", "stop", false).await.unwrap();
+
+    assert_eq!(response.status, 403);
+    let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert!(body["error"].as_str().unwrap().contains("allowlist"));
+}
+
+#[tokio::test]
+async fn test_successful_restart_is_audited() {
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_stdout("systemctl", &["restart", "nginx.service"], "")
+    );
+    let kv_store = TestKvStore::new();
+    let clock = crate::clock::FixedClock::new("2024-06-01T12:00:00Z".parse().unwrap());
+
+    services::handle_service_action(executor, &kv_store, &clock, "nginx.service", "restart", false).await.unwrap();
+
+    let entries = crate::audit::load_audit_log(&kv_store).await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].timestamp, "2024-06-01T12:00:00+00:00");
+    assert_eq!(entries[0].unit, "nginx.service");
+    assert_eq!(entries[0].action, "restart");
+    assert_eq!(entries[0].outcome, "success");
+    assert!(entries[0].error.is_none());
+}
+
+#[tokio::test]
+async fn test_failed_start_is_audited() {
+    let executor = MockCommandExecutor::new()
+        .with_response(
+            "systemctl",
+            &["start", "nginx.service"],
+            CommandOutput {
+                exit_code: 4,
+                stdout: String::new(),
+                stderr: "Failed to start nginx.service: Access denied".to_string(),
+            },
+        );
+
+    let executor = Arc::new(executor);
+    let kv_store = TestKvStore::new();
+
+    services::handle_service_action(executor, &kv_store, &SystemClock, "nginx.service", "start", false).await.unwrap();
+
+    let response = services::handle_get_audit(&kv_store).await.unwrap();
+    let entries: Vec<crate::audit::AuditEntry> =
+        serde_json::from_str(&response.body.unwrap()).unwrap();
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].action, "start");
+    assert_eq!(entries[0].outcome, "failure");
+    assert!(entries[0].error.is_some());
+}
+
+#[tokio::test]
+async fn test_audit_log_is_capped() {
+    let kv_store = TestKvStore::new();
+
+    for i in 0..crate::audit::MAX_AUDIT_ENTRIES + 3 {
+        let result: crate::Result<()> = Ok(());
+        let entry = crate::audit::AuditEntry::from_result(&format!("svc{}.service", i), "stop", &result, &SystemClock);
+        crate::audit::append_audit(&kv_store, entry).await.unwrap();
+    }
+
+    let entries = crate::audit::load_audit_log(&kv_store).await.unwrap();
+    assert_eq!(entries.len(), crate::audit::MAX_AUDIT_ENTRIES);
+    assert_eq!(entries[0].unit, "svc3.service");
+}
+
+#[tokio::test]
+async fn test_read_only_blocks_service_action() {
+    let mut data = std::collections::HashMap::new();
+    data.insert("read_only".to_string(), "true".to_string());
+    let kv_store = TestKvStore::with_data(data);
+
+    let response = check_read_only(&kv_store, "POST").await.unwrap();
+
+    let response = response.expect("start should be blocked in read-only mode");
+    assert_eq!(response.status, 403);
+    let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body["success"], false);
+    assert_eq!(body["code"], "read_only");
+}
+
+#[tokio::test]
+async fn test_read_only_still_serves_status() {
+    let executor = MockCommandExecutor::new()
+        .with_response(
+            "systemctl",
+            &["show", "nginx.service", "--timestamp=unix", "--property=Description,LoadState,ActiveState,SubState,MainPID,ActiveEnterTimestamp,ActiveEnterTimestampMonotonic,TasksCurrent,TasksMax"],
+            CommandOutput {
+                exit_code: 0,
+                stdout: "ActiveState=active\nSubState=running\nMainPID=1234\nActiveEnterTimestamp=\n".to_string(),
+                stderr: String::new(),
+            },
+        );
+
+    let executor = Arc::new(executor);
+
+    let mut data = std::collections::HashMap::new();
+    data.insert("read_only".to_string(), "true".to_string());
+    data.insert("watched_services".to_string(), r#"["nginx.service"]"#.to_string());
+    let kv_store = TestKvStore::with_data(data);
+
+    assert!(check_read_only(&kv_store, "GET").await.unwrap().is_none());
+
+    let response = services::handle_get_services(executor, &kv_store, &SystemClock, &RefreshAdvisor::new(), &CircuitBreaker::new()).await.unwrap();
+    assert_eq!(response.status, 200);
+}
+
+#[tokio::test]
+async fn test_read_only_disabled_by_default() {
+    let kv_store = TestKvStore::new();
+
+    let response = check_read_only(&kv_store, "POST").await.unwrap();
+    assert!(response.is_none());
+}
+
+#[tokio::test]
+async fn test_parse_action_path() {
+    assert_eq!(parse_action_path("/services/nginx.service/restart"), Some(("nginx.service", "restart")));
+    assert_eq!(parse_action_path("/services/nginx.service/stop-and-mask"), Some(("nginx.service", "stop-and-mask")));
+    assert_eq!(parse_action_path("/services/nginx.service/logs"), None);
+    assert_eq!(parse_action_path("/services/a/b/restart"), None);
+}
+
+#[tokio::test]
+async fn test_two_immediate_restarts_are_rate_limited() {
+    let limiter = crate::rate_limit::RateLimiter::new();
+    let kv_store = TestKvStore::new();
+
+    let first = check_rate_limit(&limiter, &kv_store, "nginx.service", "restart", std::time::Instant::now()).await.unwrap();
+    assert!(first.is_none());
+
+    let second = check_rate_limit(&limiter, &kv_store, "nginx.service", "restart", std::time::Instant::now()).await.unwrap();
+    let response = second.expect("second restart should be rate limited");
+    assert_eq!(response.status, 429);
+    assert_eq!(response.headers.get("Retry-After"), Some(&"2".to_string()));
+}
+
+#[tokio::test]
+async fn test_restart_after_cooldown_succeeds() {
+    let limiter = crate::rate_limit::RateLimiter::new();
+
+    let mut data = std::collections::HashMap::new();
+    data.insert("action_cooldown_ms".to_string(), "20".to_string());
+    let kv_store = TestKvStore::with_data(data);
+
+    assert!(check_rate_limit(&limiter, &kv_store, "nginx.service", "restart", std::time::Instant::now()).await.unwrap().is_none());
+    assert!(check_rate_limit(&limiter, &kv_store, "nginx.service", "restart", std::time::Instant::now()).await.unwrap().is_some());
+
+    tokio::time::sleep(std::time::Duration::from_millis(30)).await;
+    assert!(check_rate_limit(&limiter, &kv_store, "nginx.service", "restart", std::time::Instant::now()).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_get_logs() {
+    let executor = MockCommandExecutor::new()
+        .with_response(
+            "journalctl",
+            &["-u", "nginx.service", "-n", "50", "--no-pager", "--output=json"],
+            CommandOutput {
+                exit_code: 0,
+                stdout: r#"{"MESSAGE":"Started nginx","PRIORITY":"6","__REALTIME_TIMESTAMP":"1704902400000000"}
+{"MESSAGE":"Stopped nginx","PRIORITY":"6","__REALTIME_TIMESTAMP":"1704902500000000"}"#.to_string(),
+                stderr: String::new(),
+            },
+        );
 
     let executor = Arc::new(executor);
 
@@ -319,6 +1072,246 @@ async fn test_get_logs_default_lines() {
     assert_eq!(response.status, 200);
 }
 
+#[tokio::test]
+async fn test_get_logs_boots() {
+    let boots = r#"[{"index":-3,"boot_id":"a1","first_entry":1705000000000000,"last_entry":1705100000000000},{"index":-2,"boot_id":"b2","first_entry":1705200000000000,"last_entry":1705300000000000},{"index":-1,"boot_id":"c3","first_entry":1705315845000000,"last_entry":1705402245000000},{"index":0,"boot_id":"d4","first_entry":1705402300000000,"last_entry":1705405900000000}]"#;
+
+    let executor = MockCommandExecutor::new()
+        .with_stdout("journalctl", &["--list-boots", "--no-pager", "--output=json"], boots)
+        .with_stdout(
+            "journalctl",
+            &["-u", "nginx.service", "-n", "100", "--since", "@1705200000", "--no-pager", "--output=json"],
+            r#"{"MESSAGE":"Started nginx","PRIORITY":"6","__REALTIME_TIMESTAMP":"1705200001000000"}"#,
+        );
+
+    let executor = Arc::new(executor);
+
+    let mut params = std::collections::HashMap::new();
+    params.insert("boots".to_string(), "3".to_string());
+
+    let response = services::handle_get_logs(executor, "nginx.service", &params).await.unwrap();
+
+    assert_eq!(response.status, 200);
+    let body: Vec<crate::systemctl::LogEntry> =
+        serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body.len(), 1);
+}
+
+#[tokio::test]
+async fn test_get_logs_boots_invalid() {
+    for value in ["0", "-1", "11", "many"] {
+        let executor = Arc::new(MockCommandExecutor::new());
+
+        let mut params = std::collections::HashMap::new();
+        params.insert("boots".to_string(), value.to_string());
+
+        let response = services::handle_get_logs(executor, "nginx.service", &params).await.unwrap();
+        assert_eq!(response.status, 400, "boots={}", value);
+    }
+}
+
+#[tokio::test]
+async fn test_get_logs_context_lines() {
+    let stdout = [
+        ("line 1", 6), ("line 2", 6), ("line 3", 6), ("line 4", 6),
+        ("upstream timed out", 3),
+        ("line 6", 6), ("line 7", 6), ("line 8", 6),
+    ]
+    .iter()
+    .enumerate()
+    .map(|(i, (message, priority))| {
+        format!(
+            r#"{{"MESSAGE":"{}","PRIORITY":"{}","__REALTIME_TIMESTAMP":"17049024{:02}000000"}}"#,
+            message, priority, i
+        )
+    })
+    .collect::<Vec<_>>()
+    .join("\n");
+
+    let executor = Arc::new(MockCommandExecutor::new()
+        .with_stdout(
+            "journalctl",
+            &["-u", "nginx.service", "-n", "100", "--no-pager", "--output=json"],
+            &stdout,
+        )
+        .with_stdout(
+            "journalctl",
+            &["-u", "nginx.service", "-n", "10001", "-p", "0..4", "--no-pager", "--output=json", "--output-fields=PRIORITY"],
+            r#"{"PRIORITY":"3"}"#,
+        ));
+
+    let mut params = std::collections::HashMap::new();
+    params.insert("priority".to_string(), "warning".to_string());
+    params.insert("context".to_string(), "2".to_string());
+
+    let response = services::handle_get_logs(executor, "nginx.service", &params).await.unwrap();
+
+    assert_eq!(response.status, 200);
+    let body: services::FilteredLogsResponse = serde_json::from_str(&response.body.unwrap()).unwrap();
+    let messages: Vec<&str> = body.entries.iter().map(|e| e.message.as_str()).collect();
+    assert_eq!(messages, vec!["line 3", "line 4", "upstream timed out", "line 6", "line 7"]);
+}
+
+#[tokio::test]
+async fn test_get_logs_total_matches_counts_whole_journal() {
+    // Window of 2 lines holds one match; the journal as a whole has 3
+    let window = r#"{"MESSAGE":"upstream timed out","PRIORITY":"3","__REALTIME_TIMESTAMP":"1704902400000000"}
+{"MESSAGE":"request served","PRIORITY":"6","__REALTIME_TIMESTAMP":"1704902401000000"}"#;
+    let count = "{\"PRIORITY\":\"3\"}\n{\"PRIORITY\":\"3\"}\n{\"PRIORITY\":\"4\"}\n";
+
+    let executor = Arc::new(MockCommandExecutor::new()
+        .with_stdout(
+            "journalctl",
+            &["-u", "nginx.service", "-n", "2", "--no-pager", "--output=json"],
+            window,
+        )
+        .with_stdout(
+            "journalctl",
+            &["-u", "nginx.service", "-n", "10001", "--grep", "timed out", "--case-sensitive=false", "--no-pager", "--output=json", "--output-fields=PRIORITY"],
+            count,
+        ));
+
+    let mut params = std::collections::HashMap::new();
+    params.insert("lines".to_string(), "2".to_string());
+    params.insert("grep".to_string(), "timed+out".to_string());
+
+    let response = services::handle_get_logs(executor, "nginx.service", &params).await.unwrap();
+
+    assert_eq!(response.status, 200);
+    let body: services::FilteredLogsResponse = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body.entries.len(), 1);
+    assert_eq!(body.total_matches, Some(3));
+    assert!(!body.total_matches_capped);
+}
+
+#[tokio::test]
+async fn test_get_logs_total_matches_capped() {
+    let window = r#"{"MESSAGE":"upstream timed out","PRIORITY":"3","__REALTIME_TIMESTAMP":"1704902400000000"}"#;
+    // journalctl stops one past the limit
+    let count = "{\"PRIORITY\":\"3\"}\n".repeat(crate::systemctl::MAX_COUNTED_MATCHES + 1);
+
+    let executor = Arc::new(MockCommandExecutor::new()
+        .with_stdout(
+            "journalctl",
+            &["-u", "nginx.service", "-n", "1", "--no-pager", "--output=json"],
+            window,
+        )
+        .with_stdout(
+            "journalctl",
+            &["-u", "nginx.service", "-n", "10001", "--grep", "timed out", "--case-sensitive=false", "--no-pager", "--output=json", "--output-fields=PRIORITY"],
+            &count,
+        ));
+
+    let mut params = std::collections::HashMap::new();
+    params.insert("lines".to_string(), "1".to_string());
+    params.insert("grep".to_string(), "timed+out".to_string());
+
+    let response = services::handle_get_logs(executor, "nginx.service", &params).await.unwrap();
+
+    assert_eq!(response.status, 200);
+    let body: services::FilteredLogsResponse = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body.total_matches, Some(crate::systemctl::MAX_COUNTED_MATCHES));
+    assert!(body.total_matches_capped);
+}
+
+#[tokio::test]
+async fn test_get_logs_grep_multiple_patterns() {
+    let window = r#"{"MESSAGE":"upstream timed out","PRIORITY":"3","__REALTIME_TIMESTAMP":"1704902400000000"}
+{"MESSAGE":"request served","PRIORITY":"6","__REALTIME_TIMESTAMP":"1704902401000000"}
+{"MESSAGE":"connect() failed: Connection refused","PRIORITY":"3","__REALTIME_TIMESTAMP":"1704902402000000"}"#;
+    // journald ORs the patterns: one timeout and two refusals across the journal
+    let count = "{\"PRIORITY\":\"3\"}\n{\"PRIORITY\":\"3\"}\n{\"PRIORITY\":\"3\"}\n";
+
+    let executor = Arc::new(MockCommandExecutor::new()
+        .with_stdout(
+            "journalctl",
+            &["-u", "nginx.service", "-n", "3", "--no-pager", "--output=json"],
+            window,
+        )
+        .with_stdout(
+            "journalctl",
+            &["-u", "nginx.service", "-n", "10001", "--grep", "(timed out|refused)", "--case-sensitive=false", "--no-pager", "--output=json", "--output-fields=PRIORITY"],
+            count,
+        ));
+
+    let mut params = std::collections::HashMap::new();
+    params.insert("lines".to_string(), "3".to_string());
+    params.insert("grep".to_string(), "timed+out,refused".to_string());
+
+    let response = services::handle_get_logs(executor, "nginx.service", &params).await.unwrap();
+
+    assert_eq!(response.status, 200);
+    let body: services::FilteredLogsResponse = serde_json::from_str(&response.body.unwrap()).unwrap();
+    let messages: Vec<&str> = body.entries.iter().map(|e| e.message.as_str()).collect();
+    assert_eq!(messages, vec!["upstream timed out", "connect() failed: Connection refused"]);
+    assert_eq!(body.total_matches, Some(3));
+}
+
+#[tokio::test]
+async fn test_get_logs_after_cursor_tails_newer_entries() {
+    let first = r#"{"MESSAGE":"Starting","PRIORITY":"6","__REALTIME_TIMESTAMP":"1704902400000000","__CURSOR":"s=ab;i=1"}
+{"MESSAGE":"Started","PRIORITY":"6","__REALTIME_TIMESTAMP":"1704902401000000","__CURSOR":"s=ab;i=2"}"#;
+    let newer = r#"{"MESSAGE":"Reloading","PRIORITY":"6","__REALTIME_TIMESTAMP":"1704902402000000","__CURSOR":"s=ab;i=3"}"#;
+
+    let executor = Arc::new(MockCommandExecutor::new()
+        .with_stdout("journalctl", &["-u", "nginx.service", "-n", "100", "--no-pager", "--output=json"], first)
+        .with_stdout("journalctl", &["-u", "nginx.service", "-n", "100", "--after-cursor=s=ab;i=2", "--no-pager", "--output=json"], newer)
+        .with_stdout("journalctl", &["-u", "nginx.service", "-n", "100", "--after-cursor=s=ab;i=3", "--no-pager", "--output=json"], ""));
+
+    let response = services::handle_get_logs(executor.clone(), "nginx.service", &HashMap::new()).await.unwrap();
+    let cursor = response.headers[services::JOURNAL_CURSOR_HEADER].clone();
+    assert_eq!(cursor, "s=ab;i=2");
+
+    let params = HashMap::from([("after_cursor".to_string(), cursor)]);
+    let response = services::handle_get_logs(executor.clone(), "nginx.service", &params).await.unwrap();
+    assert_eq!(response.headers[services::JOURNAL_CURSOR_HEADER], "s=ab;i=3");
+    let logs: Vec<crate::systemctl::LogEntry> = serde_json::from_str(response.body.as_ref().unwrap()).unwrap();
+    let messages: Vec<&str> = logs.iter().map(|e| e.message.as_str()).collect();
+    assert_eq!(messages, vec!["Reloading"]);
+
+    // Nothing newer yet: the cursor is handed back unchanged
+    let params = HashMap::from([("after_cursor".to_string(), "s=ab;i=3".to_string())]);
+    let response = services::handle_get_logs(executor, "nginx.service", &params).await.unwrap();
+    assert_eq!(response.body.as_deref(), Some("[]"));
+    assert_eq!(response.headers[services::JOURNAL_CURSOR_HEADER], "s=ab;i=3");
+}
+
+#[tokio::test]
+async fn test_get_logs_invalid_cursor() {
+    let executor = Arc::new(MockCommandExecutor::new());
+
+    for cursor in ["--all", "s=ab i=2", "noequals"] {
+        let params = HashMap::from([("after_cursor".to_string(), cursor.to_string())]);
+        let response = services::handle_get_logs(executor.clone(), "nginx.service", &params).await.unwrap();
+        assert_eq!(response.status, 400, "{}", cursor);
+    }
+}
+
+#[tokio::test]
+async fn test_get_logs_invalid_filter_params() {
+    let too_many = ["x"; crate::log_filter::MAX_GREP_PATTERNS + 1].join(",");
+    for (key, value) in [("priority", "loud"), ("context", "21"), ("context", "-1"), ("grep", "timeout,,refused"), ("grep", too_many.as_str())] {
+        let executor = Arc::new(MockCommandExecutor::new());
+
+        let mut params = std::collections::HashMap::new();
+        params.insert(key.to_string(), value.to_string());
+
+        let response = services::handle_get_logs(executor, "nginx.service", &params).await.unwrap();
+        assert_eq!(response.status, 400, "{}={}", key, value);
+    }
+}
+
+#[tokio::test]
+async fn test_get_logs_invalid_namespace() {
+    let executor = Arc::new(MockCommandExecutor::new());
+
+    let mut params = std::collections::HashMap::new();
+    params.insert("namespace".to_string(), "../etc".to_string());
+
+    let response = services::handle_get_logs(executor, "nginx.service", &params).await.unwrap();
+    assert_eq!(response.status, 400);
+}
+
 #[tokio::test]
 async fn test_get_logs_service_not_found() {
     let executor = MockCommandExecutor::new()
@@ -363,6 +1356,28 @@ async fn test_path_without_query() {
     assert_eq!(path_without_query("/services"), "/services");
 }
 
+#[tokio::test]
+async fn test_decode_path_segment() {
+    assert_eq!(decode_path_segment("getty%40tty1.service"), "getty@tty1.service");
+    assert_eq!(decode_path_segment("nginx.service"), "nginx.service");
+    assert_eq!(decode_path_segment("100%"), "100%");
+    assert_eq!(decode_path_segment("a%zz"), "a%zz");
+}
+
+#[tokio::test]
+async fn test_service_action_decoded_template_name() {
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_stdout("systemctl", &["restart", "getty@tty1.service"], "")
+    );
+
+    let service_name = decode_path_segment("getty%40tty1.service");
+    let kv_store = TestKvStore::new();
+
+    let response = services::handle_service_action(executor, &kv_store, &SystemClock, &service_name, "restart", false).await.unwrap();
+
+    assert_eq!(response.status, 200);
+}
+
 #[tokio::test]
 async fn test_save_and_load_watched_services() {
     let kv_store = TestKvStore::new();
@@ -370,10 +1385,536 @@ async fn test_save_and_load_watched_services() {
     let services = vec!["nginx.service".to_string(), "sshd.service".to_string()];
     services::save_watched_services(&kv_store, &services).await.unwrap();
 
-    // Verify it was saved
-    let loaded = kv_store.get("watched_services").await.unwrap();
+    // Verify it was saved as part of the settings blob
+    let loaded = kv_store.get("settings").await.unwrap();
     assert!(loaded.is_some());
 
-    let loaded_services: Vec<String> = serde_json::from_str(&loaded.unwrap()).unwrap();
-    assert_eq!(loaded_services, services);
+    let settings: PluginSettings = serde_json::from_str(&loaded.unwrap()).unwrap();
+    assert_eq!(settings.watched_services, services);
+}
+
+#[tokio::test]
+async fn test_settings_load_defaults() {
+    let settings = PluginSettings::load(&TestKvStore::new()).await.unwrap();
+
+    assert_eq!(settings, PluginSettings::default());
+    assert!(settings.watched_services.is_empty());
+    assert!(!settings.read_only && !settings.debug_mode);
+    assert_eq!(settings.action_cooldown(), crate::rate_limit::DEFAULT_ACTION_COOLDOWN);
+
+    // Fields missing from a stored blob take their defaults
+    let mut data = std::collections::HashMap::new();
+    data.insert("settings".to_string(), r#"{"debug_mode":true}"#.to_string());
+    let settings = PluginSettings::load(&TestKvStore::with_data(data)).await.unwrap();
+    assert!(settings.debug_mode);
+    assert_eq!(settings.action_cooldown_ms, 2000);
+}
+
+#[tokio::test]
+async fn test_settings_migrate_legacy_keys() {
+    let mut data = std::collections::HashMap::new();
+    data.insert("watched_services".to_string(), r#"["nginx.service","sshd.service"]"#.to_string());
+    data.insert("debug_mode".to_string(), "true".to_string());
+    let kv_store = TestKvStore::with_data(data);
+
+    let settings = PluginSettings::load(&kv_store).await.unwrap();
+    assert_eq!(settings.watched_services, vec!["nginx.service", "sshd.service"]);
+    assert!(settings.debug_mode);
+    assert!(!settings.read_only);
+
+    // The blob is written once and the legacy keys are kept for rollback
+    let blob = kv_store.get("settings").await.unwrap().expect("settings blob written");
+    let migrated: PluginSettings = serde_json::from_str(&blob).unwrap();
+    assert_eq!(migrated, settings);
+    assert!(kv_store.get("watched_services").await.unwrap().is_some());
+    assert!(kv_store.get("debug_mode").await.unwrap().is_some());
+
+    // A fresh install has nothing to migrate
+    let kv_store = TestKvStore::new();
+    PluginSettings::load(&kv_store).await.unwrap();
+    assert!(kv_store.get("settings").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_settings_adopt_legacy_access_keys_into_existing_blob() {
+    // A blob saved before the access lists moved into it
+    let mut data = std::collections::HashMap::new();
+    data.insert("settings".to_string(), r#"{"watched_services":["nginx.service"]}"#.to_string());
+    data.insert("denied_units".to_string(), r#"["sshd.service"]"#.to_string());
+    let kv_store = TestKvStore::with_data(data);
+
+    let settings = PluginSettings::load(&kv_store).await.unwrap();
+    assert_eq!(settings.watched_services, vec!["nginx.service"]);
+    assert_eq!(settings.denied_units, vec!["sshd.service"]);
+    assert!(settings.allowed_units.is_empty());
+
+    // Recorded in the blob, so the legacy keys no longer matter
+    kv_store.delete("denied_units").await.unwrap();
+    let policy = crate::access::UnitAccessPolicy::load(&kv_store).await.unwrap();
+    assert!(policy.check("sshd.service").is_err());
+}
+
+#[tokio::test]
+async fn test_settings_round_trip() {
+    let kv_store = TestKvStore::new();
+    let settings = PluginSettings {
+        watched_services: vec!["nginx.service".to_string()],
+        allowed_units: vec!["*.service".to_string()],
+        denied_units: vec!["sshd.service".to_string()],
+        read_only: true,
+        debug_mode: true,
+        action_cooldown_ms: 500,
+        cors_origin: Some("http://localhost:5173".to_string()),
+    };
+
+    settings.save(&kv_store).await.unwrap();
+    assert_eq!(PluginSettings::load(&kv_store).await.unwrap(), settings);
+}
+
+#[tokio::test]
+async fn test_get_summary_populates_all_sections() {
+    let executor = Arc::new(
+        MockCommandExecutor::new()
+            .with_stdout(
+                "systemctl",
+                &["show", "nginx.service", "--timestamp=unix", "--property=Description,LoadState,ActiveState,SubState,MainPID,ActiveEnterTimestamp,ActiveEnterTimestampMonotonic,TasksCurrent,TasksMax,UnitFileState,MemoryCurrent,ControlGroup,Restart,RestartUSec"],
+                "Description=A high performance web server\nActiveState=active\nSubState=running\nMainPID=1234\n\
+                 ActiveEnterTimestamp=@1704880800\nActiveEnterTimestampMonotonic=0\n\
+                 LoadState=loaded\nUnitFileState=enabled\nMemoryCurrent=12582912\nTasksCurrent=5\nTasksMax=infinity\nControlGroup=/system.slice/nginx.service\n\
+                 Restart=on-failure\nRestartUSec=5s\n",
+            )
+            .with_stdout(
+                "journalctl",
+                &["-u", "nginx.service", "-n", "10", "--no-pager", "--output=json"],
+                r#"{"MESSAGE":"Starting nginx","PRIORITY":"6","__REALTIME_TIMESTAMP":"1704880800000000"}
+{"MESSAGE":"Started nginx","PRIORITY":"6","__REALTIME_TIMESTAMP":"1704880801000000"}"#,
+            )
+            .with_stdout(
+                "journalctl",
+                &["-b", "-u", "nginx.service", "MESSAGE_ID=39f53479d3a045ac8e11786248231fbf", "--no-pager", "--output=json"],
+                r#"{"MESSAGE":"Started nginx.service.","MESSAGE_ID":"39f53479d3a045ac8e11786248231fbf","UNIT":"nginx.service","JOB_RESULT":"done","__REALTIME_TIMESTAMP":"1704877200000000"}"#,
+            )
+    );
+
+    let response = services::handle_get_summary(executor, &SystemClock, "nginx.service").await.unwrap();
+
+    assert_eq!(response.status, 200);
+    let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body["status"]["active_state"], "active");
+    assert_eq!(body["status"]["main_pid"], 1234);
+    assert_eq!(body["status"]["description"], "A high performance web server");
+    assert_eq!(body["load_state"], "loaded");
+    assert_eq!(body["unit_file_state"], "enabled");
+    assert_eq!(body["memory_bytes"], 12582912);
+    assert_eq!(body["status"]["tasks_current"], 5);
+    assert_eq!(body["cgroup"], "/system.slice/nginx.service");
+    assert_eq!(body["restart"]["restart"], "on-failure");
+    assert_eq!(body["restart"]["restart_sec"], "5s");
+    assert_eq!(body["restart"]["auto_restarts"], true);
+    assert_eq!(body["recent_logs"].as_array().unwrap().len(), 2);
+    assert_eq!(body["recent_logs"][1]["message"], "Started nginx");
+    // Started an hour before the current activation: it has been restarted
+    assert_eq!(body["status"]["first_activation_timestamp"], "2024-01-10T09:00:00Z");
+}
+
+#[tokio::test]
+async fn test_get_summary_unknown_service() {
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_stdout(
+            "systemctl",
+            &["show", "ghost.service", "--timestamp=unix", "--property=Description,LoadState,ActiveState,SubState,MainPID,ActiveEnterTimestamp,ActiveEnterTimestampMonotonic,TasksCurrent,TasksMax,UnitFileState,MemoryCurrent,ControlGroup,Restart,RestartUSec"],
+            "ActiveState=inactive\nSubState=dead\nLoadState=not-found\nMemoryCurrent=[not set]\n",
+        )
+    );
+
+    let response = services::handle_get_summary(executor, &SystemClock, "ghost.service").await.unwrap();
+    assert_eq!(response.status, 404);
+}
+
+#[tokio::test]
+async fn test_get_properties_multiple() {
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_stdout(
+            "systemctl",
+            &["show", "nginx.service", "--property=MemoryMax,TasksCurrent"],
+            "MemoryMax=536870912\nTasksCurrent=3\n",
+        )
+    );
+
+    let mut params = std::collections::HashMap::new();
+    params.insert("names".to_string(), "MemoryMax,TasksCurrent".to_string());
+
+    let response = services::handle_get_properties(executor, "nginx.service", &params).await.unwrap();
+    assert_eq!(response.status, 200);
+
+    let body: std::collections::HashMap<String, String> =
+        serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body["MemoryMax"], "536870912");
+    assert_eq!(body["TasksCurrent"], "3");
+}
+
+#[tokio::test]
+async fn test_get_properties_rejects_injection() {
+    // No mock responses: nothing may reach systemctl
+    let executor = Arc::new(MockCommandExecutor::new());
+
+    let mut params = std::collections::HashMap::new();
+    params.insert("names".to_string(), "MemoryMax,%20--all".to_string());
+
+    let response = services::handle_get_properties(executor.clone(), "nginx.service", &params).await.unwrap();
+    assert_eq!(response.status, 400);
+    assert!(response.body.unwrap().contains("Property not allowed"));
+
+    let response = services::handle_get_properties(executor, "nginx.service", &std::collections::HashMap::new()).await.unwrap();
+    assert_eq!(response.status, 400);
+}
+
+#[tokio::test]
+async fn test_daemon_reload_success() {
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_stdout("systemctl", &["daemon-reload"], "")
+    );
+    let kv_store = TestKvStore::new();
+
+    let response = services::handle_daemon_reload(executor, &kv_store, &SystemClock).await.unwrap();
+    assert_eq!(response.status, 200);
+
+    let audit = crate::audit::load_audit_log(&kv_store).await.unwrap();
+    assert_eq!(audit.len(), 1);
+    assert_eq!(audit[0].action, "daemon-reload");
+    assert_eq!(audit[0].outcome, "success");
+}
+
+#[tokio::test]
+async fn test_daemon_reload_blocked_in_read_only() {
+    let mut data = std::collections::HashMap::new();
+    data.insert("read_only".to_string(), "true".to_string());
+    let kv_store = TestKvStore::with_data(data);
+
+    let response = check_read_only(&kv_store, "POST").await.unwrap().unwrap();
+    assert_eq!(response.status, 403);
+}
+
+#[tokio::test]
+async fn test_daemon_reload_blocked_by_access_policy() {
+    // No mock response: the reload must not reach systemctl
+    let executor = Arc::new(MockCommandExecutor::new());
+
+    let mut data = std::collections::HashMap::new();
+    data.insert("allowed_units".to_string(), r#"["app-*.service"]"#.to_string());
+    let kv_store = TestKvStore::with_data(data);
+
+    let response = services::handle_daemon_reload(executor, &kv_store, &SystemClock).await.unwrap();
+    assert_eq!(response.status, 403);
+    assert!(response.body.unwrap().contains("allowlist or denylist"));
+}
+
+#[tokio::test]
+async fn test_service_action_dry_run_reports_command() {
+    // The dry-run handler takes no executor, so it cannot run anything
+    let kv_store = TestKvStore::new();
+
+    for action in ["start", "stop", "restart"] {
+        let response = services::handle_service_action_dry_run(&kv_store, "nginx.service", action)
+            .await
+            .unwrap();
+
+        assert_eq!(response.status, 200);
+        let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+        assert_eq!(body["command"], format!("systemctl {} nginx.service", action));
+        assert_eq!(body["executed"], false);
+    }
+
+    // Nothing ran, so nothing was audited either
+    assert!(crate::audit::load_audit_log(&kv_store).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_service_action_dry_run_invalid_action() {
+    let kv_store = TestKvStore::new();
+
+    let response = services::handle_service_action_dry_run(&kv_store, "nginx.service", "reload")
+        .await
+        .unwrap();
+    assert_eq!(response.status, 400);
+}
+
+#[tokio::test]
+async fn test_service_action_dry_run_honours_access_policy() {
+    let mut data = std::collections::HashMap::new();
+    data.insert("denied_units".to_string(), r#"["sshd.service"]"#.to_string());
+    let kv_store = TestKvStore::with_data(data);
+
+    let response = services::handle_service_action_dry_run(&kv_store, "sshd.service", "stop")
+        .await
+        .unwrap();
+    assert_eq!(response.status, 403);
+}
+
+#[test]
+fn test_is_dry_run() {
+    let mut params = std::collections::HashMap::new();
+    assert!(!is_dry_run(&params));
+    params.insert("dry_run".to_string(), "true".to_string());
+    assert!(is_dry_run(&params));
+    params.insert("dry_run".to_string(), "false".to_string());
+    assert!(!is_dry_run(&params));
+}
+
+#[tokio::test]
+async fn test_get_journal_usage() {
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_stdout(
+            "journalctl",
+            &["--disk-usage"],
+            "Archived and active journals take up 3.5M in the file system.\n",
+        )
+    );
+
+    let response = services::handle_get_journal_usage(executor).await.unwrap();
+    assert_eq!(response.status, 200);
+
+    let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body["disk_usage_bytes"], 3_670_016);
+    assert_eq!(body["disk_usage_human"], "3.5M");
+}
+
+#[tokio::test]
+async fn test_health_ok() {
+    // A degraded system (failed units) still means systemd is reachable
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_response(
+            "systemctl",
+            &["is-system-running"],
+            CommandOutput { exit_code: 1, stdout: "degraded\n".to_string(), stderr: String::new() },
+        )
+    );
+
+    let response = services::handle_get_health(executor).await.unwrap();
+    assert_eq!(response.status, 200);
+
+    let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body["status"], "ok");
+    assert_eq!(body["systemd_available"], true);
+    assert_eq!(body["system_state"], "degraded");
+}
+
+#[tokio::test]
+async fn test_health_degraded_without_systemd() {
+    // The probe command fails outright
+    let response = services::handle_get_health(Arc::new(MockCommandExecutor::new())).await.unwrap();
+    assert_eq!(response.status, 503);
+    let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body["status"], "degraded");
+    assert_eq!(body["systemd_available"], false);
+    assert!(body["system_state"].is_null());
+
+    // systemd answers but isn't running as the service manager
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_response(
+            "systemctl",
+            &["is-system-running"],
+            CommandOutput { exit_code: 1, stdout: "offline\n".to_string(), stderr: String::new() },
+        )
+    );
+    let response = services::handle_get_health(executor).await.unwrap();
+    assert_eq!(response.status, 503);
+    let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body["systemd_available"], false);
+    assert_eq!(body["system_state"], "offline");
+}
+
+#[tokio::test]
+async fn test_get_multi_logs() {
+    let output = r#"{"MESSAGE":"GET /","PRIORITY":"6","__REALTIME_TIMESTAMP":"1705315845000000","_SYSTEMD_UNIT":"nginx.service"}
+{"MESSAGE":"pool www ready","PRIORITY":"5","__REALTIME_TIMESTAMP":"1705315846000000","_SYSTEMD_UNIT":"php-fpm.service"}"#;
+
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_stdout(
+            "journalctl",
+            &["-u", "nginx.service", "-u", "php-fpm.service", "-n", "200", "--no-pager", "--output=json"],
+            output,
+        )
+    );
+
+    let mut params = std::collections::HashMap::new();
+    params.insert("units".to_string(), "nginx.service,php-fpm.service".to_string());
+    params.insert("lines".to_string(), "200".to_string());
+
+    let response = services::handle_get_multi_logs(executor, &params).await.unwrap();
+    assert_eq!(response.status, 200);
+
+    let body: Vec<crate::systemctl::LogEntry> = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body.len(), 2);
+    assert_eq!(body[0].unit.as_deref(), Some("nginx.service"));
+    assert_eq!(body[1].unit.as_deref(), Some("php-fpm.service"));
+}
+
+#[tokio::test]
+async fn test_get_multi_logs_rejects_invalid_unit() {
+    let executor = Arc::new(MockCommandExecutor::new());
+
+    let mut params = std::collections::HashMap::new();
+    params.insert("units".to_string(), "nginx.service,bad$name".to_string());
+
+    let response = services::handle_get_multi_logs(executor.clone(), &params).await.unwrap();
+    assert_eq!(response.status, 400);
+
+    let response = services::handle_get_multi_logs(executor, &std::collections::HashMap::new()).await.unwrap();
+    assert_eq!(response.status, 400);
+}
+
+#[tokio::test]
+async fn test_get_raw_logs_passes_journal_lines_through() {
+    // Field order, unknown fields and numeric strings must survive untouched
+    let output = concat!(
+        r#"{"__CURSOR":"s=1","MESSAGE":"Started","_PID":"42","PRIORITY":"6","CUSTOM_FIELD":"x"}"#, "\n",
+        r#"{"MESSAGE":[104,105],"__REALTIME_TIMESTAMP":"1705315845000000"}"#, "\n",
+    );
+
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_stdout(
+            "journalctl",
+            &["-u", "nginx.service", "-n", "100", "--no-pager", "--output=json"],
+            output,
+        )
+    );
+
+    let mut data = std::collections::HashMap::new();
+    data.insert("debug_mode".to_string(), "true".to_string());
+    let kv_store = TestKvStore::with_data(data);
+
+    let mut params = std::collections::HashMap::new();
+    params.insert("raw".to_string(), "true".to_string());
+    assert!(is_raw(&params));
+
+    let response = services::handle_get_raw_logs(executor, &kv_store, &["nginx.service".to_string()], &params)
+        .await
+        .unwrap();
+    assert_eq!(response.status, 200);
+    assert_eq!(response.headers.get("Content-Type").unwrap(), "application/x-ndjson");
+    assert_eq!(response.body.unwrap(), output);
+}
+
+#[tokio::test]
+async fn test_get_raw_logs_requires_debug_mode() {
+    let executor = Arc::new(MockCommandExecutor::new());
+    let kv_store = TestKvStore::new();
+
+    let response = services::handle_get_raw_logs(
+        executor,
+        &kv_store,
+        &["nginx.service".to_string()],
+        &std::collections::HashMap::new(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status, 403);
+    assert!(response.body.unwrap().contains("debug_disabled"));
+}
+
+async fn start_action_status(stderr: &str) -> u16 {
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_error("systemctl", &["start", "nginx.service"], 1, stderr)
+    );
+    let kv_store = TestKvStore::new();
+
+    services::handle_service_action(executor, &kv_store, &SystemClock, "nginx.service", "start", false)
+        .await
+        .unwrap()
+        .status
+}
+
+#[tokio::test]
+async fn test_service_action_error_status_mapping() {
+    assert_eq!(start_action_status("Failed to start nginx.service: Unit nginx.service is masked.").await, 409);
+    assert_eq!(start_action_status("Failed to start nginx.service: Interactive authentication required.").await, 401);
+    assert_eq!(start_action_status("A dependency job for nginx.service failed.").await, 500);
+}
+
+#[tokio::test]
+async fn test_failed_start_includes_recent_logs_in_debug_mode() {
+    let journal = concat!(
+        r#"{"MESSAGE":"nginx: [emerg] bind() to 0.0.0.0:80 failed (98: Address already in use)","PRIORITY":"2","__REALTIME_TIMESTAMP":"1705315845000000"}"#, "\n",
+        r#"{"MESSAGE":"nginx.service: Failed with result 'exit-code'.","PRIORITY":"4","__REALTIME_TIMESTAMP":"1705315845100000"}"#, "\n",
+    );
+    let executor = Arc::new(
+        MockCommandExecutor::new()
+            .with_error("systemctl", &["start", "nginx.service"], 1, "Job for nginx.service failed because the control process exited with error code.")
+            .with_stdout("journalctl", &["-u", "nginx.service", "-n", "20", "--no-pager", "--output=json"], journal)
+    );
+
+    let mut data = std::collections::HashMap::new();
+    data.insert("debug_mode".to_string(), "true".to_string());
+    let kv_store = TestKvStore::with_data(data);
+
+    let response = services::handle_service_action(executor.clone(), &kv_store, &SystemClock, "nginx.service", "start", false).await.unwrap();
+    assert_eq!(response.status, 500);
+
+    let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(body["success"], false);
+    let logs = body["recent_logs"].as_array().unwrap();
+    assert_eq!(logs.len(), 2);
+    assert!(logs[0]["message"].as_str().unwrap().contains("Address already in use"));
+
+    // Without debug mode the error stays lean
+    let response = services::handle_service_action(executor, &TestKvStore::new(), &SystemClock, "nginx.service", "start", false).await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert!(body.get("recent_logs").is_none());
+}
+
+#[tokio::test]
+async fn test_get_activity_feed() {
+    // Service output and a "Starting" job message must be ignored; the failed
+    // start carries JOB_RESULT=failed; entries arrive out of order across units
+    let journal = concat!(
+        r#"{"MESSAGE":"Starting nginx...","MESSAGE_ID":"7d4958e842da4a758f6c1cdc7b36dcc5","UNIT":"nginx.service","__REALTIME_TIMESTAMP":"1705315800000000"}"#, "\n",
+        r#"{"MESSAGE":"Started nginx.","MESSAGE_ID":"39f53479d3a045ac8e11786248231fbf","UNIT":"nginx.service","JOB_TYPE":"start","JOB_RESULT":"done","__REALTIME_TIMESTAMP":"1705315801000000"}"#, "\n",
+        r#"{"MESSAGE":"worker ready","_SYSTEMD_UNIT":"nginx.service","__REALTIME_TIMESTAMP":"1705315802000000"}"#, "\n",
+        r#"{"MESSAGE":"Failed to start Redis.","MESSAGE_ID":"be02cf6855d2428ba40df7e9d022f03d","UNIT":"redis.service","JOB_TYPE":"start","JOB_RESULT":"failed","__REALTIME_TIMESTAMP":"1705315900000000"}"#, "\n",
+        r#"{"MESSAGE":"Stopped nginx.","MESSAGE_ID":"9d1aaa27d60140bd96365438aad20286","UNIT":"nginx.service","JOB_TYPE":"stop","JOB_RESULT":"done","__REALTIME_TIMESTAMP":"1705315850000000"}"#, "\n",
+    );
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_stdout(
+            "journalctl",
+            &[
+                "-u", "nginx.service", "-u", "redis.service",
+                "MESSAGE_ID=39f53479d3a045ac8e11786248231fbf",
+                "MESSAGE_ID=9d1aaa27d60140bd96365438aad20286",
+                "MESSAGE_ID=be02cf6855d2428ba40df7e9d022f03d",
+                "--since", "2 hours ago", "--no-pager", "--output=json",
+            ],
+            journal,
+        )
+    );
+
+    let mut data = std::collections::HashMap::new();
+    data.insert("watched_services".to_string(), r#"["nginx.service","redis.service"]"#.to_string());
+    let kv_store = TestKvStore::with_data(data);
+
+    let mut params = std::collections::HashMap::new();
+    params.insert("since".to_string(), "2%20hours+ago".to_string());
+
+    let response = services::handle_get_activity(executor, &kv_store, &params).await.unwrap();
+    assert_eq!(response.status, 200);
+
+    let events: Vec<crate::systemctl::ActivityEvent> = serde_json::from_str(&response.body.unwrap()).unwrap();
+    let summary: Vec<(&str, &str)> = events.iter().map(|e| (e.unit.as_str(), e.event.as_str())).collect();
+    assert_eq!(summary, vec![
+        ("nginx.service", "started"),
+        ("nginx.service", "stopped"),
+        ("redis.service", "failed"),
+    ]);
+    assert_eq!(events[2].job_result.as_deref(), Some("failed"));
+}
+
+#[tokio::test]
+async fn test_get_activity_rejects_bad_since() {
+    let mut params = std::collections::HashMap::new();
+    params.insert("since".to_string(), "1 hour ago;reboot".to_string());
+
+    let response = services::handle_get_activity(Arc::new(MockCommandExecutor::new()), &TestKvStore::new(), &params)
+        .await
+        .unwrap();
+    assert_eq!(response.status, 400);
 }
@@ -1,7 +1,18 @@
 // Module exports for systemd-services plugin
 
+pub mod access;
+pub mod audit;
+pub mod circuit_breaker;
+pub mod clock;
 pub mod error;
+pub mod events;
+pub mod fixtures;
 pub mod handlers;
+pub mod log_filter;
+pub mod rate_limit;
+pub mod refresh_hint;
+pub mod settings;
+pub mod stats;
 pub mod systemctl;
 
 // Re-export commonly used types
@@ -0,0 +1,168 @@
+// Client-side filtering of a fetched log window (grep, priority and context lines)
+
+use crate::systemctl::LogEntry;
+
+/// Upper bound on `?context=` so a broad match can't turn into the whole window twice over
+pub const MAX_CONTEXT_LINES: usize = 20;
+
+/// Most comma-separated patterns accepted in one `?grep=`
+pub const MAX_GREP_PATTERNS: usize = 10;
+
+/// Longest single grep pattern, in bytes
+pub const MAX_GREP_PATTERN_LEN: usize = 200;
+
+/// Which log entries count as matches
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LogFilter {
+    /// Case-insensitive substrings; the message must contain at least one
+    pub grep: Vec<String>,
+    /// Least severe priority to keep (0 = emerg ... 7 = debug)
+    pub max_priority: Option<u8>,
+}
+
+impl LogFilter {
+    /// True if no criteria are set, i.e. every entry matches
+    pub fn is_empty(&self) -> bool {
+        self.grep.is_empty() && self.max_priority.is_none()
+    }
+
+    /// The grep patterns as one regex for `journalctl --grep`
+    ///
+    /// Each pattern is escaped so it matches literally; several are combined
+    /// as an alternation, `(a|b|c)`. None when there is nothing to grep for.
+    pub fn journal_regex(&self) -> Option<String> {
+        match self.grep.as_slice() {
+            [] => None,
+            [pattern] => Some(regex::escape(pattern)),
+            patterns => {
+                let escaped: Vec<String> = patterns.iter().map(|p| regex::escape(p)).collect();
+                Some(format!("({})", escaped.join("|")))
+            }
+        }
+    }
+
+    /// Checks one entry against every criterion that is set
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        if let Some(max_priority) = self.max_priority {
+            if entry.priority > max_priority {
+                return false;
+            }
+        }
+
+        if self.grep.is_empty() {
+            return true;
+        }
+
+        let message = entry.message.to_lowercase();
+        self.grep.iter().any(|pattern| message.contains(&pattern.to_lowercase()))
+    }
+}
+
+/// Keeps the entries matching `filter` plus up to `context` entries on either
+/// side of each match, like `grep -C`
+///
+/// Overlapping context windows are merged, so no entry appears twice. Order is
+/// preserved.
+pub fn filter_with_context(entries: Vec<LogEntry>, filter: &LogFilter, context: usize) -> Vec<LogEntry> {
+    if filter.is_empty() {
+        return entries;
+    }
+
+    let mut keep = vec![false; entries.len()];
+    for (i, entry) in entries.iter().enumerate() {
+        if filter.matches(entry) {
+            let start = i.saturating_sub(context);
+            let end = (i + context).min(entries.len() - 1);
+            keep[start..=end].iter_mut().for_each(|k| *k = true);
+        }
+    }
+
+    entries
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(entry, keep)| keep.then_some(entry))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn entry(message: &str, priority: u8) -> LogEntry {
+        LogEntry {
+            timestamp: Utc::now(),
+            message: message.to_string(),
+            priority,
+            level: crate::systemctl::priority_to_level(priority).to_string(),
+            severity_class: crate::systemctl::severity_class(priority).to_string(),
+            unit: None,
+            truncated: false,
+            cursor: None,
+        }
+    }
+
+    fn messages(entries: &[LogEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.message.as_str()).collect()
+    }
+
+    #[test]
+    fn test_grep_is_case_insensitive() {
+        let filter = LogFilter {
+            grep: vec!["TIMEOUT".to_string()],
+            ..Default::default()
+        };
+
+        let entries = vec![entry("connection timeout", 4), entry("ok", 6)];
+        assert_eq!(messages(&filter_with_context(entries, &filter, 0)), vec!["connection timeout"]);
+    }
+
+    #[test]
+    fn test_grep_matches_any_pattern() {
+        let filter = LogFilter {
+            grep: vec!["timeout".to_string(), "refused".to_string()],
+            ..Default::default()
+        };
+
+        let entries = vec![entry("connection timeout", 4), entry("ok", 6), entry("Connection refused", 3)];
+        assert_eq!(
+            messages(&filter_with_context(entries, &filter, 0)),
+            vec!["connection timeout", "Connection refused"]
+        );
+    }
+
+    #[test]
+    fn test_journal_regex() {
+        let filter = |patterns: &[&str]| LogFilter {
+            grep: patterns.iter().map(|p| p.to_string()).collect(),
+            ..Default::default()
+        };
+
+        assert_eq!(filter(&[]).journal_regex(), None);
+        assert_eq!(filter(&["timed out"]).journal_regex().as_deref(), Some("timed out"));
+        assert_eq!(filter(&["timeout", "refused"]).journal_regex().as_deref(), Some("(timeout|refused)"));
+        assert_eq!(filter(&["a.b", "(c)"]).journal_regex().as_deref(), Some(r"(a\.b|\(c\))"));
+    }
+
+    #[test]
+    fn test_context_merges_overlapping_windows() {
+        let filter = LogFilter {
+            max_priority: Some(3),
+            ..Default::default()
+        };
+
+        let entries: Vec<LogEntry> = ["a", "b", "err1", "c", "err2", "d", "e", "f"]
+            .iter()
+            .map(|m| entry(m, if m.starts_with("err") { 3 } else { 6 }))
+            .collect();
+
+        let kept = filter_with_context(entries, &filter, 1);
+        assert_eq!(messages(&kept), vec!["b", "err1", "c", "err2", "d"]);
+    }
+
+    #[test]
+    fn test_empty_filter_keeps_everything() {
+        let entries = vec![entry("a", 6), entry("b", 7)];
+        assert_eq!(filter_with_context(entries, &LogFilter::default(), 2).len(), 2);
+    }
+}
@@ -2,7 +2,15 @@ use serde_json::json;
 use std::collections::HashMap;
 use std::env;
 use std::sync::Arc;
-use systemd_services::systemctl::SystemCommandExecutor;
+use std::time::Instant;
+use systemd_services::circuit_breaker::CircuitBreaker;
+use systemd_services::clock::{Clock, SystemClock};
+use systemd_services::events::EventHub;
+use systemd_services::rate_limit::RateLimiter;
+use systemd_services::refresh_hint::RefreshAdvisor;
+use systemd_services::fixtures;
+use systemd_services::stats::{CountingExecutor, PluginStats};
+use systemd_services::systemctl::{CommandExecutor, SystemCommandExecutor};
 use toru_plugin_api::{
     HttpRequest, HttpResponse, KvOp, Message, MessagePayload, PluginContext, PluginError,
     PluginKvStore, PluginMetadata, PluginProtocol, ToruPlugin,
@@ -10,14 +18,45 @@ use toru_plugin_api::{
 
 struct SystemdServicesPlugin {
     ctx: Option<PluginContext>,
-    executor: Arc<SystemCommandExecutor>,
+    executor: Arc<CountingExecutor<Arc<dyn CommandExecutor>>>,
+    rate_limiter: RateLimiter,
+    refresh_advisor: RefreshAdvisor,
+    circuit_breaker: CircuitBreaker,
+    events: EventHub,
+    clock: Arc<dyn Clock>,
+    stats: Arc<PluginStats>,
+    /// Serving canned fixtures instead of systemd
+    mock: bool,
 }
 
 impl SystemdServicesPlugin {
+    /// Creates the plugin, in mock mode when `TORU_PLUGIN_MOCK=1`
     fn new() -> Self {
+        Self::with_mock(fixtures::mock_mode_enabled())
+    }
+
+    /// Creates the plugin against systemd, or against canned fixtures for
+    /// frontend development when `mock` is set
+    fn with_mock(mock: bool) -> Self {
+        let stats = Arc::new(PluginStats::new());
+
+        let executor: Arc<dyn CommandExecutor> = if mock {
+            eprintln!("[SystemdServicesPlugin] Mock mode: using fixture data instead of systemd");
+            Arc::new(fixtures::mock_executor())
+        } else {
+            Arc::new(SystemCommandExecutor::new())
+        };
+
         Self {
             ctx: None,
-            executor: Arc::new(SystemCommandExecutor::new()),
+            executor: Arc::new(CountingExecutor::new(executor, stats.clone())),
+            rate_limiter: RateLimiter::new(),
+            refresh_advisor: RefreshAdvisor::new(),
+            circuit_breaker: CircuitBreaker::new(),
+            events: EventHub::new(),
+            clock: Arc::new(SystemClock),
+            stats,
+            mock,
         }
     }
 
@@ -42,24 +81,9 @@ impl SystemdServicesPlugin {
             .map(|ctx| ctx.kv.as_ref())
             .ok_or(PluginError::NotInitialized)
     }
-}
-
-#[async_trait::async_trait]
-impl ToruPlugin for SystemdServicesPlugin {
-    fn metadata() -> PluginMetadata {
-        Self::metadata()
-    }
 
-    async fn init(&mut self, ctx: PluginContext) -> Result<(), PluginError> {
-        eprintln!(
-            "[SystemdServicesPlugin] Initializing with instance_id: {}",
-            ctx.instance_id
-        );
-        self.ctx = Some(ctx);
-        Ok(())
-    }
-
-    async fn handle_http(&self, req: HttpRequest) -> Result<HttpResponse, PluginError> {
+    /// Dispatches a request to its handler; `handle_http` wraps this to time it
+    async fn route(&self, req: HttpRequest) -> Result<HttpResponse, PluginError> {
         eprintln!(
             "[SystemdServicesPlugin] HTTP request: {} {}",
             req.method, req.path
@@ -69,7 +93,64 @@ impl ToruPlugin for SystemdServicesPlugin {
         let path_only = systemd_services::handlers::path_without_query(&req.path);
         let query_params = systemd_services::handlers::parse_query_params(&req.path);
 
-        // Route the request
+        // A dry-run service action only reports the command it would run, so it
+        // is exempt from read-only mode and the action cooldown
+        let dry_run_action = req.method == "POST"
+            && systemd_services::handlers::is_dry_run(&query_params)
+            && systemd_services::handlers::parse_action_path(path_only).is_some();
+
+        // Read-only mode blocks every mutating request before it is routed
+        if systemd_services::handlers::is_mutating_method(&req.method) && !dry_run_action {
+            let kv = self.kv_store()?;
+            if let Some(response) = systemd_services::handlers::check_read_only(kv, &req.method)
+                .await
+                .map_err(|e| PluginError::Internal(e.to_string()))?
+            {
+                return Ok(response);
+            }
+        }
+
+        // Repeated service actions within the cooldown are rejected with a 429
+        let mut limited_action = None;
+        if req.method == "POST" && !dry_run_action {
+            if let Some((service_name, action)) = systemd_services::handlers::parse_action_path(path_only) {
+                let kv = self.kv_store()?;
+                let service_name = systemd_services::handlers::decode_path_segment(service_name);
+                let now = Instant::now();
+                if let Some(response) = systemd_services::handlers::check_rate_limit(
+                    &self.rate_limiter,
+                    kv,
+                    &service_name,
+                    action,
+                    now,
+                )
+                .await
+                .map_err(|e| PluginError::Internal(e.to_string()))?
+                {
+                    return Ok(response);
+                }
+                limited_action = Some((service_name, action, now));
+            }
+        }
+
+        let result = self.dispatch(&req, path_only, &query_params, dry_run_action).await;
+
+        // A request rejected before the action ran doesn't start the cooldown
+        if let (Some((service_name, action, now)), Ok(response)) = (&limited_action, &result) {
+            systemd_services::handlers::release_rejected_action(&self.rate_limiter, service_name, action, *now, response);
+        }
+
+        result
+    }
+
+    /// Runs the handler matching the request's method and path
+    async fn dispatch(
+        &self,
+        req: &HttpRequest,
+        path_only: &str,
+        query_params: &HashMap<String, String>,
+        dry_run_action: bool,
+    ) -> Result<HttpResponse, PluginError> {
         match (req.method.as_str(), path_only) {
             // Serve frontend bundle
             ("GET", "/bundle.js") => Ok(HttpResponse {
@@ -104,22 +185,112 @@ impl ToruPlugin for SystemdServicesPlugin {
                 })
             }
 
+            // GET /healthz - plugin liveness and systemd reachability
+            ("GET", "/healthz") => {
+                systemd_services::handlers::handle_get_health(self.executor.clone())
+                    .await
+                    .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
+            // GET /debug/stats - plugin-internal request and command counters
+            ("GET", "/debug/stats") => {
+                systemd_services::handlers::handle_get_stats(&self.stats, &self.circuit_breaker)
+                    .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
             // GET /services - watched services with status
             ("GET", "/services") => {
                 let kv = self.kv_store()?;
-                systemd_services::handlers::handle_get_services(self.executor.clone(), kv)
+                systemd_services::handlers::handle_get_services(self.executor.clone(), kv, &*self.clock, &self.refresh_advisor, &self.circuit_breaker)
+                    .await
+                    .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
+            // GET /services/snapshot - hash of watched services' states for cheap polling
+            ("GET", "/services/snapshot") => {
+                let kv = self.kv_store()?;
+                systemd_services::handlers::handle_get_services_snapshot(self.executor.clone(), kv, &*self.clock, &self.circuit_breaker)
                     .await
                     .map_err(|e| PluginError::Internal(e.to_string()))
             }
 
             // GET /services/available - all systemd services
             ("GET", "/services/available") => {
-                systemd_services::handlers::handle_get_available_services(self.executor.clone())
+                systemd_services::handlers::handle_get_available_services(self.executor.clone(), query_params)
                     .await
                     .map_err(|e| PluginError::Internal(e.to_string()))
             }
 
-            // POST /services/:name/start|stop|restart
+            // GET /services/events?wait=30&since=<version> - long-poll for state changes
+            ("GET", "/services/events") => {
+                let kv = self.kv_store()?;
+                systemd_services::handlers::handle_get_events(self.executor.clone(), kv, &*self.clock, &self.events, query_params)
+                    .await
+                    .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
+            // GET /services/activity?since= - start/stop/failure feed of watched services
+            ("GET", "/services/activity") => {
+                let kv = self.kv_store()?;
+                systemd_services::handlers::handle_get_activity(self.executor.clone(), kv, query_params)
+                    .await
+                    .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
+            // GET /services/logs?units=a,b - interleaved logs of several services
+            ("GET", "/services/logs") => {
+                if systemd_services::handlers::is_raw(query_params) {
+                    let kv = self.kv_store()?;
+                    let units = systemd_services::handlers::parse_units_param(query_params);
+                    return systemd_services::handlers::handle_get_raw_logs(self.executor.clone(), kv, &units, query_params)
+                        .await
+                        .map_err(|e| PluginError::Internal(e.to_string()));
+                }
+
+                systemd_services::handlers::handle_get_multi_logs(self.executor.clone(), query_params)
+                    .await
+                    .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
+            // GET /audit - recent mutating actions
+            ("GET", "/audit") => {
+                let kv = self.kv_store()?;
+                systemd_services::handlers::handle_get_audit(kv)
+                    .await
+                    .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
+            // GET /system/journal-usage - journal disk usage
+            ("GET", "/system/journal-usage") => {
+                systemd_services::handlers::handle_get_journal_usage(self.executor.clone())
+                    .await
+                    .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
+            // POST /system/daemon-reload - reload unit files
+            ("POST", "/system/daemon-reload") => {
+                let kv = self.kv_store()?;
+                systemd_services::handlers::handle_daemon_reload(self.executor.clone(), kv, &*self.clock)
+                    .await
+                    .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
+            // POST /services/bulk - one action on several services
+            ("POST", "/services/bulk") => {
+                let kv = self.kv_store()?;
+                let body = req.body.as_deref().unwrap_or("");
+                systemd_services::handlers::handle_bulk_action(
+                    self.executor.clone(),
+                    kv,
+                    &*self.clock,
+                    &self.rate_limiter,
+                    body,
+                )
+                .await
+                .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
+            // POST /services/:name/start|stop|restart[?dry_run=true]
             ("POST", path) if path.starts_with("/services/") => {
                 let parts: Vec<&str> = path.trim_start_matches("/services/").split('/').collect();
 
@@ -128,13 +299,23 @@ impl ToruPlugin for SystemdServicesPlugin {
                         .map_err(|e| PluginError::Internal(e.to_string()));
                 }
 
-                let service_name = parts[0];
+                let kv = self.kv_store()?;
+                let service_name = systemd_services::handlers::decode_path_segment(parts[0]);
                 let action = parts[1];
 
+                if dry_run_action {
+                    return systemd_services::handlers::handle_service_action_dry_run(kv, &service_name, action)
+                        .await
+                        .map_err(|e| PluginError::Internal(e.to_string()));
+                }
+
                 systemd_services::handlers::handle_service_action(
                     self.executor.clone(),
-                    service_name,
+                    kv,
+                    &*self.clock,
+                    &service_name,
                     action,
+                    systemd_services::handlers::is_force(query_params),
                 )
                 .await
                 .map_err(|e| PluginError::Internal(e.to_string()))
@@ -142,14 +323,47 @@ impl ToruPlugin for SystemdServicesPlugin {
 
             // GET /services/:name/logs
             ("GET", path) if path.starts_with("/services/") && path.ends_with("/logs") => {
-                let service_name = path
-                    .trim_start_matches("/services/")
-                    .trim_end_matches("/logs");
+                let service_name = systemd_services::handlers::decode_path_segment(
+                    path.trim_start_matches("/services/").trim_end_matches("/logs"),
+                );
+
+                if systemd_services::handlers::is_raw(query_params) {
+                    let kv = self.kv_store()?;
+                    return systemd_services::handlers::handle_get_raw_logs(self.executor.clone(), kv, &[service_name], query_params)
+                        .await
+                        .map_err(|e| PluginError::Internal(e.to_string()));
+                }
 
                 systemd_services::handlers::handle_get_logs(
                     self.executor.clone(),
-                    service_name,
-                    &query_params,
+                    &service_name,
+                    query_params,
+                )
+                .await
+                .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
+            // GET /services/:name/summary
+            ("GET", path) if path.starts_with("/services/") && path.ends_with("/summary") => {
+                let service_name = systemd_services::handlers::decode_path_segment(
+                    path.trim_start_matches("/services/").trim_end_matches("/summary"),
+                );
+
+                systemd_services::handlers::handle_get_summary(self.executor.clone(), &*self.clock, &service_name)
+                    .await
+                    .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
+            // GET /services/:name/properties?names=A,B
+            ("GET", path) if path.starts_with("/services/") && path.ends_with("/properties") => {
+                let service_name = systemd_services::handlers::decode_path_segment(
+                    path.trim_start_matches("/services/").trim_end_matches("/properties"),
+                );
+
+                systemd_services::handlers::handle_get_properties(
+                    self.executor.clone(),
+                    &service_name,
+                    query_params,
                 )
                 .await
                 .map_err(|e| PluginError::Internal(e.to_string()))
@@ -160,6 +374,66 @@ impl ToruPlugin for SystemdServicesPlugin {
                 .map_err(|e| PluginError::Internal(e.to_string())),
         }
     }
+}
+
+#[async_trait::async_trait]
+impl ToruPlugin for SystemdServicesPlugin {
+    fn metadata() -> PluginMetadata {
+        Self::metadata()
+    }
+
+    async fn init(&mut self, ctx: PluginContext) -> Result<(), PluginError> {
+        eprintln!(
+            "[SystemdServicesPlugin] Initializing with instance_id: {}",
+            ctx.instance_id
+        );
+        self.ctx = Some(ctx);
+        Ok(())
+    }
+
+    async fn handle_http(&self, mut req: HttpRequest) -> Result<HttpResponse, PluginError> {
+        let started = std::time::Instant::now();
+        let _in_flight = self.stats.request_started();
+        let target = format!("{} {}", req.method, req.path);
+
+        // CORS headers are only sent once an origin is configured
+        let cors_origin = match self.kv_store() {
+            Ok(kv) => systemd_services::handlers::cors_origin(kv).await,
+            Err(_) => None,
+        };
+        if req.method == "OPTIONS" {
+            if let Some(origin) = &cors_origin {
+                let mut response = systemd_services::handlers::preflight_response(origin);
+                systemd_services::handlers::set_processing_time(&mut response, started.elapsed());
+                return Ok(response);
+            }
+        }
+
+        // HEAD is answered like GET, minus the body, for cheap availability checks
+        let head = req.method == "HEAD";
+        if head {
+            req.method = "GET".to_string();
+        }
+
+        let result = self.route(req).await;
+        match &result {
+            Err(e) => self.stats.record_error(&format!("{}: {}", target, e)),
+            Ok(response) if response.status >= 500 => {
+                self.stats.record_error(&format!("{}: HTTP {}", target, response.status))
+            }
+            Ok(_) => {}
+        }
+
+        let mut response = result?;
+        if head {
+            response.body = None;
+        }
+        if let Some(origin) = &cors_origin {
+            systemd_services::handlers::set_cors_headers(&mut response, origin);
+        }
+        systemd_services::handlers::set_processing_time(&mut response, started.elapsed());
+        Ok(response)
+    }
 
     async fn handle_kv(&mut self, op: KvOp) -> Result<Option<String>, PluginError> {
         eprintln!("[SystemdServicesPlugin] KV operation: {:?}", op);
@@ -241,7 +515,7 @@ async fn main() {
                                             let ctx = PluginContext {
                                                 instance_id: init_payload.instance_id.clone(),
                                                 config: toru_plugin_api::PluginConfig::default(),
-                                                kv: Box::new(DummyKvStore),
+                                                kv: host_kv_store(plugin.mock),
                                             };
                                             if let Err(e) = plugin.init(ctx).await {
                                                 eprintln!(
@@ -354,6 +628,16 @@ fn create_http_response(request_id: String, http_response: HttpResponse) -> Mess
     )
 }
 
+/// KV store handed to the plugin on init; in mock mode an in-memory store
+/// watching the fixture services, so the dashboard has something to show
+fn host_kv_store(mock: bool) -> Box<dyn PluginKvStore> {
+    if mock {
+        Box::new(fixtures::FixtureKvStore::new())
+    } else {
+        Box::new(DummyKvStore)
+    }
+}
+
 // Dummy KV store implementation (TSC will provide real one)
 struct DummyKvStore;
 
@@ -396,4 +680,170 @@ mod tests {
         assert_eq!(parsed["id"], "systemd-services");
         assert_eq!(parsed["route"], "/systemd-services");
     }
+
+    #[tokio::test]
+    async fn test_head_returns_get_status_without_body() {
+        let plugin = SystemdServicesPlugin::new();
+        let request = |method: &str| HttpRequest {
+            method: method.to_string(),
+            path: "/".to_string(),
+            headers: HashMap::new(),
+            body: None,
+        };
+
+        let get = plugin.handle_http(request("GET")).await.unwrap();
+        let head = plugin.handle_http(request("HEAD")).await.unwrap();
+
+        assert_eq!(head.status, 200);
+        assert!(head.body.is_none());
+        assert!(get.body.is_some());
+        assert_eq!(head.headers["Content-Type"], get.headers["Content-Type"]);
+    }
+
+    #[tokio::test]
+    async fn test_cors_headers_when_origin_configured() {
+        let kv = fixtures::FixtureKvStore::new();
+        let settings = json!({ "cors_origin": "http://localhost:5173" }).to_string();
+        kv.set(systemd_services::settings::SETTINGS_KEY, &settings).await.unwrap();
+
+        let mut plugin = SystemdServicesPlugin::new();
+        plugin
+            .init(PluginContext {
+                instance_id: "test".to_string(),
+                config: toru_plugin_api::PluginConfig::default(),
+                kv: Box::new(kv),
+            })
+            .await
+            .unwrap();
+        let request = |method: &str| HttpRequest {
+            method: method.to_string(),
+            path: "/".to_string(),
+            headers: HashMap::new(),
+            body: None,
+        };
+
+        let preflight = plugin.handle_http(request("OPTIONS")).await.unwrap();
+        assert_eq!(preflight.status, 204);
+        assert!(preflight.body.is_none());
+        assert_eq!(preflight.headers["Access-Control-Allow-Origin"], "http://localhost:5173");
+        assert!(preflight.headers["Access-Control-Allow-Methods"].contains("POST"));
+
+        let response = plugin.handle_http(request("GET")).await.unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.headers["Access-Control-Allow-Origin"], "http://localhost:5173");
+
+        // Without an origin there are no CORS headers and no preflight handling
+        let plugin = SystemdServicesPlugin::new();
+        let response = plugin.handle_http(request("GET")).await.unwrap();
+        assert!(!response.headers.contains_key("Access-Control-Allow-Origin"));
+        assert_eq!(plugin.handle_http(request("OPTIONS")).await.unwrap().status, 404);
+    }
+
+    #[tokio::test]
+    async fn test_responses_carry_processing_time() {
+        let plugin = SystemdServicesPlugin::new();
+
+        for path in ["/", "/no-such-route"] {
+            let response = plugin
+                .handle_http(HttpRequest {
+                    method: "GET".to_string(),
+                    path: path.to_string(),
+                    headers: HashMap::new(),
+                    body: None,
+                })
+                .await
+                .unwrap();
+
+            let value = &response.headers[systemd_services::handlers::PROCESSING_TIME_HEADER];
+            let millis: f64 = value.parse().unwrap();
+            assert!(millis >= 0.0, "{}: {}", path, value);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_requests_update_stats() {
+        let plugin = SystemdServicesPlugin::new();
+        let get = |path: &str| HttpRequest {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            headers: HashMap::new(),
+            body: None,
+        };
+
+        plugin.handle_http(get("/")).await.unwrap();
+        // Without a context the KV store is unavailable, so this fails
+        assert!(plugin.handle_http(get("/services")).await.is_err());
+
+        let response = plugin.handle_http(get("/debug/stats")).await.unwrap();
+        assert_eq!(response.status, 200);
+        let stats: systemd_services::stats::StatsSnapshot =
+            serde_json::from_str(&response.body.unwrap()).unwrap();
+
+        // The stats request itself is counted and still in flight
+        assert_eq!(stats.requests_total, 3);
+        assert_eq!(stats.requests_in_flight, 1);
+        assert_eq!(stats.commands_total, 0);
+        assert!(stats.last_error.unwrap().starts_with("GET /services: "));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_action_does_not_start_cooldown() {
+        let mut plugin = SystemdServicesPlugin::with_mock(true);
+        plugin
+            .init(PluginContext {
+                instance_id: "mock".to_string(),
+                config: toru_plugin_api::PluginConfig::default(),
+                kv: host_kv_store(plugin.mock),
+            })
+            .await
+            .unwrap();
+        let restart = |path: &str| HttpRequest {
+            method: "POST".to_string(),
+            path: path.to_string(),
+            headers: HashMap::new(),
+            body: None,
+        };
+
+        // force is only valid for stop: each attempt is a 400, never a 429
+        let forced = "/services/nginx.service/restart?force=true";
+        assert_eq!(plugin.handle_http(restart(forced)).await.unwrap().status, 400);
+        assert_eq!(plugin.handle_http(restart(forced)).await.unwrap().status, 400);
+
+        // The corrected request isn't held back by the rejected ones
+        let response = plugin.handle_http(restart("/services/nginx.service/restart")).await.unwrap();
+        assert_ne!(response.status, 429);
+        let response = plugin.handle_http(restart("/services/nginx.service/restart")).await.unwrap();
+        assert_eq!(response.status, 429);
+    }
+
+    #[tokio::test]
+    async fn test_mock_mode_serves_fixtures() {
+        let mut plugin = SystemdServicesPlugin::with_mock(true);
+        plugin
+            .init(PluginContext {
+                instance_id: "mock".to_string(),
+                config: toru_plugin_api::PluginConfig::default(),
+                kv: host_kv_store(plugin.mock),
+            })
+            .await
+            .unwrap();
+
+        let response = plugin
+            .handle_http(HttpRequest {
+                method: "GET".to_string(),
+                path: "/services".to_string(),
+                headers: HashMap::new(),
+                body: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.status, 200);
+
+        let services: Vec<serde_json::Value> = serde_json::from_str(&response.body.unwrap()).unwrap();
+        assert_eq!(services.len(), fixtures::FIXTURE_SERVICES.len());
+        assert_eq!(services[0]["name"], "nginx.service");
+        assert_eq!(services[0]["status"], "running");
+        assert_eq!(services[2]["name"], "backup.service");
+        assert_eq!(services[2]["status"], "failed");
+    }
 }
@@ -0,0 +1,159 @@
+// Plugin settings stored as one JSON object in KV
+
+use crate::error::Result;
+use crate::rate_limit::{ACTION_COOLDOWN_KEY, DEFAULT_ACTION_COOLDOWN};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use toru_plugin_api::PluginKvStore;
+
+/// KV key holding all plugin settings as one JSON object
+pub const SETTINGS_KEY: &str = "settings";
+
+/// Legacy KV key holding the watched services as a JSON array
+pub const WATCHED_SERVICES_KEY: &str = "watched_services";
+
+/// Legacy KV key toggling read-only mode (JSON boolean)
+pub const READ_ONLY_KEY: &str = "read_only";
+
+/// Legacy KV key enabling debugging aids such as raw journal output (JSON boolean)
+pub const DEBUG_MODE_KEY: &str = "debug_mode";
+
+/// Legacy KV key holding a JSON array of glob patterns for units that may be managed
+pub const ALLOWED_UNITS_KEY: &str = "allowed_units";
+
+/// Legacy KV key holding a JSON array of glob patterns for units that must never be managed
+pub const DENIED_UNITS_KEY: &str = "denied_units";
+
+/// Plugin settings, stored under `SETTINGS_KEY`
+/// Fields missing from the stored JSON take their default, so settings added
+/// later don't invalidate an existing blob
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PluginSettings {
+    /// Services shown on the dashboard
+    pub watched_services: Vec<String>,
+    /// Glob patterns for units that may be managed (empty = all)
+    pub allowed_units: Vec<String>,
+    /// Glob patterns for units that may never be managed; wins over `allowed_units`
+    pub denied_units: Vec<String>,
+    /// Blocks all mutating actions; reads keep working
+    pub read_only: bool,
+    /// Enables raw journal output and recent logs on failed start/restart
+    pub debug_mode: bool,
+    /// Minimum gap between repeats of the same service action
+    pub action_cooldown_ms: u64,
+    /// Origin allowed to call the plugin cross-origin (CORS); None sends no CORS headers
+    pub cors_origin: Option<String>,
+}
+
+impl Default for PluginSettings {
+    fn default() -> Self {
+        Self {
+            watched_services: Vec::new(),
+            allowed_units: Vec::new(),
+            denied_units: Vec::new(),
+            read_only: false,
+            debug_mode: false,
+            action_cooldown_ms: DEFAULT_ACTION_COOLDOWN.as_millis() as u64,
+            cors_origin: None,
+        }
+    }
+}
+
+impl PluginSettings {
+    /// Loads settings from KV storage
+    ///
+    /// Installs that predate the settings blob keep each setting under its own
+    /// key. When the blob is absent those are read instead and, if any were
+    /// set, saved as the blob so the migration happens once. The legacy keys
+    /// are left in place so an older plugin version still finds them.
+    pub async fn load(kv_store: &dyn PluginKvStore) -> Result<Self> {
+        if let Some(json_str) = kv_store.get(SETTINGS_KEY).await? {
+            let mut settings: Self = serde_json::from_str(&json_str)?;
+            if settings.adopt_legacy_access_keys(kv_store, &json_str).await? {
+                settings.save(kv_store).await?;
+            }
+            return Ok(settings);
+        }
+
+        let (settings, found_legacy) = Self::load_legacy(kv_store).await?;
+        if found_legacy {
+            eprintln!("Migrating legacy settings keys to '{}'", SETTINGS_KEY);
+            settings.save(kv_store).await?;
+        }
+
+        Ok(settings)
+    }
+
+    /// Saves settings to KV storage as one JSON object
+    pub async fn save(&self, kv_store: &dyn PluginKvStore) -> Result<()> {
+        let json_str = serde_json::to_string(self)?;
+        kv_store.set(SETTINGS_KEY, &json_str).await?;
+        Ok(())
+    }
+
+    /// Cooldown between repeats of the same service action
+    pub fn action_cooldown(&self) -> Duration {
+        Duration::from_millis(self.action_cooldown_ms)
+    }
+
+    /// Fills the access lists from their legacy keys when `blob` predates them
+    ///
+    /// The lists joined the blob after it was introduced, so a blob saved
+    /// earlier lacks them while the legacy keys may still restrict units.
+    /// Returns true if the blob needs saving to record the lists.
+    async fn adopt_legacy_access_keys(&mut self, kv_store: &dyn PluginKvStore, blob: &str) -> Result<bool> {
+        let stored: serde_json::Value = serde_json::from_str(blob)?;
+        let mut missing = false;
+
+        for (field, key) in [("allowed_units", ALLOWED_UNITS_KEY), ("denied_units", DENIED_UNITS_KEY)] {
+            if stored.get(field).is_some() {
+                continue;
+            }
+            missing = true;
+            if let Some(json_str) = kv_store.get(key).await? {
+                let patterns = serde_json::from_str(&json_str)?;
+                match field {
+                    "allowed_units" => self.allowed_units = patterns,
+                    _ => self.denied_units = patterns,
+                }
+            }
+        }
+
+        Ok(missing)
+    }
+
+    /// Builds settings from the per-setting keys, defaulting any that are unset
+    /// The flag tells whether any legacy key was set at all
+    async fn load_legacy(kv_store: &dyn PluginKvStore) -> Result<(Self, bool)> {
+        let mut settings = Self::default();
+        let mut found = false;
+
+        if let Some(json_str) = kv_store.get(WATCHED_SERVICES_KEY).await? {
+            settings.watched_services = serde_json::from_str(&json_str)?;
+            found = true;
+        }
+        if let Some(json_str) = kv_store.get(READ_ONLY_KEY).await? {
+            settings.read_only = serde_json::from_str(&json_str)?;
+            found = true;
+        }
+        if let Some(json_str) = kv_store.get(DEBUG_MODE_KEY).await? {
+            settings.debug_mode = serde_json::from_str(&json_str)?;
+            found = true;
+        }
+        if let Some(json_str) = kv_store.get(ACTION_COOLDOWN_KEY).await? {
+            settings.action_cooldown_ms = serde_json::from_str(&json_str)?;
+            found = true;
+        }
+        if let Some(json_str) = kv_store.get(ALLOWED_UNITS_KEY).await? {
+            settings.allowed_units = serde_json::from_str(&json_str)?;
+            found = true;
+        }
+        if let Some(json_str) = kv_store.get(DENIED_UNITS_KEY).await? {
+            settings.denied_units = serde_json::from_str(&json_str)?;
+            found = true;
+        }
+
+        Ok((settings, found))
+    }
+}
@@ -0,0 +1,148 @@
+// Plugin-internal counters for GET /debug/stats
+
+use crate::error::Result;
+use crate::systemctl::{CommandExecutor, CommandOutput};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
+
+/// Request and command counters kept by the plugin
+///
+/// Shared by every request; counters only go up except `requests_in_flight`,
+/// which a `RequestGuard` decrements when the request finishes.
+#[derive(Debug, Default)]
+pub struct PluginStats {
+    requests_total: AtomicU64,
+    requests_in_flight: AtomicU64,
+    commands_total: AtomicU64,
+    last_error: Mutex<Option<String>>,
+}
+
+/// Point-in-time copy of the counters, as returned by GET /debug/stats
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatsSnapshot {
+    pub requests_total: u64,
+    pub requests_in_flight: u64,
+    /// systemctl and journalctl invocations
+    pub commands_total: u64,
+    /// Status polls answered from an open circuit breaker without querying systemd
+    pub cache_hits: u64,
+    /// Status polls that queried systemd
+    pub cache_misses: u64,
+    /// Message of the most recent 5xx response or dispatch error
+    pub last_error: Option<String>,
+}
+
+/// Keeps a request counted as in flight until dropped
+pub struct RequestGuard<'a> {
+    stats: &'a PluginStats,
+}
+
+impl Drop for RequestGuard<'_> {
+    fn drop(&mut self) {
+        self.stats.requests_in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl PluginStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts a request as received and in flight until the guard is dropped
+    pub fn request_started(&self) -> RequestGuard<'_> {
+        self.requests_total.fetch_add(1, Ordering::Relaxed);
+        self.requests_in_flight.fetch_add(1, Ordering::Relaxed);
+        RequestGuard { stats: self }
+    }
+
+    /// Counts one spawned command
+    pub fn command_started(&self) {
+        self.commands_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Remembers the most recent server-side error
+    pub fn record_error(&self, error: &str) {
+        let mut last_error = self.last_error.lock().unwrap_or_else(|e| e.into_inner());
+        *last_error = Some(error.to_string());
+    }
+
+    /// Copies the counters; `cache_hits`/`cache_misses` come from the circuit breaker
+    pub fn snapshot(&self, cache_hits: u64, cache_misses: u64) -> StatsSnapshot {
+        StatsSnapshot {
+            requests_total: self.requests_total.load(Ordering::Relaxed),
+            requests_in_flight: self.requests_in_flight.load(Ordering::Relaxed),
+            commands_total: self.commands_total.load(Ordering::Relaxed),
+            cache_hits,
+            cache_misses,
+            last_error: self.last_error.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+        }
+    }
+}
+
+/// Executor wrapper counting every command in `PluginStats`
+pub struct CountingExecutor<E> {
+    inner: E,
+    stats: Arc<PluginStats>,
+}
+
+impl<E: CommandExecutor> CountingExecutor<E> {
+    pub fn new(inner: E, stats: Arc<PluginStats>) -> Self {
+        Self { inner, stats }
+    }
+}
+
+#[async_trait]
+impl<E: CommandExecutor> CommandExecutor for CountingExecutor<E> {
+    async fn execute(&self, cmd: &str, args: &[&str]) -> Result<CommandOutput> {
+        self.stats.command_started();
+        self.inner.execute(cmd, args).await
+    }
+
+    async fn execute_cancellable(
+        &self,
+        cmd: &str,
+        args: &[&str],
+        token: CancellationToken,
+    ) -> Result<CommandOutput> {
+        self.stats.command_started();
+        self.inner.execute_cancellable(cmd, args, token).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systemctl::MockCommandExecutor;
+
+    #[tokio::test]
+    async fn test_counting_executor_counts_commands() {
+        let stats = Arc::new(PluginStats::new());
+        let executor = CountingExecutor::new(
+            MockCommandExecutor::new().with_stdout("systemctl", &["is-system-running"], "running\n"),
+            stats.clone(),
+        );
+
+        executor.execute("systemctl", &["is-system-running"]).await.unwrap();
+        // A failing command still counts as an invocation
+        assert!(executor.execute("systemctl", &["bogus"]).await.is_err());
+
+        assert_eq!(stats.snapshot(0, 0).commands_total, 2);
+    }
+
+    #[test]
+    fn test_request_guard_tracks_in_flight() {
+        let stats = PluginStats::new();
+
+        let guard = stats.request_started();
+        let _second = stats.request_started();
+        assert_eq!(stats.snapshot(0, 0).requests_in_flight, 2);
+
+        drop(guard);
+        let snapshot = stats.snapshot(0, 0);
+        assert_eq!(snapshot.requests_total, 2);
+        assert_eq!(snapshot.requests_in_flight, 1);
+    }
+}
@@ -2,9 +2,12 @@ use crate::error::{Result, ServiceError};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::process::Stdio;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::process::Command;
+use tokio::sync::Semaphore;
 use tokio::time::timeout;
+use tokio_util::sync::CancellationToken;
 
 /// Command execution output
 #[derive(Debug, Clone)]
@@ -18,20 +21,73 @@ pub struct CommandOutput {
 #[async_trait]
 pub trait CommandExecutor: Send + Sync {
     async fn execute(&self, cmd: &str, args: &[&str]) -> Result<CommandOutput>;
+
+    /// Like `execute`, but gives up with `ServiceError::Cancelled` once `token` fires
+    /// Executors that can't abort a command may ignore the token, which is the default.
+    async fn execute_cancellable(
+        &self,
+        cmd: &str,
+        args: &[&str],
+        token: CancellationToken,
+    ) -> Result<CommandOutput> {
+        let _ = token;
+        self.execute(cmd, args).await
+    }
+}
+
+/// Lets a shared or type-erased executor (`Arc<dyn CommandExecutor>`) be used
+/// wherever an executor is expected
+#[async_trait]
+impl<T: CommandExecutor + ?Sized> CommandExecutor for Arc<T> {
+    async fn execute(&self, cmd: &str, args: &[&str]) -> Result<CommandOutput> {
+        (**self).execute(cmd, args).await
+    }
+
+    async fn execute_cancellable(
+        &self,
+        cmd: &str,
+        args: &[&str],
+        token: CancellationToken,
+    ) -> Result<CommandOutput> {
+        (**self).execute_cancellable(cmd, args, token).await
+    }
 }
 
+/// Environment set on every spawned command so output doesn't depend on the
+/// host's locale (timestamps, messages) and systemctl is found regardless of
+/// the plugin's inherited PATH
+pub const COMMAND_ENV: &[(&str, &str)] = &[
+    ("LC_ALL", "C"),
+    ("PATH", "/usr/local/sbin:/usr/local/bin:/usr/sbin:/usr/bin:/sbin:/bin"),
+];
+
+/// Default cap on commands running at once
+pub const DEFAULT_MAX_CONCURRENT_COMMANDS: usize = 8;
+
 /// Production command executor that runs real system commands
+/// At most `max_concurrent` commands run at once; further calls wait for a slot,
+/// so a burst of fan-out queries doesn't spawn dozens of systemctl processes.
 pub struct SystemCommandExecutor {
     timeout_secs: u64,
+    permits: Arc<Semaphore>,
 }
 
 impl SystemCommandExecutor {
     pub fn new() -> Self {
-        Self { timeout_secs: 10 }
+        Self::with_timeout(10)
     }
 
     pub fn with_timeout(timeout_secs: u64) -> Self {
-        Self { timeout_secs }
+        Self {
+            timeout_secs,
+            permits: Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_COMMANDS)),
+        }
+    }
+
+    /// Sets how many commands may run at once (at least one)
+    pub fn with_max_concurrent(mut self, max_concurrent: usize) -> Self {
+        self.permits = Arc::new(Semaphore::new(max_concurrent.max(1)));
+        self
     }
 }
 
@@ -44,17 +100,45 @@ impl Default for SystemCommandExecutor {
 #[async_trait]
 impl CommandExecutor for SystemCommandExecutor {
     async fn execute(&self, cmd: &str, args: &[&str]) -> Result<CommandOutput> {
+        self.execute_cancellable(cmd, args, CancellationToken::new()).await
+    }
+
+    /// Kills the child process if the token fires before it exits
+    async fn execute_cancellable(
+        &self,
+        cmd: &str,
+        args: &[&str],
+        token: CancellationToken,
+    ) -> Result<CommandOutput> {
         let cmd_string = format!("{} {}", cmd, args.join(" "));
 
+        // Held until the command finishes; the semaphore is never closed
+        let _permit = tokio::select! {
+            permit = self.permits.acquire() => permit
+                .map_err(|e| ServiceError::Other(format!("Command limiter closed: {}", e)))?,
+            _ = token.cancelled() => {
+                return Err(ServiceError::Cancelled(format!("Command '{}' was cancelled", cmd_string)));
+            }
+        };
+
+        // Dropping the wait future below drops the child, which then kills it
         let child = Command::new(cmd)
             .args(args)
+            .envs(COMMAND_ENV.iter().copied())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
+            .kill_on_drop(true)
             .spawn()
             .map_err(|e| ServiceError::IoError(format!("Failed to spawn command '{}': {}", cmd_string, e)))?;
 
-        let output = timeout(Duration::from_secs(self.timeout_secs), child.wait_with_output())
-            .await
+        let output = tokio::select! {
+            result = timeout(Duration::from_secs(self.timeout_secs), child.wait_with_output()) => result,
+            _ = token.cancelled() => {
+                return Err(ServiceError::Cancelled(format!("Command '{}' was cancelled", cmd_string)));
+            }
+        };
+
+        let output = output
             .map_err(|_| ServiceError::Timeout(format!("Command '{}' timed out after {}s", cmd_string, self.timeout_secs)))?
             .map_err(|e| ServiceError::IoError(format!("Failed to wait for command '{}': {}", cmd_string, e)))?;
 
@@ -69,15 +153,23 @@ impl CommandExecutor for SystemCommandExecutor {
 /// Mock command executor for tests
 pub struct MockCommandExecutor {
     responses: HashMap<String, CommandOutput>,
+    timeouts: Vec<String>,
 }
 
 impl MockCommandExecutor {
     pub fn new() -> Self {
         Self {
             responses: HashMap::new(),
+            timeouts: Vec::new(),
         }
     }
 
+    /// Makes a command fail with `ServiceError::Timeout`, as if it hung
+    pub fn with_timeout(mut self, cmd: &str, args: &[&str]) -> Self {
+        self.timeouts.push(format!("{} {}", cmd, args.join(" ")));
+        self
+    }
+
     /// Adds a mock response for a specific command
     pub fn with_response(mut self, cmd: &str, args: &[&str], output: CommandOutput) -> Self {
         let key = format!("{} {}", cmd, args.join(" "));
@@ -115,6 +207,10 @@ impl CommandExecutor for MockCommandExecutor {
     async fn execute(&self, cmd: &str, args: &[&str]) -> Result<CommandOutput> {
         let key = format!("{} {}", cmd, args.join(" "));
 
+        if self.timeouts.contains(&key) {
+            return Err(ServiceError::Timeout(format!("Command '{}' timed out", key)));
+        }
+
         self.responses
             .get(&key)
             .cloned()
@@ -175,4 +271,27 @@ mod tests {
         // Should be IoError because command doesn't exist
         assert!(matches!(result.unwrap_err(), ServiceError::IoError(_)));
     }
+
+    #[tokio::test]
+    async fn test_system_executor_limits_concurrency() {
+        let executor = Arc::new(SystemCommandExecutor::new().with_max_concurrent(2));
+        let started = std::time::Instant::now();
+
+        // Six 200ms commands through two slots take at least three rounds
+        let mut tasks = tokio::task::JoinSet::new();
+        for _ in 0..6 {
+            let executor = executor.clone();
+            tasks.spawn(async move { executor.execute("sleep", &["0.2"]).await });
+        }
+
+        let mut completed = 0;
+        while let Some(result) = tasks.join_next().await {
+            assert_eq!(result.unwrap().unwrap().exit_code, 0);
+            completed += 1;
+        }
+
+        assert_eq!(completed, 6);
+        assert!(started.elapsed() >= Duration::from_millis(600));
+        assert_eq!(executor.permits.available_permits(), 2);
+    }
 }
@@ -5,11 +5,15 @@ mod parser;
 mod tests;
 
 pub use executor::{CommandExecutor, SystemCommandExecutor, MockCommandExecutor, CommandOutput};
+pub use parser::severity_class;
 
+use crate::clock::Clock;
 use crate::error::{Result, ServiceError};
+use crate::log_filter::LogFilter;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
 /// Information about a systemd service
@@ -26,11 +30,53 @@ pub struct ServiceInfo {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServiceStatus {
     pub name: String,
+    pub description: String,
+    /// "loaded", "not-found" (unit file deleted), "masked", ...
+    pub load_state: String,
     pub active_state: String,
     pub sub_state: String,
     pub uptime_seconds: u64,
+    /// Time in the current activation, measured on the monotonic clock when available
+    pub current_uptime_seconds: u64,
     pub main_pid: Option<u32>,
     pub active_enter_timestamp: Option<DateTime<Utc>>,
+    /// When the unit was first started this boot, from the journal; an
+    /// earlier value than `active_enter_timestamp` means the service has
+    /// been restarted since
+    pub first_activation_timestamp: Option<DateTime<Utc>>,
+    /// Processes and threads in the service's cgroup; None when not accounted
+    pub tasks_current: Option<u64>,
+    /// Task limit; None when unlimited (`infinity`)
+    pub tasks_max: Option<u64>,
+}
+
+/// `systemctl status`-style overview of a service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceSummary {
+    pub status: ServiceStatus,
+    /// "loaded", "not-found", "masked", ...
+    pub load_state: String,
+    /// "enabled", "disabled", "static", ...
+    pub unit_file_state: String,
+    pub memory_bytes: Option<u64>,
+    /// Control group the service's processes run in
+    pub cgroup: Option<String>,
+    /// `Restart=` policy; explains a service that comes back after a stop or crash
+    pub restart: RestartPolicy,
+    /// Last `SUMMARY_LOG_LINES` journal lines, oldest first
+    pub recent_logs: Vec<LogEntry>,
+}
+
+/// A service's `Restart=` and `RestartSec=` settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RestartPolicy {
+    /// "no", "on-failure", "always", ...; "no" when systemd doesn't report one
+    pub restart: String,
+    /// Delay before an automatic restart as systemd prints it ("5s", "100ms")
+    pub restart_sec: Option<String>,
+    /// True for any policy other than "no", so the UI can warn that the
+    /// service restarts on its own
+    pub auto_restarts: bool,
 }
 
 /// Log entry from journalctl
@@ -39,6 +85,114 @@ pub struct LogEntry {
     pub timestamp: DateTime<Utc>,
     pub message: String,
     pub priority: u8,
+    /// Syslog level name for `priority` ("err", "info", ...)
+    pub level: String,
+    /// UI colour bucket for `priority`: "error", "warn", "info" or "debug"
+    #[serde(default)]
+    pub severity_class: String,
+    /// Unit the entry belongs to (`UNIT` for systemd's own messages, else
+    /// `_SYSTEMD_UNIT`); tells template instances apart in combined queries
+    pub unit: Option<String>,
+    /// The message is incomplete: journalctl withheld an oversized field, or
+    /// it was cut to `MAX_LOG_MESSAGE_BYTES`
+    #[serde(default)]
+    pub truncated: bool,
+    /// journald's `__CURSOR` for the entry; pass it back as `after_cursor`
+    /// to read only newer entries
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+/// Longest message kept per log entry; the rest is cut and the entry flagged `truncated`
+pub const MAX_LOG_MESSAGE_BYTES: usize = 64 * 1024;
+
+/// journalctl output lines longer than this are not parsed as JSON; only
+/// their timestamp and priority are recovered
+pub const MAX_JOURNAL_LINE_BYTES: usize = 1024 * 1024;
+
+/// Upper bound on the `boots` log parameter
+pub const MAX_LOG_BOOTS: u32 = 10;
+
+/// Most journal matches `count_matching_logs` counts before reporting the total as capped
+pub const MAX_COUNTED_MATCHES: usize = 10_000;
+
+/// journald catalog `MESSAGE_ID` of systemd's "Started <unit>" job message
+pub const MESSAGE_ID_UNIT_STARTED: &str = "39f53479d3a045ac8e11786248231fbf";
+
+/// journald catalog `MESSAGE_ID` of systemd's "Stopped <unit>" job message
+pub const MESSAGE_ID_UNIT_STOPPED: &str = "9d1aaa27d60140bd96365438aad20286";
+
+/// journald catalog `MESSAGE_ID` of systemd's "Failed to start <unit>" job message
+pub const MESSAGE_ID_UNIT_FAILED: &str = "be02cf6855d2428ba40df7e9d022f03d";
+
+/// journald catalog `MESSAGE_ID` of systemd's "Reloaded <unit>" job message
+pub const MESSAGE_ID_UNIT_RELOADED: &str = "7b05ebc668384222baa8881179cfda54";
+
+/// Message IDs the activity feed asks journald for
+pub const ACTIVITY_MESSAGE_IDS: &[&str] = &[
+    MESSAGE_ID_UNIT_STARTED,
+    MESSAGE_ID_UNIT_STOPPED,
+    MESSAGE_ID_UNIT_FAILED,
+];
+
+/// Builds a journalctl `MESSAGE_ID=<id>` match; several matches on the same
+/// field are OR'ed by journald
+/// Rejects anything but a 128-bit hex ID, since the match is passed as an argument
+pub fn message_id_match(message_id: &str) -> Result<String> {
+    if message_id.len() != 32 || !message_id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ServiceError::ParseError(format!("Invalid MESSAGE_ID: {}", message_id)));
+    }
+
+    Ok(format!("MESSAGE_ID={}", message_id.to_ascii_lowercase()))
+}
+
+/// Lifecycle event systemd logged for a unit (started, stopped or failed)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityEvent {
+    pub timestamp: DateTime<Utc>,
+    pub unit: String,
+    /// "started", "stopped" or "failed"
+    pub event: String,
+    /// Job result systemd attached to the event ("done", "failed", "timeout", ...)
+    pub job_result: Option<String>,
+    pub message: String,
+}
+
+/// Maps a syslog priority (0-7) to its level name; out-of-range values are "debug"
+pub fn priority_to_level(priority: u8) -> &'static str {
+    match priority {
+        0 => "emerg",
+        1 => "alert",
+        2 => "crit",
+        3 => "err",
+        4 => "warning",
+        5 => "notice",
+        6 => "info",
+        _ => "debug",
+    }
+}
+
+/// Maps a syslog priority given as a number ("3") or level name ("err", "error", "warning") to its number
+pub fn level_to_priority(level: &str) -> Option<u8> {
+    match level.to_ascii_lowercase().as_str() {
+        "emerg" => Some(0),
+        "alert" => Some(1),
+        "crit" => Some(2),
+        "err" | "error" => Some(3),
+        "warning" | "warn" => Some(4),
+        "notice" => Some(5),
+        "info" => Some(6),
+        "debug" => Some(7),
+        other => other.parse::<u8>().ok().filter(|p| *p <= 7),
+    }
+}
+
+/// Disk space used by the systemd journal
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JournalUsage {
+    pub disk_usage_bytes: u64,
+    /// Size as reported by journalctl, e.g. "1.2G"
+    pub disk_usage_human: String,
 }
 
 /// Validates service name to prevent command injection
@@ -60,6 +214,137 @@ pub fn validate_service_name(name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Validates a journal namespace name for `journalctl --namespace=`
+/// Namespaces are directory names under /var/log/journal, so this allows
+/// letters, digits, `_`, `-` and `.` (not leading), up to 64 characters
+pub fn validate_journal_namespace(namespace: &str) -> Result<()> {
+    let valid = !namespace.is_empty()
+        && namespace.len() <= 64
+        && !namespace.starts_with('.')
+        && namespace.chars().all(|c| c.is_ascii_alphanumeric() || "_-.".contains(c));
+
+    if !valid {
+        return Err(ServiceError::InvalidNamespace(namespace.to_string()));
+    }
+
+    Ok(())
+}
+
+/// States `systemctl list-units --state=` accepts for services: load states,
+/// active states and service sub-states, as printed by `--state=help`
+pub const UNIT_LIST_STATES: &[&str] = &[
+    // Load states
+    "stub", "loaded", "not-found", "bad-setting", "error", "merged", "masked",
+    // Active states
+    "active", "reloading", "inactive", "failed", "activating", "deactivating", "maintenance", "refreshing",
+    // Service sub-states
+    "dead", "condition", "start-pre", "start", "start-post", "running", "exited", "reload", "reload-signal",
+    "reload-notify", "mounting", "stop", "stop-watchdog", "stop-sigterm", "stop-sigkill", "stop-post",
+    "final-watchdog", "final-sigterm", "final-sigkill", "auto-restart", "auto-restart-queued", "cleaning",
+];
+
+/// Validates a comma-separated `--state=` filter against [`UNIT_LIST_STATES`]
+/// Anything else is rejected before it reaches the command line
+pub fn validate_unit_states(states: &str) -> Result<()> {
+    for state in states.split(',') {
+        if !UNIT_LIST_STATES.contains(&state) {
+            return Err(ServiceError::InvalidState(state.to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Properties that may be fetched through GET /services/:name/properties
+/// Read-only unit and resource-accounting properties; nothing that exposes
+/// environment variables or credentials
+pub const ALLOWED_PROPERTIES: &[&str] = &[
+    "Id",
+    "Description",
+    "LoadState",
+    "ActiveState",
+    "SubState",
+    "UnitFileState",
+    "UnitFilePreset",
+    "FragmentPath",
+    "DropInPaths",
+    "Type",
+    "Restart",
+    "RestartUSec",
+    "NRestarts",
+    "Result",
+    "MainPID",
+    "ControlPID",
+    "ExecMainStatus",
+    "ExecMainCode",
+    "ExecMainStartTimestamp",
+    "ExecMainExitTimestamp",
+    "ActiveEnterTimestamp",
+    "ActiveExitTimestamp",
+    "InactiveEnterTimestamp",
+    "StateChangeTimestamp",
+    "MemoryCurrent",
+    "MemoryPeak",
+    "MemoryMax",
+    "MemoryHigh",
+    "MemoryLimit",
+    "CPUUsageNSec",
+    "CPUQuotaPerSecUSec",
+    "CPUWeight",
+    "TasksCurrent",
+    "TasksMax",
+    "IOReadBytes",
+    "IOWriteBytes",
+    "IPIngressBytes",
+    "IPEgressBytes",
+    "User",
+    "Group",
+    "WorkingDirectory",
+    "TimeoutStartUSec",
+    "TimeoutStopUSec",
+    "WatchdogUSec",
+    "Wants",
+    "Requires",
+    "After",
+    "Before",
+    "WantedBy",
+    "Slice",
+    "ControlGroup",
+];
+
+/// Validates property names against [`ALLOWED_PROPERTIES`]
+/// Rejecting unknown names also keeps anything flag-like off the command line
+pub fn validate_property_names(names: &[String]) -> Result<()> {
+    if names.is_empty() {
+        return Err(ServiceError::InvalidProperty("No property names given".to_string()));
+    }
+
+    for name in names {
+        if !ALLOWED_PROPERTIES.contains(&name.as_str()) {
+            return Err(ServiceError::InvalidProperty(name.clone()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Most properties requested by a single `systemctl show` call in `get_service_properties`
+pub const PROPERTY_CHUNK_SIZE: usize = 16;
+
+/// Properties read by `get_service_status`
+pub(crate) const STATUS_PROPERTIES: &str =
+    "Description,LoadState,ActiveState,SubState,MainPID,ActiveEnterTimestamp,ActiveEnterTimestampMonotonic,TasksCurrent,TasksMax";
+
+/// Makes `systemctl show` print timestamps as `@<seconds since the epoch>`
+/// instead of the locale-dependent "Wed 2024-01-15 10:30:45 UTC" form
+pub(crate) const SHOW_TIMESTAMP_ARG: &str = "--timestamp=unix";
+
+/// Properties a service summary reads on top of `STATUS_PROPERTIES`
+pub(crate) const SUMMARY_PROPERTIES: &str = "UnitFileState,MemoryCurrent,ControlGroup,Restart,RestartUSec";
+
+/// Journal lines included in a service summary
+pub const SUMMARY_LOG_LINES: u32 = 10;
+
 /// Lists all systemd services
 pub async fn list_services<E: CommandExecutor>(executor: Arc<E>) -> Result<Vec<ServiceInfo>> {
     let output = executor.execute("systemctl", &[
@@ -74,20 +359,269 @@ pub async fn list_services<E: CommandExecutor>(executor: Arc<E>) -> Result<Vec<S
     parser::parse_service_list(&output.stdout)
 }
 
-/// Gets detailed status of a specific service
+/// Lists systemd services in the given states (comma-separated, e.g. `failed`)
+///
+/// systemd does the filtering, which is cheaper than listing every service
+/// and discarding most of them.
+pub async fn list_services_in_state<E: CommandExecutor>(
+    executor: Arc<E>,
+    states: &str,
+) -> Result<Vec<ServiceInfo>> {
+    validate_unit_states(states)?;
+
+    let state_arg = format!("--state={}", states);
+    let output = executor.execute("systemctl", &[
+        "list-units",
+        "--type=service",
+        "--all",
+        &state_arg,
+        "--no-pager",
+        "--plain",
+        "--no-legend"
+    ]).await?;
+
+    parser::parse_service_list(&output.stdout)
+}
+
+/// Gets detailed status of a specific service, computing uptimes against `clock`
+///
+/// `first_activation_timestamp` comes from the journal and is left unset
+/// here; see `get_first_activations`.
 pub async fn get_service_status<E: CommandExecutor>(
     executor: Arc<E>,
-    service_name: &str
+    service_name: &str,
+    clock: &dyn Clock,
 ) -> Result<ServiceStatus> {
     validate_service_name(service_name)?;
 
+    let property_arg = format!("--property={}", STATUS_PROPERTIES);
     let output = executor.execute("systemctl", &[
         "show",
         service_name,
-        "--property=ActiveState,SubState,MainPID,ActiveEnterTimestamp"
+        SHOW_TIMESTAMP_ARG,
+        &property_arg,
     ]).await?;
 
-    parser::parse_service_status(service_name, &output.stdout)
+    parser::parse_service_status(service_name, &output.stdout, clock)
+}
+
+/// Finds when each of the given services was first started this boot
+///
+/// systemd logs a "Started" job message on every activation, automatic
+/// restarts included, so the earliest one this boot marks the first
+/// activation. All services are looked up in one journal query. Services
+/// not started this boot, or whose entry has been rotated out of the
+/// journal, are absent from the map.
+pub async fn get_first_activations<E: CommandExecutor>(
+    executor: Arc<E>,
+    service_names: &[String],
+) -> Result<HashMap<String, DateTime<Utc>>> {
+    for service_name in service_names {
+        validate_service_name(service_name)?;
+    }
+
+    if service_names.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let started_match = message_id_match(MESSAGE_ID_UNIT_STARTED)?;
+    let mut args: Vec<&str> = vec!["-b"];
+    for service_name in service_names {
+        args.push("-u");
+        args.push(service_name);
+    }
+    args.extend([started_match.as_str(), "--no-pager", "--output=json"]);
+
+    let output = executor.execute("journalctl", &args).await?;
+
+    if output.exit_code != 0 {
+        if output.stderr.contains("No journal files were found") ||
+           output.stderr.contains("No entries") {
+            return Ok(HashMap::new());
+        }
+
+        return Err(parse_journalctl_error(&output));
+    }
+
+    let mut first_activations: HashMap<String, DateTime<Utc>> = HashMap::new();
+    for event in parser::parse_activity(&output.stdout)? {
+        if event.event != "started" {
+            continue;
+        }
+
+        // The journal names the unit in full; `-u nginx` matches nginx.service
+        let Some(service_name) = service_names
+            .iter()
+            .find(|name| event.unit == **name || event.unit == format!("{}.service", name))
+        else {
+            continue;
+        };
+
+        first_activations
+            .entry(service_name.clone())
+            .and_modify(|first| *first = (*first).min(event.timestamp))
+            .or_insert(event.timestamp);
+    }
+
+    Ok(first_activations)
+}
+
+/// Gets the `systemctl status` view of a service: state, main PID, memory,
+/// tasks, control group and the last few journal lines
+///
+/// The status, the first activation this boot and the logs are queried
+/// concurrently. Logs that can't be read leave `recent_logs` empty, and an
+/// unreadable journal leaves the first activation unset, rather than failing
+/// the summary. Uptimes are computed against `clock`.
+pub async fn get_service_summary<E: CommandExecutor>(
+    executor: Arc<E>,
+    service_name: &str,
+    clock: &dyn Clock,
+) -> Result<ServiceSummary> {
+    validate_service_name(service_name)?;
+
+    let property_arg = format!("--property={},{}", STATUS_PROPERTIES, SUMMARY_PROPERTIES);
+    let show_args = ["show", service_name, SHOW_TIMESTAMP_ARG, &property_arg];
+    let activation_names = [service_name.to_string()];
+    let (output, logs, first_activations) = tokio::join!(
+        executor.execute("systemctl", &show_args),
+        get_logs(executor.clone(), service_name, SUMMARY_LOG_LINES),
+        get_first_activations(executor.clone(), &activation_names),
+    );
+    let output = output?;
+
+    let properties = parser::parse_properties(&output.stdout);
+    let property = |name: &str| properties.get(name).map(|value| value.trim()).filter(|value| !value.is_empty());
+
+    // systemctl show succeeds for unknown units and reports them as not-found
+    let load_state = property("LoadState").unwrap_or("unknown").to_string();
+    if load_state == "not-found" {
+        return Err(ServiceError::ServiceNotFound(service_name.to_string()));
+    }
+
+    let recent_logs = logs.unwrap_or_else(|e| {
+        eprintln!("Failed to read logs for {} summary: {}", service_name, e);
+        Vec::new()
+    });
+
+    let mut status = parser::parse_service_status(service_name, &output.stdout, clock)?;
+    status.first_activation_timestamp = first_activations
+        .ok()
+        .and_then(|first_activations| first_activations.get(service_name).copied());
+
+    Ok(ServiceSummary {
+        status,
+        load_state,
+        unit_file_state: property("UnitFileState").unwrap_or("unknown").to_string(),
+        memory_bytes: property("MemoryCurrent").and_then(parser::parse_systemd_optional_u64),
+        cgroup: property("ControlGroup").map(str::to_string),
+        restart: parser::parse_restart_policy(&properties),
+        recent_logs,
+    })
+}
+
+/// Gets the requested (allowlisted) properties of a service as a name -> value map
+pub async fn get_service_properties<E: CommandExecutor>(
+    executor: Arc<E>,
+    service_name: &str,
+    names: &[String],
+) -> Result<BTreeMap<String, String>> {
+    validate_service_name(service_name)?;
+    validate_property_names(names)?;
+
+    // Long property lists are split over several `show` calls to keep each
+    // command line short; the results are merged into one map
+    let mut properties = BTreeMap::new();
+    for chunk in names.chunks(PROPERTY_CHUNK_SIZE) {
+        let property_arg = format!("--property={}", chunk.join(","));
+        let output = executor.execute("systemctl", &[
+            "show",
+            service_name,
+            &property_arg,
+        ]).await?;
+
+        if output.exit_code != 0 {
+            return Err(parse_systemctl_error(&output));
+        }
+
+        properties.extend(parser::parse_properties(&output.stdout));
+    }
+
+    Ok(properties)
+}
+
+/// Returns the command a service action would run, without running it
+/// None for actions other than start, stop and restart
+pub fn service_action_command(service_name: &str, action: &str) -> Result<Option<String>> {
+    validate_service_name(service_name)?;
+
+    if let Some(steps) = compound_action_steps(action) {
+        let commands: Vec<String> = steps
+            .iter()
+            .map(|step| format!("systemctl {} {}", step, service_name))
+            .collect();
+        return Ok(Some(commands.join(" && ")));
+    }
+
+    Ok(match action {
+        "start" | "stop" | "restart" => Some(format!("systemctl {} {}", action, service_name)),
+        _ => None,
+    })
+}
+
+/// Returns the systemctl verbs a compound action runs, in order
+///
+/// - `disable-and-stop`: stop it now and keep it from starting at boot
+/// - `stop-and-mask`: stop it and prevent anything from starting it again
+///
+/// None for plain actions.
+pub fn compound_action_steps(action: &str) -> Option<&'static [&'static str]> {
+    match action {
+        "disable-and-stop" => Some(&["disable", "stop"]),
+        "stop-and-mask" => Some(&["stop", "mask"]),
+        _ => None,
+    }
+}
+
+/// Runs one step of a compound action (`stop`, `disable` or `mask`)
+pub async fn run_action_step<E: CommandExecutor>(
+    executor: Arc<E>,
+    service_name: &str,
+    step: &str,
+) -> Result<()> {
+    validate_service_name(service_name)?;
+
+    if !matches!(step, "stop" | "disable" | "mask") {
+        return Err(ServiceError::Other(format!("Unknown action step: {}", step)));
+    }
+
+    let output = executor.execute("systemctl", &[step, service_name]).await?;
+
+    if output.exit_code != 0 {
+        return Err(parse_systemctl_error(&output));
+    }
+
+    Ok(())
+}
+
+/// Checks whether systemd knows a unit, from its `LoadState` alone
+///
+/// Cheap enough to run before an action so a missing unit gets a clean 404.
+/// `loaded` and `masked` units exist (starting a masked one reports the
+/// mask); `not-found` units and invalid names don't. When systemctl can't
+/// answer, the unit is assumed to exist so the action reports the real failure.
+pub async fn unit_exists<E: CommandExecutor>(executor: Arc<E>, unit_name: &str) -> bool {
+    if validate_service_name(unit_name).is_err() {
+        return false;
+    }
+
+    let output = match executor.execute("systemctl", &["show", unit_name, "--property=LoadState"]).await {
+        Ok(output) if output.exit_code == 0 => output,
+        _ => return true,
+    };
+
+    let properties = parser::parse_properties(&output.stdout);
+    properties.get("LoadState").map(|state| state.trim()) != Some("not-found")
 }
 
 /// Starts a systemd service
@@ -122,6 +656,34 @@ pub async fn stop_service<E: CommandExecutor>(
     Ok(())
 }
 
+/// Stops a service, sending SIGKILL if the stop times out
+///
+/// For processes that ignore SIGTERM: once `systemctl stop` exceeds the
+/// command timeout, `systemctl kill --signal=SIGKILL` ends the unit's
+/// processes so the pending stop job can finish. Returns true if it had to
+/// escalate.
+pub async fn force_stop_service<E: CommandExecutor>(
+    executor: Arc<E>,
+    service_name: &str
+) -> Result<bool> {
+    match stop_service(executor.clone(), service_name).await {
+        Err(ServiceError::Timeout(reason)) => {
+            eprintln!("Stopping {} timed out ({}), sending SIGKILL", service_name, reason);
+        }
+        other => return other.map(|()| false),
+    }
+
+    let output = executor
+        .execute("systemctl", &["kill", "--signal=SIGKILL", service_name])
+        .await?;
+
+    if output.exit_code != 0 {
+        return Err(parse_systemctl_error(&output));
+    }
+
+    Ok(true)
+}
+
 /// Restarts a systemd service
 pub async fn restart_service<E: CommandExecutor>(
     executor: Arc<E>,
@@ -138,21 +700,327 @@ pub async fn restart_service<E: CommandExecutor>(
     Ok(())
 }
 
+/// Gets recent logs for several services, interleaved chronologically by journald
+pub async fn get_logs_for_units<E: CommandExecutor>(
+    executor: Arc<E>,
+    service_names: &[String],
+    lines: u32
+) -> Result<Vec<LogEntry>> {
+    if service_names.is_empty() {
+        return Err(ServiceError::InvalidServiceName("At least one unit is required".to_string()));
+    }
+
+    let output = read_journal(executor, service_names, lines).await?;
+    parser::parse_logs(&output)
+}
+
+/// Gets journald's JSON lines for several services exactly as journalctl prints them
+pub async fn get_raw_logs_for_units<E: CommandExecutor>(
+    executor: Arc<E>,
+    service_names: &[String],
+    lines: u32
+) -> Result<Vec<String>> {
+    if service_names.is_empty() {
+        return Err(ServiceError::InvalidServiceName("At least one unit is required".to_string()));
+    }
+
+    let output = read_journal(executor, service_names, lines).await?;
+    Ok(output.lines().map(str::to_string).collect())
+}
+
+/// Runs `journalctl --output=json` for the given services and returns its stdout
+/// Empty when journald has no entries for them
+async fn read_journal<E: CommandExecutor>(
+    executor: Arc<E>,
+    service_names: &[String],
+    lines: u32
+) -> Result<String> {
+    for service_name in service_names {
+        validate_service_name(service_name)?;
+    }
+
+    let lines_str = lines.to_string();
+    let mut args: Vec<&str> = Vec::new();
+    for service_name in service_names {
+        args.push("-u");
+        args.push(service_name);
+    }
+    args.extend(["-n", &lines_str, "--no-pager", "--output=json"]);
+
+    let output = executor.execute("journalctl", &args).await?;
+
+    if output.exit_code != 0 {
+        if output.stderr.contains("No journal files were found") ||
+           output.stderr.contains("No entries") {
+            return Ok(String::new());
+        }
+
+        return Err(parse_journalctl_error(&output));
+    }
+
+    Ok(output.stdout)
+}
+
+/// Gets systemd's start/stop/failure events for several services since a
+/// journalctl time spec ("1 hour ago", "2024-01-15 10:00"), oldest first
+pub async fn get_activity<E: CommandExecutor>(
+    executor: Arc<E>,
+    service_names: &[String],
+    since: &str,
+) -> Result<Vec<ActivityEvent>> {
+    for service_name in service_names {
+        validate_service_name(service_name)?;
+    }
+
+    let message_matches = ACTIVITY_MESSAGE_IDS
+        .iter()
+        .map(|id| message_id_match(id))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut args: Vec<&str> = Vec::new();
+    for service_name in service_names {
+        args.push("-u");
+        args.push(service_name);
+    }
+    args.extend(message_matches.iter().map(String::as_str));
+    args.extend(["--since", since, "--no-pager", "--output=json"]);
+
+    let output = executor.execute("journalctl", &args).await?;
+
+    if output.exit_code != 0 {
+        if output.stderr.contains("No journal files were found") ||
+           output.stderr.contains("No entries") {
+            return Ok(Vec::new());
+        }
+
+        return Err(parse_journalctl_error(&output));
+    }
+
+    let mut events = parser::parse_activity(&output.stdout)?;
+    events.sort_by_key(|event| event.timestamp);
+    Ok(events)
+}
+
+/// Lists the units a service depends on (`Requires=`, `Wants=`, ...)
+pub async fn get_dependencies<E: CommandExecutor>(
+    executor: Arc<E>,
+    service_name: &str,
+) -> Result<Vec<String>> {
+    validate_service_name(service_name)?;
+
+    let output = executor.execute("systemctl", &[
+        "list-dependencies",
+        service_name,
+        "--plain",
+        "--no-legend",
+        "--no-pager",
+    ]).await?;
+
+    if output.exit_code != 0 {
+        return Err(parse_systemctl_error(&output));
+    }
+
+    Ok(parser::parse_dependencies(&output.stdout))
+}
+
+/// Orders services so each comes after those of its dependencies that are also in the set
+///
+/// Falls back to the given order when any service's dependencies can't be
+/// listed or the dependencies form a cycle.
+pub async fn dependency_order<E: CommandExecutor>(
+    executor: Arc<E>,
+    services: &[String],
+) -> Vec<String> {
+    let listed = futures::future::join_all(
+        services.iter().map(|service| get_dependencies(executor.clone(), service))
+    ).await;
+
+    let mut dependencies = HashMap::new();
+    for (service, result) in services.iter().zip(listed) {
+        match result {
+            Ok(deps) => {
+                dependencies.insert(service.clone(), deps);
+            }
+            Err(e) => {
+                eprintln!("Failed to list dependencies of {}, keeping the given order: {}", service, e);
+                return services.to_vec();
+            }
+        }
+    }
+
+    order_by_dependencies(services, &dependencies).unwrap_or_else(|| {
+        eprintln!("Dependency cycle among {:?}, keeping the given order", services);
+        services.to_vec()
+    })
+}
+
+/// Topologically sorts `services` by the `dependencies` map, keeping the given
+/// order among services that don't depend on each other
+///
+/// Dependencies outside `services` are ignored. None on a cycle.
+pub fn order_by_dependencies(
+    services: &[String],
+    dependencies: &HashMap<String, Vec<String>>,
+) -> Option<Vec<String>> {
+    let mut ordered: Vec<String> = Vec::with_capacity(services.len());
+    let mut remaining: Vec<&String> = services.iter().collect();
+
+    while !remaining.is_empty() {
+        // The first remaining service whose in-set dependencies are all placed
+        let ready = remaining.iter().position(|service| {
+            dependencies.get(*service).into_iter().flatten().all(|dep| {
+                dep == *service || !remaining.contains(&dep)
+            })
+        })?;
+
+        ordered.push(remaining.remove(ready).clone());
+    }
+
+    Some(ordered)
+}
+
+/// Reloads systemd manager configuration (picks up edited unit files)
+pub async fn daemon_reload<E: CommandExecutor>(executor: Arc<E>) -> Result<()> {
+    let output = executor.execute("systemctl", &["daemon-reload"]).await?;
+
+    if output.exit_code != 0 {
+        return Err(parse_systemctl_error(&output));
+    }
+
+    Ok(())
+}
+
 /// Gets recent logs for a service
 pub async fn get_logs<E: CommandExecutor>(
     executor: Arc<E>,
     service_name: &str,
     lines: u32
+) -> Result<Vec<LogEntry>> {
+    get_logs_since(executor, service_name, lines, None, None, None).await
+}
+
+/// Finds the `--since` value covering the last `boots` boots
+///
+/// journalctl's `-b` selects a single boot, so the range is expressed as
+/// `--since` the first entry of the oldest boot wanted. If fewer boots are
+/// recorded, the range starts at the oldest one available. Returns None if
+/// journald has no boot list (e.g. a fresh journal).
+pub async fn boot_range_since<E: CommandExecutor>(executor: Arc<E>, boots: u32) -> Result<Option<String>> {
+    let output = executor.execute("journalctl", &["--list-boots", "--no-pager", "--output=json"]).await?;
+    if output.exit_code != 0 {
+        return Err(parse_journalctl_error(&output));
+    }
+
+    // Boot indices count back from 0 (current boot): the last N boots start at 1 - N
+    let oldest_index = 1 - boots.clamp(1, MAX_LOG_BOOTS) as i64;
+    let boot_starts = parser::parse_boot_starts(&output.stdout)?;
+
+    Ok(boot_starts
+        .iter()
+        .filter(|(index, _)| *index >= oldest_index)
+        .min_by_key(|(index, _)| *index)
+        .map(|(_, first_entry)| format!("@{}", first_entry.timestamp())))
+}
+
+/// Number of journal entries matching a log filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchCount {
+    /// Matches counted, at most `MAX_COUNTED_MATCHES`
+    pub count: usize,
+    /// True if the journal holds more matches than were counted
+    pub capped: bool,
+}
+
+/// Counts a service's journal entries matching `filter`, optionally from a `--since` time on
+///
+/// Runs the filter inside journalctl over the whole journal rather than over
+/// a fetched window. Only `PRIORITY` is emitted per entry to keep the output
+/// small; each entry is one JSON line. A broad filter over a large journal
+/// could match millions of entries, so at most `MAX_COUNTED_MATCHES` + 1 are
+/// read and anything past the limit is reported as `capped`.
+pub async fn count_matching_logs<E: CommandExecutor>(
+    executor: Arc<E>,
+    service_name: &str,
+    since: Option<&str>,
+    namespace: Option<&str>,
+    filter: &LogFilter,
+) -> Result<MatchCount> {
+    validate_service_name(service_name)?;
+    let namespace = namespace_arg(namespace)?;
+
+    let priority = filter.max_priority.map(|p| format!("0..{}", p));
+    let grep = filter.journal_regex();
+    let limit = (MAX_COUNTED_MATCHES + 1).to_string();
+
+    let mut args = vec!["-u", service_name, "-n", &limit];
+    if let Some(since) = since {
+        args.extend(["--since", since]);
+    }
+    if let Some(namespace) = &namespace {
+        args.push(namespace);
+    }
+    if let Some(priority) = &priority {
+        args.extend(["-p", priority]);
+    }
+    if let Some(grep) = &grep {
+        args.extend(["--grep", grep, "--case-sensitive=false"]);
+    }
+    args.extend(["--no-pager", "--output=json", "--output-fields=PRIORITY"]);
+
+    let output = executor.execute("journalctl", &args).await?;
+
+    if output.exit_code != 0 {
+        // --grep exits 1 with no output when nothing matched
+        if output.stderr.trim().is_empty() ||
+           output.stderr.contains("No journal files were found") ||
+           output.stderr.contains("No entries") {
+            return Ok(MatchCount { count: 0, capped: false });
+        }
+
+        return Err(parse_journalctl_error(&output));
+    }
+
+    let count = output.stdout.lines().filter(|line| !line.trim().is_empty()).count();
+    Ok(MatchCount {
+        count: count.min(MAX_COUNTED_MATCHES),
+        capped: count > MAX_COUNTED_MATCHES,
+    })
+}
+
+/// Gets recent logs for a service, optionally from a `--since` time on,
+/// only past a journal cursor, and from a journal namespace other than the
+/// default one
+pub async fn get_logs_since<E: CommandExecutor>(
+    executor: Arc<E>,
+    service_name: &str,
+    lines: u32,
+    since: Option<&str>,
+    after_cursor: Option<&str>,
+    namespace: Option<&str>,
 ) -> Result<Vec<LogEntry>> {
     validate_service_name(service_name)?;
+    let namespace = namespace_arg(namespace)?;
+    let after_cursor = after_cursor
+        .map(|cursor| validate_journal_cursor(cursor).map(|_| format!("--after-cursor={}", cursor)))
+        .transpose()?;
 
     let lines_str = lines.to_string();
-    let output = executor.execute("journalctl", &[
+    let mut args = vec![
         "-u", service_name,
         "-n", &lines_str,
-        "--no-pager",
-        "--output=json"
-    ]).await?;
+    ];
+    if let Some(since) = since {
+        args.extend(["--since", since]);
+    }
+    if let Some(after_cursor) = &after_cursor {
+        args.push(after_cursor);
+    }
+    if let Some(namespace) = &namespace {
+        args.push(namespace);
+    }
+    args.extend(["--no-pager", "--output=json"]);
+
+    let output = executor.execute("journalctl", &args).await?;
 
     if output.exit_code != 0 {
         // Check if service doesn't exist
@@ -167,8 +1035,75 @@ pub async fn get_logs<E: CommandExecutor>(
     parser::parse_logs(&output.stdout)
 }
 
+/// Longest journal cursor accepted; real ones are around 130 characters
+pub const MAX_CURSOR_LEN: usize = 512;
+
+/// Validates a journal cursor for `journalctl --after-cursor=`
+/// Cursors are `key=hex` pairs joined by `;`, so nothing else is allowed
+pub fn validate_journal_cursor(cursor: &str) -> Result<()> {
+    let valid = !cursor.is_empty()
+        && cursor.len() <= MAX_CURSOR_LEN
+        && cursor.contains('=')
+        && cursor.chars().all(|c| c.is_ascii_alphanumeric() || "=;".contains(c));
+
+    if !valid {
+        return Err(ServiceError::InvalidCursor(cursor.to_string()));
+    }
+
+    Ok(())
+}
+
+/// Builds the validated `--namespace=` argument, if any
+fn namespace_arg(namespace: Option<&str>) -> Result<Option<String>> {
+    namespace
+        .map(|ns| validate_journal_namespace(ns).map(|_| format!("--namespace={}", ns)))
+        .transpose()
+}
+
+/// How long the health probe waits for systemd before calling it unreachable
+pub const HEALTH_PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Asks systemd for the overall system state (`running`, `degraded`, `offline`, ...)
+///
+/// `is-system-running` exits non-zero for every state but `running`, so only
+/// an empty answer is an error. Gives up after `HEALTH_PROBE_TIMEOUT`.
+pub async fn get_system_state<E: CommandExecutor>(executor: Arc<E>) -> Result<String> {
+    let output = tokio::time::timeout(
+        HEALTH_PROBE_TIMEOUT,
+        executor.execute("systemctl", &["is-system-running"]),
+    )
+    .await
+    .map_err(|_| {
+        ServiceError::Timeout(format!(
+            "systemctl is-system-running timed out after {}s",
+            HEALTH_PROBE_TIMEOUT.as_secs()
+        ))
+    })??;
+
+    match output.stdout.trim() {
+        "" => Err(parse_systemctl_error(&output)),
+        state => Ok(state.to_string()),
+    }
+}
+
+/// Gets the disk space used by archived and active journal files
+pub async fn get_journal_usage<E: CommandExecutor>(executor: Arc<E>) -> Result<JournalUsage> {
+    let output = executor.execute("journalctl", &["--disk-usage"]).await?;
+
+    if output.exit_code != 0 {
+        return Err(parse_journalctl_error(&output));
+    }
+
+    parser::parse_journal_usage(&output.stdout)
+}
+
 /// Parses systemctl error from command output
 fn parse_systemctl_error(output: &CommandOutput) -> ServiceError {
+    // Masked units, polkit and dependency failures all exit 1; stderr tells them apart
+    if let Some(error) = classify_systemctl_stderr(&output.stderr) {
+        return error;
+    }
+
     match output.exit_code {
         4 => ServiceError::PermissionDenied(output.stderr.clone()),
         5 => ServiceError::ServiceNotFound(output.stderr.clone()),
@@ -180,6 +1115,35 @@ fn parse_systemctl_error(output: &CommandOutput) -> ServiceError {
     }
 }
 
+/// Maps well-known systemctl stderr messages to specific errors
+///
+/// - "Unit foo.service is masked."
+/// - "Interactive authentication required."
+/// - "A dependency job for foo.service failed. See 'journalctl -xe' for details."
+fn classify_systemctl_stderr(stderr: &str) -> Option<ServiceError> {
+    let stderr = stderr.trim();
+
+    if let Some(unit) = text_between(stderr, "Unit ", " is masked") {
+        return Some(ServiceError::Masked(unit.to_string()));
+    }
+
+    if stderr.contains("Interactive authentication required") {
+        return Some(ServiceError::AuthenticationRequired(stderr.to_string()));
+    }
+
+    if let Some(unit) = text_between(stderr, "A dependency job for ", " failed") {
+        return Some(ServiceError::DependencyFailed(unit.to_string()));
+    }
+
+    None
+}
+
+/// Returns the text between the first `start` and the following `end`
+fn text_between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let rest = &text[text.find(start)? + start.len()..];
+    Some(&rest[..rest.find(end)?])
+}
+
 /// Parses journalctl error from command output
 fn parse_journalctl_error(output: &CommandOutput) -> ServiceError {
     if output.stderr.contains("not found") || output.stderr.contains("does not exist") {
@@ -1,7 +1,12 @@
+use crate::clock::Clock;
 use crate::error::{Result, ServiceError};
-use crate::systemctl::{ServiceInfo, ServiceStatus, LogEntry};
+use crate::systemctl::{
+    priority_to_level, ActivityEvent, JournalUsage, RestartPolicy, ServiceInfo, ServiceStatus, LogEntry,
+    MAX_JOURNAL_LINE_BYTES, MAX_LOG_MESSAGE_BYTES, MESSAGE_ID_UNIT_FAILED, MESSAGE_ID_UNIT_STARTED, MESSAGE_ID_UNIT_STOPPED,
+};
 use chrono::{DateTime, Utc};
 use serde_json::Value;
+use std::collections::BTreeMap;
 
 /// Parses systemctl list-units output
 pub fn parse_service_list(output: &str) -> Result<Vec<ServiceInfo>> {
@@ -45,40 +50,57 @@ pub fn parse_service_list(output: &str) -> Result<Vec<ServiceInfo>> {
     Ok(services)
 }
 
-/// Parses systemctl show output for service status
-pub fn parse_service_status(service_name: &str, output: &str) -> Result<ServiceStatus> {
+/// Parses `KEY=VALUE` lines from systemctl show into a map
+pub fn parse_properties(output: &str) -> BTreeMap<String, String> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.to_string()))
+        .collect()
+}
+
+/// Parses systemctl show output for service status, computing uptimes
+/// against `clock`
+///
+/// When the clock has a monotonic reading, `current_uptime_seconds` is
+/// computed from `ActiveEnterTimestampMonotonic` and is immune to wall-clock
+/// changes; otherwise it falls back to the realtime `uptime_seconds`.
+pub fn parse_service_status(
+    service_name: &str,
+    output: &str,
+    clock: &dyn Clock,
+) -> Result<ServiceStatus> {
+    let mut description = String::new();
+    let mut load_state = None;
     let mut active_state = None;
     let mut sub_state = None;
     let mut main_pid = None;
     let mut active_enter_timestamp = None;
+    let mut active_enter_monotonic = None;
+    let mut tasks_current = None;
+    let mut tasks_max = None;
 
     for line in output.lines() {
         let line = line.trim();
         if let Some((key, value)) = line.split_once('=') {
             match key {
+                "Description" => description = value.to_string(),
+                "LoadState" => load_state = Some(value.to_string()),
                 "ActiveState" => active_state = Some(value.to_string()),
                 "SubState" => sub_state = Some(value.to_string()),
                 "MainPID" => {
-                    if let Ok(pid) = value.parse::<u32>() {
-                        if pid != 0 {
-                            main_pid = Some(pid);
-                        }
-                    }
+                    // 0 means no main process
+                    main_pid = parse_systemd_optional_u64(value)
+                        .filter(|pid| *pid != 0)
+                        .and_then(|pid| u32::try_from(pid).ok());
                 }
-                "ActiveEnterTimestamp" => {
-                    if !value.is_empty() {
-                        // Try to parse timestamp
-                        // Format from systemctl: "Wed 2024-01-15 10:30:45 UTC"
-                        if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
-                            active_enter_timestamp = Some(dt.with_timezone(&Utc));
-                        } else if let Ok(ts) = value.parse::<i64>() {
-                            // Handle Unix timestamp in microseconds
-                            if let Some(dt) = DateTime::from_timestamp(ts / 1_000_000, 0) {
-                                active_enter_timestamp = Some(dt);
-                            }
-                        }
-                    }
+                "ActiveEnterTimestamp" => active_enter_timestamp = parse_show_timestamp(value),
+                "ActiveEnterTimestampMonotonic" => {
+                    // 0 means the unit has not been active since boot
+                    active_enter_monotonic = parse_systemd_optional_u64(value).filter(|usec| *usec != 0);
                 }
+                "TasksCurrent" => tasks_current = parse_systemd_optional_u64(value),
+                "TasksMax" => tasks_max = parse_systemd_optional_u64(value),
                 _ => {}
             }
         }
@@ -94,24 +116,238 @@ pub fn parse_service_status(service_name: &str, output: &str) -> Result<ServiceS
 
     // Calculate uptime
     let uptime_seconds = if let Some(enter_time) = active_enter_timestamp {
-        let now = Utc::now();
+        let now = clock.now();
         let duration = now.signed_duration_since(enter_time);
         duration.num_seconds().max(0) as u64
     } else {
         0
     };
 
+    let current_uptime_seconds = match (active_enter_monotonic, clock.monotonic_usec()) {
+        (Some(enter), Some(now)) => monotonic_uptime_seconds(enter, now),
+        _ => uptime_seconds,
+    };
+
     Ok(ServiceStatus {
         name: service_name.to_string(),
+        description,
+        load_state: load_state.unwrap_or_else(|| "unknown".to_string()),
         active_state,
         sub_state,
         uptime_seconds,
+        current_uptime_seconds,
         main_pid,
         active_enter_timestamp,
+        // Read from the journal by the caller
+        first_activation_timestamp: None,
+        tasks_current,
+        tasks_max,
+    })
+}
+
+/// Parses a numeric `systemctl show` property, mapping systemd's "no value"
+/// sentinels to None
+///
+/// Empty values, `[not set]`, `[no data]`, `infinity` and u64::MAX (how
+/// unbounded limits and unaccounted counters are printed) all mean there is
+/// no number to report.
+pub fn parse_systemd_optional_u64(value: &str) -> Option<u64> {
+    match value.trim() {
+        "" | "[not set]" | "[no data]" | "infinity" => None,
+        value => value.parse::<u64>().ok().filter(|n| *n != u64::MAX),
+    }
+}
+
+/// Parses the restart settings out of `systemctl show` properties
+///
+/// systemctl reports the delay as `RestartUSec`; `RestartSec` (the unit file
+/// spelling) is accepted too. A zero delay is reported as no delay.
+pub fn parse_restart_policy(properties: &BTreeMap<String, String>) -> RestartPolicy {
+    let property = |name: &str| properties.get(name).map(|value| value.trim()).filter(|value| !value.is_empty());
+
+    let restart = property("Restart").unwrap_or("no").to_string();
+    let restart_sec = property("RestartUSec")
+        .or_else(|| property("RestartSec"))
+        .filter(|value| *value != "0")
+        .map(str::to_string);
+
+    RestartPolicy {
+        auto_restarts: restart != "no",
+        restart,
+        restart_sec,
+    }
+}
+
+/// Collapses a syslog priority into the four buckets the UI colours by:
+/// emerg-err are "error", warning is "warn", notice and info are "info",
+/// debug (and anything out of range) is "debug"
+pub fn severity_class(priority: u8) -> &'static str {
+    match priority {
+        0..=3 => "error",
+        4 => "warn",
+        5 | 6 => "info",
+        _ => "debug",
+    }
+}
+
+/// Seconds elapsed between two CLOCK_MONOTONIC readings in microseconds
+pub fn monotonic_uptime_seconds(enter_usec: u64, now_usec: u64) -> u64 {
+    now_usec.saturating_sub(enter_usec) / 1_000_000
+}
+
+/// Parses a timestamp property from systemctl show (`@<seconds>` as printed
+/// with `--timestamp=unix`, RFC 3339 or microseconds)
+/// Empty values and values in other formats yield None
+fn parse_show_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    if value.is_empty() {
+        return None;
+    }
+
+    if let Some(seconds) = value.strip_prefix('@') {
+        return DateTime::from_timestamp(seconds.parse().ok()?, 0);
+    }
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt.with_timezone(&Utc));
+    }
+
+    // Handle Unix timestamp in microseconds
+    let ts = value.parse::<i64>().ok()?;
+    DateTime::from_timestamp(ts / 1_000_000, 0)
+}
+
+/// Parses `journalctl --disk-usage` output
+/// e.g. "Archived and active journals take up 1.2G in the file system."
+pub fn parse_journal_usage(output: &str) -> Result<JournalUsage> {
+    let size = output
+        .split_whitespace()
+        .skip_while(|word| *word != "up")
+        .nth(1)
+        .ok_or_else(|| ServiceError::ParseError(format!("Unrecognized journal usage output: {}", output.trim())))?;
+
+    let disk_usage_bytes = parse_size(size)
+        .ok_or_else(|| ServiceError::ParseError(format!("Invalid journal size: {}", size)))?;
+
+    Ok(JournalUsage {
+        disk_usage_bytes,
+        disk_usage_human: size.to_string(),
     })
 }
 
+/// Parses a size as printed by systemd (`512B`, `8.0K`, `56.5M`, `1.2G`, `2T`; powers of 1024)
+fn parse_size(size: &str) -> Option<u64> {
+    let split = size.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(size.len());
+    let (number, unit) = size.split_at(split);
+    let number: f64 = number.parse().ok()?;
+
+    let multiplier: u64 = match unit {
+        "" | "B" => 1,
+        "K" => 1 << 10,
+        "M" => 1 << 20,
+        "G" => 1 << 30,
+        "T" => 1 << 40,
+        _ => return None,
+    };
+
+    Some((number * multiplier as f64).round() as u64)
+}
+
+/// Journal fields naming an entry's unit, most specific first
+const LOG_UNIT_FIELDS: [&str; 3] = ["UNIT", "USER_UNIT", "_SYSTEMD_UNIT"];
+
+/// Unit a journal entry belongs to
+///
+/// Messages systemd itself writes about a unit ("Started nginx.service") come
+/// from PID 1, whose `_SYSTEMD_UNIT` is `init.scope`; those name the unit they
+/// concern in `UNIT` (or `USER_UNIT`), which is preferred when present.
+fn log_unit(json: &Value) -> Option<String> {
+    LOG_UNIT_FIELDS
+        .iter()
+        .find_map(|field| json[*field].as_str())
+        .map(str::to_string)
+}
+
+/// Parses journalctl JSON output into unit lifecycle events
+///
+/// Only systemd's own job messages are kept, recognized by their catalog
+/// `MESSAGE_ID` rather than the (localizable) message text. A job result other
+/// than "done" marks the event as a failure.
+pub fn parse_activity(output: &str) -> Result<Vec<ActivityEvent>> {
+    let mut events = Vec::new();
+
+    for line in output.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let json: Value = serde_json::from_str(line)
+            .map_err(|e| ServiceError::ParseError(format!("Invalid JSON in journalctl output: {}", e)))?;
+
+        let event = match json["MESSAGE_ID"].as_str() {
+            Some(MESSAGE_ID_UNIT_STARTED) => "started",
+            Some(MESSAGE_ID_UNIT_STOPPED) => "stopped",
+            Some(MESSAGE_ID_UNIT_FAILED) => "failed",
+            _ => continue,
+        };
+
+        let job_result = json["JOB_RESULT"].as_str().map(str::to_string);
+        let event = match job_result.as_deref() {
+            Some(result) if result != "done" => "failed",
+            _ => event,
+        };
+
+        let (Some(unit), Some(timestamp)) = (log_unit(&json), realtime_timestamp(&json)) else {
+            continue;
+        };
+
+        events.push(ActivityEvent {
+            timestamp,
+            unit,
+            event: event.to_string(),
+            job_result,
+            message: json["MESSAGE"].as_str().unwrap_or("").to_string(),
+        });
+    }
+
+    Ok(events)
+}
+
+/// Reads `__REALTIME_TIMESTAMP` (microseconds since the epoch)
+fn realtime_timestamp(json: &Value) -> Option<DateTime<Utc>> {
+    let micros: i64 = json["__REALTIME_TIMESTAMP"].as_str()?.parse().ok()?;
+    DateTime::from_timestamp_micros(micros)
+}
+
+/// Parses `journalctl --list-boots --output=json` into (index, first entry) pairs
+///
+/// Index 0 is the current boot, -1 the one before it, and so on.
+pub fn parse_boot_starts(output: &str) -> Result<Vec<(i64, DateTime<Utc>)>> {
+    if output.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let json: Value = serde_json::from_str(output)
+        .map_err(|e| ServiceError::ParseError(format!("Invalid JSON in journalctl boot list: {}", e)))?;
+
+    let boots = json
+        .as_array()
+        .ok_or_else(|| ServiceError::ParseError("journalctl boot list is not an array".to_string()))?;
+
+    Ok(boots
+        .iter()
+        .filter_map(|boot| {
+            let index = boot["index"].as_i64()?;
+            let first_entry = DateTime::from_timestamp_micros(boot["first_entry"].as_i64()?)?;
+            Some((index, first_entry))
+        })
+        .collect())
+}
+
 /// Parses journalctl JSON output
+///
+/// Lines over `MAX_JOURNAL_LINE_BYTES` are not parsed; they yield an entry
+/// with only timestamp and priority, flagged `truncated`.
 pub fn parse_logs(output: &str) -> Result<Vec<LogEntry>> {
     let mut logs = Vec::new();
 
@@ -121,14 +357,16 @@ pub fn parse_logs(output: &str) -> Result<Vec<LogEntry>> {
             continue;
         }
 
+        if line.len() > MAX_JOURNAL_LINE_BYTES {
+            logs.push(oversized_log_entry(line));
+            continue;
+        }
+
         let json: Value = serde_json::from_str(line)
             .map_err(|e| ServiceError::ParseError(format!("Invalid JSON in journalctl output: {}", e)))?;
 
         // Extract fields from journalctl JSON
-        let message = json["MESSAGE"]
-            .as_str()
-            .unwrap_or("")
-            .to_string();
+        let (message, truncated) = log_message(&json["MESSAGE"]);
 
         let priority = json["PRIORITY"]
             .as_str()
@@ -136,30 +374,173 @@ pub fn parse_logs(output: &str) -> Result<Vec<LogEntry>> {
             .unwrap_or(6); // Default to INFO priority
 
         // Parse timestamp - journalctl provides __REALTIME_TIMESTAMP in microseconds
-        let timestamp = if let Some(ts_str) = json["__REALTIME_TIMESTAMP"].as_str() {
-            if let Ok(micros) = ts_str.parse::<i64>() {
-                DateTime::from_timestamp(micros / 1_000_000, ((micros % 1_000_000) * 1000) as u32)
-                    .unwrap_or_else(|| Utc::now())
-            } else {
-                Utc::now()
-            }
-        } else {
-            Utc::now()
-        };
+        let timestamp = json["__REALTIME_TIMESTAMP"]
+            .as_str()
+            .and_then(parse_realtime_micros)
+            .unwrap_or_else(Utc::now);
 
         logs.push(LogEntry {
             timestamp,
             message,
             priority,
+            level: priority_to_level(priority).to_string(),
+            severity_class: severity_class(priority).to_string(),
+            unit: log_unit(&json),
+            truncated,
+            cursor: json["__CURSOR"].as_str().map(str::to_string),
         });
     }
 
     Ok(logs)
 }
 
+/// Reads a journal `MESSAGE` field, returning the text and whether it is incomplete
+///
+/// journalctl prints fields over 4096 bytes as `null` (unless `--all`) and
+/// non-UTF-8 ones as an array of byte values; both are handled. Text longer
+/// than `MAX_LOG_MESSAGE_BYTES` is cut at a character boundary.
+fn log_message(field: &Value) -> (String, bool) {
+    let mut message = match field {
+        Value::String(text) => text.clone(),
+        Value::Array(bytes) => {
+            let bytes: Vec<u8> = bytes.iter().filter_map(|b| b.as_u64()).map(|b| b as u8).collect();
+            String::from_utf8_lossy(&bytes).into_owned()
+        }
+        Value::Null => return (String::new(), true),
+        _ => String::new(),
+    };
+
+    if message.len() <= MAX_LOG_MESSAGE_BYTES {
+        return (message, false);
+    }
+
+    let mut end = MAX_LOG_MESSAGE_BYTES;
+    while !message.is_char_boundary(end) {
+        end -= 1;
+    }
+    message.truncate(end);
+    (message, true)
+}
+
+/// Builds an entry for a line too long to parse, scanning it for timestamp,
+/// priority and cursor so the entry still sorts, filters and can be linked to
+fn oversized_log_entry(line: &str) -> LogEntry {
+    let priority = scan_string_field(line, "PRIORITY")
+        .and_then(|s| s.parse::<u8>().ok())
+        .unwrap_or(6);
+
+    LogEntry {
+        timestamp: scan_string_field(line, "__REALTIME_TIMESTAMP")
+            .and_then(parse_realtime_micros)
+            .unwrap_or_else(Utc::now),
+        message: String::new(),
+        priority,
+        level: priority_to_level(priority).to_string(),
+        severity_class: severity_class(priority).to_string(),
+        unit: LOG_UNIT_FIELDS
+            .iter()
+            .find_map(|field| scan_string_field(line, field))
+            .map(str::to_string),
+        truncated: true,
+        cursor: scan_string_field(line, "__CURSOR").map(str::to_string),
+    }
+}
+
+/// Finds `"FIELD":"value"` in raw JSON text; only for fields whose values never contain escapes
+fn scan_string_field<'a>(line: &'a str, field: &str) -> Option<&'a str> {
+    let key = format!("\"{}\":\"", field);
+    let start = line.find(&key)? + key.len();
+    let len = line[start..].find('"')?;
+    Some(&line[start..start + len])
+}
+
+fn parse_realtime_micros(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp_micros(value.parse().ok()?)
+}
+
+/// Parses `systemctl list-dependencies --plain` output into the listed units
+///
+/// The first line is the queried unit itself and is skipped. Tree glyphs are
+/// stripped in case a systemd version draws them despite `--plain`.
+pub fn parse_dependencies(output: &str) -> Vec<String> {
+    let mut dependencies: Vec<String> = Vec::new();
+
+    for line in output.lines().skip(1) {
+        let unit = line.trim_start_matches(|c: char| c.is_whitespace() || "●○│├└─".contains(c));
+        let Some(unit) = unit.split_whitespace().next() else {
+            continue;
+        };
+
+        if !dependencies.iter().any(|d| d == unit) {
+            dependencies.push(unit.to_string());
+        }
+    }
+
+    dependencies
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::{FixedClock, SystemClock};
+
+    #[test]
+    fn test_parse_service_status_tasks() {
+        let output = "ActiveState=active\nSubState=running\nTasksCurrent=7\nTasksMax=4915\n";
+        let status = parse_service_status("nginx.service", output, &SystemClock).unwrap();
+        assert_eq!(status.tasks_current, Some(7));
+        assert_eq!(status.tasks_max, Some(4915));
+
+        let output = "ActiveState=inactive\nSubState=dead\nTasksCurrent=[not set]\nTasksMax=infinity\n";
+        let status = parse_service_status("nginx.service", output, &SystemClock).unwrap();
+        assert_eq!(status.tasks_current, None);
+        assert_eq!(status.tasks_max, None);
+    }
+
+    #[test]
+    fn test_parse_service_status_load_state() {
+        let output = "LoadState=not-found\nActiveState=inactive\nSubState=dead\n";
+        let status = parse_service_status("gone.service", output, &SystemClock).unwrap();
+        assert_eq!(status.load_state, "not-found");
+
+        let output = "ActiveState=inactive\nSubState=dead\n";
+        let status = parse_service_status("nginx.service", output, &SystemClock).unwrap();
+        assert_eq!(status.load_state, "unknown");
+    }
+
+    #[test]
+    fn test_parse_systemd_optional_u64() {
+        assert_eq!(parse_systemd_optional_u64("12582912"), Some(12582912));
+        assert_eq!(parse_systemd_optional_u64(" 0 "), Some(0));
+        assert_eq!(parse_systemd_optional_u64(""), None);
+        assert_eq!(parse_systemd_optional_u64("[not set]"), None);
+        assert_eq!(parse_systemd_optional_u64("[no data]"), None);
+        assert_eq!(parse_systemd_optional_u64("infinity"), None);
+        assert_eq!(parse_systemd_optional_u64("18446744073709551615"), None);
+        assert_eq!(parse_systemd_optional_u64("12M"), None);
+    }
+
+    #[test]
+    fn test_parse_service_status_main_pid_sentinels() {
+        for (value, expected) in [("1234", Some(1234)), ("0", None), ("", None), ("[not set]", None)] {
+            let output = format!("ActiveState=active\nSubState=running\nMainPID={}\n", value);
+            let status = parse_service_status("nginx.service", &output, &SystemClock).unwrap();
+            assert_eq!(status.main_pid, expected, "MainPID={}", value);
+        }
+    }
+
+    #[test]
+    fn test_parse_dependencies() {
+        let output = "app.service\n  db.service\n  system.slice\n  sysinit.target\n    local-fs.target\n  db.service\n";
+        assert_eq!(
+            parse_dependencies(output),
+            vec!["db.service", "system.slice", "sysinit.target", "local-fs.target"]
+        );
+
+        let tree = "app.service\n● ├─cache.service\n○ └─db.service\n";
+        assert_eq!(parse_dependencies(tree), vec!["cache.service", "db.service"]);
+        assert!(parse_dependencies("").is_empty());
+    }
 
     #[test]
     fn test_parse_service_list() {
@@ -192,9 +573,9 @@ inactive.service               loaded inactive dead    Inactive Service"#;
         let output = r#"ActiveState=active
 SubState=running
 MainPID=1234
-ActiveEnterTimestamp=1705315845000000"#;
+ActiveEnterTimestamp=@1705315845"#;
 
-        let status = parse_service_status("nginx", output).unwrap();
+        let status = parse_service_status("nginx", output, &SystemClock).unwrap();
         assert_eq!(status.name, "nginx");
         assert_eq!(status.active_state, "active");
         assert_eq!(status.sub_state, "running");
@@ -202,6 +583,77 @@ ActiveEnterTimestamp=1705315845000000"#;
         assert!(status.uptime_seconds > 0);
     }
 
+    #[test]
+    fn test_parse_service_status_description() {
+        let output = r#"Description=NGINX HTTP and reverse proxy server (a=b)
+ActiveState=active
+SubState=running
+MainPID=1234
+ActiveEnterTimestamp="#;
+
+        let status = parse_service_status("nginx", output, &SystemClock).unwrap();
+        assert_eq!(status.description, "NGINX HTTP and reverse proxy server (a=b)");
+
+        let status = parse_service_status("nginx", "ActiveState=active\nSubState=running\n", &SystemClock).unwrap();
+        assert_eq!(status.description, "");
+    }
+
+    #[test]
+    fn test_parse_service_status_monotonic_uptime() {
+        // `systemctl show nginx.service --timestamp=unix --property=...`;
+        // the realtime stamp is two hours old but the monotonic clock says 90s ago
+        let output = r#"MainPID=1234
+ActiveState=active
+SubState=running
+Description=A high performance web server and a reverse proxy server
+LoadState=loaded
+ActiveEnterTimestamp=@1705315845
+ActiveEnterTimestampMonotonic=5000000000
+TasksCurrent=3
+TasksMax=4915
+"#;
+
+        let clock = FixedClock::new(DateTime::from_timestamp(1705315845 + 7200, 0).unwrap())
+            .with_monotonic_usec(5_090_000_000);
+        let status = parse_service_status("nginx.service", output, &clock).unwrap();
+        assert_eq!(status.active_enter_timestamp, DateTime::from_timestamp(1705315845, 0));
+        assert_eq!(status.current_uptime_seconds, 90);
+        assert_eq!(status.uptime_seconds, 7200);
+        // Filled in from the journal, not from show
+        assert_eq!(status.first_activation_timestamp, None);
+    }
+
+    #[test]
+    fn test_parse_service_status_monotonic_fallback() {
+        let output = r#"MainPID=0
+ActiveState=inactive
+SubState=dead
+ActiveEnterTimestamp=
+ActiveEnterTimestampMonotonic=0"#;
+
+        // Never active this boot: no monotonic stamp, fall back to realtime (0 here)
+        let clock = FixedClock::new(Utc::now()).with_monotonic_usec(5_090_000_000);
+        let status = parse_service_status("nginx", output, &clock).unwrap();
+        assert_eq!(status.current_uptime_seconds, 0);
+        assert_eq!(status.active_enter_timestamp, None);
+    }
+
+    #[test]
+    fn test_parse_show_timestamp() {
+        assert_eq!(parse_show_timestamp("@1705315845"), DateTime::from_timestamp(1705315845, 0));
+        assert_eq!(parse_show_timestamp("1705315845000000"), DateTime::from_timestamp(1705315845, 0));
+        assert_eq!(parse_show_timestamp(""), None);
+        // The default locale format is why `--timestamp=unix` is passed
+        assert_eq!(parse_show_timestamp("Mon 2024-01-15 10:50:45 UTC"), None);
+    }
+
+    #[test]
+    fn test_monotonic_uptime_seconds() {
+        assert_eq!(monotonic_uptime_seconds(1_000_000, 61_500_000), 60);
+        // A stale "now" never underflows
+        assert_eq!(monotonic_uptime_seconds(61_500_000, 1_000_000), 0);
+    }
+
     #[test]
     fn test_parse_service_status_stopped() {
         let output = r#"ActiveState=inactive
@@ -209,7 +661,7 @@ SubState=dead
 MainPID=0
 ActiveEnterTimestamp="#;
 
-        let status = parse_service_status("stopped-service", output).unwrap();
+        let status = parse_service_status("stopped-service", output, &SystemClock).unwrap();
         assert_eq!(status.active_state, "inactive");
         assert_eq!(status.sub_state, "dead");
         assert_eq!(status.main_pid, None);
@@ -221,9 +673,9 @@ ActiveEnterTimestamp="#;
         let output = r#"ActiveState=failed
 SubState=failed
 MainPID=0
-ActiveEnterTimestamp=1705315845000000"#;
+ActiveEnterTimestamp=@1705315845"#;
 
-        let status = parse_service_status("failed-service", output).unwrap();
+        let status = parse_service_status("failed-service", output, &SystemClock).unwrap();
         assert_eq!(status.active_state, "failed");
         assert_eq!(status.sub_state, "failed");
     }
@@ -232,7 +684,7 @@ ActiveEnterTimestamp=1705315845000000"#;
     fn test_parse_service_status_missing_fields() {
         let output = "ActiveState=active";
 
-        let result = parse_service_status("test", output);
+        let result = parse_service_status("test", output, &SystemClock);
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), ServiceError::ParseError(_)));
     }
@@ -254,6 +706,161 @@ ActiveEnterTimestamp=1705315845000000"#;
 
         assert_eq!(logs[2].message, "Debug info");
         assert_eq!(logs[2].priority, 7);
+        assert_eq!(logs[2].level, "debug");
+        assert_eq!(logs[2].severity_class, "debug");
+    }
+
+    #[test]
+    fn test_severity_class_buckets() {
+        let classes: Vec<&str> = (0..=7).map(severity_class).collect();
+        assert_eq!(classes, vec!["error", "error", "error", "error", "warn", "info", "info", "debug"]);
+        assert_eq!(severity_class(42), "debug");
+    }
+
+    #[test]
+    fn test_parse_restart_policy() {
+        let properties = parse_properties("Restart=on-failure\nRestartSec=5s\n");
+        assert_eq!(parse_restart_policy(&properties), RestartPolicy {
+            restart: "on-failure".to_string(),
+            restart_sec: Some("5s".to_string()),
+            auto_restarts: true,
+        });
+
+        // What systemctl show actually prints for the delay
+        let properties = parse_properties("Restart=always\nRestartUSec=100ms\n");
+        assert_eq!(parse_restart_policy(&properties).restart_sec.as_deref(), Some("100ms"));
+
+        let properties = parse_properties("Restart=no\nRestartUSec=0\n");
+        let policy = parse_restart_policy(&properties);
+        assert!(!policy.auto_restarts);
+        assert_eq!(policy.restart_sec, None);
+
+        let policy = parse_restart_policy(&BTreeMap::new());
+        assert_eq!(policy.restart, "no");
+        assert!(!policy.auto_restarts);
+    }
+
+    #[test]
+    fn test_parse_journal_usage() {
+        let usage = parse_journal_usage("Archived and active journals take up 1.2G in the file system.\n").unwrap();
+        assert_eq!(usage.disk_usage_human, "1.2G");
+        assert_eq!(usage.disk_usage_bytes, 1_288_490_189);
+
+        let usage = parse_journal_usage("Archived and active journals take up 56.0M in the file system.").unwrap();
+        assert_eq!(usage.disk_usage_bytes, 56 * 1024 * 1024);
+
+        let usage = parse_journal_usage("Archived and active journals take up 8.0K in the file system.").unwrap();
+        assert_eq!(usage.disk_usage_bytes, 8192);
+
+        // Older systemd wording
+        let usage = parse_journal_usage("Journals take up 512B on disk.").unwrap();
+        assert_eq!(usage.disk_usage_bytes, 512);
+    }
+
+    #[test]
+    fn test_parse_journal_usage_invalid() {
+        assert!(parse_journal_usage("").is_err());
+        assert!(parse_journal_usage("Archived and active journals take up lots in the file system.").is_err());
+    }
+
+    #[test]
+    fn test_parse_boot_starts() {
+        let output = r#"[{"index":-1,"boot_id":"3f1c0a","first_entry":1705315845000000,"last_entry":1705402245000000},{"index":0,"boot_id":"9b2e7d","first_entry":1705402300000000,"last_entry":1705405900000000}]"#;
+
+        let boots = parse_boot_starts(output).unwrap();
+        assert_eq!(boots.len(), 2);
+        assert_eq!(boots[0].0, -1);
+        assert_eq!(boots[0].1.timestamp(), 1705315845);
+        assert_eq!(boots[1].0, 0);
+
+        assert!(parse_boot_starts("").unwrap().is_empty());
+        assert!(parse_boot_starts("IDX BOOT ID").is_err());
+    }
+
+    #[test]
+    fn test_parse_logs_unit() {
+        let output = r#"{"MESSAGE":"Worker ready","__REALTIME_TIMESTAMP":"1705315845000000","_SYSTEMD_UNIT":"chfscraper@rest.service"}
+{"MESSAGE":"Started chfscraper@bcp.service.","__REALTIME_TIMESTAMP":"1705315846000000","_SYSTEMD_UNIT":"init.scope","UNIT":"chfscraper@bcp.service"}
+{"MESSAGE":"kernel line","__REALTIME_TIMESTAMP":"1705315847000000"}"#;
+
+        let logs = parse_logs(output).unwrap();
+        assert_eq!(logs[0].unit.as_deref(), Some("chfscraper@rest.service"));
+        // Manager messages are attributed to the unit they are about
+        assert_eq!(logs[1].unit.as_deref(), Some("chfscraper@bcp.service"));
+        assert_eq!(logs[2].unit, None);
+    }
+
+    #[test]
+    fn test_parse_logs_cursor() {
+        let output = r#"{"__CURSOR":"s=7f3a;i=1c2;b=9e1d;m=4a2b;t=60f1;x=88c0","MESSAGE":"Started","__REALTIME_TIMESTAMP":"1705315845000000"}
+{"MESSAGE":"no cursor","__REALTIME_TIMESTAMP":"1705315846000000"}"#;
+
+        let logs = parse_logs(output).unwrap();
+        assert_eq!(logs[0].cursor.as_deref(), Some("s=7f3a;i=1c2;b=9e1d;m=4a2b;t=60f1;x=88c0"));
+        assert_eq!(logs[1].cursor, None);
+
+        // An unparseable line still yields its cursor
+        let output = format!(
+            r#"{{"__CURSOR":"s=7f3a;i=1c3","__REALTIME_TIMESTAMP":"1705315847000000","MESSAGE":"{}"}}"#,
+            "x".repeat(MAX_JOURNAL_LINE_BYTES)
+        );
+        let logs = parse_logs(&output).unwrap();
+        assert!(logs[0].truncated);
+        assert_eq!(logs[0].cursor.as_deref(), Some("s=7f3a;i=1c3"));
+    }
+
+    #[test]
+    fn test_parse_logs_truncation_markers() {
+        // journalctl prints fields over 4096 bytes as null and binary ones as byte arrays
+        let output = r#"{"MESSAGE":null,"PRIORITY":"4","__REALTIME_TIMESTAMP":"1705315845000000"}
+{"MESSAGE":[104,105,255],"PRIORITY":"6","__REALTIME_TIMESTAMP":"1705315846000000"}
+{"MESSAGE":"normal","PRIORITY":"6","__REALTIME_TIMESTAMP":"1705315847000000"}"#;
+
+        let logs = parse_logs(output).unwrap();
+        assert_eq!(logs.len(), 3);
+        assert!(logs[0].truncated);
+        assert_eq!(logs[0].message, "");
+        assert_eq!(logs[0].priority, 4);
+        assert_eq!(logs[1].message, "hi\u{FFFD}");
+        assert!(!logs[1].truncated);
+        assert!(!logs[2].truncated);
+    }
+
+    #[test]
+    fn test_parse_logs_long_lines() {
+        // Long enough to be cut, short enough to parse
+        let long = "é".repeat(MAX_LOG_MESSAGE_BYTES);
+        let output = format!(r#"{{"MESSAGE":"{}","PRIORITY":"6","__REALTIME_TIMESTAMP":"1705315845000000"}}"#, long);
+
+        let logs = parse_logs(&output).unwrap();
+        assert!(logs[0].truncated);
+        assert!(logs[0].message.len() <= MAX_LOG_MESSAGE_BYTES);
+        assert!(logs[0].message.chars().all(|c| c == 'é'));
+
+        // A pathological 10MB line is not parsed, but keeps its timestamp and priority
+        let huge = "x".repeat(10 * 1024 * 1024);
+        let output = format!(
+            r#"{{"__REALTIME_TIMESTAMP":"1705315845000000","PRIORITY":"3","MESSAGE":"{}"}}
+{{"MESSAGE":"next","__REALTIME_TIMESTAMP":"1705315846000000"}}"#,
+            huge
+        );
+
+        let logs = parse_logs(&output).unwrap();
+        assert_eq!(logs.len(), 2);
+        assert!(logs[0].truncated);
+        assert!(logs[0].message.is_empty());
+        assert_eq!(logs[0].priority, 3);
+        assert_eq!(logs[0].timestamp.timestamp(), 1705315845);
+        assert_eq!(logs[1].message, "next");
+
+        // An unparseable message from PID 1 keeps the unit it concerns
+        let output = format!(
+            r#"{{"_SYSTEMD_UNIT":"init.scope","UNIT":"nginx.service","MESSAGE":"{}"}}"#,
+            "x".repeat(MAX_JOURNAL_LINE_BYTES)
+        );
+        let logs = parse_logs(&output).unwrap();
+        assert!(logs[0].truncated);
+        assert_eq!(logs[0].unit.as_deref(), Some("nginx.service"));
     }
 
     #[test]
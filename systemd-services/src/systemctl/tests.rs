@@ -1,4 +1,5 @@
 use super::*;
+use crate::clock::{FixedClock, SystemClock};
 use crate::systemctl::executor::MockCommandExecutor;
 use std::sync::Arc;
 
@@ -74,17 +75,17 @@ async fn test_get_service_status_running() {
     let output = r#"ActiveState=active
 SubState=running
 MainPID=1234
-ActiveEnterTimestamp=1705315845000000"#;
+ActiveEnterTimestamp=@1705315845"#;
 
     let executor = Arc::new(
         MockCommandExecutor::new().with_stdout(
             "systemctl",
-            &["show", "nginx", "--property=ActiveState,SubState,MainPID,ActiveEnterTimestamp"],
+            &["show", "nginx", "--timestamp=unix", "--property=Description,LoadState,ActiveState,SubState,MainPID,ActiveEnterTimestamp,ActiveEnterTimestampMonotonic,TasksCurrent,TasksMax"],
             output,
         )
     );
 
-    let status = get_service_status(executor, "nginx").await.unwrap();
+    let status = get_service_status(executor, "nginx", &SystemClock).await.unwrap();
     assert_eq!(status.name, "nginx");
     assert_eq!(status.active_state, "active");
     assert_eq!(status.sub_state, "running");
@@ -102,12 +103,12 @@ ActiveEnterTimestamp="#;
     let executor = Arc::new(
         MockCommandExecutor::new().with_stdout(
             "systemctl",
-            &["show", "stopped-service", "--property=ActiveState,SubState,MainPID,ActiveEnterTimestamp"],
+            &["show", "stopped-service", "--timestamp=unix", "--property=Description,LoadState,ActiveState,SubState,MainPID,ActiveEnterTimestamp,ActiveEnterTimestampMonotonic,TasksCurrent,TasksMax"],
             output,
         )
     );
 
-    let status = get_service_status(executor, "stopped-service").await.unwrap();
+    let status = get_service_status(executor, "stopped-service", &SystemClock).await.unwrap();
     assert_eq!(status.active_state, "inactive");
     assert_eq!(status.sub_state, "dead");
     assert_eq!(status.main_pid, None);
@@ -119,21 +120,51 @@ async fn test_get_service_status_failed() {
     let output = r#"ActiveState=failed
 SubState=failed
 MainPID=0
-ActiveEnterTimestamp=1705315845000000"#;
+ActiveEnterTimestamp=@1705315845"#;
 
     let executor = Arc::new(
         MockCommandExecutor::new().with_stdout(
             "systemctl",
-            &["show", "failed-service", "--property=ActiveState,SubState,MainPID,ActiveEnterTimestamp"],
+            &["show", "failed-service", "--timestamp=unix", "--property=Description,LoadState,ActiveState,SubState,MainPID,ActiveEnterTimestamp,ActiveEnterTimestampMonotonic,TasksCurrent,TasksMax"],
             output,
         )
     );
 
-    let status = get_service_status(executor, "failed-service").await.unwrap();
+    let status = get_service_status(executor, "failed-service", &SystemClock).await.unwrap();
     assert_eq!(status.active_state, "failed");
     assert_eq!(status.sub_state, "failed");
 }
 
+#[tokio::test]
+async fn test_get_service_status_uptime_with_fixed_clock() {
+    let output = r#"ActiveState=active
+SubState=running
+MainPID=1234
+ActiveEnterTimestamp=@1705315845
+ActiveEnterTimestampMonotonic=12000000"#;
+
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_stdout(
+            "systemctl",
+            &["show", "nginx", "--timestamp=unix", "--property=Description,LoadState,ActiveState,SubState,MainPID,ActiveEnterTimestamp,ActiveEnterTimestampMonotonic,TasksCurrent,TasksMax"],
+            output,
+        )
+    );
+
+    // 1h 1m 1s after the unit became active, on both clocks
+    let clock = FixedClock::new(DateTime::from_timestamp(1705315845 + 3661, 0).unwrap())
+        .with_monotonic_usec(12_000_000 + 3_661_000_000);
+    let status = get_service_status(executor.clone(), "nginx", &clock).await.unwrap();
+    assert_eq!(status.uptime_seconds, 3661);
+    assert_eq!(status.current_uptime_seconds, 3661);
+
+    // Without a monotonic reading the realtime uptime is used
+    let clock = FixedClock::new(DateTime::from_timestamp(1705315845 + 60, 0).unwrap());
+    let status = get_service_status(executor, "nginx", &clock).await.unwrap();
+    assert_eq!(status.uptime_seconds, 60);
+    assert_eq!(status.current_uptime_seconds, 60);
+}
+
 #[tokio::test]
 async fn test_start_service_success() {
     let executor = Arc::new(
@@ -161,6 +192,39 @@ async fn test_start_service_not_found() {
     assert!(matches!(err, ServiceError::ServiceNotFound(_)));
 }
 
+async fn start_error(stderr: &str) -> ServiceError {
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_error("systemctl", &["start", "backup.service"], 1, stderr)
+    );
+
+    start_service(executor, "backup.service").await.unwrap_err()
+}
+
+#[tokio::test]
+async fn test_start_service_masked() {
+    let err = start_error("Failed to start backup.service: Unit backup.service is masked.\n").await;
+    assert!(matches!(&err, ServiceError::Masked(unit) if unit == "backup.service"));
+    assert!(err.to_string().contains("unmask"));
+}
+
+#[tokio::test]
+async fn test_start_service_authentication_required() {
+    let err = start_error("Failed to start backup.service: Interactive authentication required.\nSee system logs and 'systemctl status backup.service' for details.\n").await;
+    assert!(matches!(err, ServiceError::AuthenticationRequired(_)));
+}
+
+#[tokio::test]
+async fn test_start_service_dependency_failed() {
+    let err = start_error("A dependency job for backup.service failed. See 'journalctl -xe' for details.\n").await;
+    assert!(matches!(&err, ServiceError::DependencyFailed(unit) if unit == "backup.service"));
+}
+
+#[tokio::test]
+async fn test_start_service_generic_job_failure() {
+    let err = start_error("Job for backup.service failed because the control process exited with error code.\n").await;
+    assert!(matches!(err, ServiceError::CommandFailed { exit_code: 1, .. }));
+}
+
 #[tokio::test]
 async fn test_start_service_permission_denied() {
     let executor = Arc::new(
@@ -188,6 +252,37 @@ async fn test_stop_service_success() {
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+async fn test_force_stop_service_without_escalation() {
+    // No kill mocked: escalating would fail the test
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_stdout("systemctl", &["stop", "nginx"], "")
+    );
+
+    assert!(!force_stop_service(executor, "nginx").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_force_stop_service_escalates_on_timeout() {
+    let executor = Arc::new(
+        MockCommandExecutor::new()
+            .with_timeout("systemctl", &["stop", "stuck"])
+            .with_stdout("systemctl", &["kill", "--signal=SIGKILL", "stuck"], "")
+    );
+
+    assert!(force_stop_service(executor, "stuck").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_force_stop_service_reports_other_failures() {
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_error("systemctl", &["stop", "protected"], 4, "Access denied")
+    );
+
+    let result = force_stop_service(executor, "protected").await;
+    assert!(matches!(result, Err(ServiceError::PermissionDenied(_))));
+}
+
 #[tokio::test]
 async fn test_restart_service_success() {
     let executor = Arc::new(
@@ -268,6 +363,45 @@ async fn test_get_logs_custom_line_count() {
     assert_eq!(logs.len(), 2);
 }
 
+#[tokio::test]
+async fn test_boot_range_falls_back_to_oldest_boot() {
+    // Only two boots recorded: asking for five starts at the oldest one
+    let boots = r#"[{"index":-1,"boot_id":"3f1c0a","first_entry":1705315845000000,"last_entry":1705402245000000},{"index":0,"boot_id":"9b2e7d","first_entry":1705402300000000,"last_entry":1705405900000000}]"#;
+
+    let executor = Arc::new(
+        MockCommandExecutor::new()
+            .with_stdout("journalctl", &["--list-boots", "--no-pager", "--output=json"], boots)
+    );
+
+    let since = boot_range_since(executor, 5).await.unwrap();
+    assert_eq!(since.as_deref(), Some("@1705315845"));
+}
+
+#[tokio::test]
+async fn test_get_logs_namespace() {
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_stdout(
+            "journalctl",
+            &["-u", "ingest.service", "-n", "50", "--namespace=pipeline", "--no-pager", "--output=json"],
+            r#"{"MESSAGE":"Batch done","PRIORITY":"6","__REALTIME_TIMESTAMP":"1705315845000000"}"#,
+        )
+    );
+
+    let logs = get_logs_since(executor, "ingest.service", 50, None, None, Some("pipeline")).await.unwrap();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].message, "Batch done");
+}
+
+#[tokio::test]
+async fn test_get_logs_invalid_namespace() {
+    for namespace in ["", ".hidden", "a/b", "foo --all", "ns;rm", &"n".repeat(65)] {
+        let executor = Arc::new(MockCommandExecutor::new());
+
+        let result = get_logs_since(executor, "ingest.service", 50, None, None, Some(namespace)).await;
+        assert!(matches!(result, Err(ServiceError::InvalidNamespace(_))), "namespace {:?}", namespace);
+    }
+}
+
 #[tokio::test]
 async fn test_service_name_injection_prevention() {
     let executor = Arc::new(MockCommandExecutor::new());
@@ -290,3 +424,314 @@ async fn test_service_name_injection_prevention() {
         assert!(matches!(err, ServiceError::InvalidServiceName(_)));
     }
 }
+
+#[tokio::test]
+async fn test_get_service_properties() {
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_stdout(
+            "systemctl",
+            &["show", "nginx.service", "--property=MemoryMax,TasksCurrent"],
+            "MemoryMax=infinity\nTasksCurrent=5\n",
+        )
+    );
+
+    let names = vec!["MemoryMax".to_string(), "TasksCurrent".to_string()];
+    let props = get_service_properties(executor, "nginx.service", &names).await.unwrap();
+    assert_eq!(props.len(), 2);
+    assert_eq!(props["MemoryMax"], "infinity");
+    assert_eq!(props["TasksCurrent"], "5");
+}
+
+#[tokio::test]
+async fn test_get_service_properties_chunks_long_lists() {
+    let names: Vec<String> = ALLOWED_PROPERTIES.iter().map(|name| name.to_string()).collect();
+    assert!(names.len() > PROPERTY_CHUNK_SIZE * 2);
+
+    // Only the chunked invocations have responses, so a single call would fail
+    let mut mock = MockCommandExecutor::new();
+    for chunk in names.chunks(PROPERTY_CHUNK_SIZE) {
+        let property_arg = format!("--property={}", chunk.join(","));
+        let stdout: String = chunk.iter().map(|name| format!("{}=value-of-{}\n", name, name)).collect();
+        mock = mock.with_stdout("systemctl", &["show", "nginx.service", &property_arg], &stdout);
+    }
+    let stats = Arc::new(crate::stats::PluginStats::new());
+    let executor = Arc::new(crate::stats::CountingExecutor::new(mock, stats.clone()));
+
+    let props = get_service_properties(executor, "nginx.service", &names).await.unwrap();
+
+    assert_eq!(stats.snapshot(0, 0).commands_total as usize, names.len().div_ceil(PROPERTY_CHUNK_SIZE));
+    assert_eq!(props.len(), names.len());
+    assert_eq!(props["Id"], "value-of-Id");
+    assert_eq!(props["ControlGroup"], "value-of-ControlGroup");
+}
+
+#[tokio::test]
+async fn test_get_service_properties_rejects_unlisted_names() {
+    let executor = Arc::new(MockCommandExecutor::new());
+
+    let attempts = vec![
+        "Environment",
+        "MemoryMax --all",
+        "--all",
+        "MemoryMax;whoami",
+        "",
+    ];
+
+    for attempt in attempts {
+        let names = vec![attempt.to_string()];
+        let result = get_service_properties(executor.clone(), "nginx.service", &names).await;
+        assert!(matches!(result, Err(ServiceError::InvalidProperty(_))), "Should reject: {}", attempt);
+    }
+
+    let result = get_service_properties(executor, "nginx.service", &[]).await;
+    assert!(matches!(result, Err(ServiceError::InvalidProperty(_))));
+}
+
+#[tokio::test]
+async fn test_daemon_reload() {
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_stdout("systemctl", &["daemon-reload"], "")
+    );
+    assert!(daemon_reload(executor).await.is_ok());
+
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_error("systemctl", &["daemon-reload"], 4, "Access denied")
+    );
+    let result = daemon_reload(executor).await;
+    assert!(matches!(result, Err(ServiceError::PermissionDenied(_))));
+}
+
+#[test]
+fn test_priority_to_level() {
+    let expected = ["emerg", "alert", "crit", "err", "warning", "notice", "info", "debug"];
+    for (priority, level) in expected.iter().enumerate() {
+        assert_eq!(priority_to_level(priority as u8), *level);
+    }
+
+    // Out of range clamps to the least severe level
+    assert_eq!(priority_to_level(8), "debug");
+    assert_eq!(priority_to_level(255), "debug");
+}
+
+#[tokio::test]
+async fn test_get_logs_for_units_interleaved() {
+    let output = r#"{"MESSAGE":"GET /index.php","PRIORITY":"6","__REALTIME_TIMESTAMP":"1705315845000000","_SYSTEMD_UNIT":"nginx.service"}
+{"MESSAGE":"child 42 started","PRIORITY":"6","__REALTIME_TIMESTAMP":"1705315845100000","_SYSTEMD_UNIT":"php-fpm.service"}
+{"MESSAGE":"upstream timed out","PRIORITY":"3","__REALTIME_TIMESTAMP":"1705315846000000","_SYSTEMD_UNIT":"nginx.service"}"#;
+
+    let executor = Arc::new(
+        MockCommandExecutor::new().with_stdout(
+            "journalctl",
+            &["-u", "nginx.service", "-u", "php-fpm.service", "-n", "200", "--no-pager", "--output=json"],
+            output,
+        )
+    );
+
+    let units = vec!["nginx.service".to_string(), "php-fpm.service".to_string()];
+    let logs = get_logs_for_units(executor, &units, 200).await.unwrap();
+
+    let tagged: Vec<(&str, &str)> = logs
+        .iter()
+        .map(|l| (l.unit.as_deref().unwrap(), l.message.as_str()))
+        .collect();
+    assert_eq!(tagged, vec![
+        ("nginx.service", "GET /index.php"),
+        ("php-fpm.service", "child 42 started"),
+        ("nginx.service", "upstream timed out"),
+    ]);
+}
+
+#[tokio::test]
+async fn test_get_logs_for_units_validates_each_name() {
+    let executor = Arc::new(MockCommandExecutor::new());
+
+    let units = vec!["nginx.service".to_string(), "php-fpm; rm -rf /".to_string()];
+    let result = get_logs_for_units(executor.clone(), &units, 100).await;
+    assert!(matches!(result, Err(ServiceError::InvalidServiceName(_))));
+
+    let result = get_logs_for_units(executor, &[], 100).await;
+    assert!(matches!(result, Err(ServiceError::InvalidServiceName(_))));
+}
+
+#[tokio::test]
+async fn test_system_executor_sets_deterministic_env() {
+    let output = SystemCommandExecutor::new().execute("env", &[]).await.unwrap();
+    assert_eq!(output.exit_code, 0);
+
+    let vars: Vec<&str> = output.stdout.lines().collect();
+    assert!(vars.contains(&"LC_ALL=C"));
+    assert!(vars.iter().any(|v| v.starts_with("PATH=") && v.contains("/usr/bin")));
+}
+
+#[tokio::test]
+async fn test_system_executor_cancellation_kills_command() {
+    let token = tokio_util::sync::CancellationToken::new();
+    let canceller = token.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        canceller.cancel();
+    });
+
+    let started = std::time::Instant::now();
+    let result = SystemCommandExecutor::new().execute_cancellable("sleep", &["5"], token).await;
+
+    assert!(matches!(result, Err(ServiceError::Cancelled(_))));
+    assert!(started.elapsed() < std::time::Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn test_default_execute_cancellable_ignores_token() {
+    let executor = MockCommandExecutor::new().with_stdout("systemctl", &["daemon-reload"], "");
+    let token = tokio_util::sync::CancellationToken::new();
+    token.cancel();
+
+    let output = executor.execute_cancellable("systemctl", &["daemon-reload"], token).await.unwrap();
+    assert_eq!(output.exit_code, 0);
+}
+
+#[test]
+fn test_message_id_match() {
+    assert_eq!(
+        message_id_match(MESSAGE_ID_UNIT_STARTED).unwrap(),
+        "MESSAGE_ID=39f53479d3a045ac8e11786248231fbf"
+    );
+    assert_eq!(
+        message_id_match("BE02CF6855D2428BA40DF7E9D022F03D").unwrap(),
+        "MESSAGE_ID=be02cf6855d2428ba40df7e9d022f03d"
+    );
+    assert!(message_id_match("39f53479").is_err());
+    assert!(message_id_match("39f53479d3a045ac8e11786248231fbf _PID=1").is_err());
+}
+
+#[tokio::test]
+async fn test_get_activity_emits_message_id_matches() {
+    let executor = Arc::new(MockCommandExecutor::new().with_stdout(
+        "journalctl",
+        &[
+            "-u", "nginx.service",
+            "MESSAGE_ID=39f53479d3a045ac8e11786248231fbf",
+            "MESSAGE_ID=9d1aaa27d60140bd96365438aad20286",
+            "MESSAGE_ID=be02cf6855d2428ba40df7e9d022f03d",
+            "--since", "1 hour ago", "--no-pager", "--output=json",
+        ],
+        "",
+    ));
+
+    let events = get_activity(executor, &["nginx.service".to_string()], "1 hour ago").await.unwrap();
+    assert!(events.is_empty());
+}
+
+#[tokio::test]
+async fn test_get_first_activations_takes_earliest_start_this_boot() {
+    // nginx started at boot and was restarted after a crash; postgresql started once
+    let journal = concat!(
+        r#"{"__REALTIME_TIMESTAMP":"1705312245000000","MESSAGE_ID":"39f53479d3a045ac8e11786248231fbf","UNIT":"nginx.service","JOB_RESULT":"done","MESSAGE":"Started nginx.service."}"#, "\n",
+        r#"{"__REALTIME_TIMESTAMP":"1705312246000000","MESSAGE_ID":"39f53479d3a045ac8e11786248231fbf","UNIT":"postgresql.service","JOB_RESULT":"done","MESSAGE":"Started postgresql.service."}"#, "\n",
+        r#"{"__REALTIME_TIMESTAMP":"1705315845000000","MESSAGE_ID":"39f53479d3a045ac8e11786248231fbf","UNIT":"nginx.service","JOB_RESULT":"done","MESSAGE":"Started nginx.service."}"#, "\n",
+    );
+    let executor = Arc::new(MockCommandExecutor::new().with_stdout(
+        "journalctl",
+        &[
+            "-b", "-u", "nginx.service", "-u", "postgresql",
+            "MESSAGE_ID=39f53479d3a045ac8e11786248231fbf", "--no-pager", "--output=json",
+        ],
+        journal,
+    ));
+
+    let first = get_first_activations(executor, &names(&["nginx.service", "postgresql"])).await.unwrap();
+    assert_eq!(first.len(), 2);
+    assert_eq!(first.get("nginx.service").copied(), DateTime::from_timestamp(1705312245, 0));
+    // Keyed by the name asked for, not the journal's full unit name
+    assert_eq!(first.get("postgresql").copied(), DateTime::from_timestamp(1705312246, 0));
+}
+
+#[tokio::test]
+async fn test_get_first_activations_skips_failed_starts() {
+    let journal = r#"{"__REALTIME_TIMESTAMP":"1705312245000000","MESSAGE_ID":"39f53479d3a045ac8e11786248231fbf","UNIT":"nginx.service","JOB_RESULT":"failed","MESSAGE":"Failed to start nginx.service."}"#;
+    let executor = Arc::new(MockCommandExecutor::new().with_stdout(
+        "journalctl",
+        &["-b", "-u", "nginx.service", "MESSAGE_ID=39f53479d3a045ac8e11786248231fbf", "--no-pager", "--output=json"],
+        journal,
+    ));
+
+    let first = get_first_activations(executor, &names(&["nginx.service"])).await.unwrap();
+    assert!(first.is_empty());
+}
+
+#[tokio::test]
+async fn test_get_first_activations_without_services_runs_nothing() {
+    let executor = Arc::new(MockCommandExecutor::new());
+    assert!(get_first_activations(executor, &[]).await.unwrap().is_empty());
+}
+
+fn dependency_map(edges: &[(&str, &[&str])]) -> std::collections::HashMap<String, Vec<String>> {
+    edges
+        .iter()
+        .map(|(unit, deps)| (unit.to_string(), deps.iter().map(|d| d.to_string()).collect()))
+        .collect()
+}
+
+fn names(units: &[&str]) -> Vec<String> {
+    units.iter().map(|u| u.to_string()).collect()
+}
+
+#[test]
+fn test_order_by_dependencies() {
+    // app needs api and db, api needs db; cache stands alone
+    let deps = dependency_map(&[
+        ("app.service", &["api.service", "db.service", "network.target"]),
+        ("api.service", &["db.service"]),
+        ("db.service", &["network.target"]),
+        ("cache.service", &[]),
+    ]);
+
+    let ordered = order_by_dependencies(
+        &names(&["app.service", "cache.service", "api.service", "db.service"]),
+        &deps,
+    ).unwrap();
+    assert_eq!(ordered, names(&["cache.service", "db.service", "api.service", "app.service"]));
+}
+
+#[test]
+fn test_order_by_dependencies_cycle() {
+    let deps = dependency_map(&[
+        ("a.service", &["b.service"]),
+        ("b.service", &["a.service"]),
+    ]);
+
+    assert!(order_by_dependencies(&names(&["a.service", "b.service"]), &deps).is_none());
+}
+
+#[tokio::test]
+async fn test_dependency_order_falls_back_when_unresolvable() {
+    // No response for b.service's dependencies
+    let executor = Arc::new(MockCommandExecutor::new().with_stdout(
+        "systemctl",
+        &["list-dependencies", "a.service", "--plain", "--no-legend", "--no-pager"],
+        "a.service\n  b.service\n",
+    ));
+
+    let ordered = dependency_order(executor, &names(&["a.service", "b.service"])).await;
+    assert_eq!(ordered, names(&["a.service", "b.service"]));
+}
+
+async fn unit_exists_with(load_state: &str) -> bool {
+    let executor = Arc::new(MockCommandExecutor::new().with_stdout(
+        "systemctl",
+        &["show", "nginx.service", "--property=LoadState"],
+        &format!("LoadState={}\n", load_state),
+    ));
+    unit_exists(executor, "nginx.service").await
+}
+
+#[tokio::test]
+async fn test_unit_exists() {
+    assert!(unit_exists_with("loaded").await);
+    assert!(!unit_exists_with("not-found").await);
+    // A masked unit still has a unit file; the action reports the mask
+    assert!(unit_exists_with("masked").await);
+
+    // An unanswered query doesn't block the action
+    assert!(unit_exists(Arc::new(MockCommandExecutor::new()), "nginx.service").await);
+    assert!(!unit_exists(Arc::new(MockCommandExecutor::new()), "../etc/passwd").await);
+}
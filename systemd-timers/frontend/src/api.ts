@@ -2,7 +2,7 @@ import type { TimerStatus, AvailableTimer, ExecutionHistory, ExecutionDetails, S
 
 const BASE_URL = '/api/plugins/route/systemd-timers';
 
-async function fetchAPI<T>(path: string, options?: RequestInit): Promise<T> {
+async function fetchResponse(path: string, options?: RequestInit): Promise<Response> {
   const response = await fetch(`${BASE_URL}${path}`, {
     ...options,
     headers: {
@@ -16,11 +16,27 @@ async function fetchAPI<T>(path: string, options?: RequestInit): Promise<T> {
     throw new Error(error.error || `HTTP ${response.status}`);
   }
 
+  return response;
+}
+
+async function fetchAPI<T>(path: string, options?: RequestInit): Promise<T> {
+  const response = await fetchResponse(path, options);
   return response.json();
 }
 
-export async function getTimers(): Promise<TimerStatus[]> {
-  return fetchAPI<TimerStatus[]>('/timers');
+export interface TimersPoll {
+  timers: TimerStatus[];
+  /** Server's suggested delay before the next poll (X-Refresh-After) */
+  refreshAfterSecs: number | null;
+}
+
+export async function getTimers(): Promise<TimersPoll> {
+  const response = await fetchResponse('/timers');
+  const hint = Number(response.headers.get('X-Refresh-After'));
+  return {
+    timers: await response.json(),
+    refreshAfterSecs: hint > 0 ? hint : null,
+  };
 }
 
 export async function getAvailableTimers(): Promise<AvailableTimer[]> {
@@ -56,9 +72,11 @@ export async function getSettings(): Promise<Settings> {
   return fetchAPI<Settings>('/timers/settings');
 }
 
-export async function saveSettings(watchedTimers: string[]): Promise<void> {
-  await fetchAPI('/timers/settings', {
+/** Resolves to warnings about watched timers systemd doesn't know; the save still happened */
+export async function saveSettings(watchedTimers: string[]): Promise<string[]> {
+  const result = await fetchAPI<{ data?: { warnings?: string[] } }>('/timers/settings', {
     method: 'POST',
     body: JSON.stringify({ watched_timers: watchedTimers }),
   });
+  return result.data?.warnings ?? [];
 }
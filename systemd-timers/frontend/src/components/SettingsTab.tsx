@@ -56,7 +56,11 @@ export function SettingsTab() {
     try {
       setSaving(true);
       setMessage(null);
-      await saveSettings(watchedTimers);
+      const warnings = await saveSettings(watchedTimers);
+      if (warnings.length > 0) {
+        setMessage({ type: 'error', text: `Settings saved with warnings: ${warnings.join('; ')}` });
+        return;
+      }
       setMessage({ type: 'success', text: 'Settings saved!' });
       setTimeout(() => setMessage(null), 2000);
     } catch (err) {
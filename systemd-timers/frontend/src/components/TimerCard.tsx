@@ -70,6 +70,11 @@ export function TimerCard({ timer, onRun, onTest, onToggle }: TimerCardProps) {
               </>
             )}
           </div>
+          {timer.last_condition_failed && (
+            <div className="mt-1 text-xs text-muted-foreground">
+              Last run skipped due to unmet condition
+            </div>
+          )}
         </div>
 
         {/* Actions */}
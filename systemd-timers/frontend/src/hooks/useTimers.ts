@@ -7,23 +7,37 @@ export function useTimers(autoRefreshInterval: number = 60000) {
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
 
-  const fetchTimers = async () => {
+  // Resolves to the delay before the next poll: the server's hint when
+  // present, otherwise autoRefreshInterval
+  const fetchTimers = async (): Promise<number> => {
     try {
       setLoading(true);
       setError(null);
-      const data = await getTimers();
-      setTimers(data);
+      const { timers, refreshAfterSecs } = await getTimers();
+      setTimers(timers);
+      return refreshAfterSecs ? refreshAfterSecs * 1000 : autoRefreshInterval;
     } catch (err) {
       setError(err instanceof Error ? err.message : 'Failed to fetch timers');
+      return autoRefreshInterval;
     } finally {
       setLoading(false);
     }
   };
 
   useEffect(() => {
-    fetchTimers();
-    const interval = setInterval(fetchTimers, autoRefreshInterval);
-    return () => clearInterval(interval);
+    let timeout: ReturnType<typeof setTimeout> | undefined;
+    let cancelled = false;
+    const poll = async () => {
+      const delay = await fetchTimers();
+      if (!cancelled) {
+        timeout = setTimeout(poll, delay);
+      }
+    };
+    poll();
+    return () => {
+      cancelled = true;
+      clearTimeout(timeout);
+    };
   }, [autoRefreshInterval]);
 
   const handleRunTimer = async (name: string, testMode: boolean = false) => {
@@ -15,11 +15,17 @@ export interface TimerStatus {
   next_run: string | null;
   last_run: string | null;
   last_result: "success" | "failed" | "running" | null;
+  /** Last run skipped because a Condition*= directive wasn't met */
+  last_condition_failed?: boolean | null;
+  /** Snoozed timers: when they are enabled again (RFC 3339) */
+  snoozed_until?: string | null;
 }
 
 export interface AvailableTimer {
   name: string;
   description: string;
+  left?: string | null;
+  passed?: string | null;
 }
 
 export interface ExecutionHistory {
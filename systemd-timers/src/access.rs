@@ -0,0 +1,111 @@
+use crate::error::TimerResult;
+use crate::settings::PluginSettings;
+use toru_plugin_api::PluginKvStore;
+
+/// Allowlist/denylist of unit name patterns (`*` and `?` wildcards)
+///
+/// The denylist always takes precedence. An empty allowlist permits every
+/// unit that is not denied.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UnitAccessPolicy {
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+}
+
+impl UnitAccessPolicy {
+    /// Load the policy from the plugin settings (empty lists mean "no restriction")
+    pub async fn load(kv_store: &dyn PluginKvStore) -> TimerResult<Self> {
+        Ok(Self::from_settings(&PluginSettings::load(kv_store).await?))
+    }
+
+    /// Build the policy from already loaded settings
+    pub fn from_settings(settings: &PluginSettings) -> Self {
+        Self {
+            allow: settings.allowed_units.clone(),
+            deny: settings.denied_units.clone(),
+        }
+    }
+
+    /// Check whether a unit may be managed, returning the rejection reason if not
+    pub fn check(&self, unit: &str) -> Result<(), String> {
+        if let Some(pattern) = self.deny.iter().find(|p| glob_match(p, unit)) {
+            return Err(format!(
+                "Unit {} is denied by the plugin's denylist (pattern '{}')",
+                unit, pattern
+            ));
+        }
+
+        if !self.allow.is_empty() && !self.allow.iter().any(|p| glob_match(p, unit)) {
+            return Err(format!("Unit {} is not in the plugin's allowlist", unit));
+        }
+
+        Ok(())
+    }
+}
+
+/// Match a unit name against a glob pattern supporting `*` and `?`
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` in the pattern and the name index it was tried at
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            // Let the last `*` swallow one more character and retry
+            p = star + 1;
+            n = matched + 1;
+            backtrack = Some((star, n));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("backup.timer", "backup.timer"));
+        assert!(glob_match("*.timer", "backup.timer"));
+        assert!(glob_match("chfscraper-*.timer", "chfscraper-scrape-bcp.timer"));
+        assert!(glob_match("logrotate?.timer", "logrotate2.timer"));
+
+        assert!(!glob_match("*.timer", "backup.service"));
+        assert!(!glob_match("backup", "backup.timer"));
+    }
+
+    #[test]
+    fn test_denylist_takes_precedence_over_allowlist() {
+        let policy = UnitAccessPolicy {
+            allow: vec!["*.timer".to_string()],
+            deny: vec!["fstrim.timer".to_string()],
+        };
+
+        assert!(policy.check("backup.timer").is_ok());
+        assert!(policy.check("fstrim.timer").unwrap_err().contains("denylist"));
+    }
+
+    #[test]
+    fn test_allowlist_rejects_unlisted_units() {
+        let policy = UnitAccessPolicy {
+            allow: vec!["chfscraper-*".to_string()],
+            deny: Vec::new(),
+        };
+
+        assert!(policy.check("chfscraper-scrape-bcp.timer").is_ok());
+        assert!(policy.check("backup.timer").unwrap_err().contains("allowlist"));
+    }
+}
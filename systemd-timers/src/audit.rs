@@ -0,0 +1,71 @@
+use crate::clock::Clock;
+use crate::error::{TimerError, TimerResult};
+use serde::{Deserialize, Serialize};
+use toru_plugin_api::PluginKvStore;
+
+/// KV key holding the JSON array of recent audit entries (oldest first)
+pub const AUDIT_LOG_KEY: &str = "audit_log";
+
+/// Maximum number of audit entries kept in KV storage
+pub const MAX_AUDIT_ENTRIES: usize = 200;
+
+/// Single record of a mutating action performed through the plugin
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuditEntry {
+    /// RFC 3339 timestamp of when the action finished
+    pub timestamp: String,
+    pub unit: String,
+    /// "run", "test", "enable" or "disable"
+    pub action: String,
+    /// "success" or "failure"
+    pub outcome: String,
+    /// Error message for failed actions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AuditEntry {
+    /// Build an entry stamped with `clock`'s current time; `error` is None on success
+    pub fn new(unit: &str, action: &str, error: Option<String>, clock: &dyn Clock) -> Self {
+        let outcome = if error.is_some() { "failure" } else { "success" };
+
+        Self {
+            timestamp: clock.now().to_rfc3339(),
+            unit: unit.to_string(),
+            action: action.to_string(),
+            outcome: outcome.to_string(),
+            error,
+        }
+    }
+
+    /// Build an entry stamped with `clock`'s current time from an action result
+    pub fn from_result<T>(unit: &str, action: &str, result: &TimerResult<T>, clock: &dyn Clock) -> Self {
+        Self::new(unit, action, result.as_ref().err().map(|e| e.to_string()), clock)
+    }
+}
+
+/// Load the audit log from KV storage (oldest entry first)
+pub async fn load_audit_log(kv_store: &dyn PluginKvStore) -> TimerResult<Vec<AuditEntry>> {
+    match kv_store.get(AUDIT_LOG_KEY).await {
+        Ok(Some(json_str)) => Ok(serde_json::from_str(&json_str)?),
+        Ok(None) => Ok(Vec::new()),
+        Err(e) => Err(TimerError::IoError(format!("KV storage error: {}", e))),
+    }
+}
+
+/// Append an entry to the audit log, dropping the oldest entries beyond the cap
+pub async fn append_audit(kv_store: &dyn PluginKvStore, entry: AuditEntry) -> TimerResult<()> {
+    let mut entries = load_audit_log(kv_store).await?;
+    entries.push(entry);
+
+    if entries.len() > MAX_AUDIT_ENTRIES {
+        let excess = entries.len() - MAX_AUDIT_ENTRIES;
+        entries.drain(..excess);
+    }
+
+    let json_str = serde_json::to_string(&entries)?;
+    kv_store
+        .set(AUDIT_LOG_KEY, &json_str)
+        .await
+        .map_err(|e| TimerError::IoError(format!("KV storage error: {}", e)))
+}
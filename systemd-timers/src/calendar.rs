@@ -0,0 +1,929 @@
+//! Structured OnCalendar parsing and occurrence computation
+//!
+//! Supports the common subset of systemd calendar syntax: weekday lists and
+//! ranges, `Y-M-D` / `M-D` dates, `H:M[:S]` times, comma lists, `a..b` ranges,
+//! `a/n` and `a..b/n` repetitions, an optional trailing time zone, and the
+//! `minutely`/`hourly`/`daily`/`weekly`/`monthly`/`quarterly`/`semiannually`/
+//! `yearly` (`annually`) shorthands.
+//!
+//! A parsed [`CalendarSpec`] renders back to systemd's normalized form through
+//! `Display` (`Mon..Fri *-*-* 07..21:00:00 Europe/Warsaw`), and parsing that
+//! output yields an equal spec.
+
+use crate::error::{TimerError, TimerResult};
+use crate::timestamp::parse_systemd_timestamp;
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday};
+use std::fmt;
+
+/// How far occurrence searches look before giving up (covers Feb 29 schedules)
+const MAX_SEARCH_DAYS: u32 = 366 * 8;
+
+/// One comma-separated component of a calendar field
+///
+/// `5` is `{start: 5, end: None, step: None}`, `1..5` sets `end`, and `0/15`
+/// or `1..10/3` set `step` (repeat from `start`, up to `end` if given).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalendarValue {
+    pub start: u32,
+    pub end: Option<u32>,
+    pub step: Option<u32>,
+}
+
+impl CalendarValue {
+    fn matches(&self, value: u32) -> bool {
+        match (self.end, self.step) {
+            (None, None) => value == self.start,
+            (Some(end), None) => (self.start..=end).contains(&value),
+            (end, Some(step)) => {
+                value >= self.start
+                    && end.is_none_or(|end| value <= end)
+                    && (value - self.start).is_multiple_of(step)
+            }
+        }
+    }
+}
+
+impl fmt::Display for CalendarValue {
+    /// Zero-padded like systemd's normalized form: `05`, `07..21`, `00/15`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}", self.start)?;
+        if let Some(end) = self.end {
+            write!(f, "..{:02}", end)?;
+        }
+        if let Some(step) = self.step {
+            write!(f, "/{}", step)?;
+        }
+        Ok(())
+    }
+}
+
+/// A calendar field; an empty list is the `*` wildcard
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CalendarField(pub Vec<CalendarValue>);
+
+impl CalendarField {
+    /// The `*` wildcard
+    pub fn any() -> Self {
+        Self(Vec::new())
+    }
+
+    /// A single exact value
+    pub fn exact(value: u32) -> Self {
+        Self(vec![CalendarValue { start: value, end: None, step: None }])
+    }
+
+    pub fn is_any(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn matches(&self, value: u32) -> bool {
+        self.is_any() || self.0.iter().any(|v| v.matches(value))
+    }
+}
+
+impl fmt::Display for CalendarField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_any() {
+            return f.write_str("*");
+        }
+
+        for (i, value) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}", value)?;
+        }
+        Ok(())
+    }
+}
+
+/// An inclusive weekday range; a single day has `start == end`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeekdayRange {
+    pub start: Weekday,
+    pub end: Weekday,
+}
+
+impl WeekdayRange {
+    fn contains(&self, day: Weekday) -> bool {
+        let (start, end, day) = (
+            self.start.num_days_from_monday(),
+            self.end.num_days_from_monday(),
+            day.num_days_from_monday(),
+        );
+
+        if start <= end {
+            (start..=end).contains(&day)
+        } else {
+            // Wrapping range such as Sat..Mon
+            day >= start || day <= end
+        }
+    }
+}
+
+impl fmt::Display for WeekdayRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start == self.end {
+            write!(f, "{}", self.start)
+        } else {
+            write!(f, "{}..{}", self.start, self.end)
+        }
+    }
+}
+
+/// Parsed OnCalendar expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarSpec {
+    /// Empty means every day of the week
+    pub weekdays: Vec<WeekdayRange>,
+    pub year: CalendarField,
+    pub month: CalendarField,
+    pub day: CalendarField,
+    pub hour: CalendarField,
+    pub minute: CalendarField,
+    pub second: CalendarField,
+    /// Trailing time zone (e.g. "UTC", "Europe/Warsaw"); None is local time
+    pub timezone: Option<String>,
+}
+
+impl CalendarSpec {
+    /// Parse an OnCalendar expression
+    pub fn parse(expression: &str) -> TimerResult<Self> {
+        let expression = expression.trim();
+
+        let expanded = match expression.to_ascii_lowercase().as_str() {
+            "minutely" => "*-*-* *:*:00",
+            "hourly" => "*-*-* *:00:00",
+            "daily" => "*-*-* 00:00:00",
+            "weekly" => "Mon *-*-* 00:00:00",
+            "monthly" => "*-*-01 00:00:00",
+            "quarterly" => "*-01,04,07,10-01 00:00:00",
+            "semiannually" => "*-01,07-01 00:00:00",
+            "yearly" | "annually" => "*-01-01 00:00:00",
+            _ => expression,
+        };
+
+        let mut tokens: Vec<&str> = expanded.split_whitespace().collect();
+        if tokens.is_empty() {
+            return Err(parse_error(expression, "empty expression"));
+        }
+
+        let mut weekdays = Vec::new();
+        if tokens[0].starts_with(|c: char| c.is_ascii_alphabetic()) {
+            weekdays = parse_weekdays(tokens.remove(0))
+                .ok_or_else(|| parse_error(expression, "invalid weekday list"))?;
+        }
+
+        let mut timezone = None;
+        if tokens.last().is_some_and(|t| t.starts_with(|c: char| c.is_ascii_alphabetic())) {
+            timezone = tokens.pop().map(str::to_string);
+        }
+
+        let mut spec = Self {
+            weekdays,
+            year: CalendarField::any(),
+            month: CalendarField::any(),
+            day: CalendarField::any(),
+            hour: CalendarField::exact(0),
+            minute: CalendarField::exact(0),
+            second: CalendarField::exact(0),
+            timezone,
+        };
+
+        let mut seen_date = false;
+        let mut seen_time = false;
+
+        for token in tokens {
+            if token.contains(':') && !seen_time {
+                let parts: Vec<&str> = token.split(':').collect();
+                let (hour, minute, second) = match parts.as_slice() {
+                    [h, m] => (*h, *m, "00"),
+                    [h, m, s] => (*h, *m, *s),
+                    _ => return Err(parse_error(expression, "time must be H:M or H:M:S")),
+                };
+                spec.hour = parse_field(hour, 0, 23).ok_or_else(|| parse_error(expression, "invalid hour"))?;
+                spec.minute = parse_field(minute, 0, 59).ok_or_else(|| parse_error(expression, "invalid minute"))?;
+                // Fractional seconds are not tracked
+                let second = second.split('.').next().unwrap_or(second);
+                spec.second = parse_field(second, 0, 59).ok_or_else(|| parse_error(expression, "invalid second"))?;
+                seen_time = true;
+            } else if token.contains('-') && !seen_date {
+                let parts: Vec<&str> = token.split('-').collect();
+                let (year, month, day) = match parts.as_slice() {
+                    [m, d] => ("*", *m, *d),
+                    [y, m, d] => (*y, *m, *d),
+                    _ => return Err(parse_error(expression, "date must be Y-M-D or M-D")),
+                };
+                spec.year = parse_field(year, 1970, 9999).ok_or_else(|| parse_error(expression, "invalid year"))?;
+                spec.month = parse_field(month, 1, 12).ok_or_else(|| parse_error(expression, "invalid month"))?;
+                spec.day = parse_field(day, 1, 31).ok_or_else(|| parse_error(expression, "invalid day"))?;
+                seen_date = true;
+            } else {
+                return Err(parse_error(expression, &format!("unexpected component '{}'", token)));
+            }
+        }
+
+        Ok(spec)
+    }
+
+    /// True if the spec fires on the given calendar date
+    pub fn matches_date(&self, date: NaiveDate) -> bool {
+        (self.weekdays.is_empty() || self.weekdays.iter().any(|r| r.contains(date.weekday())))
+            && u32::try_from(date.year()).is_ok_and(|y| self.year.matches(y))
+            && self.month.matches(date.month())
+            && self.day.matches(date.day())
+    }
+
+    /// First occurrence strictly after `after`, evaluated in the wall-clock time of `after`'s zone
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let naive = self.search(after.naive_local(), true)?;
+        after.timezone().from_local_datetime(&naive).earliest()
+    }
+
+    /// Latest occurrence at or before `at`, evaluated in the wall-clock time of `at`'s zone
+    pub fn previous_at_or_before<Tz: TimeZone>(&self, at: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let naive = self.search(at.naive_local(), false)?;
+        at.timezone().from_local_datetime(&naive).earliest()
+    }
+
+    /// Latest occurrence at or before `at`, honouring the spec's time zone
+    ///
+    /// Only UTC is resolved explicitly; any other zone (or none) is evaluated
+    /// in the system's local time, which is where the timer itself runs.
+    pub fn previous_occurrence(&self, at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if self.is_utc() {
+            self.previous_at_or_before(&at)
+        } else {
+            self.previous_at_or_before(&at.with_timezone(&Local))
+                .map(|dt| dt.with_timezone(&Utc))
+        }
+    }
+
+    /// First occurrence strictly after `after`, honouring the spec's time zone
+    pub fn next_occurrence(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if self.is_utc() {
+            self.next_after(&after)
+        } else {
+            self.next_after(&after.with_timezone(&Local))
+                .map(|dt| dt.with_timezone(&Utc))
+        }
+    }
+
+    fn is_utc(&self) -> bool {
+        matches!(self.timezone.as_deref(), Some("UTC") | Some("GMT"))
+    }
+
+    /// Walk day by day from `from` to the nearest matching date-time
+    /// Forward searches are exclusive of `from`, backward ones inclusive
+    fn search(&self, from: NaiveDateTime, forward: bool) -> Option<NaiveDateTime> {
+        let mut date = from.date();
+
+        for offset in 0..=MAX_SEARCH_DAYS {
+            if self.matches_date(date) {
+                let bound = (offset == 0).then(|| from.time());
+                if let Some(time) = self.time_on_day(bound, forward) {
+                    return Some(date.and_time(time));
+                }
+            }
+
+            date = if forward { date.succ_opt()? } else { date.pred_opt()? };
+        }
+
+        None
+    }
+
+    /// Earliest matching time after `bound` (forward) or latest at or before it (backward)
+    fn time_on_day(&self, bound: Option<NaiveTime>, forward: bool) -> Option<NaiveTime> {
+        let candidates = |max: u32| -> Vec<u32> {
+            if forward { (0..=max).collect() } else { (0..=max).rev().collect() }
+        };
+
+        for hour in candidates(23).into_iter().filter(|h| self.hour.matches(*h)) {
+            for minute in candidates(59).into_iter().filter(|m| self.minute.matches(*m)) {
+                for second in candidates(59).into_iter().filter(|s| self.second.matches(*s)) {
+                    let time = NaiveTime::from_hms_opt(hour, minute, second)?;
+                    match bound {
+                        Some(bound) if forward && time <= bound => continue,
+                        Some(bound) if !forward && time > bound => continue,
+                        _ => return Some(time),
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Natural-language description, e.g. "Every Monday and Thursday at 6:30 AM"
+    /// (or "... at 06:30" with `use_24h`)
+    ///
+    /// Returns None for specs that have no natural phrasing (callers fall back
+    /// to the terse form or the raw expression).
+    pub fn describe(&self, use_24h: bool) -> Option<String> {
+        let days = self.describe_days()?;
+        let phrase = match self.describe_times(use_24h)? {
+            TimePhrase::At(times) => match days {
+                DayPhrase::EveryDay => format!("every day at {}", times),
+                DayPhrase::Weekdays(list) => format!("every {} at {}", list, times),
+                DayPhrase::Dated(dates) => format!("{} at {}", dates, times),
+            },
+            TimePhrase::Interval(interval) => match days {
+                DayPhrase::EveryDay => interval,
+                DayPhrase::Weekdays(list) => format!("{}, {}", interval, list),
+                DayPhrase::Dated(dates) => format!("{}, {}", interval, dates),
+            },
+        };
+
+        Some(capitalize(&phrase))
+    }
+
+    fn describe_days(&self) -> Option<DayPhrase> {
+        let dates_any = self.year.is_any() && self.month.is_any() && self.day.is_any();
+
+        if !self.weekdays.is_empty() {
+            if !dates_any {
+                return None;
+            }
+            let names: Vec<String> = self
+                .weekdays
+                .iter()
+                .map(|r| {
+                    if r.start == r.end {
+                        weekday_name(r.start).to_string()
+                    } else {
+                        format!("{} through {}", weekday_name(r.start), weekday_name(r.end))
+                    }
+                })
+                .collect();
+            return Some(DayPhrase::Weekdays(join_list(&names)));
+        }
+
+        if dates_any {
+            return Some(DayPhrase::EveryDay);
+        }
+
+        if !self.year.is_any() {
+            return None;
+        }
+
+        let days = exact_values(&self.day)?;
+        let ordinals: Vec<String> = days.iter().map(|d| ordinal(*d)).collect();
+
+        if self.month.is_any() {
+            return Some(DayPhrase::Dated(format!("monthly on the {}", join_list(&ordinals))));
+        }
+
+        match (exact_values(&self.month)?.as_slice(), days.as_slice()) {
+            ([month], [day]) => Some(DayPhrase::Dated(format!("yearly on {} {}", month_name(*month), day))),
+            (months, [day]) => {
+                let names: Vec<String> = months.iter().map(|m| month_name(*m).to_string()).collect();
+                Some(DayPhrase::Dated(format!("on the {} of {}", ordinal(*day), join_list(&names))))
+            }
+            _ => None,
+        }
+    }
+
+    fn describe_times(&self, use_24h: bool) -> Option<TimePhrase> {
+        // Sub-minute schedules have no natural phrasing
+        let second = single_value(&self.second)?;
+
+        // Fixed times of day: "at 6:30 AM" / "at 6:00 AM and 6:00 PM"
+        if let (Some(hours), Some(minutes)) = (exact_values(&self.hour), exact_values(&self.minute)) {
+            let times: Vec<String> = hours
+                .iter()
+                .flat_map(|h| minutes.iter().map(move |m| format_clock(*h, *m, second, use_24h)))
+                .collect();
+            return Some(TimePhrase::At(join_list(&times)));
+        }
+
+        if second != 0 {
+            return None;
+        }
+
+        // Every minute: "*:*"
+        if self.hour.is_any() && self.minute.is_any() {
+            return Some(TimePhrase::Interval("every minute".to_string()));
+        }
+
+        // Every N minutes: "*:0/15"
+        if self.hour.is_any() {
+            if let [CalendarValue { start: 0, end: None, step: Some(step) }] = self.minute.0.as_slice() {
+                return Some(TimePhrase::Interval(format!("every {} minutes", step)));
+            }
+        }
+
+        // Hourly at a fixed minute, optionally within an hour range
+        let minute = single_value(&self.minute)?;
+        let at_minute = if minute == 0 { String::new() } else { format!(" at :{:02}", minute) };
+
+        match self.hour.0.as_slice() {
+            [] => Some(TimePhrase::Interval(format!("every hour{}", at_minute))),
+            [CalendarValue { start, end: Some(end), step: None }] => Some(TimePhrase::Interval(format!(
+                "every hour{} from {} to {}",
+                at_minute,
+                format_hour(*start, use_24h),
+                format_hour(*end, use_24h)
+            ))),
+            [CalendarValue { start: 0, end: None, step: Some(step) }] => {
+                Some(TimePhrase::Interval(format!("every {} hours{}", step, at_minute)))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CalendarSpec {
+    /// Normalized systemd form: `[weekdays ]Y-M-D H:M:S[ zone]`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.weekdays.is_empty() {
+            for (i, range) in self.weekdays.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(",")?;
+                }
+                write!(f, "{}", range)?;
+            }
+            f.write_str(" ")?;
+        }
+
+        write!(
+            f,
+            "{}-{}-{} {}:{}:{}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )?;
+
+        if let Some(timezone) = &self.timezone {
+            write!(f, " {}", timezone)?;
+        }
+        Ok(())
+    }
+}
+
+/// Day part of a description
+enum DayPhrase {
+    EveryDay,
+    /// e.g. "Monday and Thursday"
+    Weekdays(String),
+    /// e.g. "monthly on the 15th"
+    Dated(String),
+}
+
+/// Time part of a description
+enum TimePhrase {
+    /// Fixed times of day, e.g. "6:30 AM"
+    At(String),
+    /// Repeating pattern, e.g. "every 15 minutes"
+    Interval(String),
+}
+
+/// Values of a field made only of single values (no ranges or repetitions)
+fn exact_values(field: &CalendarField) -> Option<Vec<u32>> {
+    if field.is_any() {
+        return None;
+    }
+    field
+        .0
+        .iter()
+        .map(|v| (v.end.is_none() && v.step.is_none()).then_some(v.start))
+        .collect()
+}
+
+/// The value of a field holding exactly one single value
+fn single_value(field: &CalendarField) -> Option<u32> {
+    match exact_values(field)?.as_slice() {
+        [value] => Some(*value),
+        _ => None,
+    }
+}
+
+/// "6:30 AM" / "12:00 PM" (or "06:30" / "12:00"), with seconds only when non-zero
+fn format_clock(hour: u32, minute: u32, second: u32, use_24h: bool) -> String {
+    let seconds = if second == 0 { String::new() } else { format!(":{:02}", second) };
+
+    if use_24h {
+        return format!("{:02}:{:02}{}", hour, minute, seconds);
+    }
+
+    let hour12 = match hour % 12 {
+        0 => 12,
+        h => h,
+    };
+    let suffix = if hour < 12 { "AM" } else { "PM" };
+    format!("{}:{:02}{} {}", hour12, minute, seconds, suffix)
+}
+
+/// A whole hour: "8 AM" / "9 PM" (or "08:00" / "21:00")
+pub fn format_hour(hour: u32, use_24h: bool) -> String {
+    if use_24h {
+        return format!("{:02}:00", hour);
+    }
+
+    let hour12 = match hour % 12 {
+        0 => 12,
+        h => h,
+    };
+    format!("{} {}", hour12, if hour < 12 { "AM" } else { "PM" })
+}
+
+fn ordinal(n: u32) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
+
+fn weekday_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+fn month_name(month: u32) -> &'static str {
+    const MONTHS: [&str; 12] = [
+        "January", "February", "March", "April", "May", "June",
+        "July", "August", "September", "October", "November", "December",
+    ];
+    MONTHS[(month as usize).saturating_sub(1).min(11)]
+}
+
+/// "a", "a and b", "a, b and c"
+fn join_list(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Seconds between the most recent scheduled run and the timer's actual last trigger
+///
+/// Computed as (latest occurrence at or before `now`) - `last_trigger` across all
+/// calendar entries. Around zero means the timer fired on schedule; a large
+/// positive value means the expected run was missed or is late. None when the
+/// schedule can't be parsed or the timer never ran.
+pub fn schedule_drift(
+    calendar: &[String],
+    last_trigger: Option<&str>,
+    now: DateTime<Utc>,
+) -> Option<i64> {
+    let last_trigger = parse_systemd_timestamp(last_trigger?)?;
+
+    let expected = calendar
+        .iter()
+        .filter_map(|expr| CalendarSpec::parse(expr).ok())
+        .filter_map(|spec| spec.previous_occurrence(now))
+        .max()?;
+
+    Some((expected - last_trigger).num_seconds())
+}
+
+fn parse_error(expression: &str, reason: &str) -> TimerError {
+    TimerError::ParseError {
+        source: format!("OnCalendar '{}'", expression),
+        reason: reason.to_string(),
+    }
+}
+
+/// Parse a comma list of `*`, `n`, `a..b`, `a/n` and `a..b/n` components
+fn parse_field(input: &str, min: u32, max: u32) -> Option<CalendarField> {
+    if input == "*" {
+        return Some(CalendarField::any());
+    }
+
+    let in_range = |v: u32| (min..=max).contains(&v);
+    let mut values = Vec::new();
+
+    for item in input.split(',') {
+        let (base, step) = match item.split_once('/') {
+            Some((base, step)) => (base, Some(step.parse::<u32>().ok().filter(|s| *s > 0)?)),
+            None => (item, None),
+        };
+
+        let (start, end) = match base.split_once("..") {
+            Some((start, end)) => (start.parse::<u32>().ok()?, Some(end.parse::<u32>().ok()?)),
+            None => (base.parse::<u32>().ok()?, None),
+        };
+
+        if !in_range(start) || end.is_some_and(|e| !in_range(e) || e < start) {
+            return None;
+        }
+
+        values.push(CalendarValue { start, end, step });
+    }
+
+    Some(CalendarField(values))
+}
+
+/// Parse `Mon`, `Mon,Thu`, `Mon..Fri` or `Mon-Fri` (full day names are accepted too)
+fn parse_weekdays(input: &str) -> Option<Vec<WeekdayRange>> {
+    input
+        .split(',')
+        .map(|item| {
+            let (start, end) = item
+                .split_once("..")
+                .or_else(|| item.split_once('-'))
+                .unwrap_or((item, item));
+            Some(WeekdayRange {
+                start: start.parse().ok()?,
+                end: end.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_full_expression() {
+        let spec = CalendarSpec::parse("Mon..Fri *-*-* 07..21:00:00 Europe/Warsaw").unwrap();
+        assert_eq!(spec.weekdays, vec![WeekdayRange { start: Weekday::Mon, end: Weekday::Fri }]);
+        assert!(spec.day.is_any());
+        assert!(spec.hour.matches(7) && spec.hour.matches(21) && !spec.hour.matches(22));
+        assert_eq!(spec.timezone.as_deref(), Some("Europe/Warsaw"));
+    }
+
+    #[test]
+    fn test_parse_defaults_and_shorthands() {
+        let spec = CalendarSpec::parse("Mon,Thu 06:30").unwrap();
+        assert_eq!(spec.weekdays.len(), 2);
+        assert!(spec.second.matches(0) && !spec.second.matches(1));
+
+        let daily = CalendarSpec::parse("daily").unwrap();
+        assert_eq!(daily, CalendarSpec::parse("*-*-* 00:00:00").unwrap());
+    }
+
+    /// Expressions whose normalized form is already what they render to
+    const CANONICAL: &[&str] = &[
+        "*-*-* 00:00:00",
+        "*-*-* *:00:00",
+        "Mon *-*-* 00:00:00",
+        "*-*-01 00:00:00",
+        "Mon..Fri *-*-* 07..21:00:00 Europe/Warsaw",
+        "Mon,Thu *-*-* 06:30:00",
+        "Sat..Mon *-*-* 23:59:59 UTC",
+        "*-*-* *:00/15:00",
+        "*-*-* 00/2:30:00",
+        "2024-02-29 12:00:00",
+        "*-01,04,07,10-01 00:00:00",
+        "*-*-01..07/2 08:00:00",
+        "Mon,Wed..Fri 2025..2030-*-15 09,17:00,30:00",
+    ];
+
+    /// Alternative spellings that normalize to one of the canonical forms
+    const NON_CANONICAL: &[(&str, &str)] = &[
+        ("daily", "*-*-* 00:00:00"),
+        ("hourly", "*-*-* *:00:00"),
+        ("weekly", "Mon *-*-* 00:00:00"),
+        ("monthly", "*-*-01 00:00:00"),
+        ("Mon-Fri 7..21:00", "Mon..Fri *-*-* 07..21:00:00"),
+        ("Monday 6:30", "Mon *-*-* 06:30:00"),
+        ("*:0/15", "*-*-* *:00/15:00"),
+        ("12-25 8:05:09.5", "*-12-25 08:05:09"),
+    ];
+
+    #[test]
+    fn test_display_canonical_is_identity() {
+        for expr in CANONICAL {
+            let spec = CalendarSpec::parse(expr).unwrap();
+            assert_eq!(spec.to_string(), *expr);
+        }
+    }
+
+    #[test]
+    fn test_display_normalizes() {
+        for (expr, normalized) in NON_CANONICAL {
+            let spec = CalendarSpec::parse(expr).unwrap();
+            assert_eq!(spec.to_string(), *normalized, "normalizing {}", expr);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_is_stable() {
+        let corpus = CANONICAL.iter().chain(NON_CANONICAL.iter().map(|(expr, _)| expr));
+
+        for expr in corpus {
+            let spec = CalendarSpec::parse(expr).unwrap();
+            let rendered = spec.to_string();
+            let reparsed = CalendarSpec::parse(&rendered).unwrap();
+            assert_eq!(reparsed, spec, "round-trip of {}", expr);
+            assert_eq!(reparsed.to_string(), rendered, "second render of {}", expr);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_generated_fields() {
+        // Every combination of a few shapes per field must survive parse -> display -> parse
+        let weekdays = ["", "Tue ", "Fri..Sun ", "Mon,Wed "];
+        let dates = ["*-*-*", "*-06-*", "2030-*-1..3", "*-*-01/10"];
+        let times = ["00:00:00", "*:*:00", "05..09:15:00", "*:00,20,40:00/30"];
+        let zones = ["", " UTC"];
+
+        for weekday in weekdays {
+            for date in dates {
+                for time in times {
+                    for zone in zones {
+                        let expr = format!("{}{} {}{}", weekday, date, time, zone);
+                        let spec = CalendarSpec::parse(&expr).unwrap();
+                        let reparsed = CalendarSpec::parse(&spec.to_string()).unwrap();
+                        assert_eq!(reparsed, spec, "round-trip of {}", expr);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_shorthand_expansions() {
+        let cases = [
+            ("minutely", "*-*-* *:*:00"),
+            ("hourly", "*-*-* *:00:00"),
+            ("daily", "*-*-* 00:00:00"),
+            ("weekly", "Mon *-*-* 00:00:00"),
+            ("monthly", "*-*-01 00:00:00"),
+            ("quarterly", "*-01,04,07,10-01 00:00:00"),
+            ("semiannually", "*-01,07-01 00:00:00"),
+            ("yearly", "*-01-01 00:00:00"),
+            ("annually", "*-01-01 00:00:00"),
+            ("Quarterly", "*-01,04,07,10-01 00:00:00"),
+        ];
+
+        for (shorthand, expansion) in cases {
+            let spec = CalendarSpec::parse(shorthand).unwrap();
+            assert_eq!(spec, CalendarSpec::parse(expansion).unwrap(), "{}", shorthand);
+            assert_eq!(spec.to_string(), expansion);
+        }
+    }
+
+    #[test]
+    fn test_shorthand_next_occurrence() {
+        let now = utc("2024-05-15T10:30:20Z");
+        let next = |expr: &str| {
+            let mut spec = CalendarSpec::parse(expr).unwrap();
+            spec.timezone = Some("UTC".to_string());
+            spec.next_occurrence(now).unwrap()
+        };
+
+        assert_eq!(next("minutely"), utc("2024-05-15T10:31:00Z"));
+        assert_eq!(next("quarterly"), utc("2024-07-01T00:00:00Z"));
+        assert_eq!(next("semiannually"), utc("2024-07-01T00:00:00Z"));
+        assert_eq!(next("yearly"), utc("2025-01-01T00:00:00Z"));
+        assert_eq!(next("annually"), utc("2025-01-01T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_describe_shorthands() {
+        let describe = |expr: &str| CalendarSpec::parse(expr).unwrap().describe(false).unwrap();
+
+        assert_eq!(describe("minutely"), "Every minute");
+        assert_eq!(describe("yearly"), "Yearly on January 1 at 12:00 AM");
+        assert_eq!(
+            describe("quarterly"),
+            "On the 1st of January, April, July and October at 12:00 AM"
+        );
+        assert_eq!(describe("semiannually"), "On the 1st of January and July at 12:00 AM");
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(CalendarSpec::parse("").is_err());
+        assert!(CalendarSpec::parse("Funday 10:00").is_err());
+        assert!(CalendarSpec::parse("*-*-* 25:00").is_err());
+        assert!(CalendarSpec::parse("*-13-01").is_err());
+    }
+
+    #[test]
+    fn test_repetition() {
+        let spec = CalendarSpec::parse("*:0/15 UTC").unwrap();
+        let next = spec.next_after(&utc("2024-01-15T10:07:00Z")).unwrap();
+        assert_eq!(next, utc("2024-01-15T10:15:00Z"));
+    }
+
+    #[test]
+    fn test_next_and_previous_occurrence() {
+        let spec = CalendarSpec::parse("Mon,Thu 06:30 UTC").unwrap();
+        // 2024-01-15 is a Monday
+        let now = utc("2024-01-16T12:00:00Z");
+
+        assert_eq!(spec.previous_occurrence(now), Some(utc("2024-01-15T06:30:00Z")));
+        assert_eq!(spec.next_occurrence(now), Some(utc("2024-01-18T06:30:00Z")));
+
+        // Previous is inclusive, next is exclusive
+        let at = utc("2024-01-15T06:30:00Z");
+        assert_eq!(spec.previous_occurrence(at), Some(at));
+        assert_eq!(spec.next_occurrence(at), Some(utc("2024-01-18T06:30:00Z")));
+    }
+
+    #[test]
+    fn test_leap_day_schedule() {
+        let spec = CalendarSpec::parse("*-02-29 00:00:00 UTC").unwrap();
+        let next = spec.next_occurrence(utc("2024-03-01T00:00:00Z")).unwrap();
+        assert_eq!(next, utc("2028-02-29T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_describe_weekday_list() {
+        let spec = CalendarSpec::parse("Mon,Thu 06:30").unwrap();
+        assert_eq!(spec.describe(false).unwrap(), "Every Monday and Thursday at 6:30 AM");
+    }
+
+    #[test]
+    fn test_describe_monthly() {
+        let spec = CalendarSpec::parse("*-*-15 12:00").unwrap();
+        assert_eq!(spec.describe(false).unwrap(), "Monthly on the 15th at 12:00 PM");
+    }
+
+    #[test]
+    fn test_describe_daily() {
+        let spec = CalendarSpec::parse("*-*-* 02:00").unwrap();
+        assert_eq!(spec.describe(false).unwrap(), "Every day at 2:00 AM");
+    }
+
+    #[test]
+    fn test_describe_intervals() {
+        let every_15 = CalendarSpec::parse("*:0/15").unwrap();
+        assert_eq!(every_15.describe(false).unwrap(), "Every 15 minutes");
+
+        let business_hours = CalendarSpec::parse("Mon..Fri *-*-* 08..21:00:00").unwrap();
+        assert_eq!(
+            business_hours.describe(false).unwrap(),
+            "Every hour from 8 AM to 9 PM, Monday through Friday"
+        );
+
+        let yearly = CalendarSpec::parse("*-07-04 00:00").unwrap();
+        assert_eq!(yearly.describe(false).unwrap(), "Yearly on July 4 at 12:00 AM");
+    }
+
+    #[test]
+    fn test_describe_24h() {
+        let spec = CalendarSpec::parse("Mon..Fri *-*-* 08..21:00:00").unwrap();
+        assert_eq!(
+            spec.describe(true).unwrap(),
+            "Every hour from 08:00 to 21:00, Monday through Friday"
+        );
+
+        let spec = CalendarSpec::parse("Mon,Thu 06:30").unwrap();
+        assert_eq!(spec.describe(true).unwrap(), "Every Monday and Thursday at 06:30");
+    }
+
+    #[test]
+    fn test_ordinal() {
+        assert_eq!(ordinal(1), "1st");
+        assert_eq!(ordinal(2), "2nd");
+        assert_eq!(ordinal(3), "3rd");
+        assert_eq!(ordinal(11), "11th");
+        assert_eq!(ordinal(22), "22nd");
+    }
+
+    #[test]
+    fn test_drift_on_time() {
+        let calendar = vec!["*-*-* 02:00:00 UTC".to_string()];
+        let now = utc("2024-01-15T10:00:00Z");
+
+        // Fired at 02:00:03 today
+        let last = utc("2024-01-15T02:00:03Z").timestamp_micros().to_string();
+        assert_eq!(schedule_drift(&calendar, Some(&last), now), Some(-3));
+    }
+
+    #[test]
+    fn test_drift_missed_run() {
+        let calendar = vec!["*-*-* 02:00:00 UTC".to_string()];
+        let now = utc("2024-01-15T10:00:00Z");
+
+        // Last fired yesterday; today's 02:00 run never happened
+        let drift = schedule_drift(&calendar, Some("Sun 2024-01-14 02:00:00 UTC"), now).unwrap();
+        assert_eq!(drift, 86400);
+    }
+
+    #[test]
+    fn test_drift_unknown() {
+        let calendar = vec!["*-*-* 02:00:00 UTC".to_string()];
+        let now = utc("2024-01-15T10:00:00Z");
+
+        assert_eq!(schedule_drift(&calendar, None, now), None);
+        assert_eq!(schedule_drift(&calendar, Some("0"), now), None);
+        assert_eq!(schedule_drift(&["not a schedule".to_string()], Some("1705282800000000"), now), None);
+    }
+}
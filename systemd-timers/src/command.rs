@@ -1,6 +1,7 @@
 use crate::error::TimerResult;
 use async_trait::async_trait;
 use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
 
 /// Output from a command execution
 #[derive(Debug, Clone)]
@@ -15,6 +16,19 @@ pub struct CommandOutput {
 pub trait CommandExecutor: Send + Sync {
     /// Execute a command with arguments
     async fn execute(&self, program: &str, args: &[&str]) -> TimerResult<CommandOutput>;
+
+    /// Execute a command, giving up with `TimerError::Cancelled` once `token` fires
+    ///
+    /// The default ignores the token and runs the command to completion.
+    async fn execute_cancellable(
+        &self,
+        program: &str,
+        args: &[&str],
+        token: CancellationToken,
+    ) -> TimerResult<CommandOutput> {
+        let _ = token;
+        self.execute(program, args).await
+    }
 }
 
 /// Blanket implementation for Arc<E> where E: CommandExecutor
@@ -23,20 +37,63 @@ impl<E: CommandExecutor> CommandExecutor for Arc<E> {
     async fn execute(&self, program: &str, args: &[&str]) -> TimerResult<CommandOutput> {
         self.as_ref().execute(program, args).await
     }
+
+    async fn execute_cancellable(
+        &self,
+        program: &str,
+        args: &[&str],
+        token: CancellationToken,
+    ) -> TimerResult<CommandOutput> {
+        self.as_ref().execute_cancellable(program, args, token).await
+    }
 }
 
+/// Environment set on every spawned command: a fixed locale keeps systemctl's
+/// human-readable timestamps parseable and an explicit PATH keeps systemctl
+/// findable whatever environment the plugin was started with
+pub const COMMAND_ENV: &[(&str, &str)] = &[
+    ("LC_ALL", "C"),
+    ("PATH", "/usr/local/sbin:/usr/local/bin:/usr/sbin:/usr/bin:/sbin:/bin"),
+];
+
 /// Production command executor using std::process::Command
 pub struct SystemCommandExecutor;
 
 #[async_trait]
 impl CommandExecutor for SystemCommandExecutor {
     async fn execute(&self, program: &str, args: &[&str]) -> TimerResult<CommandOutput> {
+        self.execute_cancellable(program, args, CancellationToken::new()).await
+    }
+
+    /// Kill the child process if the token fires before it exits
+    async fn execute_cancellable(
+        &self,
+        program: &str,
+        args: &[&str],
+        token: CancellationToken,
+    ) -> TimerResult<CommandOutput> {
         use tokio::process::Command;
 
-        let output = Command::new(program)
+        // kill_on_drop: abandoning the output future below kills the child
+        let child = Command::new(program)
             .args(args)
-            .output()
-            .await?;
+            .envs(COMMAND_ENV.iter().copied())
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let output = tokio::select! {
+            output = child.wait_with_output() => output?,
+            _ = token.cancelled() => {
+                return Err(crate::error::TimerError::Cancelled(format!(
+                    "{} {}",
+                    program,
+                    args.join(" ")
+                )));
+            }
+        };
 
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -50,7 +107,9 @@ impl CommandExecutor for SystemCommandExecutor {
     }
 }
 
-#[cfg(test)]
+/// Mock executor; built for the crate's tests and, with the `mock` feature,
+/// for downstream tests and tools
+#[cfg(any(test, feature = "mock"))]
 pub mod mock {
     use super::*;
     use std::sync::{Arc, Mutex};
@@ -59,15 +118,22 @@ pub mod mock {
     /// Mock command executor for tests
     pub struct MockCommandExecutor {
         responses: Arc<Mutex<HashMap<String, CommandOutput>>>,
+        calls: Arc<Mutex<Vec<String>>>,
     }
 
     impl MockCommandExecutor {
         pub fn new() -> Self {
             Self {
                 responses: Arc::new(Mutex::new(HashMap::new())),
+                calls: Arc::new(Mutex::new(Vec::new())),
             }
         }
 
+        /// Commands executed so far, in order, as "program arg1 arg2 ..."
+        pub fn calls(&self) -> Vec<String> {
+            self.calls.lock().unwrap().clone()
+        }
+
         /// Set expected response for a command
         pub fn expect(&self, command_key: &str, output: CommandOutput) {
             let mut responses = self.responses.lock().unwrap();
@@ -79,10 +145,17 @@ pub mod mock {
         }
     }
 
+    impl Default for MockCommandExecutor {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
     #[async_trait]
     impl CommandExecutor for MockCommandExecutor {
         async fn execute(&self, program: &str, args: &[&str]) -> TimerResult<CommandOutput> {
             let key = Self::make_key(program, args);
+            self.calls.lock().unwrap().push(key.clone());
             let responses = self.responses.lock().unwrap();
 
             responses.get(&key)
@@ -95,3 +168,34 @@ pub mod mock {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_system_executor_sets_deterministic_env() {
+        let output = SystemCommandExecutor.execute("env", &[]).await.unwrap();
+        assert_eq!(output.exit_code, 0);
+
+        let vars: Vec<&str> = output.stdout.lines().collect();
+        assert!(vars.contains(&"LC_ALL=C"));
+        assert!(vars.iter().any(|v| v.starts_with("PATH=") && v.contains("/usr/bin")));
+    }
+
+    #[tokio::test]
+    async fn test_system_executor_cancellation_returns_promptly() {
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            canceller.cancel();
+        });
+
+        let started = std::time::Instant::now();
+        let result = SystemCommandExecutor.execute_cancellable("sleep", &["5"], token).await;
+
+        assert!(matches!(result, Err(crate::error::TimerError::Cancelled(_))));
+        assert!(started.elapsed() < std::time::Duration::from_secs(2));
+    }
+}
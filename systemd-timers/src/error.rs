@@ -17,10 +17,25 @@ pub enum TimerError {
     /// Permission denied
     PermissionDenied(String),
 
+    /// Unit is masked and must be unmasked before it can be started
+    Masked(String),
+
+    /// A file or unit that would be created already exists
+    AlreadyExists(String),
+
+    /// polkit wants interactive authentication the plugin can't provide
+    AuthenticationRequired(String),
+
     /// I/O error
     IoError(String),
 
-    /// JSON serialization/deserialization error
+    /// Command cancelled before it finished
+    Cancelled(String),
+
+    /// Request body the client sent is oversized or not valid JSON
+    BadRequest(String),
+
+    /// JSON serialization/deserialization error on the plugin's own data
     JsonError(String),
 }
 
@@ -36,12 +51,30 @@ impl fmt::Display for TimerError {
             }
             TimerError::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
             TimerError::PermissionDenied(msg) => write!(f, "Permission denied: {}", msg),
+            TimerError::Masked(unit) => write!(f, "Unit {} is masked; unmask it first", unit),
+            TimerError::AlreadyExists(what) => write!(f, "Already exists: {}", what),
+            TimerError::AuthenticationRequired(msg) => write!(f, "Authentication required: {}", msg),
             TimerError::IoError(msg) => write!(f, "I/O error: {}", msg),
+            TimerError::Cancelled(msg) => write!(f, "Cancelled: {}", msg),
+            TimerError::BadRequest(msg) => write!(f, "{}", msg),
             TimerError::JsonError(msg) => write!(f, "JSON error: {}", msg),
         }
     }
 }
 
+impl TimerError {
+    /// HTTP status for an error that reaches the client unhandled
+    ///
+    /// Only problems with the request itself are the client's fault; failing
+    /// to serialize a response or read stored data is a bug and stays a 500.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            TimerError::BadRequest(_) => 400,
+            _ => 500,
+        }
+    }
+}
+
 impl std::error::Error for TimerError {}
 
 impl From<std::io::Error> for TimerError {
@@ -1,25 +1,56 @@
 //! HTTP handlers module - Phase 9
 //! Implements all REST API endpoints for the systemd-timers plugin
 
-use crate::command::CommandExecutor;
+use crate::access::UnitAccessPolicy;
+use crate::audit::{append_audit, load_audit_log, AuditEntry};
+use crate::calendar::schedule_drift;
+use crate::clock::Clock;
+use crate::command::{CommandExecutor, SystemCommandExecutor};
 use crate::error::{TimerError, TimerResult};
+use crate::journal::{ExecutionStatus, JournalClient, JournalSource};
 use crate::log_reader::LogReader;
-use crate::systemctl::SystemctlClient;
+use crate::metrics::{render_prometheus, TimerMetrics, PROMETHEUS_CONTENT_TYPE};
+use crate::schedule::Schedule;
+use crate::settings::PluginSettings;
+use crate::stats::PluginStats;
+use crate::rate_limit::RateLimiter;
+use crate::reconcile::resume_expired_snoozes;
+use crate::refresh_hint::{RefreshAdvisor, REFRESH_AFTER_HEADER};
+use crate::systemctl::{SystemctlClient, TimerInfo};
+use crate::timestamp::{parse_systemd_timestamp, relative_systemd_timestamp_at};
+use crate::unit_writer::{TimerTemplate, UnitWriter};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Instant;
 use toru_plugin_api::{HttpResponse, PluginKvStore};
 
 /// Response format for GET /timers
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimerStatusResponse {
     pub name: String,
+    pub description: String,
     pub service: String,
+    /// Deprecated: `enabled_at_boot && active`; kept for one release
     pub enabled: bool,
+    pub enabled_at_boot: bool,
+    pub active: bool,
     pub schedule: String,
     pub schedule_human: String,
     pub next_run: Option<String>,
     pub last_run: Option<String>,
+    /// `last_run` relative to now, e.g. "3 minutes ago"
+    pub last_run_relative: Option<String>,
     pub last_result: Option<String>, // "success", "failed", "running"
+    /// Latest scheduled run minus actual last trigger; large positive = missed/late
+    pub drift_secs: Option<i64>,
+    pub fragment_path: Option<String>,
+    pub drop_in_paths: Vec<String>,
+    /// True when the last run was skipped because a `Condition*=` wasn't met
+    pub last_condition_failed: Option<bool>,
+    /// When a snoozed timer will be enabled again (RFC 3339)
+    pub snoozed_until: Option<String>,
 }
 
 /// Response format for available timers
@@ -27,6 +58,12 @@ pub struct TimerStatusResponse {
 pub struct AvailableTimerResponse {
     pub name: String,
     pub description: String,
+    /// Time until the next run as `list-timers` prints it ("45min left")
+    #[serde(default)]
+    pub left: Option<String>,
+    /// Time since the last run as `list-timers` prints it ("2h ago")
+    #[serde(default)]
+    pub passed: Option<String>,
 }
 
 /// Creates a JSON response with given status and data
@@ -43,6 +80,73 @@ pub fn json_response<T: Serialize>(status: u16, data: T) -> TimerResult<HttpResp
     })
 }
 
+/// Response header carrying how long the plugin took to handle the request
+pub const PROCESSING_TIME_HEADER: &str = "X-Processing-Time-Ms";
+
+/// Set `PROCESSING_TIME_HEADER` to `elapsed` in milliseconds (one decimal)
+///
+/// Measured around the whole dispatch, systemctl and journalctl calls
+/// included, so a slow dashboard can be told apart from a slow network.
+pub fn set_processing_time(response: &mut HttpResponse, elapsed: std::time::Duration) {
+    let millis = elapsed.as_secs_f64() * 1000.0;
+    response.headers.insert(PROCESSING_TIME_HEADER.to_string(), format!("{:.1}", millis));
+}
+
+/// Methods advertised to CORS preflight requests
+pub const CORS_ALLOWED_METHODS: &str = "GET, HEAD, POST, PUT, DELETE, OPTIONS";
+
+/// Request headers advertised to CORS preflight requests
+pub const CORS_ALLOWED_HEADERS: &str = "Content-Type";
+
+/// Read the configured CORS origin; None when unset, empty or unreadable
+pub async fn cors_origin(kv_store: &dyn PluginKvStore) -> Option<String> {
+    PluginSettings::load(kv_store)
+        .await
+        .ok()
+        .and_then(|settings| settings.cors_origin)
+        .filter(|origin| !origin.trim().is_empty())
+}
+
+/// Add the CORS headers allowing `origin` to a response
+pub fn set_cors_headers(response: &mut HttpResponse, origin: &str) {
+    response.headers.insert("Access-Control-Allow-Origin".to_string(), origin.to_string());
+    response.headers.insert("Access-Control-Allow-Methods".to_string(), CORS_ALLOWED_METHODS.to_string());
+    response.headers.insert("Access-Control-Allow-Headers".to_string(), CORS_ALLOWED_HEADERS.to_string());
+    response.headers.insert("Vary".to_string(), "Origin".to_string());
+}
+
+/// Answer an `OPTIONS` preflight request: 204 with the CORS headers
+pub fn preflight_response(origin: &str) -> HttpResponse {
+    let mut response = HttpResponse {
+        status: 204,
+        headers: HashMap::new(),
+        body: None,
+    };
+    set_cors_headers(&mut response, origin);
+    response
+}
+
+/// Largest request body accepted, in bytes
+pub const MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// Parse a JSON request body
+///
+/// Oversized bodies are refused before parsing. Failures are
+/// `TimerError::BadRequest` rather than `JsonError`, so a bad payload becomes
+/// a 400 instead of being mistaken for a serialization bug.
+pub fn parse_json_body<T: DeserializeOwned>(body: &str) -> TimerResult<T> {
+    if body.len() > MAX_BODY_BYTES {
+        return Err(TimerError::BadRequest(format!(
+            "Request body too large: {} bytes (max {})",
+            body.len(),
+            MAX_BODY_BYTES
+        )));
+    }
+
+    serde_json::from_str(body)
+        .map_err(|e| TimerError::BadRequest(format!("Invalid request body: {}", e)))
+}
+
 /// Creates an error response
 pub fn error_response(status: u16, error: &str) -> TimerResult<HttpResponse> {
     let error_obj = serde_json::json!({
@@ -63,6 +167,110 @@ pub fn success_response(message: &str) -> TimerResult<HttpResponse> {
     json_response(200, success_obj)
 }
 
+/// Creates a success response carrying the affected resource under `data`
+pub fn success_response_with<T: Serialize>(message: &str, data: T) -> TimerResult<HttpResponse> {
+    let success_obj = serde_json::json!({
+        "success": true,
+        "message": message,
+        "data": data
+    });
+
+    json_response(200, success_obj)
+}
+
+/// Returns true for HTTP methods that may change system state
+pub fn is_mutating_method(method: &str) -> bool {
+    matches!(method, "POST" | "PUT" | "PATCH" | "DELETE")
+}
+
+/// Blocks mutating requests while the plugin is in read-only mode
+/// Settings stay writable so read-only mode can be switched off again
+pub async fn check_read_only(
+    kv_store: &dyn PluginKvStore,
+    method: &str,
+    path: &str,
+) -> TimerResult<Option<HttpResponse>> {
+    // Settings stay editable (and reloadable) so read-only mode can be turned off
+    if !is_mutating_method(method) || path == "/timers/settings" || path == "/settings/reload" {
+        return Ok(None);
+    }
+
+    if !PluginSettings::load(kv_store).await?.read_only {
+        return Ok(None);
+    }
+
+    let error_obj = serde_json::json!({
+        "success": false,
+        "code": "read_only",
+        "error": "Plugin is in read-only mode"
+    });
+
+    json_response(403, error_obj).map(Some)
+}
+
+/// Rejects a repeat of the same unit action within the configured cooldown with a 429
+///
+/// An action let through is recorded at `now`; pass the handler's response
+/// to `release_rejected_action` so a rejected request doesn't start the cooldown.
+pub async fn check_rate_limit(
+    limiter: &RateLimiter,
+    kv_store: &dyn PluginKvStore,
+    timer_name: &str,
+    action: &str,
+    now: Instant,
+) -> TimerResult<Option<HttpResponse>> {
+    let timer_name = &normalize_timer_name(timer_name);
+    let cooldown = PluginSettings::load(kv_store).await?.action_cooldown();
+
+    let wait = match limiter.check_at(timer_name, action, cooldown, now) {
+        Ok(()) => return Ok(None),
+        Err(wait) => wait,
+    };
+
+    let error_obj = serde_json::json!({
+        "success": false,
+        "error": format!("Timer {} was just asked to {}; try again shortly", timer_name, action),
+        "retry_after_ms": wait.as_millis() as u64
+    });
+
+    let mut response = json_response(429, error_obj)?;
+    let retry_after_secs = (wait.as_millis() as u64).div_ceil(1000).max(1);
+    response.headers.insert("Retry-After".to_string(), retry_after_secs.to_string());
+    Ok(Some(response))
+}
+
+/// Forget an action let through by `check_rate_limit` at `now` if its
+/// handler rejected it (4xx) without running it
+///
+/// A bad name, a denied unit or a missing timer then doesn't hold back the
+/// corrected retry.
+pub fn release_rejected_action(
+    limiter: &RateLimiter,
+    timer_name: &str,
+    action: &str,
+    now: Instant,
+    response: &HttpResponse,
+) {
+    if (400..500).contains(&response.status) {
+        limiter.release(&normalize_timer_name(timer_name), action, now);
+    }
+}
+
+/// Splits `/timers/:name/:action` for the unit actions (run, test, enable, disable, snooze)
+pub fn parse_action_path(path: &str) -> Option<(&str, &str)> {
+    let rest = path.strip_prefix("/timers/")?;
+    let (name, action) = rest.rsplit_once('/')?;
+
+    if name.is_empty() || name.contains('/') {
+        return None;
+    }
+
+    match action {
+        "run" | "test" | "enable" | "disable" | "snooze" => Some((name, action)),
+        _ => None,
+    }
+}
+
 /// Parses query parameters from a path
 pub fn parse_query_params(path: &str) -> HashMap<String, String> {
     let mut params = HashMap::new();
@@ -86,20 +294,82 @@ pub fn path_without_query(path: &str) -> &str {
     path.split('?').next().unwrap_or(path)
 }
 
+/// Decodes percent-encoded characters in a path segment (e.g. `%40` -> `@`)
+/// Malformed escapes are kept as-is; the result still goes through name validation
+pub fn decode_path_segment(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let high = (bytes[i + 1] as char).to_digit(16);
+            let low = (bytes[i + 2] as char).to_digit(16);
+            if let (Some(high), Some(low)) = (high, low) {
+                decoded.push((high * 16 + low) as u8);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Appends `.timer` to a bare unit name, so `backup` addresses `backup.timer`
+/// Names with a `.timer`/`.service` suffix, and names that would still be
+/// invalid, are returned unchanged for validation to reject.
+pub fn normalize_timer_name(name: &str) -> String {
+    if name.is_empty() || name.ends_with(".timer") || name.ends_with(".service") {
+        return name.to_string();
+    }
+
+    let candidate = format!("{}.timer", name);
+    match SystemctlClient::<SystemCommandExecutor>::validate_timer_name(&candidate) {
+        Ok(()) => candidate,
+        Err(_) => name.to_string(),
+    }
+}
+
+/// Parses `/timers/:name/history` and `/timers/:name/history/:id` paths
+/// Returns the timer name and the invocation id (if present), tolerating trailing slashes
+pub fn parse_history_path(path: &str) -> Option<(&str, Option<&str>)> {
+    let rest = path.strip_prefix("/timers/")?.trim_end_matches('/');
+    let segments: Vec<&str> = rest.split('/').collect();
+
+    if segments.iter().any(|s| s.is_empty()) {
+        return None;
+    }
+
+    match segments.as_slice() {
+        [name, "history"] => Some((name, None)),
+        [name, "history", id] => Some((name, Some(id))),
+        _ => None,
+    }
+}
+
 /// Handle GET /timers - return watched timers with status
 pub async fn handle_get_timers<E: CommandExecutor + Clone>(
     executor: E,
     kv_store: &dyn PluginKvStore,
+    advisor: &RefreshAdvisor,
+    clock: &dyn Clock,
 ) -> TimerResult<HttpResponse> {
-    // Get watched timers from KV storage
-    let watched_timers = get_watched_timers(kv_store).await?;
+    let now = clock.now();
 
-    if watched_timers.is_empty() {
-        return json_response(200, Vec::<TimerStatusResponse>::new());
-    }
+    // Get watched timers from KV storage
+    let settings = PluginSettings::load(kv_store).await?;
+    let watched_timers = settings.watched_timers;
+    let snoozed = resume_expired_snoozes(executor.clone(), kv_store, settings.snoozed_until, clock)
+        .await
+        .pending;
+    let snoozed_until = |name: &str| snoozed.get(name).map(|until| until.to_rfc3339());
 
-    let client = SystemctlClient::new(executor.clone());
-    let log_reader = LogReader::new(executor);
+    let use_24h = settings.use_24h;
+    let client = SystemctlClient::new(executor.clone()).with_24h_clock(use_24h);
+    let log_reader = LogReader::new(executor).with_clock(clock);
     let mut results = Vec::new();
 
     for timer_name in watched_timers {
@@ -113,15 +383,26 @@ pub async fn handle_get_timers<E: CommandExecutor + Clone>(
                     .and_then(|history| history.first().cloned())
                     .map(|h| format!("{:?}", h.status).to_lowercase());
 
+                let drift_secs = schedule_drift(&info.calendar, info.last_trigger.as_deref(), now);
+
                 results.push(TimerStatusResponse {
                     name: info.name.clone(),
+                    description: info.description,
                     service: info.service,
                     enabled: info.enabled,
+                    enabled_at_boot: info.enabled_at_boot,
+                    active: info.active,
                     schedule: info.schedule.clone(),
-                    schedule_human: info.schedule, // Already humanized in systemctl.rs
+                    schedule_human: verbose_schedule(&info.calendar, use_24h).unwrap_or(info.schedule),
                     next_run: info.next_run,
+                    last_run_relative: info.last_trigger.as_deref().and_then(|value| relative_systemd_timestamp_at(value, now)),
                     last_run: info.last_trigger,
                     last_result,
+                    drift_secs,
+                    fragment_path: info.fragment_path,
+                    drop_in_paths: info.drop_in_paths,
+                    last_condition_failed: info.last_condition_failed,
+                    snoozed_until: snoozed_until(&info.name),
                 });
             }
             Err(e) => {
@@ -129,35 +410,171 @@ pub async fn handle_get_timers<E: CommandExecutor + Clone>(
                 // Include timers that failed to query but mark them as unavailable
                 results.push(TimerStatusResponse {
                     name: timer_name.clone(),
+                    description: String::new(),
                     service: timer_name.replace(".timer", ".service"),
                     enabled: false,
+                    enabled_at_boot: false,
+                    active: false,
                     schedule: "unknown".to_string(),
                     schedule_human: "Unable to read schedule".to_string(),
                     next_run: None,
                     last_run: None,
+                    last_run_relative: None,
                     last_result: None,
+                    drift_secs: None,
+                    fragment_path: None,
+                    drop_in_paths: Vec::new(),
+                    last_condition_failed: None,
+                    snoozed_until: snoozed_until(&timer_name),
                 });
             }
         }
     }
 
-    json_response(200, results)
+    // Hint how soon the dashboard should poll again, based on how much is changing
+    let refresh_after = advisor.observe(results.iter().map(|timer| {
+        let fingerprint = format!(
+            "{}/{}/{:?}/{:?}",
+            timer.enabled, timer.active, timer.last_run, timer.last_result
+        );
+        (timer.name.clone(), fingerprint)
+    }));
+
+    let mut response = json_response(200, results)?;
+    response.headers.insert(REFRESH_AFTER_HEADER.to_string(), refresh_after.as_secs().to_string());
+    Ok(response)
+}
+
+/// Response format for GET /healthz
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthResponse {
+    /// "ok" when systemd answered, "degraded" when it didn't
+    pub status: String,
+    pub systemd_available: bool,
+    /// What `systemctl is-system-running` reported, if anything
+    pub system_state: Option<String>,
+}
+
+/// Handle GET /healthz - whether the plugin process is up and can reach systemd
+///
+/// Answering at all shows the plugin is alive. If systemd can't be queried
+/// within the probe timeout, or reports itself `offline`/`unknown`, respond
+/// `degraded` with a 503 so supervisors can act on the status code.
+pub async fn handle_get_health<E: CommandExecutor>(executor: E) -> TimerResult<HttpResponse> {
+    let system_state = match SystemctlClient::new(executor).system_state().await {
+        Ok(state) => Some(state),
+        Err(e) => {
+            eprintln!("Health probe could not reach systemd: {}", e);
+            None
+        }
+    };
+    let systemd_available = system_state
+        .as_deref()
+        .is_some_and(|state| !matches!(state, "offline" | "unknown"));
+
+    let health = HealthResponse {
+        status: if systemd_available { "ok" } else { "degraded" }.to_string(),
+        systemd_available,
+        system_state,
+    };
+
+    json_response(if systemd_available { 200 } else { 503 }, health)
+}
+
+/// Handle GET /debug/stats - plugin-internal request and command counters
+pub fn handle_get_stats(stats: &PluginStats) -> TimerResult<HttpResponse> {
+    json_response(200, stats.snapshot())
+}
+
+/// Handle GET /metrics - Prometheus metrics for watched timers
+///
+/// Timers are queried concurrently. A timer that can't be queried is left out
+/// of the scrape rather than failing it.
+pub async fn handle_get_metrics<E: CommandExecutor + Clone + 'static>(
+    executor: E,
+    kv_store: &dyn PluginKvStore,
+    clock: &dyn Clock,
+) -> TimerResult<HttpResponse> {
+    let watched_timers = get_watched_timers(kv_store).await?;
+    let now = clock.now();
+
+    let mut tasks = tokio::task::JoinSet::new();
+    for (index, timer_name) in watched_timers.into_iter().enumerate() {
+        let executor = executor.clone();
+        tasks.spawn(async move { (index, collect_timer_metrics(executor, timer_name, now).await) });
+    }
+
+    let mut gathered = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok((index, Some(metrics))) => gathered.push((index, metrics)),
+            Ok((_, None)) => {}
+            Err(e) => eprintln!("Metrics task failed: {}", e),
+        }
+    }
+
+    // Keep the watched-list order so scrapes are stable
+    gathered.sort_by_key(|(index, _)| *index);
+    let metrics: Vec<TimerMetrics> = gathered.into_iter().map(|(_, m)| m).collect();
+
+    let mut headers = HashMap::new();
+    headers.insert("Content-Type".to_string(), PROMETHEUS_CONTENT_TYPE.to_string());
+
+    Ok(HttpResponse {
+        status: 200,
+        headers,
+        body: Some(render_prometheus(&metrics)),
+    })
 }
 
-/// Handle GET /timers/available - return all systemd timers
+/// Handle GET /timers/available?state=active|inactive|overdue - all systemd timers
+///
+/// Without `state` this is a single `list-timers` call. With it, each timer is
+/// looked up individually to get its enabled flag and calendar, so only the
+/// filtered view pays for the extra queries.
 pub async fn handle_get_available_timers<E: CommandExecutor>(
     executor: E,
+    query_params: &HashMap<String, String>,
+    clock: &dyn Clock,
 ) -> TimerResult<HttpResponse> {
+    let state = match query_params.get("state").map(String::as_str) {
+        None | Some("") => None,
+        Some(state @ ("active" | "inactive" | "overdue")) => Some(state),
+        Some(other) => {
+            return error_response(400, &format!(
+                "Invalid state filter: {} (expected active, inactive or overdue)",
+                other
+            ));
+        }
+    };
+
     let client = SystemctlClient::new(executor);
     let timers = client.list_timers().await?;
 
-    let available: Vec<AvailableTimerResponse> = timers
-        .into_iter()
-        .map(|t| AvailableTimerResponse {
-            name: t.name,
-            description: format!("Activates {}", t.service),
-        })
-        .collect();
+    let mut available = Vec::new();
+    let now = clock.now();
+
+    for timer in timers {
+        if let Some(state) = state {
+            let info = match client.get_timer_info(&timer.name).await {
+                Ok(info) => info,
+                Err(e) => {
+                    eprintln!("Failed to get info for timer {}: {}", timer.name, e);
+                    continue;
+                }
+            };
+            if !matches_state_filter(&info, state, now) {
+                continue;
+            }
+        }
+
+        available.push(AvailableTimerResponse {
+            name: timer.name,
+            description: format!("Activates {}", timer.service),
+            left: timer.left,
+            passed: timer.passed,
+        });
+    }
 
     json_response(200, available)
 }
@@ -165,25 +582,43 @@ pub async fn handle_get_available_timers<E: CommandExecutor>(
 /// Handle POST /timers/:name/run - run timer now (full production)
 pub async fn handle_run_timer<E: CommandExecutor>(
     executor: E,
+    kv_store: &dyn PluginKvStore,
     timer_name: &str,
+    clock: &dyn Clock,
 ) -> TimerResult<HttpResponse> {
+    let timer_name = &normalize_timer_name(timer_name);
+    if let Some(response) = check_unit_access(kv_store, timer_name).await? {
+        return Ok(response);
+    }
+
     let client = SystemctlClient::new(executor);
+    if let Some(response) = check_unit_exists(&client, timer_name).await? {
+        return Ok(response);
+    }
 
-    match client.run_timer(timer_name, false).await {
-        Ok(_) => {
-            let response = serde_json::json!({
-                "success": true,
-                "message": format!("Timer {} started", timer_name),
+    let result = client.run_timer(timer_name, false).await;
+    record_audit(kv_store, timer_name, "run", &result, clock).await;
+
+    match result {
+        Ok(_) => success_response_with(
+            &format!("Timer {} started", timer_name),
+            serde_json::json!({
+                "timer": timer_name,
                 "mode": "production"
-            });
-            json_response(200, response)
-        }
+            }),
+        ),
         Err(TimerError::NotFound(_)) => {
             error_response(404, "Timer not found")
         }
         Err(TimerError::PermissionDenied(_)) => {
             error_response(403, "Permission denied")
         }
+        Err(e @ TimerError::Masked(_)) => {
+            error_response(409, &e.to_string())
+        }
+        Err(e @ TimerError::AuthenticationRequired(_)) => {
+            error_response(401, &e.to_string())
+        }
         Err(e) => {
             error_response(500, &format!("Failed to start timer: {}", e))
         }
@@ -193,25 +628,43 @@ pub async fn handle_run_timer<E: CommandExecutor>(
 /// Handle POST /timers/:name/test - run timer in test mode
 pub async fn handle_test_timer<E: CommandExecutor>(
     executor: E,
+    kv_store: &dyn PluginKvStore,
     timer_name: &str,
+    clock: &dyn Clock,
 ) -> TimerResult<HttpResponse> {
+    let timer_name = &normalize_timer_name(timer_name);
+    if let Some(response) = check_unit_access(kv_store, timer_name).await? {
+        return Ok(response);
+    }
+
     let client = SystemctlClient::new(executor);
+    if let Some(response) = check_unit_exists(&client, timer_name).await? {
+        return Ok(response);
+    }
+
+    let result = client.run_timer(timer_name, true).await;
+    record_audit(kv_store, timer_name, "test", &result, clock).await;
 
-    match client.run_timer(timer_name, true).await {
-        Ok(_) => {
-            let response = serde_json::json!({
-                "success": true,
-                "message": format!("Timer {} started in test mode", timer_name),
+    match result {
+        Ok(_) => success_response_with(
+            &format!("Timer {} started in test mode", timer_name),
+            serde_json::json!({
+                "timer": timer_name,
                 "mode": "test"
-            });
-            json_response(200, response)
-        }
+            }),
+        ),
         Err(TimerError::NotFound(_)) => {
             error_response(404, "Timer not found")
         }
         Err(TimerError::PermissionDenied(_)) => {
             error_response(403, "Permission denied")
         }
+        Err(e @ TimerError::Masked(_)) => {
+            error_response(409, &e.to_string())
+        }
+        Err(e @ TimerError::AuthenticationRequired(_)) => {
+            error_response(401, &e.to_string())
+        }
         Err(e) => {
             error_response(500, &format!("Failed to start timer in test mode: {}", e))
         }
@@ -221,18 +674,49 @@ pub async fn handle_test_timer<E: CommandExecutor>(
 /// Handle POST /timers/:name/enable
 pub async fn handle_enable_timer<E: CommandExecutor>(
     executor: E,
+    kv_store: &dyn PluginKvStore,
     timer_name: &str,
+    clock: &dyn Clock,
 ) -> TimerResult<HttpResponse> {
+    let timer_name = &normalize_timer_name(timer_name);
+    if let Some(response) = check_unit_access(kv_store, timer_name).await? {
+        return Ok(response);
+    }
+
     let client = SystemctlClient::new(executor);
+    if let Some(response) = check_unit_exists(&client, timer_name).await? {
+        return Ok(response);
+    }
 
-    match client.enable_timer(timer_name).await {
-        Ok(_) => success_response(&format!("Timer {} enabled", timer_name)),
+    let result = client.enable_timer(timer_name).await;
+    let error = sequence_error(&result, |s| &s.error);
+    if error.is_none() {
+        clear_snooze(kv_store, timer_name).await?;
+    }
+    record_audit_outcome(kv_store, timer_name, "enable", error, clock).await;
+
+    match result {
+        Ok(steps) => {
+            let message = match (&steps.error, steps.rolled_back) {
+                (None, _) if !steps.changed => format!("Timer {} already enabled", timer_name),
+                (None, _) => format!("Timer {} enabled", timer_name),
+                (Some(_), true) => format!("Timer {} failed to start; enable was rolled back", timer_name),
+                (Some(_), false) => format!("Timer {} enabled but failed to start", timer_name),
+            };
+            step_response(&steps, steps.error.is_none(), &message)
+        }
         Err(TimerError::NotFound(_)) => {
             error_response(404, "Timer not found")
         }
         Err(TimerError::PermissionDenied(_)) => {
             error_response(403, "Permission denied")
         }
+        Err(e @ TimerError::Masked(_)) => {
+            error_response(409, &e.to_string())
+        }
+        Err(e @ TimerError::AuthenticationRequired(_)) => {
+            error_response(401, &e.to_string())
+        }
         Err(e) => {
             error_response(500, &format!("Failed to enable timer: {}", e))
         }
@@ -242,164 +726,2405 @@ pub async fn handle_enable_timer<E: CommandExecutor>(
 /// Handle POST /timers/:name/disable
 pub async fn handle_disable_timer<E: CommandExecutor>(
     executor: E,
+    kv_store: &dyn PluginKvStore,
     timer_name: &str,
+    clock: &dyn Clock,
 ) -> TimerResult<HttpResponse> {
+    let timer_name = &normalize_timer_name(timer_name);
+    if let Some(response) = check_unit_access(kv_store, timer_name).await? {
+        return Ok(response);
+    }
+
     let client = SystemctlClient::new(executor);
+    if let Some(response) = check_unit_exists(&client, timer_name).await? {
+        return Ok(response);
+    }
+
+    let result = client.disable_timer(timer_name).await;
+    let error = sequence_error(&result, |s| &s.error);
+    if error.is_none() {
+        clear_snooze(kv_store, timer_name).await?;
+    }
+    record_audit_outcome(kv_store, timer_name, "disable", error, clock).await;
 
-    match client.disable_timer(timer_name).await {
-        Ok(_) => success_response(&format!("Timer {} disabled", timer_name)),
+    match result {
+        Ok(steps) => {
+            let message = match (&steps.error, steps.rolled_back) {
+                (None, _) if !steps.changed => format!("Timer {} already disabled", timer_name),
+                (None, _) => format!("Timer {} disabled", timer_name),
+                (Some(_), true) => format!("Timer {} failed to disable; timer was started again", timer_name),
+                (Some(_), false) => format!("Timer {} stopped but failed to disable", timer_name),
+            };
+            step_response(&steps, steps.error.is_none(), &message)
+        }
         Err(TimerError::NotFound(_)) => {
             error_response(404, "Timer not found")
         }
         Err(TimerError::PermissionDenied(_)) => {
             error_response(403, "Permission denied")
         }
+        Err(e @ TimerError::Masked(_)) => {
+            error_response(409, &e.to_string())
+        }
+        Err(e @ TimerError::AuthenticationRequired(_)) => {
+            error_response(401, &e.to_string())
+        }
         Err(e) => {
             error_response(500, &format!("Failed to disable timer: {}", e))
         }
     }
 }
 
-/// Handle GET /timers/:name/history - get execution history
-pub async fn handle_get_history<E: CommandExecutor>(
+/// Longest window accepted by POST /timers/:name/snooze, in days
+pub const MAX_SNOOZE_DAYS: i64 = 30;
+
+/// Handle POST /timers/:name/snooze - disable a timer until `{until}` (RFC 3339)
+///
+/// The timer is stopped and disabled like `disable`, and the resume time is
+/// stored in the settings. It is enabled again by the first GET /timers poll
+/// after that time. Enabling or disabling it by hand cancels the snooze.
+pub async fn handle_snooze_timer<E: CommandExecutor>(
     executor: E,
+    kv_store: &dyn PluginKvStore,
     timer_name: &str,
-    query_params: &HashMap<String, String>,
+    body: &str,
+    clock: &dyn Clock,
 ) -> TimerResult<HttpResponse> {
-    // Convert timer name to service name
-    let service_name = timer_name.replace(".timer", ".service");
+    let timer_name = &normalize_timer_name(timer_name);
+    #[derive(Deserialize)]
+    struct SnoozeRequest {
+        until: String,
+    }
 
-    // Parse limit parameter (default to 20)
-    let limit = query_params
-        .get("limit")
-        .and_then(|s| s.parse::<usize>().ok())
-        .unwrap_or(20);
+    let request: SnoozeRequest = match parse_json_body(body) {
+        Ok(request) => request,
+        Err(e) => return error_response(e.status_code(), &e.to_string()),
+    };
 
-    let log_reader = LogReader::new(executor);
+    let until = match DateTime::parse_from_rfc3339(request.until.trim()) {
+        Ok(until) => until.with_timezone(&Utc),
+        Err(_) => return error_response(400, "until must be an RFC 3339 timestamp"),
+    };
+    let now = clock.now();
+    if until <= now {
+        return error_response(400, "until must be in the future");
+    }
+    if until > now + chrono::Duration::days(MAX_SNOOZE_DAYS) {
+        return error_response(400, &format!("A timer can be snoozed for at most {} days", MAX_SNOOZE_DAYS));
+    }
 
-    match log_reader.get_execution_history(&service_name, limit).await {
-        Ok(history) => json_response(200, history),
+    if let Some(response) = check_unit_access(kv_store, timer_name).await? {
+        return Ok(response);
+    }
+
+    let client = SystemctlClient::new(executor);
+    if let Some(response) = check_unit_exists(&client, timer_name).await? {
+        return Ok(response);
+    }
+
+    let result = client.disable_timer(timer_name).await;
+    let error = sequence_error(&result, |s| &s.error);
+    if error.is_none() {
+        PluginSettings::update(kv_store, |settings| {
+            settings.snoozed_until.insert(timer_name.clone(), until);
+        })
+        .await?;
+    }
+    record_audit_outcome(kv_store, timer_name, "snooze", error, clock).await;
+
+    match result {
+        Ok(steps) if steps.error.is_none() => {
+            let mut body = serde_json::to_value(&steps)?;
+            body["snoozed_until"] = serde_json::json!(until.to_rfc3339());
+            step_response(&body, true, &format!("Timer {} snoozed until {}", timer_name, until.to_rfc3339()))
+        }
+        Ok(steps) => step_response(&steps, false, &format!("Timer {} could not be snoozed", timer_name)),
         Err(TimerError::NotFound(_)) => {
             error_response(404, "Timer not found")
         }
+        Err(TimerError::PermissionDenied(_)) => {
+            error_response(403, "Permission denied")
+        }
+        Err(e @ TimerError::Masked(_)) => {
+            error_response(409, &e.to_string())
+        }
+        Err(e @ TimerError::AuthenticationRequired(_)) => {
+            error_response(401, &e.to_string())
+        }
         Err(e) => {
-            error_response(500, &format!("Failed to get history: {}", e))
+            error_response(500, &format!("Failed to snooze timer: {}", e))
         }
     }
 }
 
-/// Handle GET /timers/:name/history/:timestamp - get execution details
-pub async fn handle_get_history_details<E: CommandExecutor + Clone>(
+/// Forget a pending snooze after the timer was enabled or disabled by hand
+async fn clear_snooze(kv_store: &dyn PluginKvStore, timer_name: &str) -> TimerResult<()> {
+    if !PluginSettings::load(kv_store).await?.snoozed_until.contains_key(timer_name) {
+        return Ok(());
+    }
+
+    PluginSettings::update(kv_store, |settings| {
+        settings.snoozed_until.remove(timer_name);
+    })
+    .await
+    .map(|_| ())
+}
+
+/// Handle POST /timers/create - write a `.service` + `.timer` pair for a scheduled job
+///
+/// The units go into the configured `unit_dir` and are never overwritten.
+/// After a daemon-reload the timer is added to the watched list and, with
+/// `enable: true`, enabled and started. If the reload fails the written
+/// files are removed again.
+pub async fn handle_create_timer<E: CommandExecutor>(
     executor: E,
-    timer_name: &str,
-    timestamp: &str,
+    kv_store: &dyn PluginKvStore,
+    body: &str,
+    clock: &dyn Clock,
 ) -> TimerResult<HttpResponse> {
-    // Convert timer name to service name
-    let service_name = timer_name.replace(".timer", ".service");
+    #[derive(Deserialize)]
+    struct CreateTimerRequest {
+        name: String,
+        command: String,
+        on_calendar: String,
+        #[serde(default)]
+        description: Option<String>,
+        /// Enable and start the timer once created
+        #[serde(default)]
+        enable: bool,
+    }
 
-    let log_reader = LogReader::new(executor);
+    let request: CreateTimerRequest = match parse_json_body(body) {
+        Ok(request) => request,
+        Err(e) => return error_response(e.status_code(), &e.to_string()),
+    };
 
-    match log_reader.get_execution_details(&service_name, timestamp).await {
-        Ok(details) => json_response(200, details),
-        Err(TimerError::NotFound(_)) => {
-            error_response(404, "Execution not found")
+    let template = match TimerTemplate::new(
+        &request.name,
+        &request.command,
+        &request.on_calendar,
+        request.description.as_deref(),
+    ) {
+        Ok(template) => template,
+        Err(e) => return error_response(400, &e.to_string()),
+    };
+    let timer_name = template.timer_unit();
+
+    for unit in [&timer_name, &template.service_unit()] {
+        if let Some(response) = check_unit_access(kv_store, unit).await? {
+            return Ok(response);
         }
-        Err(e) => {
-            error_response(500, &format!("Failed to get execution details: {}", e))
+    }
+
+    // A unit of the same name loaded from another directory (a vendor unit
+    // in /lib/systemd/system, say) would be silently overridden by ours
+    let client = SystemctlClient::new(executor);
+    for unit in [&timer_name, &template.service_unit()] {
+        match client.load_state(unit).await {
+            Ok(load_state) if load_state == "not-found" => {}
+            Ok(load_state) => {
+                return error_response(409, &format!("Unit {} already exists (LoadState={})", unit, load_state));
+            }
+            Err(e) => return error_response(500, &format!("Failed to check for an existing {}: {}", unit, e)),
         }
     }
-}
 
-/// Handle POST /timers/settings - save watched timers
-pub async fn handle_save_settings(
-    kv_store: &dyn PluginKvStore,
-    body: &str,
-) -> TimerResult<HttpResponse> {
-    // Parse request body
-    #[derive(Deserialize)]
-    struct SaveSettingsRequest {
-        watched_timers: Vec<String>,
+    let settings = PluginSettings::load(kv_store).await?;
+    let writer = UnitWriter::new(&settings.unit_dir);
+
+    let written = match writer.write(&template).await {
+        Ok(written) => written,
+        Err(e @ TimerError::AlreadyExists(_)) => return error_response(409, &e.to_string()),
+        Err(e) => {
+            record_audit_outcome(kv_store, &timer_name, "create", Some(e.to_string()), clock).await;
+            return error_response(500, &format!("Failed to write timer units: {}", e));
+        }
+    };
+
+    let reload = client.daemon_reload().await;
+    record_audit(kv_store, &timer_name, "create", &reload, clock).await;
+    if let Err(e) = reload {
+        writer.remove(&written).await;
+        return match e {
+            TimerError::PermissionDenied(_) => error_response(403, "Permission denied"),
+            e @ TimerError::AuthenticationRequired(_) => error_response(401, &e.to_string()),
+            e => error_response(500, &format!("Failed to reload systemd: {}", e)),
+        };
     }
 
-    let request: SaveSettingsRequest = serde_json::from_str(body).map_err(|e| {
-        TimerError::ParseError {
-            source: "request_body".to_string(),
-            reason: e.to_string(),
+    PluginSettings::update(kv_store, |settings| {
+        if !settings.watched_timers.contains(&timer_name) {
+            settings.watched_timers.push(timer_name.clone());
+        }
+        if !settings.managed_timers.contains(&timer_name) {
+            settings.managed_timers.push(timer_name.clone());
         }
-    })?;
+    })
+    .await?;
 
-    // Save to KV storage
-    save_watched_timers(kv_store, &request.watched_timers).await?;
+    let enable = if request.enable {
+        Some(client.enable_timer(&timer_name).await)
+    } else {
+        None
+    };
+    if let Some(result) = &enable {
+        record_audit_outcome(kv_store, &timer_name, "enable", sequence_error(result, |s| &s.error), clock).await;
+    }
+
+    let enabled = matches!(&enable, Some(Ok(steps)) if steps.error.is_none());
+    let message = match &enable {
+        Some(_) if !enabled => format!("Timer {} created but failed to enable", timer_name),
+        _ => format!("Timer {} created", timer_name),
+    };
 
-    success_response("Settings saved")
+    success_response_with(&message, serde_json::json!({
+        "timer": timer_name,
+        "service": template.service_unit(),
+        "enabled": enabled,
+        "files": [written.service_path, written.timer_path]
+    }))
 }
 
-/// Handle GET /timers/settings - get current settings
-pub async fn handle_get_settings(
+/// Handle DELETE /timers/:name - remove a timer created by the plugin
+///
+/// Only timers recorded as plugin-created whose unit files still carry the
+/// generated marker are deleted: the timer is stopped and disabled, both
+/// unit files are removed and systemd is reloaded.
+pub async fn handle_delete_timer<E: CommandExecutor>(
+    executor: E,
     kv_store: &dyn PluginKvStore,
+    timer_name: &str,
+    clock: &dyn Clock,
 ) -> TimerResult<HttpResponse> {
-    let watched_timers = get_watched_timers(kv_store).await?;
+    let timer_name = &normalize_timer_name(timer_name);
+    let Some(job_name) = timer_name.strip_suffix(".timer") else {
+        return error_response(400, "Timer name must end with .timer");
+    };
 
-    let response = serde_json::json!({
-        "watched_timers": watched_timers
-    });
+    let settings = PluginSettings::load(kv_store).await?;
+    if !settings.managed_timers.iter().any(|t| t == timer_name) {
+        return error_response(403, &format!("Timer {} was not created by this plugin", timer_name));
+    }
 
-    json_response(200, response)
-}
+    if let Some(response) = check_unit_access(kv_store, timer_name).await? {
+        return Ok(response);
+    }
 
-/// Helper: Get watched timers from KV storage
-pub async fn get_watched_timers(kv_store: &dyn PluginKvStore) -> TimerResult<Vec<String>> {
-    match kv_store.get("watched_timers").await {
-        Ok(Some(json_str)) => {
-            let timers: Vec<String> = serde_json::from_str(&json_str)?;
-            Ok(timers)
-        }
-        Ok(None) => Ok(Vec::new()),
-        Err(e) => Err(TimerError::IoError(format!("KV storage error: {}", e))),
+    let client = SystemctlClient::new(executor);
+
+    let disabled = client.disable_timer(timer_name).await;
+    if let Some(error) = sequence_error(&disabled, |s| &s.error) {
+        record_audit_outcome(kv_store, timer_name, "delete", Some(error.clone()), clock).await;
+        return error_response(500, &format!("Failed to disable timer before deleting it: {}", error));
     }
-}
 
-/// Helper: Save watched timers to KV storage
-pub async fn save_watched_timers(
-    kv_store: &dyn PluginKvStore,
-    timers: &[String],
-) -> TimerResult<()> {
-    let json_str = serde_json::to_string(timers)?;
-    kv_store.set("watched_timers", &json_str).await
-        .map_err(|e| TimerError::IoError(format!("KV storage error: {}", e)))?;
-    Ok(())
-}
+    let writer = UnitWriter::new(&settings.unit_dir);
+    let result = match writer.remove_generated(job_name).await {
+        Ok(_) => client.daemon_reload().await,
+        Err(e) => Err(e),
+    };
+    record_audit(kv_store, timer_name, "delete", &result, clock).await;
 
-#[cfg(test)]
+    match result {
+        Ok(()) => {
+            PluginSettings::update(kv_store, |settings| {
+                settings.managed_timers.retain(|t| t != timer_name);
+                settings.watched_timers.retain(|t| t != timer_name);
+                settings.snoozed_until.remove(timer_name);
+            })
+            .await?;
+
+            success_response_with(
+                &format!("Timer {} deleted", timer_name),
+                serde_json::json!({ "timer": timer_name }),
+            )
+        }
+        Err(e @ TimerError::NotFound(_)) => {
+            error_response(404, &e.to_string())
+        }
+        Err(e @ TimerError::PermissionDenied(_)) => {
+            error_response(403, &e.to_string())
+        }
+        Err(e @ TimerError::AuthenticationRequired(_)) => {
+            error_response(401, &e.to_string())
+        }
+        Err(e) => {
+            error_response(500, &format!("Failed to delete timer: {}", e))
+        }
+    }
+}
+
+/// Handle PUT /timers/:name/schedule - change the `OnCalendar=` of a plugin-created timer
+///
+/// The new expression is validated before the unit is touched. After the
+/// rewrite systemd is reloaded and a running timer is restarted so the
+/// next elapse is computed from the new schedule.
+pub async fn handle_update_schedule<E: CommandExecutor>(
+    executor: E,
+    kv_store: &dyn PluginKvStore,
+    timer_name: &str,
+    body: &str,
+    clock: &dyn Clock,
+) -> TimerResult<HttpResponse> {
+    let timer_name = &normalize_timer_name(timer_name);
+    #[derive(Deserialize)]
+    struct UpdateScheduleRequest {
+        on_calendar: String,
+    }
+
+    let request: UpdateScheduleRequest = match parse_json_body(body) {
+        Ok(request) => request,
+        Err(e) => return error_response(e.status_code(), &e.to_string()),
+    };
+
+    let Some(job_name) = timer_name.strip_suffix(".timer") else {
+        return error_response(400, "Timer name must end with .timer");
+    };
+
+    let settings = PluginSettings::load(kv_store).await?;
+    if !settings.managed_timers.iter().any(|t| t == timer_name) {
+        return error_response(403, &format!("Timer {} was not created by this plugin", timer_name));
+    }
+
+    if let Some(response) = check_unit_access(kv_store, timer_name).await? {
+        return Ok(response);
+    }
+
+    let on_calendar = match crate::unit_writer::validate_on_calendar(&request.on_calendar) {
+        Ok(on_calendar) => on_calendar,
+        Err(e) => return error_response(400, &e.to_string()),
+    };
+
+    let client = SystemctlClient::new(executor);
+    let writer = UnitWriter::new(&settings.unit_dir);
+
+    let result = match writer.rewrite_on_calendar(job_name, on_calendar).await {
+        Ok(_) => match client.daemon_reload().await {
+            Ok(()) => client.try_restart(timer_name).await,
+            Err(e) => Err(e),
+        },
+        Err(e) => Err(e),
+    };
+    record_audit(kv_store, timer_name, "schedule", &result, clock).await;
+
+    match result {
+        Ok(()) => success_response_with(
+            &format!("Timer {} schedule updated", timer_name),
+            serde_json::json!({
+                "timer": timer_name,
+                "on_calendar": on_calendar
+            }),
+        ),
+        Err(TimerError::NotFound(_)) => {
+            error_response(404, "Timer not found")
+        }
+        Err(e @ TimerError::PermissionDenied(_)) => {
+            error_response(403, &e.to_string())
+        }
+        Err(e @ TimerError::AuthenticationRequired(_)) => {
+            error_response(401, &e.to_string())
+        }
+        Err(e) => {
+            error_response(500, &format!("Failed to update schedule: {}", e))
+        }
+    }
+}
+
+/// Handle GET /timers/:name/state - enabled/active/next run/last result without log lookups
+pub async fn handle_get_timer_state<E: CommandExecutor>(
+    executor: E,
+    timer_name: &str,
+) -> TimerResult<HttpResponse> {
+    let timer_name = &normalize_timer_name(timer_name);
+    let client = SystemctlClient::new(executor);
+
+    // A service name addresses the timer that shares its name
+    let timer_name = &if timer_name.ends_with(".service") {
+        match client.timer_for_service(timer_name).await {
+            Ok(Some(timer)) => timer,
+            Ok(None) => return error_response(404, &format!("No timer found for {}", timer_name)),
+            Err(e @ TimerError::InvalidInput(_)) => return error_response(400, &e.to_string()),
+            Err(e) => return error_response(500, &format!("Failed to look up timer: {}", e)),
+        }
+    } else {
+        timer_name.to_string()
+    };
+
+    match client.get_timer_state(timer_name).await {
+        Ok(state) => json_response(200, state),
+        Err(TimerError::NotFound(_)) => {
+            error_response(404, "Timer not found")
+        }
+        Err(e @ TimerError::InvalidInput(_)) => {
+            error_response(400, &e.to_string())
+        }
+        Err(e) => {
+            error_response(500, &format!("Failed to get timer state: {}", e))
+        }
+    }
+}
+
+/// Handle GET /timers/:name/history - get execution history
+pub async fn handle_get_history<E: CommandExecutor + Clone>(
+    executor: E,
+    kv_store: &dyn PluginKvStore,
+    timer_name: &str,
+    query_params: &HashMap<String, String>,
+    clock: &dyn Clock,
+) -> TimerResult<HttpResponse> {
+    let timer_name = &normalize_timer_name(timer_name);
+
+    // Services logging to a dedicated journal namespace are only visible there
+    let namespace = query_params.get("namespace").map(String::as_str).filter(|ns| !ns.is_empty());
+    let journal = match namespace {
+        Some(namespace) => match JournalClient::new(executor.clone()).with_clock(clock).with_namespace(namespace) {
+            Ok(journal) => journal,
+            Err(e) => return error_response(400, &e.to_string()),
+        },
+        None => JournalClient::new(executor.clone()).with_clock(clock),
+    };
+
+    // Only a unit systemd doesn't know is a 404; one that never ran has an empty history
+    let client = SystemctlClient::new(executor.clone());
+    if let Some(response) = check_unit_exists(&client, timer_name).await? {
+        return Ok(response);
+    }
+
+    // Convert timer name to service name
+    let service_name = timer_name.replace(".timer", ".service");
+
+    // Parse limit parameter (default to 20)
+    let limit = query_params
+        .get("limit")
+        .and_then(|s| s.parse::<usize>().ok())
+        .unwrap_or(20);
+
+    let settings = PluginSettings::load(kv_store).await?;
+    let mut history_sources = settings.history_sources;
+
+    if is_raw(query_params) {
+        let source = history_sources
+            .remove(timer_name.as_str())
+            .unwrap_or(JournalSource::Unit(service_name));
+        return raw_history_response(journal, &source, settings.debug_mode, query_params).await;
+    }
+
+    // Jobs logging under another unit or identifier, or to a namespace (which
+    // the log files know nothing of), are read from the journal
+    let history_source = history_sources.remove(timer_name.as_str());
+    let history = match (history_source, namespace) {
+        (Some(source), _) => journal.get_execution_history_from(&source, limit).await,
+        (None, Some(_)) => journal.get_execution_history(&service_name, limit).await,
+        (None, None) => LogReader::new(executor).with_clock(clock).get_execution_history(&service_name, limit).await,
+    };
+
+    match history {
+        Ok(history) => json_response(200, history),
+        Err(TimerError::NotFound(_)) => {
+            error_response(404, "Timer not found")
+        }
+        Err(e @ TimerError::InvalidInput(_)) => {
+            error_response(400, &e.to_string())
+        }
+        Err(e) => {
+            error_response(500, &format!("Failed to get history: {}", e))
+        }
+    }
+}
+
+/// Returns true if the request asks for unprocessed journal lines (`?raw=true`)
+fn is_raw(query_params: &HashMap<String, String>) -> bool {
+    query_params.get("raw").is_some_and(|v| v == "true" || v == "1")
+}
+
+/// Answer GET /timers/:name/history?raw=true with journald's JSON lines as NDJSON
+///
+/// Only available while debug mode is on. `lines` (default 100) caps the entries.
+async fn raw_history_response<E: CommandExecutor>(
+    journal: JournalClient<'_, E>,
+    source: &JournalSource,
+    debug_mode: bool,
+    query_params: &HashMap<String, String>,
+) -> TimerResult<HttpResponse> {
+    if !debug_mode {
+        let error_obj = serde_json::json!({
+            "success": false,
+            "code": "debug_disabled",
+            "error": "Raw journal output requires debug mode"
+        });
+        return json_response(403, error_obj);
+    }
+
+    let lines = query_params
+        .get("lines")
+        .and_then(|s| s.parse::<usize>().ok())
+        .unwrap_or(100);
+
+    match journal.get_raw_entries_from(source, lines).await {
+        Ok(raw_lines) => {
+            let mut body = raw_lines.join("\n");
+            if !body.is_empty() {
+                body.push('\n');
+            }
+
+            let mut headers = HashMap::new();
+            headers.insert("Content-Type".to_string(), "application/x-ndjson".to_string());
+
+            Ok(HttpResponse {
+                status: 200,
+                headers,
+                body: Some(body),
+            })
+        }
+        Err(e @ TimerError::InvalidInput(_)) => error_response(400, &e.to_string()),
+        Err(e) => error_response(500, &format!("Failed to get history: {}", e)),
+    }
+}
+
+/// Handle GET /timers/:name/history/:timestamp - get execution details
+pub async fn handle_get_history_details<E: CommandExecutor + Clone>(
+    executor: E,
+    timer_name: &str,
+    timestamp: &str,
+    clock: &dyn Clock,
+) -> TimerResult<HttpResponse> {
+    let timer_name = &normalize_timer_name(timer_name);
+    // Convert timer name to service name
+    let service_name = timer_name.replace(".timer", ".service");
+
+    let log_reader = LogReader::new(executor).with_clock(clock);
+
+    match log_reader.get_execution_details(&service_name, timestamp).await {
+        Ok(details) => json_response(200, details),
+        Err(TimerError::NotFound(_)) => {
+            error_response(404, "Execution not found")
+        }
+        Err(e) => {
+            error_response(500, &format!("Failed to get execution details: {}", e))
+        }
+    }
+}
+
+/// Handle GET /audit - recent mutating actions, newest first
+pub async fn handle_get_audit(
+    kv_store: &dyn PluginKvStore,
+) -> TimerResult<HttpResponse> {
+    let mut entries = load_audit_log(kv_store).await?;
+    entries.reverse();
+
+    json_response(200, entries)
+}
+
+/// Handle POST /timers/settings - save watched timers
+///
+/// Unless `check_units` is false, the watched timers are compared against
+/// `systemctl list-timers` and any systemd doesn't know are returned as
+/// warnings. The save goes ahead either way.
+pub async fn handle_save_settings<E: CommandExecutor>(
+    executor: E,
+    kv_store: &dyn PluginKvStore,
+    body: &str,
+) -> TimerResult<HttpResponse> {
+    // Parse request body
+    #[derive(Deserialize)]
+    struct SaveSettingsRequest {
+        watched_timers: Vec<String>,
+        /// Left unchanged when omitted
+        #[serde(default)]
+        use_24h: Option<bool>,
+        #[serde(default = "default_check_units")]
+        check_units: bool,
+    }
+
+    fn default_check_units() -> bool {
+        true
+    }
+
+    let request: SaveSettingsRequest = match parse_json_body(body) {
+        Ok(request) => request,
+        Err(e) => return error_response(e.status_code(), &e.to_string()),
+    };
+
+    let warnings = if request.check_units {
+        unknown_timer_warnings(executor, &request.watched_timers).await
+    } else {
+        Vec::new()
+    };
+
+    // Save to KV storage
+    PluginSettings::update(kv_store, |settings| {
+        settings.watched_timers = request.watched_timers;
+        if let Some(use_24h) = request.use_24h {
+            settings.use_24h = use_24h;
+        }
+    })
+    .await?;
+
+    success_response_with("Settings saved", serde_json::json!({ "warnings": warnings }))
+}
+
+/// Helper: Warn about watched timers `systemctl list-timers` doesn't report
+///
+/// If the listing itself fails that becomes the only warning, since nothing
+/// could be checked.
+async fn unknown_timer_warnings<E: CommandExecutor>(executor: E, timers: &[String]) -> Vec<String> {
+    let known = match SystemctlClient::new(executor).list_timers().await {
+        Ok(known) => known,
+        Err(e) => return vec![format!("Could not check watched timers against systemd: {}", e)],
+    };
+
+    timers
+        .iter()
+        .filter(|name| !known.iter().any(|timer| &timer.name == *name))
+        .map(|name| format!("Timer {} does not exist", name))
+        .collect()
+}
+
+/// Handle GET /timers/settings - get current settings
+pub async fn handle_get_settings(
+    kv_store: &dyn PluginKvStore,
+) -> TimerResult<HttpResponse> {
+    let settings = PluginSettings::load(kv_store).await?;
+
+    let response = serde_json::json!({
+        "watched_timers": settings.watched_timers,
+        "use_24h": settings.use_24h,
+        "timer_notes": settings.timer_notes
+    });
+
+    json_response(200, response)
+}
+
+/// Outcome of POST /timers/import
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportSummary {
+    /// Timers now watched, in request order without duplicates
+    pub added: Vec<String>,
+    /// Watched timers systemd doesn't currently list; stored anyway
+    pub skipped_unknown: Vec<String>,
+    /// Entries rejected because the name isn't a valid timer unit
+    pub invalid: Vec<String>,
+}
+
+/// Handle POST /timers/import - replace the watched list (and notes) in one go
+///
+/// Every name is checked against `systemctl list-timers`. Unknown timers are
+/// still stored, since the unit may only be installed later, but reported in
+/// `skipped_unknown`. Invalid names are dropped and duplicates collapse into
+/// their first occurrence.
+pub async fn handle_import_timers<E: CommandExecutor>(
+    executor: E,
+    kv_store: &dyn PluginKvStore,
+    body: &str,
+) -> TimerResult<HttpResponse> {
+    #[derive(Deserialize)]
+    struct ImportEntry {
+        name: String,
+        #[serde(default)]
+        note: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    struct ImportRequest {
+        timers: Vec<ImportEntry>,
+    }
+
+    let request: ImportRequest = match parse_json_body(body) {
+        Ok(request) => request,
+        Err(e) => return error_response(e.status_code(), &e.to_string()),
+    };
+
+    let known: Vec<String> = SystemctlClient::new(executor)
+        .list_timers()
+        .await?
+        .into_iter()
+        .map(|timer| timer.name)
+        .collect();
+
+    let mut summary = ImportSummary::default();
+    let mut notes = HashMap::new();
+
+    for entry in request.timers {
+        let name = entry.name.trim().to_string();
+        let valid = name.ends_with(".timer") && SystemctlClient::<E>::validate_timer_name(&name).is_ok();
+        if !valid {
+            summary.invalid.push(entry.name);
+            continue;
+        }
+        if summary.added.contains(&name) {
+            continue;
+        }
+
+        if !known.contains(&name) {
+            eprintln!("Importing timer {} which systemd does not list", name);
+            summary.skipped_unknown.push(name.clone());
+        }
+        if let Some(note) = entry.note.map(|note| note.trim().to_string()).filter(|note| !note.is_empty()) {
+            notes.insert(name.clone(), note);
+        }
+        summary.added.push(name);
+    }
+
+    let watched = summary.added.clone();
+    PluginSettings::update(kv_store, |settings| {
+        settings.watched_timers = watched;
+        settings.timer_notes = notes;
+    })
+    .await?;
+
+    success_response_with(&format!("Imported {} timers", summary.added.len()), summary)
+}
+
+/// Re-read settings from KV storage and return them
+///
+/// The standalone file store is refreshed by the caller first; this reports
+/// what the plugin now sees.
+pub async fn handle_reload_settings(
+    kv_store: &dyn PluginKvStore,
+) -> TimerResult<HttpResponse> {
+    let settings = PluginSettings::load(kv_store).await?;
+
+    success_response_with("Settings reloaded", settings)
+}
+
+/// Helper: Reject units outside the configured allowlist/denylist with a 403
+async fn check_unit_access(
+    kv_store: &dyn PluginKvStore,
+    timer_name: &str,
+) -> TimerResult<Option<HttpResponse>> {
+    let policy = UnitAccessPolicy::load(kv_store).await?;
+
+    match policy.check(timer_name) {
+        Ok(()) => Ok(None),
+        Err(reason) => error_response(403, &reason).map(Some),
+    }
+}
+
+/// Helper: 404 response when systemd has no unit named `timer_name`
+/// Checked before an action so a typo doesn't reach the heavier systemctl calls
+async fn check_unit_exists<E: CommandExecutor>(
+    client: &SystemctlClient<E>,
+    timer_name: &str,
+) -> TimerResult<Option<HttpResponse>> {
+    if client.unit_exists(timer_name).await {
+        return Ok(None);
+    }
+
+    error_response(404, "Timer not found").map(Some)
+}
+
+/// Helper: Record the outcome of a mutating action in the audit log
+async fn record_audit<T>(
+    kv_store: &dyn PluginKvStore,
+    timer_name: &str,
+    action: &str,
+    result: &TimerResult<T>,
+    clock: &dyn Clock,
+) {
+    let error = result.as_ref().err().map(|e| e.to_string());
+    record_audit_outcome(kv_store, timer_name, action, error, clock).await;
+}
+
+/// Helper: Append an audit entry; `error` is None for a successful action
+/// A failing audit write is logged but never fails the action itself
+pub(crate) async fn record_audit_outcome(
+    kv_store: &dyn PluginKvStore,
+    timer_name: &str,
+    action: &str,
+    error: Option<String>,
+    clock: &dyn Clock,
+) {
+    let entry = AuditEntry::new(timer_name, action, error, clock);
+
+    if let Err(e) = append_audit(kv_store, entry).await {
+        eprintln!("Failed to write audit entry for {} {}: {}", action, timer_name, e);
+    }
+}
+
+/// How late a timer may be before the available-timers filter calls it overdue
+const OVERDUE_GRACE_SECS: i64 = 60;
+
+/// Helper: Whether a timer matches an available-timers `state` filter
+/// `active`/`inactive` follow the enabled flag; `overdue` is an enabled timer
+/// whose next elapse has already passed or that missed its latest scheduled run
+fn matches_state_filter(info: &TimerInfo, state: &str, now: DateTime<Utc>) -> bool {
+    match state {
+        "active" => info.enabled,
+        "inactive" => !info.enabled,
+        "overdue" => info.enabled && is_overdue(info, now),
+        _ => true,
+    }
+}
+
+/// Helper: True if the timer's next elapse is in the past or its last run is late
+fn is_overdue(info: &TimerInfo, now: DateTime<Utc>) -> bool {
+    let next_run_passed = info
+        .next_run
+        .as_deref()
+        .and_then(parse_systemd_timestamp)
+        .is_some_and(|next| (now - next).num_seconds() > OVERDUE_GRACE_SECS);
+
+    let missed_run = schedule_drift(&info.calendar, info.last_trigger.as_deref(), now)
+        .is_some_and(|drift| drift > OVERDUE_GRACE_SECS);
+
+    next_run_passed || missed_run
+}
+
+/// Helper: Status and latest run of one timer, or None if it can't be queried
+async fn collect_timer_metrics<E: CommandExecutor + Clone>(
+    executor: E,
+    timer_name: String,
+    now: DateTime<Utc>,
+) -> Option<TimerMetrics> {
+    let client = SystemctlClient::new(executor.clone());
+    let info = match client.get_timer_info(&timer_name).await {
+        Ok(info) => info,
+        Err(e) => {
+            eprintln!("Failed to get info for timer {}: {}", timer_name, e);
+            return None;
+        }
+    };
+
+    let last_run = LogReader::new(executor)
+        .get_execution_history(&info.service, 1)
+        .await
+        .ok()
+        .and_then(|history| history.into_iter().next())
+        .filter(|run| run.status != ExecutionStatus::Running);
+
+    Some(TimerMetrics {
+        timer: info.name.clone(),
+        enabled: info.enabled,
+        next_run_seconds: info
+            .next_run
+            .as_deref()
+            .and_then(parse_systemd_timestamp)
+            .map(|next| next.timestamp()),
+        last_run_success: last_run.as_ref().map(|run| run.status == ExecutionStatus::Success),
+        last_duration_seconds: last_run.and_then(|run| run.duration_secs),
+        overdue: info.enabled && is_overdue(&info, now),
+    })
+}
+
+/// Helper: Full-sentence schedule from the raw OnCalendar expressions, if any
+fn verbose_schedule(calendar: &[String], use_24h: bool) -> Option<String> {
+    if calendar.is_empty() {
+        return None;
+    }
+
+    Some(
+        calendar
+            .iter()
+            .map(|expression| {
+                Schedule::Calendar { expression: expression.clone() }.humanize_verbose_with_clock(use_24h)
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+/// Helper: Error of a multi-step sequence, whether it failed outright or part-way
+pub(crate) fn sequence_error<T>(
+    result: &TimerResult<T>,
+    step_error: impl Fn(&T) -> &Option<String>,
+) -> Option<String> {
+    match result {
+        Ok(steps) => step_error(steps).clone(),
+        Err(e) => Some(e.to_string()),
+    }
+}
+
+/// Helper: Report the per-step state of a sequence (200 if complete, 500 if partial)
+fn step_response<T: Serialize>(steps: &T, complete: bool, message: &str) -> TimerResult<HttpResponse> {
+    let mut body = serde_json::to_value(steps)?;
+    body["success"] = serde_json::json!(complete);
+    body["message"] = serde_json::json!(message);
+
+    json_response(if complete { 200 } else { 500 }, body)
+}
+
+/// Helper: Get watched timers from the plugin settings
+pub async fn get_watched_timers(kv_store: &dyn PluginKvStore) -> TimerResult<Vec<String>> {
+    Ok(PluginSettings::load(kv_store).await?.watched_timers)
+}
+
+/// Helper: Save watched timers to the plugin settings
+pub async fn save_watched_timers(
+    kv_store: &dyn PluginKvStore,
+    timers: &[String],
+) -> TimerResult<()> {
+    PluginSettings::update(kv_store, |settings| {
+        settings.watched_timers = timers.to_vec();
+    })
+    .await
+    .map(|_| ())
+}
+
+/// Helper: Whether schedules are shown on a 24-hour clock (default: false)
+pub async fn get_use_24h(kv_store: &dyn PluginKvStore) -> TimerResult<bool> {
+    Ok(PluginSettings::load(kv_store).await?.use_24h)
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::{FixedClock, SystemClock};
+    use crate::command::mock::MockCommandExecutor;
+    use crate::command::CommandOutput;
+    use crate::test_support::{ok_output, TestKvStore};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use toru_plugin_api::PluginResult;
+
+    #[tokio::test]
+    async fn test_settings_load_defaults() {
+        let settings = PluginSettings::load(&TestKvStore::new()).await.unwrap();
+
+        assert_eq!(settings, PluginSettings::default());
+        assert!(settings.watched_timers.is_empty());
+        assert!(!settings.read_only);
+        assert_eq!(settings.action_cooldown(), crate::rate_limit::DEFAULT_ACTION_COOLDOWN);
+
+        // Fields missing from a stored blob take their defaults
+        let kv_store = TestKvStore::with(&[("settings", r#"{"watched_timers":["backup.timer"]}"#)]);
+        let settings = PluginSettings::load(&kv_store).await.unwrap();
+        assert_eq!(settings.watched_timers, vec!["backup.timer"]);
+        assert_eq!(settings.action_cooldown_ms, 2000);
+    }
+
+    #[tokio::test]
+    async fn test_settings_round_trip() {
+        let kv_store = TestKvStore::new();
+        let settings = PluginSettings {
+            watched_timers: vec!["backup.timer".to_string(), "cleanup.timer".to_string()],
+            allowed_units: vec!["*.timer".to_string()],
+            denied_units: vec!["fstrim.timer".to_string()],
+            use_24h: true,
+            read_only: true,
+            action_cooldown_ms: 500,
+            unit_dir: "/srv/units".to_string(),
+            managed_timers: vec!["backup.timer".to_string()],
+            timer_notes: HashMap::from([("backup.timer".to_string(), "Nightly dump".to_string())]),
+            history_sources: HashMap::from([(
+                "backup.timer".to_string(),
+                crate::journal::JournalSource::Identifier("backup-job".to_string()),
+            )]),
+            cors_origin: Some("http://localhost:5173".to_string()),
+            snoozed_until: HashMap::from([(
+                "backup.timer".to_string(),
+                "2024-06-01T12:00:00Z".parse().unwrap(),
+            )]),
+            reconcile_interval_secs: 300,
+            debug_mode: true,
+        };
+
+        settings.save(&kv_store).await.unwrap();
+        assert_eq!(PluginSettings::load(&kv_store).await.unwrap(), settings);
+    }
+
+    #[tokio::test]
+    async fn test_settings_fall_back_to_legacy_keys() {
+        let kv_store = TestKvStore::with(&[
+            ("watched_timers", r#"["backup.timer"]"#),
+            ("use_24h", "true"),
+            ("action_cooldown_ms", "20"),
+        ]);
+
+        let settings = PluginSettings::load(&kv_store).await.unwrap();
+        assert_eq!(settings.watched_timers, vec!["backup.timer"]);
+        assert!(settings.use_24h);
+        assert!(!settings.read_only);
+        assert_eq!(settings.action_cooldown_ms, 20);
+    }
+
+    #[tokio::test]
+    async fn test_settings_migrate_legacy_watched_timers() {
+        let kv_store = TestKvStore::with(&[("watched_timers", r#"["backup.timer","cleanup.timer"]"#)]);
+
+        let settings = PluginSettings::load(&kv_store).await.unwrap();
+        assert_eq!(settings.watched_timers, vec!["backup.timer", "cleanup.timer"]);
+
+        // The blob is written once and the legacy key is kept for rollback
+        let blob = kv_store.get("settings").await.unwrap().expect("settings blob written");
+        let migrated: PluginSettings = serde_json::from_str(&blob).unwrap();
+        assert_eq!(migrated, settings);
+        assert!(kv_store.get("watched_timers").await.unwrap().is_some());
+
+        // Later loads read the blob, not the legacy key
+        kv_store.set("watched_timers", "[]").await.unwrap();
+        assert_eq!(PluginSettings::load(&kv_store).await.unwrap(), settings);
+    }
+
+    #[tokio::test]
+    async fn test_settings_adopt_legacy_access_keys_into_existing_blob() {
+        // A blob saved before the access lists moved into it
+        let kv_store = TestKvStore::with(&[
+            ("settings", r#"{"watched_timers":["backup.timer"]}"#),
+            ("allowed_units", r#"["backup.timer"]"#),
+        ]);
+
+        let settings = PluginSettings::load(&kv_store).await.unwrap();
+        assert_eq!(settings.watched_timers, vec!["backup.timer"]);
+        assert_eq!(settings.allowed_units, vec!["backup.timer"]);
+        assert!(settings.denied_units.is_empty());
+
+        // Recorded in the blob, so the legacy key no longer matters
+        kv_store.delete("allowed_units").await.unwrap();
+        let policy = UnitAccessPolicy::load(&kv_store).await.unwrap();
+        assert!(policy.check("cleanup.timer").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_settings_load_without_keys_writes_nothing() {
+        let kv_store = TestKvStore::new();
+
+        PluginSettings::load(&kv_store).await.unwrap();
+        assert!(kv_store.get("settings").await.unwrap().is_none());
+    }
+
+    /// KV store that yields inside every read, so concurrent read-modify-write
+    /// cycles interleave the way they do against a real store
+    struct YieldingKvStore(TestKvStore);
+
+    #[async_trait::async_trait]
+    impl PluginKvStore for YieldingKvStore {
+        async fn get(&self, key: &str) -> PluginResult<Option<String>> {
+            let value = self.0.get(key).await;
+            tokio::task::yield_now().await;
+            value
+        }
+
+        async fn set(&self, key: &str, value: &str) -> PluginResult<()> {
+            self.0.set(key, value).await
+        }
+
+        async fn delete(&self, key: &str) -> PluginResult<()> {
+            self.0.delete(key).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_settings_concurrent_adds_both_survive() {
+        let kv_store = Arc::new(YieldingKvStore(TestKvStore::new()));
+
+        let adds: Vec<_> = ["backup.timer", "cleanup.timer"]
+            .into_iter()
+            .map(|timer| {
+                let kv_store = kv_store.clone();
+                tokio::spawn(async move {
+                    PluginSettings::update(kv_store.as_ref(), |settings| {
+                        settings.watched_timers.push(timer.to_string());
+                    })
+                    .await
+                })
+            })
+            .collect();
+        for add in adds {
+            add.await.unwrap().unwrap();
+        }
+
+        let mut watched = PluginSettings::load(kv_store.as_ref()).await.unwrap().watched_timers;
+        watched.sort();
+        assert_eq!(watched, vec!["backup.timer", "cleanup.timer"]);
+    }
+
+    #[test]
+    fn test_parse_query_params() {
+        let path = "/timers/test.timer/history?limit=50&offset=10";
+        let params = parse_query_params(path);
+        assert_eq!(params.get("limit"), Some(&"50".to_string()));
+        assert_eq!(params.get("offset"), Some(&"10".to_string()));
+    }
+
+    #[test]
+    fn test_path_without_query() {
+        let path = "/timers/test.timer/history?limit=50";
+        assert_eq!(path_without_query(path), "/timers/test.timer/history");
+    }
+
+    #[test]
+    fn test_decode_path_segment() {
+        assert_eq!(decode_path_segment("getty%40tty1.service"), "getty@tty1.service");
+        assert_eq!(decode_path_segment("backup.timer"), "backup.timer");
+        assert_eq!(decode_path_segment("a%2Fb.timer"), "a/b.timer");
+    }
+
+    #[test]
+    fn test_decode_path_segment_malformed() {
+        assert_eq!(decode_path_segment("100%"), "100%");
+        assert_eq!(decode_path_segment("a%zzb"), "a%zzb");
+        assert_eq!(decode_path_segment("a%4"), "a%4");
+    }
+
+    #[test]
+    fn test_normalize_timer_name() {
+        assert_eq!(normalize_timer_name("backup"), "backup.timer");
+        assert_eq!(normalize_timer_name("getty@tty1"), "getty@tty1.timer");
+        assert_eq!(normalize_timer_name("backup.timer"), "backup.timer");
+        assert_eq!(normalize_timer_name("backup.service"), "backup.service");
+        // Invalid names are left for validation to reject
+        assert_eq!(normalize_timer_name("foo;bar"), "foo;bar");
+        assert_eq!(normalize_timer_name(""), "");
+    }
+
+    #[test]
+    fn test_parse_history_path() {
+        assert_eq!(
+            parse_history_path("/timers/test.timer/history"),
+            Some(("test.timer", None))
+        );
+        assert_eq!(
+            parse_history_path("/timers/test.timer/history/2026-01-15_140000"),
+            Some(("test.timer", Some("2026-01-15_140000")))
+        );
+        // Timer names containing "history" are not confused with the route segment
+        assert_eq!(
+            parse_history_path("/timers/history-cleanup.timer/history"),
+            Some(("history-cleanup.timer", None))
+        );
+    }
+
+    #[test]
+    fn test_parse_history_path_trailing_slash() {
+        assert_eq!(
+            parse_history_path("/timers/a.timer/history/"),
+            Some(("a.timer", None))
+        );
+        assert_eq!(
+            parse_history_path("/timers/a.timer/history/abc123/"),
+            Some(("a.timer", Some("abc123")))
+        );
+    }
+
+    #[test]
+    fn test_parse_history_path_invalid() {
+        // Empty timer name or empty segments in the middle
+        assert_eq!(parse_history_path("/timers//history"), None);
+        assert_eq!(parse_history_path("/timers/a.timer//abc123"), None);
+        // Not a history route at all
+        assert_eq!(parse_history_path("/timers/a.timer/run"), None);
+        assert_eq!(parse_history_path("/timers/a.timer/history/abc/extra"), None);
+    }
+
+    #[test]
+    fn test_error_response() {
+        let resp = error_response(404, "Not found").unwrap();
+        assert_eq!(resp.status, 404);
+        assert!(resp.body.as_ref().unwrap().contains("Not found"));
+    }
+
+    #[test]
+    fn test_success_response() {
+        let resp = success_response("Timer started").unwrap();
+        assert_eq!(resp.status, 200);
+        assert!(resp.body.as_ref().unwrap().contains("success"));
+        assert!(resp.body.as_ref().unwrap().contains("Timer started"));
+    }
+
+    #[tokio::test]
+    async fn test_run_timer_returns_affected_timer() {
+        let mock = MockCommandExecutor::new();
+        mock.expect("systemctl start --no-block backup.service", ok_output());
+        let kv_store = TestKvStore::new();
+
+        let resp = handle_test_timer(mock, &kv_store, "backup.timer", &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 200);
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["success"], true);
+        assert_eq!(body["data"]["timer"], "backup.timer");
+        assert_eq!(body["data"]["mode"], "test");
+    }
+
+    #[tokio::test]
+    async fn test_test_timer_accepts_bare_name() {
+        let mock = MockCommandExecutor::new();
+        mock.expect("systemctl start --no-block backup.service", ok_output());
+        let kv_store = TestKvStore::new();
+
+        let resp = handle_test_timer(mock, &kv_store, "backup", &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 200);
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["data"]["timer"], "backup.timer");
+    }
+
+    #[tokio::test]
+    async fn test_test_timer_rejects_injection_in_bare_name() {
+        let mock = std::sync::Arc::new(MockCommandExecutor::new());
+        let kv_store = TestKvStore::new();
+
+        // Rejected as an unknown unit without running anything
+        let resp = handle_test_timer(mock.clone(), &kv_store, "foo;bar", &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 404);
+        assert!(mock.calls().is_empty());
+    }
+
+    /// A fresh, empty unit directory and a store whose settings point at it
+    fn create_timer_store(test: &str) -> (std::path::PathBuf, TestKvStore) {
+        let dir = std::env::temp_dir().join(format!("create-timer-{}-{}", test, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let settings = serde_json::json!({ "unit_dir": dir }).to_string();
+        (dir, TestKvStore::with(&[("settings", &settings)]))
+    }
+
+    /// Mock systemd not knowing either unit of `name` yet
+    fn expect_new_units(mock: &MockCommandExecutor, name: &str) {
+        for unit in [format!("{}.timer", name), format!("{}.service", name)] {
+            mock.expect(&format!("systemctl show {} --property=LoadState", unit), CommandOutput {
+                stdout: "LoadState=not-found\n".to_string(),
+                stderr: String::new(),
+                exit_code: 0,
+            });
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_timer_writes_units() {
+        let (dir, kv_store) = create_timer_store("writes");
+        let mock = MockCommandExecutor::new();
+        expect_new_units(&mock, "backup-db");
+        mock.expect("systemctl daemon-reload", ok_output());
+        let body = r#"{"name":"backup-db","command":"/usr/local/bin/backup","on_calendar":"*-*-* 03:00:00","description":"Nightly backup"}"#;
+
+        let resp = handle_create_timer(mock, &kv_store, body, &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 200);
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["data"]["timer"], "backup-db.timer");
+        assert_eq!(body["data"]["enabled"], false);
+
+        let timer = std::fs::read_to_string(dir.join("backup-db.timer")).unwrap();
+        assert!(timer.contains("OnCalendar=*-*-* 03:00:00\n"));
+        let service = std::fs::read_to_string(dir.join("backup-db.service")).unwrap();
+        assert!(service.contains("ExecStart=/usr/local/bin/backup\n"));
+        assert!(service.contains("Description=Nightly backup\n"));
+
+        let settings = PluginSettings::load(&kv_store).await.unwrap();
+        assert_eq!(settings.watched_timers, vec!["backup-db.timer"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_timer_rejects_invalid_calendar() {
+        let (dir, kv_store) = create_timer_store("calendar");
+        let body = r#"{"name":"backup-db","command":"/usr/local/bin/backup","on_calendar":"every night"}"#;
+
+        let resp = handle_create_timer(MockCommandExecutor::new(), &kv_store, body, &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 400);
+        assert!(!dir.join("backup-db.timer").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_timer_removes_units_when_reload_fails() {
+        let (dir, kv_store) = create_timer_store("reload");
+        let mock = MockCommandExecutor::new();
+        expect_new_units(&mock, "backup-db");
+        mock.expect("systemctl daemon-reload", CommandOutput {
+            stdout: String::new(),
+            stderr: "Failed to reload daemon".to_string(),
+            exit_code: 1,
+        });
+        let body = r#"{"name":"backup-db","command":"/usr/local/bin/backup","on_calendar":"daily"}"#;
+
+        let resp = handle_create_timer(mock, &kv_store, body, &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 500);
+        assert!(!dir.join("backup-db.timer").exists());
+        assert!(!dir.join("backup-db.service").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_timer_existing_unit_conflicts() {
+        let (dir, kv_store) = create_timer_store("exists");
+        std::fs::write(dir.join("backup-db.service"), "[Service]\n").unwrap();
+        let mock = MockCommandExecutor::new();
+        expect_new_units(&mock, "backup-db");
+        let body = r#"{"name":"backup-db","command":"/usr/local/bin/backup","on_calendar":"daily"}"#;
+
+        let resp = handle_create_timer(mock, &kv_store, body, &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 409);
+        assert_eq!(std::fs::read_to_string(dir.join("backup-db.service")).unwrap(), "[Service]\n");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_timer_refuses_unit_loaded_elsewhere() {
+        let (dir, kv_store) = create_timer_store("loaded");
+        let mock = Arc::new(MockCommandExecutor::new());
+        mock.expect("systemctl show nginx.timer --property=LoadState", CommandOutput {
+            stdout: "LoadState=not-found\n".to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+        // The vendor unit in /lib/systemd/system
+        mock.expect("systemctl show nginx.service --property=LoadState", CommandOutput {
+            stdout: "LoadState=loaded\n".to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+        let body = r#"{"name":"nginx","command":"/usr/local/bin/backup","on_calendar":"daily"}"#;
+
+        let resp = handle_create_timer(mock.clone(), &kv_store, body, &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 409);
+        assert!(!dir.join("nginx.timer").exists());
+        assert!(!dir.join("nginx.service").exists());
+        assert!(!mock.calls().contains(&"systemctl daemon-reload".to_string()));
+        assert!(PluginSettings::load(&kv_store).await.unwrap().managed_timers.is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_delete_managed_timer() {
+        let (dir, kv_store) = create_timer_store("delete");
+        let mock = MockCommandExecutor::new();
+        expect_new_units(&mock, "backup-db");
+        mock.expect("systemctl daemon-reload", ok_output());
+        let body = r#"{"name":"backup-db","command":"/usr/local/bin/backup","on_calendar":"daily"}"#;
+        assert_eq!(handle_create_timer(mock, &kv_store, body, &SystemClock).await.unwrap().status, 200);
+
+        let mock = MockCommandExecutor::new();
+        mock.expect("systemctl show backup-db.timer --property=UnitFileState,ActiveState", CommandOutput {
+            stdout: "UnitFileState=disabled\nActiveState=inactive\n".to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+        mock.expect("systemctl daemon-reload", ok_output());
+
+        let resp = handle_delete_timer(mock, &kv_store, "backup-db.timer", &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 200);
+        assert!(!dir.join("backup-db.timer").exists());
+        assert!(!dir.join("backup-db.service").exists());
+
+        let settings = PluginSettings::load(&kv_store).await.unwrap();
+        assert!(settings.managed_timers.is_empty());
+        assert!(settings.watched_timers.is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_delete_snoozed_timer_clears_snooze() {
+        let (dir, kv_store) = create_timer_store("delete-snoozed");
+        let mock = MockCommandExecutor::new();
+        expect_new_units(&mock, "backup-db");
+        mock.expect("systemctl daemon-reload", ok_output());
+        let body = r#"{"name":"backup-db","command":"/usr/local/bin/backup","on_calendar":"daily"}"#;
+        assert_eq!(handle_create_timer(mock, &kv_store, body, &SystemClock).await.unwrap().status, 200);
+
+        let until = SystemClock.now() + chrono::Duration::hours(2);
+        PluginSettings::update(&kv_store, |settings| {
+            settings.snoozed_until.insert("backup-db.timer".to_string(), until);
+        })
+        .await
+        .unwrap();
+
+        let mock = MockCommandExecutor::new();
+        mock.expect("systemctl show backup-db.timer --property=UnitFileState,ActiveState", CommandOutput {
+            stdout: "UnitFileState=disabled\nActiveState=inactive\n".to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+        mock.expect("systemctl daemon-reload", ok_output());
+
+        let resp = handle_delete_timer(mock, &kv_store, "backup-db.timer", &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 200);
+
+        // Nothing is left to resume once the window passes
+        let settings = PluginSettings::load(&kv_store).await.unwrap();
+        assert!(settings.snoozed_until.is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_delete_timer_without_unit_files_is_not_found() {
+        let (dir, kv_store) = create_timer_store("delete-missing");
+        PluginSettings::update(&kv_store, |settings| {
+            settings.managed_timers.push("backup-db.timer".to_string());
+        })
+        .await
+        .unwrap();
+
+        let mock = Arc::new(MockCommandExecutor::new());
+        mock.expect("systemctl show backup-db.timer --property=UnitFileState,ActiveState", CommandOutput {
+            stdout: "UnitFileState=\nActiveState=inactive\n".to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+
+        let resp = handle_delete_timer(mock.clone(), &kv_store, "backup-db.timer", &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 404);
+        assert!(!mock.calls().contains(&"systemctl daemon-reload".to_string()));
+        // Nothing was deleted, so the timer is still recorded as managed
+        let settings = PluginSettings::load(&kv_store).await.unwrap();
+        assert_eq!(settings.managed_timers, vec!["backup-db.timer"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_delete_unmanaged_timer_refused() {
+        let (dir, kv_store) = create_timer_store("delete-unmanaged");
+        std::fs::write(dir.join("logrotate.timer"), "[Timer]\nOnCalendar=daily\n").unwrap();
+
+        let resp = handle_delete_timer(MockCommandExecutor::new(), &kv_store, "logrotate.timer", &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 403);
+        assert!(dir.join("logrotate.timer").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_update_schedule_of_managed_timer() {
+        let (dir, kv_store) = create_timer_store("schedule");
+        let mock = MockCommandExecutor::new();
+        expect_new_units(&mock, "backup-db");
+        mock.expect("systemctl daemon-reload", ok_output());
+        let body = r#"{"name":"backup-db","command":"/usr/local/bin/backup","on_calendar":"daily"}"#;
+        assert_eq!(handle_create_timer(mock, &kv_store, body, &SystemClock).await.unwrap().status, 200);
+
+        let mock = MockCommandExecutor::new();
+        mock.expect("systemctl daemon-reload", ok_output());
+        mock.expect("systemctl try-restart backup-db.timer", ok_output());
+
+        let body = r#"{"on_calendar":"*-*-* 04:15:00"}"#;
+        let resp = handle_update_schedule(mock, &kv_store, "backup-db.timer", body, &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 200);
+        let timer = std::fs::read_to_string(dir.join("backup-db.timer")).unwrap();
+        assert!(timer.contains("OnCalendar=*-*-* 04:15:00\n"));
+        assert!(!timer.contains("OnCalendar=daily"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_update_schedule_rejects_invalid_expression() {
+        let (dir, kv_store) = create_timer_store("schedule-invalid");
+        let mock = MockCommandExecutor::new();
+        expect_new_units(&mock, "backup-db");
+        mock.expect("systemctl daemon-reload", ok_output());
+        let body = r#"{"name":"backup-db","command":"/usr/local/bin/backup","on_calendar":"daily"}"#;
+        assert_eq!(handle_create_timer(mock, &kv_store, body, &SystemClock).await.unwrap().status, 200);
+
+        let body = r#"{"on_calendar":"at some point"}"#;
+        let resp = handle_update_schedule(MockCommandExecutor::new(), &kv_store, "backup-db.timer", body, &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 400);
+        let timer = std::fs::read_to_string(dir.join("backup-db.timer")).unwrap();
+        assert!(timer.contains("OnCalendar=daily\n"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_update_schedule_of_unmanaged_timer_refused() {
+        let kv_store = TestKvStore::new();
+        let body = r#"{"on_calendar":"weekly"}"#;
+
+        let resp = handle_update_schedule(MockCommandExecutor::new(), &kv_store, "logrotate.timer", body, &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 403);
+    }
+
+    #[tokio::test]
+    async fn test_enable_timer_allowed() {
+        let mock = MockCommandExecutor::new();
+        mock.expect("systemctl enable backup.timer", ok_output());
+        mock.expect("systemctl start backup.timer", ok_output());
+        let kv_store = TestKvStore::with(&[("allowed_units", r#"["backup.timer"]"#)]);
+
+        let resp = handle_enable_timer(mock, &kv_store, "backup.timer", &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 200);
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["enabled"], true);
+        assert_eq!(body["started"], true);
+    }
+
+    #[tokio::test]
+    async fn test_enable_timer_already_enabled_reports_no_change() {
+        let mock = MockCommandExecutor::new();
+        mock.expect(
+            "systemctl show backup.timer --property=UnitFileState,ActiveState",
+            CommandOutput {
+                stdout: "UnitFileState=enabled\nActiveState=active\n".to_string(),
+                stderr: String::new(),
+                exit_code: 0,
+            },
+        );
+        let kv_store = TestKvStore::new();
+
+        let resp = handle_enable_timer(mock, &kv_store, "backup.timer", &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 200);
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["success"], true);
+        assert_eq!(body["changed"], false);
+        assert_eq!(body["message"], "Timer backup.timer already enabled");
+    }
+
+    #[tokio::test]
+    async fn test_disable_timer_already_disabled_reports_no_change() {
+        let mock = MockCommandExecutor::new();
+        mock.expect(
+            "systemctl show backup.timer --property=UnitFileState,ActiveState",
+            CommandOutput {
+                stdout: "UnitFileState=disabled\nActiveState=inactive\n".to_string(),
+                stderr: String::new(),
+                exit_code: 0,
+            },
+        );
+        let kv_store = TestKvStore::new();
+
+        let resp = handle_disable_timer(mock, &kv_store, "backup.timer", &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 200);
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["changed"], false);
+    }
+
+    #[tokio::test]
+    async fn test_enable_timer_start_fails_reports_steps() {
+        let mock = MockCommandExecutor::new();
+        mock.expect("systemctl enable backup.timer", ok_output());
+        mock.expect(
+            "systemctl start backup.timer",
+            CommandOutput {
+                stdout: String::new(),
+                stderr: "Job for backup.timer failed".to_string(),
+                exit_code: 1,
+            },
+        );
+        mock.expect("systemctl disable backup.timer", ok_output());
+        let kv_store = TestKvStore::new();
+
+        let resp = handle_enable_timer(mock, &kv_store, "backup.timer", &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 500);
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["success"], false);
+        assert_eq!(body["started"], false);
+        assert_eq!(body["rolled_back"], true);
+        assert!(body["error"].as_str().unwrap().contains("Job for backup.timer failed"));
+
+        let entries = load_audit_log(&kv_store).await.unwrap();
+        assert_eq!(entries[0].outcome, "failure");
+    }
+
+    #[tokio::test]
+    async fn test_disable_timer_disable_fails_reports_steps() {
+        let mock = MockCommandExecutor::new();
+        mock.expect("systemctl stop backup.timer", ok_output());
+        mock.expect(
+            "systemctl disable backup.timer",
+            CommandOutput {
+                stdout: String::new(),
+                stderr: "Failed to disable unit".to_string(),
+                exit_code: 1,
+            },
+        );
+        let kv_store = TestKvStore::new();
+
+        let resp = handle_disable_timer(mock, &kv_store, "backup.timer", &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 500);
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["stopped"], true);
+        assert_eq!(body["disabled"], false);
+        assert_eq!(body["rolled_back"], false);
+    }
+
+    #[tokio::test]
+    async fn test_run_timer_denied_even_if_allowlisted() {
+        // No mock responses: shelling out would surface as a 500
+        let mock = MockCommandExecutor::new();
+        let kv_store = TestKvStore::with(&[
+            ("allowed_units", r#"["*.timer"]"#),
+            ("denied_units", r#"["fstrim.timer"]"#),
+        ]);
+
+        let resp = handle_run_timer(mock, &kv_store, "fstrim.timer", &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 403);
+        assert!(resp.body.as_ref().unwrap().contains("denylist"));
+    }
+
+    #[tokio::test]
+    async fn test_run_timer_missing_unit_is_404() {
+        // Only the LoadState query is mocked; the start must not be attempted
+        let mock = MockCommandExecutor::new();
+        mock.expect("systemctl show gone.timer --property=LoadState", CommandOutput {
+            stdout: "LoadState=not-found\n".to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+        let kv_store = TestKvStore::new();
+
+        let resp = handle_run_timer(Arc::new(mock), &kv_store, "gone.timer", &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 404);
+        assert!(kv_store.get("audit_log").await.unwrap().is_none());
+    }
+
+    async fn run_timer_status(stderr: &str) -> u16 {
+        let mock = MockCommandExecutor::new();
+        mock.expect("systemctl start --no-block backup.service", CommandOutput {
+            stdout: String::new(),
+            stderr: stderr.to_string(),
+            exit_code: 1,
+        });
+
+        handle_run_timer(mock, &TestKvStore::new(), "backup.timer", &SystemClock).await.unwrap().status
+    }
+
+    #[tokio::test]
+    async fn test_run_timer_error_status_mapping() {
+        assert_eq!(run_timer_status("Failed to start backup.service: Unit backup.service is masked.\n").await, 409);
+        assert_eq!(run_timer_status("Failed to start backup.service: Interactive authentication required.\n").await, 401);
+        assert_eq!(run_timer_status("Job for backup.service failed.\n").await, 500);
+    }
+
+    #[tokio::test]
+    async fn test_disable_timer_outside_allowlist_rejected() {
+        let mock = MockCommandExecutor::new();
+        let kv_store = TestKvStore::with(&[("allowed_units", r#"["chfscraper-*"]"#)]);
+
+        let resp = handle_disable_timer(mock, &kv_store, "backup.timer", &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 403);
+        assert!(resp.body.as_ref().unwrap().contains("allowlist"));
+    }
+
+    #[tokio::test]
+    async fn test_read_only_blocks_mutations() {
+        let kv_store = TestKvStore::with(&[("read_only", "true")]);
+
+        let resp = check_read_only(&kv_store, "POST", "/timers/backup.timer/run")
+            .await
+            .unwrap()
+            .expect("run should be blocked");
+        assert_eq!(resp.status, 403);
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["code"], "read_only");
+
+        // Settings stay writable so the mode can be turned off again
+        assert!(check_read_only(&kv_store, "POST", "/timers/settings").await.unwrap().is_none());
+        assert!(check_read_only(&kv_store, "POST", "/settings/reload").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_read_only_allows_reads() {
+        let kv_store = TestKvStore::with(&[("read_only", "true")]);
+
+        assert!(check_read_only(&kv_store, "GET", "/timers").await.unwrap().is_none());
+
+        let resp = handle_get_timers(Arc::new(MockCommandExecutor::new()), &kv_store, &RefreshAdvisor::new(), &SystemClock)
+            .await
+            .unwrap();
+        assert_eq!(resp.status, 200);
+    }
+
+    #[tokio::test]
+    async fn test_read_only_disabled_by_default() {
+        let kv_store = TestKvStore::new();
+        assert!(check_read_only(&kv_store, "POST", "/timers/backup.timer/enable")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_snooze_disables_and_records_resume_time() {
+        let mock = Arc::new(MockCommandExecutor::new());
+        mock.expect("systemctl stop backup.timer", ok_output());
+        mock.expect("systemctl disable backup.timer", ok_output());
+        let kv_store = TestKvStore::new();
+        let clock = FixedClock::new("2024-06-01T12:00:00Z".parse().unwrap());
+        let until = (clock.now() + chrono::Duration::hours(2)).to_rfc3339();
+        let body = serde_json::json!({ "until": until }).to_string();
+
+        let resp = handle_snooze_timer(mock.clone(), &kv_store, "backup.timer", &body, &clock).await.unwrap();
+        assert_eq!(resp.status, 200);
+        let response: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(response["success"], true);
+        assert_eq!(response["disabled"], true);
+
+        assert!(mock.calls().contains(&"systemctl disable backup.timer".to_string()));
+        let settings = PluginSettings::load(&kv_store).await.unwrap();
+        let stored = settings.snoozed_until["backup.timer"];
+        assert_eq!(stored, DateTime::parse_from_rfc3339(&until).unwrap());
+        assert_eq!(response["snoozed_until"], stored.to_rfc3339());
+
+        let audit = load_audit_log(&kv_store).await.unwrap();
+        assert_eq!(audit[0].action, "snooze");
+    }
+
+    #[tokio::test]
+    async fn test_snooze_rejects_invalid_windows() {
+        let mock = Arc::new(MockCommandExecutor::new());
+        let kv_store = TestKvStore::new();
+        let clock = FixedClock::new("2024-06-01T12:00:00Z".parse().unwrap());
+        let past = (clock.now() - chrono::Duration::minutes(1)).to_rfc3339();
+        let too_far = (clock.now() + chrono::Duration::days(MAX_SNOOZE_DAYS + 1)).to_rfc3339();
+
+        for until in [past.as_str(), too_far.as_str(), "tomorrow"] {
+            let body = serde_json::json!({ "until": until }).to_string();
+            let resp = handle_snooze_timer(mock.clone(), &kv_store, "backup.timer", &body, &clock).await.unwrap();
+            assert_eq!(resp.status, 400, "{}", until);
+        }
+
+        assert!(mock.calls().is_empty());
+        assert!(PluginSettings::load(&kv_store).await.unwrap().snoozed_until.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_snoozed_timer_resumes_on_poll_after_window() {
+        let mock = Arc::new(MockCommandExecutor::new());
+        mock.expect("systemctl enable backup.timer", ok_output());
+        mock.expect("systemctl start backup.timer", ok_output());
+        let kv_store = TestKvStore::new();
+        let clock = FixedClock::new("2024-06-01T12:00:00Z".parse().unwrap());
+        let earlier = clock.now() + chrono::Duration::minutes(30);
+        let later = clock.now() + chrono::Duration::hours(2);
+        PluginSettings {
+            watched_timers: vec!["backup.timer".to_string(), "cleanup.timer".to_string()],
+            snoozed_until: HashMap::from([
+                ("backup.timer".to_string(), earlier),
+                ("cleanup.timer".to_string(), later),
+            ]),
+            ..PluginSettings::default()
+        }
+        .save(&kv_store)
+        .await
+        .unwrap();
+
+        // Neither window has passed yet
+        handle_get_timers(mock.clone(), &kv_store, &RefreshAdvisor::new(), &clock).await.unwrap();
+        assert!(!mock.calls().iter().any(|call| call.contains("systemctl enable")));
+
+        clock.advance(chrono::Duration::hours(1));
+        let resp = handle_get_timers(mock.clone(), &kv_store, &RefreshAdvisor::new(), &clock).await.unwrap();
+        assert_eq!(resp.status, 200);
+
+        // Only the timer whose window passed is enabled again
+        let calls = mock.calls();
+        assert!(calls.contains(&"systemctl enable backup.timer".to_string()));
+        assert!(calls.contains(&"systemctl start backup.timer".to_string()));
+        assert!(!calls.iter().any(|call| call.contains("enable cleanup.timer")));
+
+        let timers: Vec<TimerStatusResponse> = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(timers[0].snoozed_until, None);
+        assert_eq!(timers[1].snoozed_until, Some(later.to_rfc3339()));
+
+        let settings = PluginSettings::load(&kv_store).await.unwrap();
+        assert_eq!(settings.snoozed_until.keys().collect::<Vec<_>>(), vec!["cleanup.timer"]);
+        assert_eq!(load_audit_log(&kv_store).await.unwrap()[0].action, "resume");
+    }
+
+    #[tokio::test]
+    async fn test_successful_enable_is_audited() {
+        let mock = MockCommandExecutor::new();
+        mock.expect("systemctl enable backup.timer", ok_output());
+        mock.expect("systemctl start backup.timer", ok_output());
+        let kv_store = TestKvStore::new();
+
+        handle_enable_timer(mock, &kv_store, "backup.timer", &SystemClock).await.unwrap();
+
+        let entries = load_audit_log(&kv_store).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].unit, "backup.timer");
+        assert_eq!(entries[0].action, "enable");
+        assert_eq!(entries[0].outcome, "success");
+        assert!(entries[0].error.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_failed_run_is_audited() {
+        let mock = MockCommandExecutor::new();
+        mock.expect(
+            "systemctl start --no-block backup.service",
+            CommandOutput {
+                stdout: String::new(),
+                stderr: "Job for backup.service failed".to_string(),
+                exit_code: 1,
+            },
+        );
+        let kv_store = TestKvStore::new();
+
+        let resp = handle_run_timer(mock, &kv_store, "backup.timer", &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 500);
+
+        let resp = handle_get_audit(&kv_store).await.unwrap();
+        let entries: Vec<AuditEntry> = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, "run");
+        assert_eq!(entries[0].outcome, "failure");
+        assert!(entries[0].error.as_ref().unwrap().contains("Job for backup.service failed"));
+    }
+
+    #[tokio::test]
+    async fn test_audit_log_is_capped() {
+        let kv_store = TestKvStore::new();
+
+        for i in 0..crate::audit::MAX_AUDIT_ENTRIES + 5 {
+            let result: TimerResult<()> = Ok(());
+            let entry = AuditEntry::from_result(&format!("t{}.timer", i), "run", &result, &SystemClock);
+            append_audit(&kv_store, entry).await.unwrap();
+        }
+
+        let entries = load_audit_log(&kv_store).await.unwrap();
+        assert_eq!(entries.len(), crate::audit::MAX_AUDIT_ENTRIES);
+        assert_eq!(entries[0].unit, "t5.timer");
+    }
 
     #[test]
-    fn test_parse_query_params() {
-        let path = "/timers/test.timer/history?limit=50&offset=10";
-        let params = parse_query_params(path);
-        assert_eq!(params.get("limit"), Some(&"50".to_string()));
-        assert_eq!(params.get("offset"), Some(&"10".to_string()));
+    fn test_parse_action_path() {
+        assert_eq!(parse_action_path("/timers/backup.timer/run"), Some(("backup.timer", "run")));
+        assert_eq!(parse_action_path("/timers/backup.timer/disable"), Some(("backup.timer", "disable")));
+        assert_eq!(parse_action_path("/timers/backup.timer/snooze"), Some(("backup.timer", "snooze")));
+        assert_eq!(parse_action_path("/timers/backup.timer/history"), None);
+        assert_eq!(parse_action_path("/timers/settings"), None);
+        assert_eq!(parse_action_path("/timers//run"), None);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_rejects_immediate_repeat() {
+        let limiter = RateLimiter::new();
+        let kv_store = TestKvStore::new();
+
+        assert!(check_rate_limit(&limiter, &kv_store, "backup.timer", "run", Instant::now()).await.unwrap().is_none());
+
+        let resp = check_rate_limit(&limiter, &kv_store, "backup.timer", "run", Instant::now())
+            .await
+            .unwrap()
+            .expect("second run should be rate limited");
+        assert_eq!(resp.status, 429);
+        assert_eq!(resp.headers.get("Retry-After"), Some(&"2".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_allows_after_cooldown() {
+        let limiter = RateLimiter::new();
+        let kv_store = TestKvStore::with(&[("action_cooldown_ms", "20")]);
+
+        assert!(check_rate_limit(&limiter, &kv_store, "backup.timer", "run", Instant::now()).await.unwrap().is_none());
+        assert!(check_rate_limit(&limiter, &kv_store, "backup.timer", "run", Instant::now()).await.unwrap().is_some());
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(check_rate_limit(&limiter, &kv_store, "backup.timer", "run", Instant::now()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_released_for_rejected_action() {
+        let limiter = RateLimiter::new();
+        let kv_store = TestKvStore::new();
+        let not_found = error_response(404, "Timer backup.timer not found").unwrap();
+        let started = success_response("Timer backup.timer started").unwrap();
+
+        let now = Instant::now();
+        assert!(check_rate_limit(&limiter, &kv_store, "backup", "run", now).await.unwrap().is_none());
+        release_rejected_action(&limiter, "backup", "run", now, &not_found);
+
+        // The corrected retry goes through; once it has run, repeats are limited
+        let now = Instant::now();
+        assert!(check_rate_limit(&limiter, &kv_store, "backup.timer", "run", now).await.unwrap().is_none());
+        release_rejected_action(&limiter, "backup.timer", "run", now, &started);
+        assert!(check_rate_limit(&limiter, &kv_store, "backup.timer", "run", Instant::now()).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_settings_round_trip_use_24h() {
+        let kv_store = TestKvStore::new();
+        let executor = Arc::new(MockCommandExecutor::new());
+
+        handle_save_settings(executor.clone(), &kv_store, r#"{"watched_timers":["backup.timer"],"use_24h":true}"#)
+            .await
+            .unwrap();
+        // Omitting use_24h leaves it unchanged
+        handle_save_settings(executor, &kv_store, r#"{"watched_timers":["backup.timer"]}"#)
+            .await
+            .unwrap();
+
+        let resp = handle_get_settings(&kv_store).await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["use_24h"], true);
     }
 
     #[test]
-    fn test_path_without_query() {
-        let path = "/timers/test.timer/history?limit=50";
-        assert_eq!(path_without_query(path), "/timers/test.timer/history");
+    fn test_verbose_schedule_clock() {
+        let calendar = vec!["*-*-* 08:00".to_string()];
+        assert_eq!(verbose_schedule(&calendar, false).unwrap(), "Every day at 8:00 AM");
+        assert_eq!(verbose_schedule(&calendar, true).unwrap(), "Every day at 08:00");
+        assert!(verbose_schedule(&[], true).is_none());
+    }
+
+    /// Mock with three timers: an on-time active one, an overdue one and a disabled one
+    fn available_timers_mock() -> MockCommandExecutor {
+        let mock = MockCommandExecutor::new();
+        mock.expect("systemctl list-timers --all --no-pager --plain --timestamp=unix", CommandOutput {
+            stdout: "NEXT LEFT LAST PASSED UNIT ACTIVATES\n\
+                     @4072168800 45min left n/a n/a fresh.timer fresh.service\n\
+                     @1579095000 15min left n/a n/a stale.timer stale.service\n\
+                     n/a n/a n/a n/a n/a n/a off.timer off.service\n".to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+
+        let show = |name: &str, stdout: &str| {
+            mock.expect(
+                &format!("systemctl show {} {} --property=Id,Description,LoadState,UnitFileState,ActiveState,NextElapseUSecRealtime,LastTriggerUSec,TimersCalendar,TimersMonotonic,FragmentPath,DropInPaths,ConditionResult,ConditionTimestamp", name, name.replace(".timer", ".service")),
+                CommandOutput { stdout: stdout.to_string(), stderr: String::new(), exit_code: 0 },
+            );
+        };
+        show("fresh.timer", "Id=fresh.timer\nLoadState=loaded\nUnitFileState=enabled\nActiveState=active\nNextElapseUSecRealtime=4071849600000000\n");
+        show("stale.timer", "Id=stale.timer\nLoadState=loaded\nUnitFileState=enabled\nActiveState=active\nNextElapseUSecRealtime=1579095000000000\n");
+        show("off.timer", "Id=off.timer\nLoadState=loaded\nUnitFileState=disabled\nActiveState=inactive\nNextElapseUSecRealtime=0\n");
+
+        mock
+    }
+
+    #[tokio::test]
+    async fn test_health_ok() {
+        let mock = MockCommandExecutor::new();
+        // A degraded system (failed units) still means systemd is reachable
+        mock.expect("systemctl is-system-running", CommandOutput {
+            stdout: "degraded\n".to_string(),
+            stderr: String::new(),
+            exit_code: 1,
+        });
+
+        let resp = handle_get_health(Arc::new(mock)).await.unwrap();
+        assert_eq!(resp.status, 200);
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["status"], "ok");
+        assert_eq!(body["systemd_available"], true);
+        assert_eq!(body["system_state"], "degraded");
+    }
+
+    #[tokio::test]
+    async fn test_health_degraded_without_systemd() {
+        let resp = handle_get_health(Arc::new(MockCommandExecutor::new())).await.unwrap();
+        assert_eq!(resp.status, 503);
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["status"], "degraded");
+        assert_eq!(body["systemd_available"], false);
+        assert!(body["system_state"].is_null());
+
+        let mock = MockCommandExecutor::new();
+        mock.expect("systemctl is-system-running", CommandOutput {
+            stdout: "offline\n".to_string(),
+            stderr: String::new(),
+            exit_code: 1,
+        });
+        let resp = handle_get_health(Arc::new(mock)).await.unwrap();
+        assert_eq!(resp.status, 503);
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["system_state"], "offline");
+    }
+
+    #[tokio::test]
+    async fn test_save_settings_warns_about_unknown_timers() {
+        let kv_store = TestKvStore::new();
+        let body = r#"{"watched_timers":["fresh.timer","renamed.timer"]}"#;
+
+        let resp = handle_save_settings(Arc::new(available_timers_mock()), &kv_store, body).await.unwrap();
+        assert_eq!(resp.status, 200);
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["data"]["warnings"], serde_json::json!(["Timer renamed.timer does not exist"]));
+
+        // The warning doesn't block the save
+        let settings = PluginSettings::load(&kv_store).await.unwrap();
+        assert_eq!(settings.watched_timers, vec!["fresh.timer", "renamed.timer"]);
+
+        // Opting out skips the systemd lookup entirely
+        let body = r#"{"watched_timers":["renamed.timer"],"check_units":false}"#;
+        let resp = handle_save_settings(Arc::new(MockCommandExecutor::new()), &kv_store, body).await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["data"]["warnings"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_save_settings_rejects_malformed_json() {
+        let kv_store = TestKvStore::new();
+
+        let resp = handle_save_settings(Arc::new(MockCommandExecutor::new()), &kv_store, r#"{"watched_timers": ["#)
+            .await
+            .unwrap();
+        assert_eq!(resp.status, 400);
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert!(body["error"].as_str().unwrap().starts_with("Invalid request body"));
+        assert!(kv_store.get("settings").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_save_settings_rejects_oversized_body() {
+        let kv_store = TestKvStore::new();
+        let names: Vec<String> = (0..MAX_BODY_BYTES / 10).map(|i| format!("t{:05}.timer", i)).collect();
+        let body = serde_json::json!({ "watched_timers": names, "check_units": false }).to_string();
+        assert!(body.len() > MAX_BODY_BYTES);
+
+        let resp = handle_save_settings(Arc::new(MockCommandExecutor::new()), &kv_store, &body).await.unwrap();
+        assert_eq!(resp.status, 400);
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert!(body["error"].as_str().unwrap().starts_with("Request body too large"));
+        assert!(kv_store.get("settings").await.unwrap().is_none());
     }
 
     #[test]
-    fn test_error_response() {
-        let resp = error_response(404, "Not found").unwrap();
-        assert_eq!(resp.status, 404);
-        assert!(resp.body.as_ref().unwrap().contains("Not found"));
+    fn test_request_and_response_json_errors_map_to_distinct_statuses() {
+        let err = parse_json_body::<serde_json::Value>("{").unwrap_err();
+        assert!(matches!(err, TimerError::BadRequest(_)));
+        assert_eq!(err.status_code(), 400);
+
+        // Non-string map keys can't be serialized; that's our bug, not the client's
+        let mut data = HashMap::new();
+        data.insert((1, 2), "value");
+        let err = json_response(200, data).unwrap_err();
+        assert!(matches!(err, TimerError::JsonError(_)));
+        assert_eq!(err.status_code(), 500);
+    }
+
+    #[tokio::test]
+    async fn test_import_stores_unknown_timers() {
+        let kv_store = TestKvStore::with(&[("watched_timers", r#"["old.timer"]"#)]);
+        let body = r#"{"timers": [
+            {"name": "fresh.timer", "note": "Refreshes the cache"},
+            {"name": "later.timer"},
+            {"name": "fresh.timer", "note": "duplicate"}
+        ]}"#;
+
+        let resp = handle_import_timers(Arc::new(available_timers_mock()), &kv_store, body).await.unwrap();
+        assert_eq!(resp.status, 200);
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["data"]["added"], serde_json::json!(["fresh.timer", "later.timer"]));
+        assert_eq!(body["data"]["skipped_unknown"], serde_json::json!(["later.timer"]));
+        assert_eq!(body["data"]["invalid"], serde_json::json!([]));
+
+        // The import replaces the watched list; unknown timers are kept
+        let settings = PluginSettings::load(&kv_store).await.unwrap();
+        assert_eq!(settings.watched_timers, vec!["fresh.timer", "later.timer"]);
+        assert_eq!(settings.timer_notes.get("fresh.timer").map(String::as_str), Some("Refreshes the cache"));
+        assert!(!settings.timer_notes.contains_key("later.timer"));
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_invalid_names() {
+        let kv_store = TestKvStore::new();
+        let body = r#"{"timers": [
+            {"name": "stale.timer"},
+            {"name": "backup.service"},
+            {"name": "../etc/passwd.timer"},
+            {"name": ""}
+        ]}"#;
+
+        let resp = handle_import_timers(Arc::new(available_timers_mock()), &kv_store, body).await.unwrap();
+        assert_eq!(resp.status, 200);
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["data"]["added"], serde_json::json!(["stale.timer"]));
+        assert_eq!(body["data"]["invalid"], serde_json::json!(["backup.service", "../etc/passwd.timer", ""]));
+
+        let settings = PluginSettings::load(&kv_store).await.unwrap();
+        assert_eq!(settings.watched_timers, vec!["stale.timer"]);
+
+        let resp = handle_import_timers(Arc::new(available_timers_mock()), &kv_store, "{}").await.unwrap();
+        assert_eq!(resp.status, 400);
+    }
+
+    async fn available_names(state: Option<&str>) -> Vec<String> {
+        let mut params = HashMap::new();
+        if let Some(state) = state {
+            params.insert("state".to_string(), state.to_string());
+        }
+
+        let resp = handle_get_available_timers(available_timers_mock(), &params, &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 200);
+        let body: Vec<AvailableTimerResponse> = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        body.into_iter().map(|t| t.name).collect()
+    }
+
+    #[tokio::test]
+    async fn test_available_timers_state_filters() {
+        assert_eq!(available_names(None).await.len(), 3);
+        assert_eq!(available_names(Some("inactive")).await, vec!["off.timer"]);
+        assert_eq!(available_names(Some("overdue")).await, vec!["stale.timer"]);
+        assert_eq!(available_names(Some("active")).await, vec!["fresh.timer", "stale.timer"]);
     }
 
     #[test]
-    fn test_success_response() {
-        let resp = success_response("Timer started").unwrap();
+    fn test_available_timers_overdue_by_missed_run() {
+        let info = TimerInfo {
+            name: "backup.timer".to_string(),
+            description: String::new(),
+            enabled: true,
+            enabled_at_boot: true,
+            active: true,
+            schedule: String::new(),
+            calendar: vec!["*-*-* 02:00:00 UTC".to_string()],
+            next_run: None,
+            // Two days ago, so at least one 02:00 run was missed
+            last_trigger: Some((Utc::now() - chrono::Duration::days(2)).timestamp_micros().to_string()),
+            service: "backup.service".to_string(),
+            fragment_path: None,
+            drop_in_paths: Vec::new(),
+            last_condition_failed: None,
+            left: None,
+            passed: None,
+        };
+        assert!(matches_state_filter(&info, "overdue", Utc::now()));
+
+        let on_time = TimerInfo { last_trigger: None, ..info };
+        assert!(!matches_state_filter(&on_time, "overdue", Utc::now()));
+    }
+
+    #[tokio::test]
+    async fn test_available_timers_invalid_state() {
+        let mut params = HashMap::new();
+        params.insert("state".to_string(), "sleeping".to_string());
+
+        let resp = handle_get_available_timers(MockCommandExecutor::new(), &params, &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 400);
+    }
+
+    #[tokio::test]
+    async fn test_timer_state_skips_journal_and_logs() {
+        let mock = Arc::new(MockCommandExecutor::new());
+        mock.expect(
+            "systemctl show backup.timer backup.service --property=LoadState,UnitFileState,ActiveState,NextElapseUSecRealtime,Result,ExecMainStartTimestampMonotonic",
+            CommandOutput {
+                stdout: "LoadState=loaded\nUnitFileState=enabled\nActiveState=active\nNextElapseUSecRealtime=1705324800000000\n\n\
+                         LoadState=loaded\nActiveState=activating\nResult=success\nExecMainStartTimestampMonotonic=5\n".to_string(),
+                stderr: String::new(),
+                exit_code: 0,
+            },
+        );
+
+        let resp = handle_get_timer_state(mock.clone(), "backup.timer").await.unwrap();
         assert_eq!(resp.status, 200);
-        assert!(resp.body.as_ref().unwrap().contains("success"));
-        assert!(resp.body.as_ref().unwrap().contains("Timer started"));
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["enabled"], true);
+        assert_eq!(body["last_result"], "running");
+
+        // Exactly one systemctl call: no journalctl and no log directory reads
+        let calls = mock.calls();
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].starts_with("systemctl show"));
+    }
+
+    #[tokio::test]
+    async fn test_history_uses_configured_source() {
+        let entries = CommandOutput {
+            stdout: r#"{"INVOCATION_ID":"abc","__REALTIME_TIMESTAMP":"1705320000000000","EXIT_STATUS":"0"}"#.to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        };
+        let mock = Arc::new(MockCommandExecutor::new());
+        mock.expect("journalctl -t backup-job --since 7 days ago -o json --no-pager", entries.clone());
+        mock.expect("journalctl -u pipeline.service --since 7 days ago -o json --no-pager", entries);
+        let settings = serde_json::json!({
+            "history_sources": {
+                "backup.timer": {"identifier": "backup-job"},
+                "export.timer": {"unit": "pipeline.service"}
+            }
+        })
+        .to_string();
+        let kv_store = TestKvStore::with(&[("settings", &settings)]);
+        let params = HashMap::new();
+
+        for timer in ["backup.timer", "export.timer"] {
+            let resp = handle_get_history(mock.clone(), &kv_store, timer, &params, &SystemClock).await.unwrap();
+            assert_eq!(resp.status, 200, "{}", timer);
+            let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+            assert_eq!(body[0]["invocation_id"], "abc");
+        }
+        let journal_calls = mock.calls().iter().filter(|c| c.starts_with("journalctl")).count();
+        assert_eq!(journal_calls, 2);
+    }
+
+    #[tokio::test]
+    async fn test_raw_history_returns_journal_lines_unmodified() {
+        let lines = [
+            r#"{"INVOCATION_ID":"abc","__REALTIME_TIMESTAMP":"1705320000000000","MESSAGE":"Starting","_CUSTOM":[1,2]}"#,
+            r#"{ "INVOCATION_ID" : "abc", "__REALTIME_TIMESTAMP":"1705320010000000", "EXIT_STATUS":"0" }"#,
+        ];
+        let mock = Arc::new(MockCommandExecutor::new());
+        mock.expect("journalctl -u backup.service --since 7 days ago -n 50 -o json --no-pager", CommandOutput {
+            stdout: format!("{}\n{}\n", lines[0], lines[1]),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+        let params = HashMap::from([
+            ("raw".to_string(), "true".to_string()),
+            ("lines".to_string(), "50".to_string()),
+        ]);
+
+        // Off by default
+        let resp = handle_get_history(mock.clone(), &TestKvStore::new(), "backup.timer", &params, &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 403);
+        assert!(!mock.calls().iter().any(|call| call.starts_with("journalctl")));
+
+        let kv_store = TestKvStore::with(&[("settings", r#"{"debug_mode": true}"#)]);
+        let resp = handle_get_history(mock.clone(), &kv_store, "backup.timer", &params, &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 200);
+        assert_eq!(resp.headers["Content-Type"], "application/x-ndjson");
+        let body = resp.body.unwrap();
+        assert_eq!(body.lines().collect::<Vec<_>>(), lines);
+    }
+
+    #[tokio::test]
+    async fn test_history_reads_journal_namespace() {
+        let mock = Arc::new(MockCommandExecutor::new());
+        mock.expect("journalctl -u backup.service --since 7 days ago -o json --no-pager --namespace=pipeline", CommandOutput {
+            stdout: r#"{"INVOCATION_ID":"ns1","__REALTIME_TIMESTAMP":"1705320000000000","EXIT_STATUS":"0"}"#.to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+        let kv_store = TestKvStore::new();
+        let params = HashMap::from([("namespace".to_string(), "pipeline".to_string())]);
+
+        let resp = handle_get_history(mock.clone(), &kv_store, "backup.timer", &params, &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 200);
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(body[0]["invocation_id"], "ns1");
+        assert!(!mock.calls().iter().any(|call| call.starts_with("ls ")));
+    }
+
+    #[tokio::test]
+    async fn test_history_rejects_invalid_namespace() {
+        let mock = Arc::new(MockCommandExecutor::new());
+        let kv_store = TestKvStore::new();
+        let params = HashMap::from([("namespace".to_string(), "ns --all".to_string())]);
+
+        let resp = handle_get_history(mock.clone(), &kv_store, "backup.timer", &params, &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 400);
+        assert!(mock.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_history_without_override_reads_log_files() {
+        let mock = Arc::new(MockCommandExecutor::new());
+        mock.expect("ls -1t /var/log/timers/backup", CommandOutput {
+            stdout: String::new(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+        let kv_store = TestKvStore::new();
+
+        let resp = handle_get_history(mock.clone(), &kv_store, "backup.timer", &HashMap::new(), &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 200);
+        assert_eq!(mock.calls().last().map(String::as_str), Some("ls -1t /var/log/timers/backup"));
+    }
+
+    fn load_state_output(state: &str) -> CommandOutput {
+        CommandOutput {
+            stdout: format!("LoadState={}\n", state),
+            stderr: String::new(),
+            exit_code: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_history_of_unit_that_never_ran_is_empty() {
+        let mock = Arc::new(MockCommandExecutor::new());
+        mock.expect("systemctl show fresh.timer --property=LoadState", load_state_output("loaded"));
+        // No log directory yet
+        mock.expect("ls -1t /var/log/timers/fresh", CommandOutput {
+            stdout: String::new(),
+            stderr: "ls: cannot access '/var/log/timers/fresh': No such file or directory".to_string(),
+            exit_code: 2,
+        });
+        // Nor any journal entries for the identifier override
+        mock.expect("journalctl -t fresh-job --since 7 days ago -o json --no-pager", CommandOutput {
+            stdout: String::new(),
+            stderr: "-- No entries --".to_string(),
+            exit_code: 1,
+        });
+        let params = HashMap::new();
+
+        for kv_store in [
+            TestKvStore::new(),
+            TestKvStore::with(&[("settings", r#"{"history_sources": {"fresh.timer": {"identifier": "fresh-job"}}}"#)]),
+        ] {
+            let resp = handle_get_history(mock.clone(), &kv_store, "fresh.timer", &params, &SystemClock).await.unwrap();
+            assert_eq!(resp.status, 200);
+            assert_eq!(resp.body.as_deref(), Some("[]"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_history_of_missing_unit_is_404() {
+        let mock = Arc::new(MockCommandExecutor::new());
+        mock.expect("systemctl show gone.timer --property=LoadState", load_state_output("not-found"));
+        let kv_store = TestKvStore::new();
+
+        let resp = handle_get_history(mock.clone(), &kv_store, "gone.timer", &HashMap::new(), &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 404);
+        // No history lookup for a unit that doesn't exist
+        assert_eq!(mock.calls().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_timer_state_by_service_name() {
+        let mock = Arc::new(MockCommandExecutor::new());
+        mock.expect("systemctl list-timers --all --no-pager --plain --timestamp=unix", CommandOutput {
+            stdout: "@1705324800 45min left n/a n/a backup.timer backup.service\n".to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+        mock.expect(
+            "systemctl show backup.timer backup.service --property=LoadState,UnitFileState,ActiveState,NextElapseUSecRealtime,Result,ExecMainStartTimestampMonotonic",
+            CommandOutput {
+                stdout: "LoadState=loaded\nUnitFileState=enabled\nActiveState=active\n\n\
+                         LoadState=loaded\nActiveState=inactive\nResult=success\n".to_string(),
+                stderr: String::new(),
+                exit_code: 0,
+            },
+        );
+
+        let resp = handle_get_timer_state(mock.clone(), "backup.service").await.unwrap();
+        assert_eq!(resp.status, 200);
+        let body: serde_json::Value = serde_json::from_str(resp.body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["enabled"], true);
+
+        // No timer of that name
+        let resp = handle_get_timer_state(mock, "nginx.service").await.unwrap();
+        assert_eq!(resp.status, 404);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_exposition() {
+        let mock = Arc::new(MockCommandExecutor::new());
+        let output = |stdout: &str| CommandOutput { stdout: stdout.to_string(), stderr: String::new(), exit_code: 0 };
+        let show = |name: &str| format!("systemctl show {} {} --property=Id,Description,LoadState,UnitFileState,ActiveState,NextElapseUSecRealtime,LastTriggerUSec,TimersCalendar,TimersMonotonic,FragmentPath,DropInPaths,ConditionResult,ConditionTimestamp", name, name.replace(".timer", ".service"));
+
+        // On schedule, last run succeeded in 42s
+        mock.expect(&show("backup.timer"), output("Id=backup.timer\nLoadState=loaded\nUnitFileState=enabled\nActiveState=active\nNextElapseUSecRealtime=4071849600000000\n"));
+        mock.expect("ls -1t /var/log/timers/backup", output("latest.log\n2024-01-15_020000.log\n"));
+        mock.expect("tail -n 1 /var/log/timers/backup/2024-01-15_020000.log", output("[END] 2024-01-15 02:00:42 exit_code=0 duration=42s\n"));
+
+        // Next elapse in 2020, last run failed
+        mock.expect(&show("stale.timer"), output("Id=stale.timer\nLoadState=loaded\nUnitFileState=enabled\nActiveState=active\nNextElapseUSecRealtime=1579095000000000\n"));
+        mock.expect("ls -1t /var/log/timers/stale", output("2020-01-15_133000.log\n"));
+        mock.expect("tail -n 1 /var/log/timers/stale/2020-01-15_133000.log", output("[END] 2020-01-15 13:30:07 exit_code=1 duration=7s\n"));
+
+        let kv_store = TestKvStore::with(&[("watched_timers", r#"["backup.timer","stale.timer","gone.timer"]"#)]);
+        let resp = handle_get_metrics(mock, &kv_store, &SystemClock).await.unwrap();
+        assert_eq!(resp.status, 200);
+        assert!(resp.headers.get("Content-Type").unwrap().starts_with("text/plain; version=0.0.4"));
+
+        let body = resp.body.unwrap();
+        for line in [
+            "# TYPE systemd_timer_enabled gauge",
+            "systemd_timer_enabled{timer=\"backup.timer\"} 1",
+            "systemd_timer_enabled{timer=\"stale.timer\"} 1",
+            "systemd_timer_next_run_seconds{timer=\"backup.timer\"} 4071849600",
+            "systemd_timer_next_run_seconds{timer=\"stale.timer\"} 1579095000",
+            "systemd_timer_last_run_success{timer=\"backup.timer\"} 1",
+            "systemd_timer_last_run_success{timer=\"stale.timer\"} 0",
+            "systemd_timer_last_duration_seconds{timer=\"backup.timer\"} 42",
+            "systemd_timer_last_duration_seconds{timer=\"stale.timer\"} 7",
+            "systemd_timer_overdue{timer=\"backup.timer\"} 0",
+            "systemd_timer_overdue{timer=\"stale.timer\"} 1",
+        ] {
+            assert!(body.lines().any(|l| l == line), "missing line {:?} in:\n{}", line, body);
+        }
+
+        // Timers that can't be queried are left out
+        assert!(!body.contains("gone.timer"));
     }
 }
@@ -1,5 +1,7 @@
+use crate::clock::{Clock, SystemClock};
 use crate::command::CommandExecutor;
 use crate::error::{TimerError, TimerResult};
+use crate::timestamp::relative_systemd_timestamp_at;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -25,10 +27,19 @@ pub enum TriggerType {
 pub struct ExecutionHistory {
     pub invocation_id: String,
     pub start_time: String,
+    /// `start_time` relative to when the response was built, e.g. "3 minutes ago"
+    #[serde(default)]
+    pub start_time_relative: Option<String>,
     pub end_time: Option<String>,
     pub duration_secs: Option<u64>,
     pub status: ExecutionStatus,
     pub exit_code: Option<i32>,
+    /// Signal that terminated the process, e.g. "SIGKILL"
+    #[serde(default)]
+    pub signal: Option<String>,
+    /// Why a failed run failed, when it's more than a non-zero exit code
+    #[serde(default)]
+    pub failure_reason: Option<String>,
     pub trigger: TriggerType,
 }
 
@@ -37,10 +48,16 @@ pub struct ExecutionHistory {
 pub struct ExecutionDetails {
     pub invocation_id: String,
     pub start_time: String,
+    #[serde(default)]
+    pub start_time_relative: Option<String>,
     pub end_time: Option<String>,
     pub duration_secs: Option<u64>,
     pub status: ExecutionStatus,
     pub exit_code: Option<i32>,
+    #[serde(default)]
+    pub signal: Option<String>,
+    #[serde(default)]
+    pub failure_reason: Option<String>,
     pub trigger: TriggerType,
     pub output: Vec<String>,
 }
@@ -51,27 +68,138 @@ struct JournalEntry {
     #[serde(rename = "INVOCATION_ID")]
     invocation_id: Option<String>,
 
+    /// Set on the unit's own output rather than systemd's messages about it
+    #[serde(rename = "_SYSTEMD_INVOCATION_ID")]
+    systemd_invocation_id: Option<String>,
+
     #[serde(rename = "__REALTIME_TIMESTAMP")]
     timestamp: Option<String>,
 
-    #[serde(rename = "MESSAGE")]
+    #[serde(rename = "MESSAGE", default, deserialize_with = "deserialize_message")]
     message: Option<String>,
 
     #[serde(rename = "EXIT_STATUS")]
     exit_status: Option<String>,
 
+    /// How the main process ended: "exited", "killed" or "dumped"
+    #[serde(rename = "EXIT_CODE")]
+    exit_code: Option<String>,
+
     #[serde(rename = "_SYSTEMD_UNIT")]
     unit: Option<String>,
 }
 
+/// Output line shown in place of a message journalctl withheld for size
+pub const TRUNCATED_MESSAGE_MARKER: &str = "[message too large, truncated]";
+
+/// journalctl output lines longer than this are skipped rather than parsed
+pub const MAX_JOURNAL_LINE_BYTES: usize = 1024 * 1024;
+
+/// Read MESSAGE as text whatever form journalctl chose for it
+///
+/// Fields over 4096 bytes come out as `null` and non-UTF-8 ones as an array
+/// of byte values; a missing field stays None.
+fn deserialize_message<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let value = serde_json::Value::deserialize(deserializer)?;
+
+    Ok(Some(match value {
+        serde_json::Value::String(text) => text,
+        serde_json::Value::Array(bytes) => {
+            let bytes: Vec<u8> = bytes.iter().filter_map(|b| b.as_u64()).map(|b| b as u8).collect();
+            String::from_utf8_lossy(&bytes).into_owned()
+        }
+        serde_json::Value::Null => TRUNCATED_MESSAGE_MARKER.to_string(),
+        other => other.to_string(),
+    }))
+}
+
+/// Where a timer's execution history is read from in the journal
+///
+/// Stored per timer in `PluginSettings::history_sources` as `{"unit": ...}`
+/// or `{"identifier": ...}`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum JournalSource {
+    /// Entries of this unit (`journalctl -u`)
+    Unit(String),
+    /// Entries logged under this `SYSLOG_IDENTIFIER` (`journalctl -t`)
+    Identifier(String),
+}
+
+impl JournalSource {
+    /// Check the unit or identifier is safe to hand to journalctl
+    pub fn validate(&self) -> TimerResult<()> {
+        let (kind, value) = match self {
+            Self::Unit(unit) => ("unit", unit),
+            Self::Identifier(identifier) => ("identifier", identifier),
+        };
+
+        let valid = !value.is_empty()
+            && value.len() <= 256
+            && !value.starts_with('-')
+            && value.chars().all(|c| c.is_ascii_alphanumeric() || "_-.@:".contains(c));
+
+        if !valid {
+            return Err(TimerError::InvalidInput(format!("Invalid journal {}: {}", kind, value)));
+        }
+        Ok(())
+    }
+
+    /// journalctl match arguments selecting this source
+    fn journalctl_args(&self) -> [&str; 2] {
+        match self {
+            Self::Unit(unit) => ["-u", unit],
+            Self::Identifier(identifier) => ["-t", identifier],
+        }
+    }
+}
+
 /// Journal client for querying execution history
-pub struct JournalClient<E: CommandExecutor> {
+pub struct JournalClient<'c, E: CommandExecutor> {
     executor: E,
+    /// `--namespace=` argument for services logging to a dedicated journal namespace
+    namespace_arg: Option<String>,
+    /// Clock `start_time_relative` is computed against
+    clock: &'c dyn Clock,
 }
 
-impl<E: CommandExecutor> JournalClient<E> {
+impl<'c, E: CommandExecutor> JournalClient<'c, E> {
     pub fn new(executor: E) -> Self {
-        Self { executor }
+        Self { executor, namespace_arg: None, clock: &SystemClock }
+    }
+
+    /// Compute relative start times against `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: &'c dyn Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Read from the journal namespace `namespace` instead of the default journal
+    ///
+    /// Namespaces are directory names under /var/log/journal: letters,
+    /// digits, `_`, `-` and `.` (not leading), up to 64 characters.
+    pub fn with_namespace(mut self, namespace: &str) -> TimerResult<Self> {
+        let valid = !namespace.is_empty()
+            && namespace.len() <= 64
+            && !namespace.starts_with('.')
+            && namespace.chars().all(|c| c.is_ascii_alphanumeric() || "_-.".contains(c));
+
+        if !valid {
+            return Err(TimerError::InvalidInput(format!("Invalid journal namespace: {}", namespace)));
+        }
+
+        self.namespace_arg = Some(format!("--namespace={}", namespace));
+        Ok(self)
+    }
+
+    /// Run journalctl with `args`, selecting the namespace if one is set
+    async fn journalctl(&self, args: &[&str]) -> TimerResult<crate::command::CommandOutput> {
+        let mut all_args = args.to_vec();
+        if let Some(namespace_arg) = &self.namespace_arg {
+            all_args.push(namespace_arg);
+        }
+
+        self.executor.execute("journalctl", &all_args).await
     }
 
     /// Get execution history for a service
@@ -80,18 +208,34 @@ impl<E: CommandExecutor> JournalClient<E> {
         service: &str,
         limit: usize,
     ) -> TimerResult<Vec<ExecutionHistory>> {
-        let output = self.executor
-            .execute("journalctl", &[
-                "-u", service,
+        self.get_execution_history_from(&JournalSource::Unit(service.to_string()), limit).await
+    }
+
+    /// Get execution history from a unit or syslog identifier
+    pub async fn get_execution_history_from(
+        &self,
+        source: &JournalSource,
+        limit: usize,
+    ) -> TimerResult<Vec<ExecutionHistory>> {
+        source.validate()?;
+        let [match_flag, match_value] = source.journalctl_args();
+
+        let output = self
+            .journalctl(&[
+                match_flag, match_value,
                 "--since", "7 days ago",
                 "-o", "json",
                 "--no-pager",
             ])
             .await?;
 
+        if output.exit_code != 0 && Self::is_no_entries(&output) {
+            return Ok(Vec::new());
+        }
+
         if output.exit_code != 0 {
             return Err(TimerError::CommandFailed {
-                command: format!("journalctl -u {}", service),
+                command: format!("journalctl {} {}", match_flag, match_value),
                 stderr: output.stderr,
                 exit_code: Some(output.exit_code),
             });
@@ -103,6 +247,60 @@ impl<E: CommandExecutor> JournalClient<E> {
         Ok(history)
     }
 
+    /// Get the newest `lines` journal entries of a unit or syslog identifier
+    /// from the last 7 days, as the JSON lines journalctl printed
+    ///
+    /// Nothing is parsed, so this shows which fields the entries really carry.
+    pub async fn get_raw_entries_from(
+        &self,
+        source: &JournalSource,
+        lines: usize,
+    ) -> TimerResult<Vec<String>> {
+        source.validate()?;
+        let [match_flag, match_value] = source.journalctl_args();
+        let lines_arg = lines.to_string();
+
+        let output = self
+            .journalctl(&[
+                match_flag, match_value,
+                "--since", "7 days ago",
+                "-n", &lines_arg,
+                "-o", "json",
+                "--no-pager",
+            ])
+            .await?;
+
+        if output.exit_code != 0 && Self::is_no_entries(&output) {
+            return Ok(Vec::new());
+        }
+
+        if output.exit_code != 0 {
+            return Err(TimerError::CommandFailed {
+                command: format!("journalctl {} {}", match_flag, match_value),
+                stderr: output.stderr,
+                exit_code: Some(output.exit_code),
+            });
+        }
+
+        Ok(output.stdout
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Whether a failed journalctl run only means nothing was logged
+    ///
+    /// Some journalctl versions exit non-zero when no entry matches, or when
+    /// no journal file exists yet, instead of printing nothing.
+    fn is_no_entries(output: &crate::command::CommandOutput) -> bool {
+        let stderr = output.stderr.trim();
+        output.stdout.trim().is_empty()
+            && (stderr.is_empty()
+                || stderr.contains("-- No entries --")
+                || stderr.contains("No journal files were found"))
+    }
+
     /// Get detailed execution information including output
     pub async fn get_execution_details(
         &self,
@@ -110,8 +308,8 @@ impl<E: CommandExecutor> JournalClient<E> {
         invocation_id: &str,
     ) -> TimerResult<ExecutionDetails> {
         let invocation_filter = format!("INVOCATION_ID={}", invocation_id);
-        let output = self.executor
-            .execute("journalctl", &[
+        let output = self
+            .journalctl(&[
                 "-u", service,
                 &invocation_filter,
                 "-o", "json",
@@ -141,6 +339,12 @@ impl<E: CommandExecutor> JournalClient<E> {
                 continue;
             }
 
+            // Don't let one pathological entry balloon memory while parsing
+            if line.len() > MAX_JOURNAL_LINE_BYTES {
+                eprintln!("Warning: Skipping oversized journal line ({} bytes)", line.len());
+                continue;
+            }
+
             match serde_json::from_str::<JournalEntry>(line) {
                 Ok(entry) => entries.push(entry),
                 Err(e) => {
@@ -163,7 +367,8 @@ impl<E: CommandExecutor> JournalClient<E> {
         let mut invocations: HashMap<String, Vec<JournalEntry>> = HashMap::new();
 
         for entry in entries {
-            if let Some(id) = &entry.invocation_id {
+            let id = entry.invocation_id.as_ref().or(entry.systemd_invocation_id.as_ref());
+            if let Some(id) = id {
                 invocations.entry(id.clone()).or_default().push(entry);
             }
         }
@@ -204,14 +409,27 @@ impl<E: CommandExecutor> JournalClient<E> {
             None
         };
 
-        let exit_code = entries
-            .iter()
-            .rev()
-            .find_map(|e| e.exit_status.as_ref())
-            .and_then(|s| s.parse::<i32>().ok());
+        let signal = Self::termination_signal(&entries);
+
+        // For signal kills EXIT_STATUS carries the signal number, not an exit code
+        let exit_code = if signal.is_some() {
+            None
+        } else {
+            Self::main_exit_code(&entries)
+        };
+
+        let failure_reason = signal.as_ref().map(|signal| {
+            if Self::was_oom_killed(&entries) {
+                format!("killed by {} (OOM)", signal)
+            } else {
+                format!("killed by {}", signal)
+            }
+        });
 
         let status = if end_time.is_none() {
             ExecutionStatus::Running
+        } else if signal.is_some() {
+            ExecutionStatus::Failed
         } else if exit_code == Some(0) {
             ExecutionStatus::Success
         } else if exit_code.is_some() {
@@ -223,13 +441,18 @@ impl<E: CommandExecutor> JournalClient<E> {
 
         let trigger = self.determine_trigger(&entries);
 
+        let start_time = Self::format_timestamp(&start_time);
+
         Ok(ExecutionHistory {
             invocation_id: invocation_id.to_string(),
-            start_time: Self::format_timestamp(&start_time),
+            start_time_relative: relative_systemd_timestamp_at(&start_time, self.clock.now()),
+            start_time,
             end_time: end_time.as_ref().map(|t| Self::format_timestamp(t)),
             duration_secs,
             status,
             exit_code,
+            signal,
+            failure_reason,
             trigger,
         })
     }
@@ -250,15 +473,105 @@ impl<E: CommandExecutor> JournalClient<E> {
         Ok(ExecutionDetails {
             invocation_id: history.invocation_id,
             start_time: history.start_time,
+            start_time_relative: history.start_time_relative,
             end_time: history.end_time,
             duration_secs: history.duration_secs,
             status: history.status,
             exit_code: history.exit_code,
+            signal: history.signal,
+            failure_reason: history.failure_reason,
             trigger: history.trigger,
             output,
         })
     }
 
+    /// Find the signal that killed the main process, if it was killed
+    ///
+    /// Uses the structured EXIT_CODE/EXIT_STATUS fields, falling back to
+    /// systemd's "Main process exited, code=killed, status=9/KILL" message.
+    fn termination_signal(entries: &[JournalEntry]) -> Option<String> {
+        entries.iter().rev().find_map(|e| {
+            let structured = match (e.exit_code.as_deref(), e.exit_status.as_deref()) {
+                (Some("killed" | "dumped"), Some(status)) => Some(Self::signal_name(status)),
+                _ => None,
+            };
+
+            structured.or_else(|| {
+                let (code, status, name) = Self::parse_main_process_exit(e.message.as_deref()?)?;
+                if code != "killed" && code != "dumped" {
+                    return None;
+                }
+                // Prefer the name systemd already resolved ("9/KILL")
+                Some(Self::signal_name(name.unwrap_or(status)))
+            })
+        })
+    }
+
+    /// Exit code of the main process, from EXIT_STATUS or systemd's own message
+    ///
+    /// Oneshot services often log no EXIT_STATUS field; systemd's
+    /// "Main process exited, code=exited, status=1/FAILURE" line still has it.
+    fn main_exit_code(entries: &[JournalEntry]) -> Option<i32> {
+        let structured = entries
+            .iter()
+            .rev()
+            .find_map(|e| e.exit_status.as_ref())
+            .and_then(|s| s.parse::<i32>().ok());
+
+        structured.or_else(|| {
+            entries.iter().rev().find_map(|e| {
+                match Self::parse_main_process_exit(e.message.as_deref()?)? {
+                    ("exited", status, _) => status.parse().ok(),
+                    _ => None,
+                }
+            })
+        })
+    }
+
+    /// Split "...: Main process exited, code=exited, status=1/FAILURE" into
+    /// ("exited", "1", Some("FAILURE"))
+    fn parse_main_process_exit(message: &str) -> Option<(&str, &str, Option<&str>)> {
+        let rest = message.split_once("Main process exited, code=")?.1;
+        let (code, rest) = rest.split_once(", status=")?;
+        let status = rest.split([',', ' ']).next()?;
+
+        match status.split_once('/') {
+            Some((value, name)) => Some((code, value, Some(name))),
+            None => Some((code, status, None)),
+        }
+    }
+
+    /// True if the kernel OOM killer or systemd-oomd ended the run
+    fn was_oom_killed(entries: &[JournalEntry]) -> bool {
+        entries.iter().any(|e| {
+            e.message.as_deref().is_some_and(|m| {
+                m.contains("OOM killer") || m.contains("'oom-kill'") || m.contains("systemd-oomd")
+            })
+        })
+    }
+
+    /// Normalize a signal number or name ("9", "KILL", "SIGKILL") to "SIGKILL"
+    fn signal_name(signal: &str) -> String {
+        let name = match signal.parse::<i32>() {
+            Ok(1) => "HUP",
+            Ok(2) => "INT",
+            Ok(3) => "QUIT",
+            Ok(4) => "ILL",
+            Ok(6) => "ABRT",
+            Ok(7) => "BUS",
+            Ok(8) => "FPE",
+            Ok(9) => "KILL",
+            Ok(11) => "SEGV",
+            Ok(13) => "PIPE",
+            Ok(14) => "ALRM",
+            Ok(15) => "TERM",
+            Ok(number) => return format!("signal {}", number),
+            Err(_) => signal.trim_start_matches("SIG"),
+        };
+
+        format!("SIG{}", name)
+    }
+
     /// Calculate duration between timestamps (in microseconds)
     fn calculate_duration(start: &str, end: &str) -> Option<u64> {
         let start_us: u64 = start.parse().ok()?;
@@ -369,13 +682,92 @@ mod tests {
         };
         mock.expect("journalctl -u test.service --since 7 days ago -o json --no-pager", output);
 
-        let client = JournalClient::new(mock);
+        let clock = crate::clock::FixedClock::new("2024-01-15T15:00:00Z".parse().unwrap());
+        let client = JournalClient::new(mock).with_clock(&clock);
         let history = client.get_execution_history("test.service", 10).await.unwrap();
 
         assert_eq!(history.len(), 1);
         assert_eq!(history[0].invocation_id, "abc123");
         assert_eq!(history[0].status, ExecutionStatus::Success);
         assert_eq!(history[0].duration_secs, Some(45));
+        assert_eq!(history[0].start_time_relative.as_deref(), Some("3 hours ago"));
+    }
+
+    #[tokio::test]
+    async fn test_get_execution_history_namespace() {
+        let mock = MockCommandExecutor::new();
+        let output = CommandOutput {
+            stdout: r#"{"INVOCATION_ID":"ns1","__REALTIME_TIMESTAMP":"1705320000000000","MESSAGE":"Starting"}
+{"INVOCATION_ID":"ns1","__REALTIME_TIMESTAMP":"1705320010000000","EXIT_STATUS":"0"}
+"#.to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        };
+        mock.expect("journalctl -u test.service --since 7 days ago -o json --no-pager --namespace=pipeline", output);
+
+        let client = JournalClient::new(mock).with_namespace("pipeline").unwrap();
+        let history = client.get_execution_history("test.service", 10).await.unwrap();
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].invocation_id, "ns1");
+    }
+
+    #[tokio::test]
+    async fn test_get_execution_history_by_identifier() {
+        let mock = MockCommandExecutor::new();
+        let output = CommandOutput {
+            stdout: r#"{"_SYSTEMD_INVOCATION_ID":"job1","__REALTIME_TIMESTAMP":"1705320000000000","MESSAGE":"backup started","SYSLOG_IDENTIFIER":"backup-job"}
+{"_SYSTEMD_INVOCATION_ID":"job1","__REALTIME_TIMESTAMP":"1705320030000000","MESSAGE":"backup done","SYSLOG_IDENTIFIER":"backup-job"}
+"#.to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        };
+        mock.expect("journalctl -t backup-job --since 7 days ago -o json --no-pager", output);
+
+        let client = JournalClient::new(mock);
+        let source = JournalSource::Identifier("backup-job".to_string());
+        let history = client.get_execution_history_from(&source, 10).await.unwrap();
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].invocation_id, "job1");
+        assert_eq!(history[0].duration_secs, Some(30));
+    }
+
+    #[tokio::test]
+    async fn test_get_execution_history_no_entries() {
+        let mock = MockCommandExecutor::new();
+        mock.expect("journalctl -u never.service --since 7 days ago -o json --no-pager", CommandOutput {
+            stdout: String::new(),
+            stderr: "-- No entries --\n".to_string(),
+            exit_code: 1,
+        });
+        mock.expect("journalctl -u denied.service --since 7 days ago -o json --no-pager", CommandOutput {
+            stdout: String::new(),
+            stderr: "Failed to open journal: Permission denied\n".to_string(),
+            exit_code: 1,
+        });
+        let client = JournalClient::new(mock);
+
+        assert!(client.get_execution_history("never.service", 10).await.unwrap().is_empty());
+        // Other failures are still errors
+        assert!(client.get_execution_history("denied.service", 10).await.is_err());
+    }
+
+    #[test]
+    fn test_journal_source_rejects_invalid() {
+        for value in ["", "--all", "a b", "x;y"] {
+            assert!(JournalSource::Identifier(value.to_string()).validate().is_err(), "{:?}", value);
+            assert!(JournalSource::Unit(value.to_string()).validate().is_err(), "{:?}", value);
+        }
+        assert!(JournalSource::Unit("getty@tty1.service".to_string()).validate().is_ok());
+    }
+
+    #[test]
+    fn test_with_namespace_rejects_invalid() {
+        for namespace in ["", ".hidden", "a/b", "foo --all", "ns;rm"] {
+            let result = JournalClient::new(MockCommandExecutor::new()).with_namespace(namespace);
+            assert!(matches!(result, Err(TimerError::InvalidInput(_))), "namespace {:?}", namespace);
+        }
     }
 
     #[tokio::test]
@@ -398,6 +790,95 @@ mod tests {
         assert_eq!(history[0].exit_code, Some(1));
     }
 
+    #[tokio::test]
+    async fn test_get_execution_history_oom_killed() {
+        let mock = MockCommandExecutor::new();
+        let output = CommandOutput {
+            stdout: r#"{"INVOCATION_ID":"oom1","__REALTIME_TIMESTAMP":"1705320000000000","MESSAGE":"Loading dataset","_SYSTEMD_UNIT":"test.service"}
+{"INVOCATION_ID":"oom1","__REALTIME_TIMESTAMP":"1705320030000000","MESSAGE":"test.service: A process of this unit has been killed by the OOM killer.","_SYSTEMD_UNIT":"init.scope"}
+{"INVOCATION_ID":"oom1","__REALTIME_TIMESTAMP":"1705320030100000","MESSAGE":"test.service: Main process exited, code=killed, status=9/KILL","EXIT_CODE":"killed","EXIT_STATUS":"9","_SYSTEMD_UNIT":"init.scope"}
+{"INVOCATION_ID":"oom1","__REALTIME_TIMESTAMP":"1705320030200000","MESSAGE":"test.service: Failed with result 'oom-kill'.","_SYSTEMD_UNIT":"init.scope"}
+"#.to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        };
+        mock.expect("journalctl -u test.service --since 7 days ago -o json --no-pager", output);
+
+        let client = JournalClient::new(mock);
+        let history = client.get_execution_history("test.service", 10).await.unwrap();
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].status, ExecutionStatus::Failed);
+        assert_eq!(history[0].exit_code, None);
+        assert_eq!(history[0].signal.as_deref(), Some("SIGKILL"));
+        assert_eq!(history[0].failure_reason.as_deref(), Some("killed by SIGKILL (OOM)"));
+    }
+
+    #[tokio::test]
+    async fn test_get_execution_history_exit_from_systemd_message() {
+        let mock = MockCommandExecutor::new();
+        // No EXIT_STATUS field anywhere; only systemd's message has the exit code
+        let output = CommandOutput {
+            stdout: r#"{"INVOCATION_ID":"one1","__REALTIME_TIMESTAMP":"1705320000000000","MESSAGE":"Syncing","_SYSTEMD_UNIT":"test.service"}
+{"INVOCATION_ID":"one1","__REALTIME_TIMESTAMP":"1705320010000000","MESSAGE":"test.service: Main process exited, code=exited, status=1/FAILURE","_SYSTEMD_UNIT":"init.scope"}
+"#.to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        };
+        mock.expect("journalctl -u test.service --since 7 days ago -o json --no-pager", output);
+
+        let client = JournalClient::new(mock);
+        let history = client.get_execution_history("test.service", 10).await.unwrap();
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].exit_code, Some(1));
+        assert_eq!(history[0].status, ExecutionStatus::Failed);
+        assert_eq!(history[0].signal, None);
+    }
+
+    #[tokio::test]
+    async fn test_get_execution_details_oversized_and_truncated_messages() {
+        let mock = MockCommandExecutor::new();
+        let huge = format!(
+            r#"{{"INVOCATION_ID":"big1","__REALTIME_TIMESTAMP":"1705320001000000","MESSAGE":"{}"}}"#,
+            "x".repeat(10 * 1024 * 1024)
+        );
+        let output = CommandOutput {
+            stdout: [
+                r#"{"INVOCATION_ID":"big1","__REALTIME_TIMESTAMP":"1705320000000000","MESSAGE":"Starting"}"#,
+                &huge,
+                r#"{"INVOCATION_ID":"big1","__REALTIME_TIMESTAMP":"1705320002000000","MESSAGE":null}"#,
+                r#"{"INVOCATION_ID":"big1","__REALTIME_TIMESTAMP":"1705320003000000","MESSAGE":[111,107]}"#,
+                r#"{"INVOCATION_ID":"big1","__REALTIME_TIMESTAMP":"1705320004000000","EXIT_STATUS":"0"}"#,
+            ]
+            .join("\n"),
+            stderr: String::new(),
+            exit_code: 0,
+        };
+        mock.expect("journalctl -u test.service INVOCATION_ID=big1 -o json --no-pager", output);
+
+        let client = JournalClient::new(mock);
+        let details = client.get_execution_details("test.service", "big1").await.unwrap();
+
+        assert_eq!(details.output, vec!["Starting", TRUNCATED_MESSAGE_MARKER, "ok"]);
+        assert_eq!(details.status, ExecutionStatus::Success);
+    }
+
+    #[test]
+    fn test_termination_signal_from_message_only() {
+        let entries: Vec<JournalEntry> = [
+            r#"{"MESSAGE":"Working"}"#,
+            r#"{"MESSAGE":"test.service: Main process exited, code=killed, status=15/TERM"}"#,
+        ]
+        .iter()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+        let signal = JournalClient::<crate::command::SystemCommandExecutor>::termination_signal(&entries);
+        assert_eq!(signal.as_deref(), Some("SIGTERM"));
+        assert!(!JournalClient::<crate::command::SystemCommandExecutor>::was_oom_killed(&entries));
+    }
+
     #[tokio::test]
     async fn test_get_execution_history_running() {
         let mock = MockCommandExecutor::new();
@@ -483,9 +964,11 @@ not json at all
         let entries = vec![
             JournalEntry {
                 invocation_id: Some("test".to_string()),
+                systemd_invocation_id: None,
                 timestamp: Some("123".to_string()),
                 message: Some("Started by timer".to_string()),
                 exit_status: None,
+                exit_code: None,
                 unit: Some("test.service".to_string()),
             }
         ];
@@ -500,9 +983,11 @@ not json at all
         let entries = vec![
             JournalEntry {
                 invocation_id: Some("test".to_string()),
+                systemd_invocation_id: None,
                 timestamp: Some("123".to_string()),
                 message: Some("Started manually via systemctl start".to_string()),
                 exit_status: None,
+                exit_code: None,
                 unit: Some("test.service".to_string()),
             }
         ];
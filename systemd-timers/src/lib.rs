@@ -1,10 +1,25 @@
+pub mod access;
+pub mod audit;
+pub mod calendar;
+pub mod clock;
 pub mod command;
 pub mod error;
 pub mod systemctl;
 pub mod schedule;
 pub mod journal;
 pub mod log_reader;
+pub mod metrics;
+pub mod rate_limit;
+pub mod reconcile;
+pub mod refresh_hint;
 pub mod handlers;
+pub mod settings;
+pub mod stats;
+pub mod timestamp;
+pub mod unit_writer;
+
+#[cfg(test)]
+mod test_support;
 
 pub use error::{TimerError, TimerResult};
 pub use command::CommandExecutor;
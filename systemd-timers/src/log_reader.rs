@@ -1,18 +1,28 @@
+use crate::clock::{Clock, SystemClock};
 use crate::command::CommandExecutor;
 use crate::error::{TimerError, TimerResult};
 use crate::journal::{ExecutionDetails, ExecutionHistory, ExecutionStatus, TriggerType};
+use crate::timestamp::relative_systemd_timestamp_at;
 
 /// Log directory base path
 const LOG_BASE_DIR: &str = "/var/log/timers";
 
 /// Log reader for file-based execution logs
-pub struct LogReader<E: CommandExecutor> {
+pub struct LogReader<'c, E: CommandExecutor> {
     executor: E,
+    /// Clock `start_time_relative` is computed against
+    clock: &'c dyn Clock,
 }
 
-impl<E: CommandExecutor> LogReader<E> {
+impl<'c, E: CommandExecutor> LogReader<'c, E> {
     pub fn new(executor: E) -> Self {
-        Self { executor }
+        Self { executor, clock: &SystemClock }
+    }
+
+    /// Compute relative start times against `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: &'c dyn Clock) -> Self {
+        self.clock = clock;
+        self
     }
 
     /// Get execution history from log files
@@ -100,11 +110,14 @@ impl<E: CommandExecutor> LogReader<E> {
 
         Ok(ExecutionHistory {
             invocation_id: timestamp.to_string(),
+            start_time_relative: relative_systemd_timestamp_at(&start_time, self.clock.now()),
             start_time,
             end_time,
             duration_secs,
             status,
             exit_code,
+            signal: None,
+            failure_reason: None,
             trigger: TriggerType::Scheduled, // Default, could be enhanced later
         })
     }
@@ -143,11 +156,14 @@ impl<E: CommandExecutor> LogReader<E> {
 
         Ok(ExecutionDetails {
             invocation_id: timestamp.to_string(),
+            start_time_relative: relative_systemd_timestamp_at(&start_time, self.clock.now()),
             start_time,
             end_time,
             duration_secs,
             status,
             exit_code,
+            signal: None,
+            failure_reason: None,
             trigger: TriggerType::Scheduled,
             output,
         })
@@ -232,7 +248,7 @@ mod tests {
 
     #[test]
     fn test_extract_value() {
-        let reader = LogReader { executor: crate::command::SystemCommandExecutor };
+        let reader = LogReader::new(crate::command::SystemCommandExecutor);
 
         let line = "[END] 2026-01-15T14:00:45+01:00 exit_code=0 duration=45s";
         assert_eq!(LogReader::<crate::command::SystemCommandExecutor>::extract_value(line, "exit_code="), Some("0".to_string()));
@@ -241,7 +257,7 @@ mod tests {
 
     #[test]
     fn test_parse_end_line() {
-        let reader = LogReader { executor: crate::command::SystemCommandExecutor };
+        let reader = LogReader::new(crate::command::SystemCommandExecutor);
 
         let line = "[END] 2026-01-15T14:00:45+01:00 exit_code=0 duration=45s";
         let (end_time, exit_code, duration, status) = reader.parse_end_line(line);
@@ -254,7 +270,7 @@ mod tests {
 
     #[test]
     fn test_parse_end_line_failed() {
-        let reader = LogReader { executor: crate::command::SystemCommandExecutor };
+        let reader = LogReader::new(crate::command::SystemCommandExecutor);
 
         let line = "[END] 2026-01-15T14:02:00+01:00 exit_code=1 duration=120s";
         let (end_time, exit_code, duration, status) = reader.parse_end_line(line);
@@ -266,7 +282,7 @@ mod tests {
 
     #[test]
     fn test_parse_end_line_no_end() {
-        let reader = LogReader { executor: crate::command::SystemCommandExecutor };
+        let reader = LogReader::new(crate::command::SystemCommandExecutor);
 
         let line = "Some random log line";
         let (end_time, exit_code, duration, status) = reader.parse_end_line(line);
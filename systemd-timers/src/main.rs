@@ -2,7 +2,15 @@ use serde_json::json;
 use std::collections::HashMap;
 use std::env;
 use std::sync::Arc;
+use std::time::Instant;
+use systemd_timers::clock::{Clock, SystemClock};
 use systemd_timers::command::SystemCommandExecutor;
+use systemd_timers::rate_limit::RateLimiter;
+use systemd_timers::reconcile;
+use systemd_timers::refresh_hint::RefreshAdvisor;
+use systemd_timers::stats::{CountingExecutor, PluginStats};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 use toru_plugin_api::{
     HttpRequest, HttpResponse, KvOp, Message, MessagePayload, PluginContext,
     PluginError, PluginKvStore, PluginMetadata, PluginProtocol, ToruPlugin,
@@ -10,14 +18,67 @@ use toru_plugin_api::{
 
 struct SystemdTimersPlugin {
     ctx: Option<PluginContext>,
-    executor: Arc<SystemCommandExecutor>,
+    executor: Arc<CountingExecutor<SystemCommandExecutor>>,
+    rate_limiter: Arc<RateLimiter>,
+    refresh_advisor: RefreshAdvisor,
+    clock: Arc<dyn Clock>,
+    stats: Arc<PluginStats>,
+    /// Handle on the standalone KV file so `/settings/reload` can re-read it
+    kv_file: Option<FileKvStore>,
+    /// Background reconciler, when `reconcile_interval_secs` enables it
+    reconciler: Option<(CancellationToken, JoinHandle<()>)>,
 }
 
 impl SystemdTimersPlugin {
     fn new() -> Self {
+        let stats = Arc::new(PluginStats::new());
         Self {
             ctx: None,
-            executor: Arc::new(SystemCommandExecutor),
+            executor: Arc::new(CountingExecutor::new(SystemCommandExecutor, stats.clone())),
+            rate_limiter: Arc::new(RateLimiter::new()),
+            refresh_advisor: RefreshAdvisor::new(),
+            clock: Arc::new(SystemClock),
+            stats,
+            kv_file: None,
+            reconciler: None,
+        }
+    }
+
+    /// Start the background reconciler if the settings enable it
+    async fn start_reconciler(&mut self, kv_file: FileKvStore) {
+        self.stop_reconciler().await;
+
+        let interval = match systemd_timers::settings::PluginSettings::load(&kv_file).await {
+            Ok(settings) => settings.reconcile_interval(),
+            Err(e) => {
+                eprintln!("[SystemdTimersPlugin] Failed to read reconcile interval: {}", e);
+                None
+            }
+        };
+        let Some(interval) = interval else {
+            return;
+        };
+
+        eprintln!("[SystemdTimersPlugin] Reconciling every {}s", interval.as_secs());
+        let token = CancellationToken::new();
+        let handle = reconcile::spawn_reconciler(
+            self.executor.clone(),
+            kv_file,
+            self.rate_limiter.clone(),
+            self.clock.clone(),
+            interval,
+            token.clone(),
+        );
+        self.reconciler = Some((token, handle));
+    }
+
+    /// Cancel the background reconciler and wait for its current pass to end
+    async fn stop_reconciler(&mut self) {
+        if let Some((token, handle)) = self.reconciler.take() {
+            token.cancel();
+            if let Err(e) = handle.await {
+                eprintln!("[SystemdTimersPlugin] Reconciler ended abnormally: {}", e);
+            }
         }
     }
 
@@ -42,24 +103,9 @@ impl SystemdTimersPlugin {
             .map(|ctx| ctx.kv.as_ref())
             .ok_or(PluginError::NotInitialized)
     }
-}
-
-#[async_trait::async_trait]
-impl ToruPlugin for SystemdTimersPlugin {
-    fn metadata() -> PluginMetadata {
-        Self::metadata()
-    }
-
-    async fn init(&mut self, ctx: PluginContext) -> Result<(), PluginError> {
-        eprintln!(
-            "[SystemdTimersPlugin] Initializing with instance_id: {}",
-            ctx.instance_id
-        );
-        self.ctx = Some(ctx);
-        Ok(())
-    }
 
-    async fn handle_http(&self, req: HttpRequest) -> Result<HttpResponse, PluginError> {
+    /// Dispatch a request to its handler; `handle_http` wraps this to time it
+    async fn route(&self, req: HttpRequest) -> Result<HttpResponse, PluginError> {
         eprintln!(
             "[SystemdTimersPlugin] HTTP request: {} {}",
             req.method, req.path
@@ -68,6 +114,58 @@ impl ToruPlugin for SystemdTimersPlugin {
         let path_only = systemd_timers::handlers::path_without_query(&req.path);
         let query_params = systemd_timers::handlers::parse_query_params(&req.path);
 
+        // Read-only mode blocks every mutating request before it is routed
+        if systemd_timers::handlers::is_mutating_method(&req.method) {
+            let kv = self.kv_store()?;
+            if let Some(response) =
+                systemd_timers::handlers::check_read_only(kv, &req.method, path_only)
+                    .await
+                    .map_err(|e| PluginError::Internal(e.to_string()))?
+            {
+                return Ok(response);
+            }
+        }
+
+        // Repeated unit actions within the cooldown are rejected with a 429
+        let mut limited_action = None;
+        if req.method == "POST" {
+            if let Some((timer_name, action)) = systemd_timers::handlers::parse_action_path(path_only) {
+                let kv = self.kv_store()?;
+                let timer_name = systemd_timers::handlers::decode_path_segment(timer_name);
+                let now = Instant::now();
+                if let Some(response) = systemd_timers::handlers::check_rate_limit(
+                    &self.rate_limiter,
+                    kv,
+                    &timer_name,
+                    action,
+                    now,
+                )
+                .await
+                .map_err(|e| PluginError::Internal(e.to_string()))?
+                {
+                    return Ok(response);
+                }
+                limited_action = Some((timer_name, action, now));
+            }
+        }
+
+        let result = self.dispatch(&req, path_only, &query_params).await;
+
+        // A request rejected before the action ran doesn't start the cooldown
+        if let (Some((timer_name, action, now)), Ok(response)) = (&limited_action, &result) {
+            systemd_timers::handlers::release_rejected_action(&self.rate_limiter, timer_name, action, *now, response);
+        }
+
+        result
+    }
+
+    /// Run the handler matching the request's method and path
+    async fn dispatch(
+        &self,
+        req: &HttpRequest,
+        path_only: &str,
+        query_params: &HashMap<String, String>,
+    ) -> Result<HttpResponse, PluginError> {
         match (req.method.as_str(), path_only) {
             // Serve frontend bundle
             ("GET", "/bundle.js") => Ok(HttpResponse {
@@ -102,17 +200,38 @@ impl ToruPlugin for SystemdTimersPlugin {
                 })
             }
 
+            // GET /healthz - plugin liveness and systemd reachability
+            ("GET", "/healthz") => {
+                systemd_timers::handlers::handle_get_health(self.executor.clone())
+                    .await
+                    .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
+            // GET /debug/stats - plugin-internal request and command counters
+            ("GET", "/debug/stats") => {
+                systemd_timers::handlers::handle_get_stats(&self.stats)
+                    .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
+            // GET /metrics - Prometheus metrics for watched timers
+            ("GET", "/metrics") => {
+                let kv = self.kv_store()?;
+                systemd_timers::handlers::handle_get_metrics(self.executor.clone(), kv, self.clock.as_ref())
+                    .await
+                    .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
             // GET /timers - watched timers with status
             ("GET", "/timers") => {
                 let kv = self.kv_store()?;
-                systemd_timers::handlers::handle_get_timers(self.executor.clone(), kv)
+                systemd_timers::handlers::handle_get_timers(self.executor.clone(), kv, &self.refresh_advisor, self.clock.as_ref())
                     .await
                     .map_err(|e| PluginError::Internal(e.to_string()))
             }
 
-            // GET /timers/available - all systemd timers
+            // GET /timers/available?state= - all systemd timers, optionally filtered
             ("GET", "/timers/available") => {
-                systemd_timers::handlers::handle_get_available_timers(self.executor.clone())
+                systemd_timers::handlers::handle_get_available_timers(self.executor.clone(), query_params, self.clock.as_ref())
                     .await
                     .map_err(|e| PluginError::Internal(e.to_string()))
             }
@@ -129,81 +248,169 @@ impl ToruPlugin for SystemdTimersPlugin {
             ("POST", "/timers/settings") => {
                 let kv = self.kv_store()?;
                 let body = req.body.as_deref().unwrap_or("{}");
-                systemd_timers::handlers::handle_save_settings(kv, body)
+                systemd_timers::handlers::handle_save_settings(self.executor.clone(), kv, body)
+                    .await
+                    .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
+            // POST /timers/import - replace the watched list and notes in one go
+            ("POST", "/timers/import") => {
+                let kv = self.kv_store()?;
+                let body = req.body.as_deref().unwrap_or("{}");
+                systemd_timers::handlers::handle_import_timers(self.executor.clone(), kv, body)
+                    .await
+                    .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
+            // POST /settings/reload - pick up settings edited outside the plugin
+            ("POST", "/settings/reload") => {
+                if let Some(kv_file) = &self.kv_file {
+                    if let Err(e) = kv_file.reload() {
+                        return systemd_timers::handlers::error_response(
+                            500,
+                            &format!("Failed to reload KV file: {}", e),
+                        )
+                        .map_err(|e| PluginError::Internal(e.to_string()));
+                    }
+                }
+                let kv = self.kv_store()?;
+                systemd_timers::handlers::handle_reload_settings(kv)
+                    .await
+                    .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
+            // GET /audit - recent mutating actions
+            ("GET", "/audit") => {
+                let kv = self.kv_store()?;
+                systemd_timers::handlers::handle_get_audit(kv)
+                    .await
+                    .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
+            // POST /timers/create - write a new scheduled job
+            ("POST", "/timers/create") => {
+                let kv = self.kv_store()?;
+                let body = req.body.as_deref().unwrap_or("{}");
+                systemd_timers::handlers::handle_create_timer(self.executor.clone(), kv, body, self.clock.as_ref())
                     .await
                     .map_err(|e| PluginError::Internal(e.to_string()))
             }
 
             // POST /timers/:name/run - run timer
             ("POST", path) if path.starts_with("/timers/") && path.ends_with("/run") => {
-                let timer_name = path
-                    .trim_start_matches("/timers/")
-                    .trim_end_matches("/run");
-                systemd_timers::handlers::handle_run_timer(self.executor.clone(), timer_name)
+                let kv = self.kv_store()?;
+                let timer_name = systemd_timers::handlers::decode_path_segment(
+                    path.trim_start_matches("/timers/").trim_end_matches("/run"),
+                );
+                systemd_timers::handlers::handle_run_timer(self.executor.clone(), kv, &timer_name, self.clock.as_ref())
                     .await
                     .map_err(|e| PluginError::Internal(e.to_string()))
             }
 
             // POST /timers/:name/test - test timer
             ("POST", path) if path.starts_with("/timers/") && path.ends_with("/test") => {
-                let timer_name = path
-                    .trim_start_matches("/timers/")
-                    .trim_end_matches("/test");
-                systemd_timers::handlers::handle_test_timer(self.executor.clone(), timer_name)
+                let kv = self.kv_store()?;
+                let timer_name = systemd_timers::handlers::decode_path_segment(
+                    path.trim_start_matches("/timers/").trim_end_matches("/test"),
+                );
+                systemd_timers::handlers::handle_test_timer(self.executor.clone(), kv, &timer_name, self.clock.as_ref())
                     .await
                     .map_err(|e| PluginError::Internal(e.to_string()))
             }
 
             // POST /timers/:name/enable - enable timer
             ("POST", path) if path.starts_with("/timers/") && path.ends_with("/enable") => {
-                let timer_name = path
-                    .trim_start_matches("/timers/")
-                    .trim_end_matches("/enable");
-                systemd_timers::handlers::handle_enable_timer(self.executor.clone(), timer_name)
+                let kv = self.kv_store()?;
+                let timer_name = systemd_timers::handlers::decode_path_segment(
+                    path.trim_start_matches("/timers/").trim_end_matches("/enable"),
+                );
+                systemd_timers::handlers::handle_enable_timer(self.executor.clone(), kv, &timer_name, self.clock.as_ref())
                     .await
                     .map_err(|e| PluginError::Internal(e.to_string()))
             }
 
             // POST /timers/:name/disable - disable timer
             ("POST", path) if path.starts_with("/timers/") && path.ends_with("/disable") => {
-                let timer_name = path
-                    .trim_start_matches("/timers/")
-                    .trim_end_matches("/disable");
-                systemd_timers::handlers::handle_disable_timer(self.executor.clone(), timer_name)
+                let kv = self.kv_store()?;
+                let timer_name = systemd_timers::handlers::decode_path_segment(
+                    path.trim_start_matches("/timers/").trim_end_matches("/disable"),
+                );
+                systemd_timers::handlers::handle_disable_timer(self.executor.clone(), kv, &timer_name, self.clock.as_ref())
                     .await
                     .map_err(|e| PluginError::Internal(e.to_string()))
             }
 
-            // GET /timers/:name/history/:id - execution details
-            ("GET", path) if path.starts_with("/timers/") && path.contains("/history/") => {
-                let parts: Vec<&str> = path.trim_start_matches("/timers/").split("/history/").collect();
-                if parts.len() == 2 {
-                    let timer_name = parts[0];
-                    let invocation_id = parts[1];
-                    systemd_timers::handlers::handle_get_history_details(
-                        self.executor.clone(),
-                        timer_name,
-                        invocation_id,
-                    )
+            // POST /timers/:name/snooze - disable timer until a given time
+            ("POST", path) if path.starts_with("/timers/") && path.ends_with("/snooze") => {
+                let kv = self.kv_store()?;
+                let timer_name = systemd_timers::handlers::decode_path_segment(
+                    path.trim_start_matches("/timers/").trim_end_matches("/snooze"),
+                );
+                let body = req.body.as_deref().unwrap_or("{}");
+                systemd_timers::handlers::handle_snooze_timer(self.executor.clone(), kv, &timer_name, body, self.clock.as_ref())
                     .await
                     .map_err(|e| PluginError::Internal(e.to_string()))
-                } else {
-                    systemd_timers::handlers::error_response(400, "Invalid path format")
-                        .map_err(|e| PluginError::Internal(e.to_string()))
-                }
             }
 
-            // GET /timers/:name/history - execution history
-            ("GET", path) if path.starts_with("/timers/") && path.ends_with("/history") => {
-                let timer_name = path
-                    .trim_start_matches("/timers/")
-                    .trim_end_matches("/history");
-                systemd_timers::handlers::handle_get_history(
-                    self.executor.clone(),
-                    timer_name,
-                    &query_params,
-                )
-                .await
+            // PUT /timers/:name/schedule - change a plugin-created timer's OnCalendar
+            ("PUT", path) if path.starts_with("/timers/") && path.ends_with("/schedule") => {
+                let kv = self.kv_store()?;
+                let timer_name = systemd_timers::handlers::decode_path_segment(
+                    path.trim_start_matches("/timers/").trim_end_matches("/schedule"),
+                );
+                let body = req.body.as_deref().unwrap_or("{}");
+                systemd_timers::handlers::handle_update_schedule(self.executor.clone(), kv, &timer_name, body, self.clock.as_ref())
+                    .await
+                    .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
+            // DELETE /timers/:name - remove a plugin-created timer
+            ("DELETE", path) if path.starts_with("/timers/") => {
+                let kv = self.kv_store()?;
+                let timer_name = systemd_timers::handlers::decode_path_segment(
+                    path.trim_start_matches("/timers/"),
+                );
+                systemd_timers::handlers::handle_delete_timer(self.executor.clone(), kv, &timer_name, self.clock.as_ref())
+                    .await
+                    .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
+            // GET /timers/:name/state - lightweight current state
+            ("GET", path) if path.starts_with("/timers/") && path.ends_with("/state") => {
+                let timer_name = systemd_timers::handlers::decode_path_segment(
+                    path.trim_start_matches("/timers/").trim_end_matches("/state"),
+                );
+
+                systemd_timers::handlers::handle_get_timer_state(self.executor.clone(), &timer_name)
+                    .await
+                    .map_err(|e| PluginError::Internal(e.to_string()))
+            }
+
+            // GET /timers/:name/history[/:id] - execution history or details
+            ("GET", path) if path.starts_with("/timers/") => {
+                match systemd_timers::handlers::parse_history_path(path) {
+                    Some((timer_name, Some(invocation_id))) => {
+                        systemd_timers::handlers::handle_get_history_details(
+                            self.executor.clone(),
+                            &systemd_timers::handlers::decode_path_segment(timer_name),
+                            &systemd_timers::handlers::decode_path_segment(invocation_id),
+                            self.clock.as_ref(),
+                        )
+                        .await
+                    }
+                    Some((timer_name, None)) => {
+                        let kv = self.kv_store()?;
+                        systemd_timers::handlers::handle_get_history(
+                            self.executor.clone(),
+                            kv,
+                            &systemd_timers::handlers::decode_path_segment(timer_name),
+                            query_params,
+                            self.clock.as_ref(),
+                        )
+                        .await
+                    }
+                    None => systemd_timers::handlers::error_response(404, "Not found"),
+                }
                 .map_err(|e| PluginError::Internal(e.to_string()))
             }
 
@@ -212,6 +419,66 @@ impl ToruPlugin for SystemdTimersPlugin {
                 .map_err(|e| PluginError::Internal(e.to_string())),
         }
     }
+}
+
+#[async_trait::async_trait]
+impl ToruPlugin for SystemdTimersPlugin {
+    fn metadata() -> PluginMetadata {
+        Self::metadata()
+    }
+
+    async fn init(&mut self, ctx: PluginContext) -> Result<(), PluginError> {
+        eprintln!(
+            "[SystemdTimersPlugin] Initializing with instance_id: {}",
+            ctx.instance_id
+        );
+        self.ctx = Some(ctx);
+        Ok(())
+    }
+
+    async fn handle_http(&self, mut req: HttpRequest) -> Result<HttpResponse, PluginError> {
+        let started = std::time::Instant::now();
+        let _in_flight = self.stats.request_started();
+        let target = format!("{} {}", req.method, req.path);
+
+        // CORS headers are only sent once an origin is configured
+        let cors_origin = match self.kv_store() {
+            Ok(kv) => systemd_timers::handlers::cors_origin(kv).await,
+            Err(_) => None,
+        };
+        if req.method == "OPTIONS" {
+            if let Some(origin) = &cors_origin {
+                let mut response = systemd_timers::handlers::preflight_response(origin);
+                systemd_timers::handlers::set_processing_time(&mut response, started.elapsed());
+                return Ok(response);
+            }
+        }
+
+        // HEAD is answered like GET, minus the body, for cheap availability checks
+        let head = req.method == "HEAD";
+        if head {
+            req.method = "GET".to_string();
+        }
+
+        let result = self.route(req).await;
+        match &result {
+            Err(e) => self.stats.record_error(&format!("{}: {}", target, e)),
+            Ok(response) if response.status >= 500 => {
+                self.stats.record_error(&format!("{}: HTTP {}", target, response.status))
+            }
+            Ok(_) => {}
+        }
+
+        let mut response = result?;
+        if head {
+            response.body = None;
+        }
+        if let Some(origin) = &cors_origin {
+            systemd_timers::handlers::set_cors_headers(&mut response, origin);
+        }
+        systemd_timers::handlers::set_processing_time(&mut response, started.elapsed());
+        Ok(response)
+    }
 
     async fn handle_kv(&mut self, op: KvOp) -> Result<Option<String>, PluginError> {
         eprintln!("[SystemdTimersPlugin] KV operation: {:?}", op);
@@ -291,10 +558,12 @@ async fn main() {
                                     if action == "init" {
                                         if let Some(init_payload) = payload {
                                             let plugin_id = SystemdTimersPlugin::metadata().id;
+                                            let kv_file = FileKvStore::new(&plugin_id);
+                                            plugin.kv_file = Some(kv_file.clone());
                                             let ctx = PluginContext {
                                                 instance_id: init_payload.instance_id.clone(),
                                                 config: toru_plugin_api::PluginConfig::default(),
-                                                kv: Box::new(FileKvStore::new(&plugin_id)),
+                                                kv: Box::new(kv_file.clone()),
                                             };
                                             if let Err(e) = plugin.init(ctx).await {
                                                 eprintln!(
@@ -302,9 +571,11 @@ async fn main() {
                                                     e
                                                 );
                                             }
+                                            plugin.start_reconciler(kv_file).await;
                                         }
                                     } else if action == "shutdown" {
                                         eprintln!("[SystemdTimersPlugin] Shutdown received");
+                                        plugin.stop_reconciler().await;
                                         std::process::exit(0);
                                     }
                                 }
@@ -410,17 +681,21 @@ fn create_http_response(request_id: String, http_response: HttpResponse) -> Mess
 // File-based KV store implementation for persistent settings
 use std::sync::Mutex;
 
+// Clones share the cache, so the plugin can reload the store it handed to its context
+#[derive(Clone)]
 struct FileKvStore {
     file_path: std::path::PathBuf,
-    cache: Mutex<HashMap<String, String>>,
+    cache: Arc<Mutex<HashMap<String, String>>>,
 }
 
 impl FileKvStore {
     fn new(plugin_id: &str) -> Self {
         let data_dir = std::path::PathBuf::from("/var/lib/toru-plugins");
         std::fs::create_dir_all(&data_dir).ok();
-        let file_path = data_dir.join(format!("{}.json", plugin_id));
+        Self::at(data_dir.join(format!("{}.json", plugin_id)))
+    }
 
+    fn at(file_path: std::path::PathBuf) -> Self {
         // Load existing data
         let cache = if file_path.exists() {
             std::fs::read_to_string(&file_path)
@@ -433,10 +708,25 @@ impl FileKvStore {
 
         Self {
             file_path,
-            cache: Mutex::new(cache),
+            cache: Arc::new(Mutex::new(cache)),
         }
     }
 
+    /// Replace the cache with the file's current contents
+    /// Unlike startup, an unreadable or malformed file is an error here so a
+    /// bad manual edit doesn't silently wipe the settings in memory
+    fn reload(&self) -> std::io::Result<()> {
+        let data = if self.file_path.exists() {
+            let content = std::fs::read_to_string(&self.file_path)?;
+            serde_json::from_str(&content)?
+        } else {
+            HashMap::new()
+        };
+
+        *self.cache.lock().unwrap() = data;
+        Ok(())
+    }
+
     fn save(&self) -> std::io::Result<()> {
         let cache = self.cache.lock().unwrap();
         let json = serde_json::to_string_pretty(&*cache)?;
@@ -494,4 +784,253 @@ mod tests {
         assert_eq!(parsed["id"], "systemd-timers");
         assert_eq!(parsed["route"], "/systemd-timers");
     }
+
+    #[tokio::test]
+    async fn test_head_returns_get_status_without_body() {
+        let plugin = SystemdTimersPlugin::new();
+        let request = |method: &str| HttpRequest {
+            method: method.to_string(),
+            path: "/".to_string(),
+            headers: HashMap::new(),
+            body: None,
+        };
+
+        let get = plugin.handle_http(request("GET")).await.unwrap();
+        let head = plugin.handle_http(request("HEAD")).await.unwrap();
+
+        assert_eq!(head.status, 200);
+        assert!(head.body.is_none());
+        assert!(get.body.is_some());
+        assert_eq!(head.headers["Content-Type"], get.headers["Content-Type"]);
+    }
+
+    #[tokio::test]
+    async fn test_cors_headers_when_origin_configured() {
+        let dir = std::env::temp_dir().join(format!("systemd-timers-cors-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file_path = dir.join("systemd-timers.json");
+        let settings = json!({ "cors_origin": "http://localhost:5173" }).to_string();
+        std::fs::write(&file_path, json!({ systemd_timers::settings::SETTINGS_KEY: settings }).to_string()).unwrap();
+
+        let mut plugin = SystemdTimersPlugin::new();
+        plugin
+            .init(PluginContext {
+                instance_id: "test".to_string(),
+                config: toru_plugin_api::PluginConfig::default(),
+                kv: Box::new(FileKvStore::at(file_path)),
+            })
+            .await
+            .unwrap();
+        let request = |method: &str| HttpRequest {
+            method: method.to_string(),
+            path: "/".to_string(),
+            headers: HashMap::new(),
+            body: None,
+        };
+
+        let preflight = plugin.handle_http(request("OPTIONS")).await.unwrap();
+        assert_eq!(preflight.status, 204);
+        assert!(preflight.body.is_none());
+        assert_eq!(preflight.headers["Access-Control-Allow-Origin"], "http://localhost:5173");
+        assert!(preflight.headers["Access-Control-Allow-Methods"].contains("POST"));
+        assert!(preflight.headers["Access-Control-Allow-Methods"].contains("DELETE"));
+
+        let response = plugin.handle_http(request("GET")).await.unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.headers["Access-Control-Allow-Origin"], "http://localhost:5173");
+
+        // Without an origin there are no CORS headers and no preflight handling
+        let plugin = SystemdTimersPlugin::new();
+        let response = plugin.handle_http(request("GET")).await.unwrap();
+        assert!(!response.headers.contains_key("Access-Control-Allow-Origin"));
+        assert_eq!(plugin.handle_http(request("OPTIONS")).await.unwrap().status, 404);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_rejected_action_does_not_start_cooldown() {
+        let dir = std::env::temp_dir().join(format!("systemd-timers-cooldown-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file_path = dir.join("systemd-timers.json");
+        let settings = json!({ "denied_units": ["backup.timer"] }).to_string();
+        std::fs::write(&file_path, json!({ systemd_timers::settings::SETTINGS_KEY: settings }).to_string()).unwrap();
+
+        let mut plugin = SystemdTimersPlugin::new();
+        plugin
+            .init(PluginContext {
+                instance_id: "test".to_string(),
+                config: toru_plugin_api::PluginConfig::default(),
+                kv: Box::new(FileKvStore::at(file_path)),
+            })
+            .await
+            .unwrap();
+        let run = || HttpRequest {
+            method: "POST".to_string(),
+            path: "/timers/backup.timer/run".to_string(),
+            headers: HashMap::new(),
+            body: None,
+        };
+
+        // Each attempt is refused by the access policy, never by the cooldown
+        assert_eq!(plugin.handle_http(run()).await.unwrap().status, 403);
+        assert_eq!(plugin.handle_http(run()).await.unwrap().status, 403);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_preflight_advertises_every_routed_method() {
+        // Collect the method of every `route` match arm, e.g. `("PUT", path)`
+        let source = include_str!("main.rs");
+        let routed: std::collections::BTreeSet<&str> = source
+            .lines()
+            .filter_map(|line| line.trim_start().strip_prefix("(\""))
+            .filter_map(|rest| rest.split_once("\", "))
+            .map(|(method, _)| method)
+            .filter(|method| !method.is_empty() && method.chars().all(|c| c.is_ascii_uppercase()))
+            .collect();
+        assert!(routed.contains("PUT") && routed.contains("DELETE"));
+
+        let response = systemd_timers::handlers::preflight_response("http://localhost:5173");
+        let advertised: Vec<&str> = response.headers["Access-Control-Allow-Methods"].split(", ").collect();
+        for method in routed {
+            assert!(advertised.contains(&method), "{} is routed but not advertised", method);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reconciler_is_opt_in_and_stops() {
+        let dir = std::env::temp_dir().join(format!("systemd-timers-reconcile-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file_path = dir.join("systemd-timers.json");
+        let mut plugin = SystemdTimersPlugin::new();
+
+        // Off by default
+        plugin.start_reconciler(FileKvStore::at(file_path.clone())).await;
+        assert!(plugin.reconciler.is_none());
+
+        let settings = json!({ "reconcile_interval_secs": 60 }).to_string();
+        std::fs::write(&file_path, json!({ systemd_timers::settings::SETTINGS_KEY: settings }).to_string()).unwrap();
+        plugin.start_reconciler(FileKvStore::at(file_path)).await;
+        assert!(plugin.reconciler.is_some());
+
+        plugin.stop_reconciler().await;
+        assert!(plugin.reconciler.is_none());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_responses_carry_processing_time() {
+        let plugin = SystemdTimersPlugin::new();
+
+        for path in ["/", "/no-such-route"] {
+            let response = plugin
+                .handle_http(HttpRequest {
+                    method: "GET".to_string(),
+                    path: path.to_string(),
+                    headers: HashMap::new(),
+                    body: None,
+                })
+                .await
+                .unwrap();
+
+            let value = &response.headers[systemd_timers::handlers::PROCESSING_TIME_HEADER];
+            let millis: f64 = value.parse().unwrap();
+            assert!(millis >= 0.0, "{}: {}", path, value);
+        }
+    }
+
+    fn reload_request() -> HttpRequest {
+        HttpRequest {
+            method: "POST".to_string(),
+            path: "/settings/reload".to_string(),
+            headers: HashMap::new(),
+            body: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_settings_reload_reflects_file_changes() {
+        let dir = std::env::temp_dir().join(format!("systemd-timers-kv-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file_path = dir.join("systemd-timers.json");
+        let write_settings = |timers: &[&str]| {
+            let settings = serde_json::to_string(&systemd_timers::settings::PluginSettings {
+                watched_timers: timers.iter().map(|t| t.to_string()).collect(),
+                ..Default::default()
+            })
+            .unwrap();
+            let data = json!({ systemd_timers::settings::SETTINGS_KEY: settings });
+            std::fs::write(&file_path, data.to_string()).unwrap();
+        };
+        write_settings(&["backup.timer"]);
+
+        let kv_file = FileKvStore::at(file_path.clone());
+        let mut plugin = SystemdTimersPlugin::new();
+        plugin.kv_file = Some(kv_file.clone());
+        plugin
+            .init(PluginContext {
+                instance_id: "test".to_string(),
+                config: toru_plugin_api::PluginConfig::default(),
+                kv: Box::new(kv_file),
+            })
+            .await
+            .unwrap();
+
+        // Edited behind the plugin's back: the cache still has the old value
+        write_settings(&["backup.timer", "cleanup.timer"]);
+        let settings = systemd_timers::settings::PluginSettings::load(plugin.kv_store().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(settings.watched_timers, vec!["backup.timer"]);
+
+        let response = plugin.handle_http(reload_request()).await.unwrap();
+        assert_eq!(response.status, 200);
+        let body: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+        assert_eq!(body["data"]["watched_timers"], json!(["backup.timer", "cleanup.timer"]));
+
+        let settings = systemd_timers::settings::PluginSettings::load(plugin.kv_store().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(settings.watched_timers, vec!["backup.timer", "cleanup.timer"]);
+
+        // A malformed file is reported and the cached settings are kept
+        std::fs::write(&file_path, "{ not json").unwrap();
+        let response = plugin.handle_http(reload_request()).await.unwrap();
+        assert_eq!(response.status, 500);
+        let settings = systemd_timers::settings::PluginSettings::load(plugin.kv_store().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(settings.watched_timers, vec!["backup.timer", "cleanup.timer"]);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_requests_update_stats() {
+        let plugin = SystemdTimersPlugin::new();
+        let get = |path: &str| HttpRequest {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            headers: HashMap::new(),
+            body: None,
+        };
+
+        plugin.handle_http(get("/")).await.unwrap();
+        // Without a context the KV store is unavailable, so this fails
+        assert!(plugin.handle_http(get("/timers")).await.is_err());
+
+        let response = plugin.handle_http(get("/debug/stats")).await.unwrap();
+        assert_eq!(response.status, 200);
+        let stats: systemd_timers::stats::StatsSnapshot =
+            serde_json::from_str(&response.body.unwrap()).unwrap();
+
+        // The stats request itself is counted and still in flight
+        assert_eq!(stats.requests_total, 3);
+        assert_eq!(stats.requests_in_flight, 1);
+        assert_eq!(stats.commands_total, 0);
+        assert!(stats.last_error.unwrap().starts_with("GET /timers: "));
+    }
 }
@@ -0,0 +1,94 @@
+use std::fmt::Write;
+
+/// Content type of the Prometheus text exposition format
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Metric values gathered for one watched timer
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TimerMetrics {
+    pub timer: String,
+    pub enabled: bool,
+    /// Next elapse as a Unix timestamp
+    pub next_run_seconds: Option<i64>,
+    /// Outcome of the latest finished run; None if it never ran or is running
+    pub last_run_success: Option<bool>,
+    pub last_duration_seconds: Option<u64>,
+    pub overdue: bool,
+}
+
+/// Render timer metrics in the Prometheus text exposition format
+///
+/// Samples whose value is unknown are omitted rather than reported as 0.
+pub fn render_prometheus(timers: &[TimerMetrics]) -> String {
+    let mut out = String::new();
+
+    write_family(&mut out, "systemd_timer_enabled", "Whether the timer is enabled and active (1) or not (0)", timers, |t| {
+        Some(t.enabled as u8 as f64)
+    });
+    write_family(&mut out, "systemd_timer_next_run_seconds", "Next scheduled run as a Unix timestamp", timers, |t| {
+        t.next_run_seconds.map(|s| s as f64)
+    });
+    write_family(&mut out, "systemd_timer_last_run_success", "Whether the latest finished run succeeded (1) or failed (0)", timers, |t| {
+        t.last_run_success.map(|ok| ok as u8 as f64)
+    });
+    write_family(&mut out, "systemd_timer_last_duration_seconds", "Duration of the latest finished run", timers, |t| {
+        t.last_duration_seconds.map(|s| s as f64)
+    });
+    write_family(&mut out, "systemd_timer_overdue", "Whether the timer missed its latest scheduled run (1) or not (0)", timers, |t| {
+        Some(t.overdue as u8 as f64)
+    });
+
+    out
+}
+
+/// Write one metric family (HELP, TYPE and a gauge sample per timer)
+fn write_family(
+    out: &mut String,
+    name: &str,
+    help: &str,
+    timers: &[TimerMetrics],
+    value: impl Fn(&TimerMetrics) -> Option<f64>,
+) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} gauge", name);
+
+    for timer in timers {
+        if let Some(value) = value(timer) {
+            let _ = writeln!(out, "{}{{timer=\"{}\"}} {}", name, escape_label_value(&timer.timer), value);
+        }
+    }
+}
+
+/// Escape a label value: backslash, double quote and newline
+pub fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_label_value() {
+        assert_eq!(escape_label_value("backup.timer"), "backup.timer");
+        assert_eq!(escape_label_value(r#"a"b\c"#), r#"a\"b\\c"#);
+        assert_eq!(escape_label_value("a\nb"), "a\\nb");
+    }
+
+    #[test]
+    fn test_render_omits_unknown_values() {
+        let timers = vec![TimerMetrics {
+            timer: "new.timer".to_string(),
+            ..Default::default()
+        }];
+
+        let text = render_prometheus(&timers);
+        assert!(text.contains("systemd_timer_enabled{timer=\"new.timer\"} 0\n"));
+        assert!(text.contains("# TYPE systemd_timer_next_run_seconds gauge\n"));
+        assert!(!text.contains("systemd_timer_next_run_seconds{"));
+        assert!(!text.contains("systemd_timer_last_run_success{"));
+    }
+}
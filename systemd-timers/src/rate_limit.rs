@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Legacy KV key holding the per unit+action cooldown in milliseconds (now part of the settings blob)
+pub const ACTION_COOLDOWN_KEY: &str = "action_cooldown_ms";
+
+/// Cooldown applied when no value is configured
+pub const DEFAULT_ACTION_COOLDOWN: Duration = Duration::from_secs(2);
+
+/// Rejects repeats of the same action on the same unit within a cooldown
+///
+/// Shared across concurrent requests; the check and the timestamp update
+/// happen under one lock so two racing requests cannot both get through.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    last_action: Mutex<HashMap<(String, String), Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an action, returning the remaining wait if it is still cooling down
+    pub fn check(&self, unit: &str, action: &str, cooldown: Duration) -> Result<(), Duration> {
+        self.check_at(unit, action, cooldown, Instant::now())
+    }
+
+    /// Same as `check` with an explicit clock reading
+    pub fn check_at(
+        &self,
+        unit: &str,
+        action: &str,
+        cooldown: Duration,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let mut last_action = self.last_action.lock().unwrap_or_else(|e| e.into_inner());
+        let key = (unit.to_string(), action.to_string());
+
+        if let Some(last) = last_action.get(&key) {
+            let elapsed = now.saturating_duration_since(*last);
+            if elapsed < cooldown {
+                return Err(cooldown - elapsed);
+            }
+        }
+
+        last_action.insert(key, now);
+        Ok(())
+    }
+
+    /// Forget the action recorded at `recorded_at`, as if it never happened
+    ///
+    /// For actions rejected before they ran, so the rejected request doesn't
+    /// start the cooldown. An entry recorded at another time, by a later
+    /// request, is kept.
+    pub fn release(&self, unit: &str, action: &str, recorded_at: Instant) {
+        let mut last_action = self.last_action.lock().unwrap_or_else(|e| e.into_inner());
+        let key = (unit.to_string(), action.to_string());
+
+        if last_action.get(&key) == Some(&recorded_at) {
+            last_action.remove(&key);
+        }
+    }
+
+    /// Forget actions older than `max_age`, returning how many were dropped
+    ///
+    /// Entries past the cooldown no longer limit anything; pruning keeps the
+    /// map from growing with every unit ever acted on.
+    pub fn prune(&self, max_age: Duration, now: Instant) -> usize {
+        let mut last_action = self.last_action.lock().unwrap_or_else(|e| e.into_inner());
+        let before = last_action.len();
+        last_action.retain(|_, last| now.saturating_duration_since(*last) < max_age);
+        before - last_action.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_immediate_repeat_is_limited() {
+        let limiter = RateLimiter::new();
+        let now = Instant::now();
+        let cooldown = Duration::from_secs(2);
+
+        assert!(limiter.check_at("backup.timer", "run", cooldown, now).is_ok());
+        let wait = limiter
+            .check_at("backup.timer", "run", cooldown, now + Duration::from_millis(500))
+            .unwrap_err();
+        assert_eq!(wait, Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn test_prune_drops_expired_entries() {
+        let limiter = RateLimiter::new();
+        let now = Instant::now();
+        let cooldown = Duration::from_secs(2);
+
+        limiter.check_at("backup.timer", "run", cooldown, now).unwrap();
+        limiter.check_at("cleanup.timer", "run", cooldown, now + Duration::from_secs(3)).unwrap();
+
+        assert_eq!(limiter.prune(cooldown, now + Duration::from_secs(4)), 1);
+        // The pruned entry no longer limits, the kept one still does
+        assert!(limiter.check_at("backup.timer", "run", cooldown, now + Duration::from_secs(4)).is_ok());
+        assert!(limiter.check_at("cleanup.timer", "run", cooldown, now + Duration::from_secs(4)).is_err());
+    }
+
+    #[test]
+    fn test_released_action_does_not_cool_down() {
+        let limiter = RateLimiter::new();
+        let now = Instant::now();
+        let cooldown = Duration::from_secs(2);
+
+        limiter.check_at("backup.timer", "run", cooldown, now).unwrap();
+        limiter.release("backup.timer", "run", now);
+        assert!(limiter.check_at("backup.timer", "run", cooldown, now + Duration::from_millis(500)).is_ok());
+
+        // A release for an older recording leaves the newer one in place
+        limiter.release("backup.timer", "run", now);
+        assert!(limiter.check_at("backup.timer", "run", cooldown, now + Duration::from_secs(1)).is_err());
+    }
+
+    #[test]
+    fn test_repeat_after_cooldown_succeeds() {
+        let limiter = RateLimiter::new();
+        let now = Instant::now();
+        let cooldown = Duration::from_secs(2);
+
+        assert!(limiter.check_at("backup.timer", "run", cooldown, now).is_ok());
+        assert!(limiter
+            .check_at("backup.timer", "run", cooldown, now + Duration::from_secs(2))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_limit_is_per_unit_and_action() {
+        let limiter = RateLimiter::new();
+        let now = Instant::now();
+        let cooldown = Duration::from_secs(2);
+
+        assert!(limiter.check_at("backup.timer", "run", cooldown, now).is_ok());
+        assert!(limiter.check_at("backup.timer", "disable", cooldown, now).is_ok());
+        assert!(limiter.check_at("logrotate.timer", "run", cooldown, now).is_ok());
+    }
+}
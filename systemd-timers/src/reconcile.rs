@@ -0,0 +1,274 @@
+// Background reconciliation - resumes snoozed timers and prunes caches between polls
+
+use crate::clock::Clock;
+use crate::command::CommandExecutor;
+use crate::error::TimerResult;
+use crate::handlers::{record_audit_outcome, sequence_error};
+use crate::rate_limit::RateLimiter;
+use crate::settings::PluginSettings;
+use crate::systemctl::SystemctlClient;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use toru_plugin_api::PluginKvStore;
+
+/// What one reconciliation pass did
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ReconcileReport {
+    /// Snoozed timers enabled again
+    pub resumed: Vec<String>,
+    /// Snoozes dropped because their timer no longer exists
+    pub dropped: Vec<String>,
+    /// Rate limiter entries dropped because their cooldown had passed
+    pub pruned: usize,
+}
+
+/// Run one reconciliation pass as of `clock`'s current time
+///
+/// Resumes snoozed timers whose window has passed, drops snoozes of timers
+/// that no longer exist and drops rate limiter entries older than the
+/// action cooldown.
+pub async fn reconcile_once<E: CommandExecutor>(
+    executor: E,
+    kv_store: &dyn PluginKvStore,
+    limiter: &RateLimiter,
+    clock: &dyn Clock,
+) -> TimerResult<ReconcileReport> {
+    let settings = PluginSettings::load(kv_store).await?;
+    let cooldown = settings.action_cooldown();
+
+    let sweep = resume_expired_snoozes(executor, kv_store, settings.snoozed_until, clock).await;
+    let pruned = limiter.prune(cooldown, Instant::now());
+
+    Ok(ReconcileReport {
+        resumed: sweep.resumed,
+        dropped: sweep.dropped,
+        pruned,
+    })
+}
+
+/// Start the background reconciler, running a pass every `interval` until `token` is cancelled
+pub fn spawn_reconciler<E, K>(
+    executor: E,
+    kv_store: K,
+    limiter: Arc<RateLimiter>,
+    clock: Arc<dyn Clock>,
+    interval: Duration,
+    token: CancellationToken,
+) -> JoinHandle<()>
+where
+    E: CommandExecutor + Clone + 'static,
+    K: PluginKvStore + 'static,
+{
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = token.cancelled() => break,
+                _ = tokio::time::sleep(interval) => {}
+            }
+
+            match reconcile_once(executor.clone(), &kv_store, &limiter, clock.as_ref()).await {
+                Ok(report) => {
+                    if !report.resumed.is_empty() {
+                        eprintln!("Reconciler resumed snoozed timers: {}", report.resumed.join(", "));
+                    }
+                    if !report.dropped.is_empty() {
+                        eprintln!("Reconciler dropped snoozes of deleted timers: {}", report.dropped.join(", "));
+                    }
+                }
+                Err(e) => eprintln!("Reconciliation pass failed: {}", e),
+            }
+        }
+    })
+}
+
+/// Snoozes left after `resume_expired_snoozes`, and what happened to the expired ones
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SnoozeSweep {
+    /// Snoozes still in effect, including those that failed to resume
+    pub pending: HashMap<String, DateTime<Utc>>,
+    /// Timers enabled again, sorted by name
+    pub resumed: Vec<String>,
+    /// Timers whose unit no longer exists, sorted by name
+    pub dropped: Vec<String>,
+}
+
+/// Enable snoozed timers whose window has passed
+///
+/// Runs on each GET /timers poll and, when enabled, from the background
+/// reconciler. A timer that fails to come back stays snoozed and is retried
+/// on the next pass, unless systemd no longer knows the unit: that snooze
+/// can never resume and is dropped.
+pub async fn resume_expired_snoozes<E: CommandExecutor>(
+    executor: E,
+    kv_store: &dyn PluginKvStore,
+    snoozed: HashMap<String, DateTime<Utc>>,
+    clock: &dyn Clock,
+) -> SnoozeSweep {
+    let now = clock.now();
+    let mut expired: Vec<(String, DateTime<Utc>)> = snoozed
+        .iter()
+        .filter(|(_, until)| **until <= now)
+        .map(|(name, until)| (name.clone(), *until))
+        .collect();
+    let mut sweep = SnoozeSweep { pending: snoozed, ..SnoozeSweep::default() };
+    if expired.is_empty() {
+        return sweep;
+    }
+    expired.sort();
+
+    let client = SystemctlClient::new(executor);
+    let mut cleared = Vec::new();
+    for (timer_name, until) in expired {
+        let result = client.enable_timer(&timer_name).await;
+        match sequence_error(&result, |s| &s.error) {
+            None => {
+                record_audit_outcome(kv_store, &timer_name, "resume", None, clock).await;
+                sweep.resumed.push(timer_name.clone());
+            }
+            // A deleted unit will never come back; stop retrying it
+            Some(_) if client.load_state(&timer_name).await.is_ok_and(|state| state == "not-found") => {
+                sweep.dropped.push(timer_name.clone());
+            }
+            Some(e) => {
+                eprintln!("Failed to resume snoozed timer {}: {}", timer_name, e);
+                record_audit_outcome(kv_store, &timer_name, "resume", Some(e), clock).await;
+                continue;
+            }
+        }
+        sweep.pending.remove(&timer_name);
+        cleared.push((timer_name, until));
+    }
+
+    // Only drop snoozes that weren't renewed in the meantime
+    let saved = PluginSettings::update(kv_store, |settings| {
+        for (timer_name, until) in &cleared {
+            if settings.snoozed_until.get(timer_name) == Some(until) {
+                settings.snoozed_until.remove(timer_name);
+            }
+        }
+    })
+    .await;
+    if let Err(e) = saved {
+        eprintln!("Failed to clear expired snoozes: {}", e);
+    }
+
+    sweep
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::FixedClock;
+    use crate::command::mock::MockCommandExecutor;
+    use crate::command::CommandOutput;
+    use crate::test_support::{ok_output, TestKvStore};
+
+    #[tokio::test]
+    async fn test_reconcile_resumes_timer_once_window_elapsed() {
+        let mock = Arc::new(MockCommandExecutor::new());
+        mock.expect("systemctl enable backup.timer", ok_output());
+        mock.expect("systemctl start backup.timer", ok_output());
+        let kv_store = TestKvStore::new();
+        let limiter = RateLimiter::new();
+        let until: DateTime<Utc> = "2024-06-01T12:00:00Z".parse().unwrap();
+        PluginSettings {
+            snoozed_until: HashMap::from([("backup.timer".to_string(), until)]),
+            ..PluginSettings::default()
+        }
+        .save(&kv_store)
+        .await
+        .unwrap();
+
+        // Still within the window: nothing happens
+        let clock = FixedClock::new(until - chrono::Duration::minutes(1));
+        let report = reconcile_once(mock.clone(), &kv_store, &limiter, &clock).await.unwrap();
+        assert!(report.resumed.is_empty());
+        assert!(mock.calls().is_empty());
+
+        clock.advance(chrono::Duration::minutes(2));
+        let report = reconcile_once(mock.clone(), &kv_store, &limiter, &clock).await.unwrap();
+        assert_eq!(report.resumed, vec!["backup.timer"]);
+        assert!(mock.calls().contains(&"systemctl enable backup.timer".to_string()));
+        assert!(PluginSettings::load(&kv_store).await.unwrap().snoozed_until.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_keeps_snooze_when_enable_fails() {
+        let mock = Arc::new(MockCommandExecutor::new());
+        mock.expect("systemctl enable backup.timer", CommandOutput {
+            stdout: String::new(),
+            stderr: "Access denied".to_string(),
+            exit_code: 1,
+        });
+        let kv_store = TestKvStore::new();
+        let until: DateTime<Utc> = "2024-06-01T12:00:00Z".parse().unwrap();
+        PluginSettings {
+            snoozed_until: HashMap::from([("backup.timer".to_string(), until)]),
+            ..PluginSettings::default()
+        }
+        .save(&kv_store)
+        .await
+        .unwrap();
+
+        let clock = FixedClock::new(until + chrono::Duration::hours(1));
+        let report = reconcile_once(mock, &kv_store, &RateLimiter::new(), &clock).await.unwrap();
+        assert!(report.resumed.is_empty());
+        assert_eq!(PluginSettings::load(&kv_store).await.unwrap().snoozed_until["backup.timer"], until);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_drops_snooze_of_deleted_timer() {
+        let mock = Arc::new(MockCommandExecutor::new());
+        mock.expect("systemctl enable backup.timer", CommandOutput {
+            stdout: String::new(),
+            stderr: "Failed to enable unit: Unit file backup.timer does not exist.".to_string(),
+            exit_code: 1,
+        });
+        mock.expect("systemctl show backup.timer --property=LoadState", CommandOutput {
+            stdout: "LoadState=not-found\n".to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+        let kv_store = TestKvStore::new();
+        let until: DateTime<Utc> = "2024-06-01T12:00:00Z".parse().unwrap();
+        PluginSettings {
+            snoozed_until: HashMap::from([("backup.timer".to_string(), until)]),
+            ..PluginSettings::default()
+        }
+        .save(&kv_store)
+        .await
+        .unwrap();
+
+        let clock = FixedClock::new(until + chrono::Duration::hours(1));
+        let report = reconcile_once(mock.clone(), &kv_store, &RateLimiter::new(), &clock).await.unwrap();
+        assert!(report.resumed.is_empty());
+        assert_eq!(report.dropped, vec!["backup.timer"]);
+        assert!(PluginSettings::load(&kv_store).await.unwrap().snoozed_until.is_empty());
+        assert!(crate::audit::load_audit_log(&kv_store).await.unwrap().is_empty());
+
+        // The next pass has nothing left to retry
+        let calls = mock.calls().len();
+        reconcile_once(mock.clone(), &kv_store, &RateLimiter::new(), &clock).await.unwrap();
+        assert_eq!(mock.calls().len(), calls);
+    }
+
+    #[tokio::test]
+    async fn test_reconciler_stops_when_cancelled() {
+        let token = CancellationToken::new();
+        let handle = spawn_reconciler(
+            Arc::new(MockCommandExecutor::new()),
+            TestKvStore::new(),
+            Arc::new(RateLimiter::new()),
+            Arc::new(crate::clock::SystemClock),
+            Duration::from_secs(3600),
+            token.clone(),
+        );
+
+        token.cancel();
+        tokio::time::timeout(Duration::from_secs(1), handle).await.unwrap().unwrap();
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Response header carrying the suggested poll interval in seconds
+pub const REFRESH_AFTER_HEADER: &str = "X-Refresh-After";
+
+/// Interval suggested right after a watched unit changed
+pub const MIN_REFRESH_AFTER: Duration = Duration::from_secs(5);
+
+/// Longest interval suggested once everything has been stable for a while
+pub const MAX_REFRESH_AFTER: Duration = Duration::from_secs(120);
+
+/// How long a unit counts as volatile after it changed
+pub const RECENT_CHANGE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Interval cap while any unit is volatile
+pub const VOLATILE_REFRESH_AFTER: Duration = Duration::from_secs(20);
+
+#[derive(Debug)]
+struct UnitObservation {
+    fingerprint: String,
+    changed_at: Option<Instant>,
+}
+
+#[derive(Debug, Default)]
+struct AdvisorState {
+    units: HashMap<String, UnitObservation>,
+    unchanged_polls: u32,
+}
+
+/// Suggest how long a dashboard can wait before polling unit status again
+///
+/// Each status poll reports a fingerprint per watched unit. The suggestion
+/// doubles with every poll where nothing changed, drops back to the minimum
+/// as soon as something does, and stays short while any unit changed within
+/// `RECENT_CHANGE_WINDOW`. Shared by all dashboards talking to the plugin.
+#[derive(Debug, Default)]
+pub struct RefreshAdvisor {
+    state: Mutex<AdvisorState>,
+}
+
+impl RefreshAdvisor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one poll of `(unit, fingerprint)` pairs and return the suggested interval
+    pub fn observe<I>(&self, units: I) -> Duration
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        self.observe_at(units, Instant::now())
+    }
+
+    /// Same as `observe` with an explicit clock reading
+    pub fn observe_at<I>(&self, units: I, now: Instant) -> Duration
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let mut changed = false;
+        let mut observed = HashMap::new();
+
+        for (unit, fingerprint) in units {
+            // A unit seen for the first time is not a change
+            let changed_at = match state.units.remove(&unit) {
+                Some(previous) if previous.fingerprint != fingerprint => {
+                    changed = true;
+                    Some(now)
+                }
+                Some(previous) => previous.changed_at,
+                None => None,
+            };
+            observed.insert(unit, UnitObservation { fingerprint, changed_at });
+        }
+
+        // Units no longer watched are forgotten
+        state.units = observed;
+        state.unchanged_polls = if changed { 0 } else { state.unchanged_polls.saturating_add(1) };
+
+        let backoff = MIN_REFRESH_AFTER
+            .saturating_mul(2u32.saturating_pow(state.unchanged_polls))
+            .min(MAX_REFRESH_AFTER);
+
+        let volatile = state.units.values().any(|unit| {
+            unit.changed_at
+                .is_some_and(|at| now.saturating_duration_since(at) < RECENT_CHANGE_WINDOW)
+        });
+
+        if volatile {
+            backoff.min(VOLATILE_REFRESH_AFTER)
+        } else {
+            backoff
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poll(state: &str) -> Vec<(String, String)> {
+        vec![
+            ("backup.timer".to_string(), state.to_string()),
+            ("cleanup.timer".to_string(), "enabled/success".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_interval_grows_while_stable() {
+        let advisor = RefreshAdvisor::new();
+        let start = Instant::now();
+
+        let intervals: Vec<u64> = (0..6)
+            .map(|i| {
+                advisor
+                    .observe_at(poll("enabled/success"), start + Duration::from_secs(i * 30))
+                    .as_secs()
+            })
+            .collect();
+
+        assert_eq!(intervals, vec![10, 20, 40, 80, 120, 120]);
+    }
+
+    #[test]
+    fn test_change_shrinks_interval() {
+        let advisor = RefreshAdvisor::new();
+        let start = Instant::now();
+
+        for i in 0..4 {
+            advisor.observe_at(poll("enabled/success"), start + Duration::from_secs(i * 30));
+        }
+        let changed_at = start + Duration::from_secs(120);
+        assert_eq!(advisor.observe_at(poll("enabled/failed"), changed_at), MIN_REFRESH_AFTER);
+
+        // Stays short while the change is recent, then backs off again
+        assert_eq!(
+            advisor.observe_at(poll("enabled/failed"), changed_at + Duration::from_secs(5)),
+            Duration::from_secs(10)
+        );
+        assert_eq!(
+            advisor.observe_at(poll("enabled/failed"), changed_at + Duration::from_secs(15)),
+            VOLATILE_REFRESH_AFTER
+        );
+        assert_eq!(
+            advisor.observe_at(poll("enabled/failed"), changed_at + Duration::from_secs(35)),
+            VOLATILE_REFRESH_AFTER
+        );
+        assert_eq!(
+            advisor.observe_at(poll("enabled/failed"), changed_at + Duration::from_secs(65)),
+            Duration::from_secs(80)
+        );
+    }
+
+    #[test]
+    fn test_newly_watched_unit_is_not_a_change() {
+        let advisor = RefreshAdvisor::new();
+        let start = Instant::now();
+
+        advisor.observe_at(poll("enabled/success"), start);
+        let mut units = poll("enabled/success");
+        units.push(("report.timer".to_string(), "disabled/none".to_string()));
+
+        assert_eq!(advisor.observe_at(units, start + Duration::from_secs(10)), Duration::from_secs(20));
+    }
+}
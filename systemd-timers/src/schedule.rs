@@ -1,3 +1,4 @@
+use crate::calendar::{format_hour, CalendarSpec};
 use crate::error::{TimerError, TimerResult};
 
 /// Parsed schedule information
@@ -47,26 +48,78 @@ impl Schedule {
         }
     }
 
-    /// Humanize the schedule for display
+    /// Humanize the schedule for display (12-hour clock)
     pub fn humanize(&self) -> String {
+        self.humanize_with_clock(false)
+    }
+
+    /// Humanize the schedule for display, with times on a 24-hour clock if `use_24h`
+    pub fn humanize_with_clock(&self, use_24h: bool) -> String {
         match self {
-            Schedule::Calendar { expression } => Self::humanize_calendar(expression),
+            Schedule::Calendar { expression } => Self::humanize_calendar_with_clock(expression, use_24h),
             Schedule::OnBoot { seconds } => format!("{} after boot", Self::humanize_duration(*seconds)),
             Schedule::Recurring { seconds } => format!("Every {}", Self::humanize_duration(*seconds)),
             Schedule::Multiple(schedules) => {
                 schedules.iter()
-                    .map(|s| s.humanize())
+                    .map(|s| s.humanize_with_clock(use_24h))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }
+        }
+    }
+
+    /// Humanize the schedule in full sentences (e.g., "Every Monday and Thursday at 6:30 AM")
+    /// Falls back to the terse `humanize` form where no fuller phrasing exists
+    pub fn humanize_verbose(&self) -> String {
+        self.humanize_verbose_with_clock(false)
+    }
+
+    /// Full-sentence humanization, with times on a 24-hour clock if `use_24h`
+    pub fn humanize_verbose_with_clock(&self, use_24h: bool) -> String {
+        match self {
+            Schedule::Calendar { expression } => CalendarSpec::parse(expression)
+                .ok()
+                .and_then(|spec| spec.describe(use_24h))
+                .unwrap_or_else(|| Self::humanize_calendar_with_clock(expression, use_24h)),
+            Schedule::Multiple(schedules) => {
+                schedules.iter()
+                    .map(|s| s.humanize_verbose_with_clock(use_24h))
                     .collect::<Vec<_>>()
                     .join(", ")
             }
+            _ => self.humanize_with_clock(use_24h),
         }
     }
 
-    /// Parse time span (e.g., "5min", "1h", "30s")
+    /// Parse time span (e.g., "5min", "1h", "30s", "1d")
+    ///
+    /// systemd reports longer spans in several components ("1h 30min"),
+    /// which are summed.
     fn parse_time_span(expr: &str) -> TimerResult<u64> {
         let expr = expr.trim();
 
-        if expr.ends_with("min") || expr.ends_with("m") {
+        if expr.contains(char::is_whitespace) {
+            return expr.split_whitespace().map(Self::parse_time_span).sum();
+        }
+
+        // Checked first: "days" and "weeks" would otherwise match the seconds suffix
+        if expr.ends_with("week") || expr.ends_with("weeks") || expr.ends_with('w') {
+            let num_str = expr.trim_end_matches("weeks").trim_end_matches("week").trim_end_matches('w');
+            let weeks: u64 = num_str.parse()
+                .map_err(|_| TimerError::ParseError {
+                    source: "time_span".to_string(),
+                    reason: format!("Invalid weeks: {}", expr),
+                })?;
+            Ok(weeks * 7 * 86400)
+        } else if expr.ends_with("day") || expr.ends_with("days") || expr.ends_with('d') {
+            let num_str = expr.trim_end_matches("days").trim_end_matches("day").trim_end_matches('d');
+            let days: u64 = num_str.parse()
+                .map_err(|_| TimerError::ParseError {
+                    source: "time_span".to_string(),
+                    reason: format!("Invalid days: {}", expr),
+                })?;
+            Ok(days * 86400)
+        } else if expr.ends_with("min") || expr.ends_with("m") {
             let num_str = expr.trim_end_matches("min").trim_end_matches('m');
             let minutes: u64 = num_str.parse()
                 .map_err(|_| TimerError::ParseError {
@@ -131,8 +184,14 @@ impl Schedule {
         }
     }
 
-    /// Humanize OnCalendar expression
+    /// Humanize OnCalendar expression (12-hour clock)
+    #[cfg(test)]
     fn humanize_calendar(expression: &str) -> String {
+        Self::humanize_calendar_with_clock(expression, false)
+    }
+
+    /// Humanize OnCalendar expression
+    fn humanize_calendar_with_clock(expression: &str, use_24h: bool) -> String {
         let expr = expression.trim();
 
         // Common patterns
@@ -148,12 +207,24 @@ impl Schedule {
         if expr == "monthly" {
             return "Monthly".to_string();
         }
+        if expr == "minutely" {
+            return "Every minute".to_string();
+        }
+        if expr == "quarterly" {
+            return "Quarterly".to_string();
+        }
+        if expr == "semiannually" {
+            return "Twice a year".to_string();
+        }
+        if expr == "yearly" || expr == "annually" {
+            return "Yearly".to_string();
+        }
 
         // Day patterns
         if expr.starts_with("Mon-Fri") {
             let time_part = expr.strip_prefix("Mon-Fri").unwrap_or("").trim();
             if time_part.contains("08-21") || time_part.contains("08:00-21:00") {
-                return "Mon-Fri, 8 AM - 9 PM".to_string();
+                return format!("Mon-Fri, {}", Self::hour_range(8, 21, use_24h));
             }
             return format!("Mon-Fri {}", time_part);
         }
@@ -166,13 +237,18 @@ impl Schedule {
         // Hourly during specific times
         if expr.contains("*:00:00") || expr.contains("*:00") {
             if expr.contains("08-21") || expr.contains("08:00-21:00") {
-                return "Hourly, 8 AM - 9 PM".to_string();
+                return format!("Hourly, {}", Self::hour_range(8, 21, use_24h));
             }
         }
 
         // Default: return as-is
         expression.to_string()
     }
+
+    /// Format an hour range, e.g. "8 AM - 9 PM" or "08:00 - 21:00"
+    fn hour_range(start: u32, end: u32, use_24h: bool) -> String {
+        format!("{} - {}", format_hour(start, use_24h), format_hour(end, use_24h))
+    }
 }
 
 #[cfg(test)]
@@ -201,6 +277,22 @@ mod tests {
         assert_eq!(Schedule::parse_time_span("120").unwrap(), 120);
     }
 
+    #[test]
+    fn test_parse_time_span_days_and_weeks() {
+        assert_eq!(Schedule::parse_time_span("1d").unwrap(), 86400);
+        assert_eq!(Schedule::parse_time_span("2days").unwrap(), 172800);
+        assert_eq!(Schedule::parse_time_span("1w").unwrap(), 604800);
+        assert_eq!(Schedule::parse_time_span("2weeks").unwrap(), 1209600);
+    }
+
+    #[test]
+    fn test_parse_time_span_compound() {
+        assert_eq!(Schedule::parse_time_span("1h 30min").unwrap(), 5400);
+        assert_eq!(Schedule::parse_time_span("1d 2h").unwrap(), 93600);
+        assert_eq!(Schedule::parse_time_span("15min 30s").unwrap(), 930);
+        assert!(Schedule::parse_time_span("1h soon").is_err());
+    }
+
     #[test]
     fn test_parse_time_span_invalid() {
         assert!(Schedule::parse_time_span("invalid").is_err());
@@ -242,6 +334,11 @@ mod tests {
         assert_eq!(Schedule::humanize_calendar("daily"), "Daily at midnight");
         assert_eq!(Schedule::humanize_calendar("weekly"), "Weekly on Monday");
         assert_eq!(Schedule::humanize_calendar("monthly"), "Monthly");
+        assert_eq!(Schedule::humanize_calendar("minutely"), "Every minute");
+        assert_eq!(Schedule::humanize_calendar("quarterly"), "Quarterly");
+        assert_eq!(Schedule::humanize_calendar("semiannually"), "Twice a year");
+        assert_eq!(Schedule::humanize_calendar("yearly"), "Yearly");
+        assert_eq!(Schedule::humanize_calendar("annually"), "Yearly");
     }
 
     #[test]
@@ -251,6 +348,17 @@ mod tests {
         assert_eq!(Schedule::humanize_calendar("Mon,Wed,Fri 14:00"), "Mon, Wed, Fri 14:00");
     }
 
+    #[test]
+    fn test_humanize_12h_vs_24h() {
+        let schedule = Schedule::parse(Some("Mon-Fri 08-21:00"), None, None).unwrap();
+        assert_eq!(schedule.humanize_with_clock(false), "Mon-Fri, 8 AM - 9 PM");
+        assert_eq!(schedule.humanize_with_clock(true), "Mon-Fri, 08:00 - 21:00");
+
+        let daily = Schedule::parse(Some("*-*-* 20:30"), None, None).unwrap();
+        assert_eq!(daily.humanize_verbose_with_clock(false), "Every day at 8:30 PM");
+        assert_eq!(daily.humanize_verbose_with_clock(true), "Every day at 20:30");
+    }
+
     #[test]
     fn test_humanize_calendar_hourly_range() {
         assert_eq!(Schedule::humanize_calendar("*:00:00 08-21"), "Hourly, 8 AM - 9 PM");
@@ -263,6 +371,28 @@ mod tests {
         assert_eq!(Schedule::humanize_calendar("*-*-01 00:00"), "*-*-01 00:00");
     }
 
+    #[test]
+    fn test_humanize_verbose_calendar() {
+        let weekly = Schedule::parse(Some("Mon,Thu 06:30"), None, None).unwrap();
+        assert_eq!(weekly.humanize_verbose(), "Every Monday and Thursday at 6:30 AM");
+
+        let monthly = Schedule::parse(Some("*-*-15 12:00"), None, None).unwrap();
+        assert_eq!(monthly.humanize_verbose(), "Monthly on the 15th at 12:00 PM");
+
+        let daily = Schedule::parse(Some("*-*-* 02:00"), None, None).unwrap();
+        assert_eq!(daily.humanize_verbose(), "Every day at 2:00 AM");
+    }
+
+    #[test]
+    fn test_humanize_verbose_keeps_terse_form_available() {
+        let schedule = Schedule::parse(Some("*-*-* 02:00"), None, None).unwrap();
+        assert_eq!(schedule.humanize(), "*-*-* 02:00");
+
+        // Unparseable expressions fall back to the terse form
+        let custom = Schedule::parse(Some("Mon-Fri 08-21:00"), Some("5min"), None).unwrap();
+        assert_eq!(custom.humanize_verbose(), "Mon-Fri, 8 AM - 9 PM, 5min after boot");
+    }
+
     #[test]
     fn test_parse_schedule_calendar() {
         let schedule = Schedule::parse(Some("Mon-Fri 08-21:00"), None, None).unwrap();
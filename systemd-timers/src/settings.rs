@@ -0,0 +1,220 @@
+use crate::error::{TimerError, TimerResult};
+use crate::journal::JournalSource;
+use crate::rate_limit::{ACTION_COOLDOWN_KEY, DEFAULT_ACTION_COOLDOWN};
+use crate::unit_writer::DEFAULT_UNIT_DIR;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use toru_plugin_api::PluginKvStore;
+
+/// KV key holding all plugin settings as one JSON object
+pub const SETTINGS_KEY: &str = "settings";
+
+/// Legacy KV key holding the watched timers as a JSON array
+pub const WATCHED_TIMERS_KEY: &str = "watched_timers";
+
+/// Legacy KV key selecting 24-hour clock formatting for schedules (JSON boolean)
+pub const USE_24H_KEY: &str = "use_24h";
+
+/// Legacy KV key toggling read-only mode (JSON boolean)
+pub const READ_ONLY_KEY: &str = "read_only";
+
+/// Legacy KV key holding a JSON array of glob patterns for units that may be managed
+pub const ALLOWED_UNITS_KEY: &str = "allowed_units";
+
+/// Legacy KV key holding a JSON array of glob patterns for units that must never be managed
+pub const DENIED_UNITS_KEY: &str = "denied_units";
+
+/// Serializes read-modify-write cycles on the settings blob
+///
+/// Requests are handled concurrently; two updates that each load, change and
+/// save the blob would otherwise drop whichever change was saved first.
+static UPDATE_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// Plugin settings, stored under `SETTINGS_KEY`
+///
+/// Fields missing from the stored JSON take their default, so settings added
+/// later don't invalidate an existing blob.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PluginSettings {
+    /// Timers shown on the dashboard
+    pub watched_timers: Vec<String>,
+    /// Glob patterns for units that may be managed (empty = all)
+    pub allowed_units: Vec<String>,
+    /// Glob patterns for units that may never be managed; wins over `allowed_units`
+    pub denied_units: Vec<String>,
+    /// Render schedules on a 24-hour clock
+    pub use_24h: bool,
+    /// Block all mutating actions; reads keep working
+    pub read_only: bool,
+    /// Minimum gap between repeats of the same unit action
+    pub action_cooldown_ms: u64,
+    /// Directory units created through `POST /timers/create` are written to
+    pub unit_dir: String,
+    /// Timers created by the plugin; only these may be edited or deleted
+    pub managed_timers: Vec<String>,
+    /// Operator notes keyed by timer name
+    pub timer_notes: HashMap<String, String>,
+    /// Journal unit or syslog identifier to read history from, keyed by timer
+    /// name, for jobs that don't log under their own unit
+    pub history_sources: HashMap<String, JournalSource>,
+    /// Origin allowed to call the plugin cross-origin (CORS); None sends no CORS headers
+    pub cors_origin: Option<String>,
+    /// Timers disabled by `POST /timers/:name/snooze`, keyed by name, with
+    /// the time they are to be enabled again
+    pub snoozed_until: HashMap<String, DateTime<Utc>>,
+    /// Seconds between background reconciliation passes (resuming snoozed
+    /// timers, pruning caches); 0 leaves it off. Read at startup
+    pub reconcile_interval_secs: u64,
+    /// Enable debugging aids such as `raw=true` journal output
+    pub debug_mode: bool,
+}
+
+impl Default for PluginSettings {
+    fn default() -> Self {
+        Self {
+            watched_timers: Vec::new(),
+            allowed_units: Vec::new(),
+            denied_units: Vec::new(),
+            use_24h: false,
+            read_only: false,
+            action_cooldown_ms: DEFAULT_ACTION_COOLDOWN.as_millis() as u64,
+            unit_dir: DEFAULT_UNIT_DIR.to_string(),
+            managed_timers: Vec::new(),
+            timer_notes: HashMap::new(),
+            history_sources: HashMap::new(),
+            cors_origin: None,
+            snoozed_until: HashMap::new(),
+            reconcile_interval_secs: 0,
+            debug_mode: false,
+        }
+    }
+}
+
+impl PluginSettings {
+    /// Load settings from KV storage
+    ///
+    /// Installs that predate the settings blob keep each setting under its own
+    /// key. When the blob is absent those are read instead and, if any were
+    /// set, saved as the blob so the migration happens once. The legacy keys
+    /// are left in place so an older plugin version still finds them.
+    pub async fn load(kv_store: &dyn PluginKvStore) -> TimerResult<Self> {
+        if let Some(json_str) = kv_get(kv_store, SETTINGS_KEY).await? {
+            let mut settings: Self = serde_json::from_str(&json_str)?;
+            if settings.adopt_legacy_access_keys(kv_store, &json_str).await? {
+                settings.save(kv_store).await?;
+            }
+            return Ok(settings);
+        }
+
+        let (settings, found_legacy) = Self::load_legacy(kv_store).await?;
+        if found_legacy {
+            eprintln!("Migrating legacy settings keys to '{}'", SETTINGS_KEY);
+            settings.save(kv_store).await?;
+        }
+
+        Ok(settings)
+    }
+
+    /// Save settings to KV storage as one JSON object
+    pub async fn save(&self, kv_store: &dyn PluginKvStore) -> TimerResult<()> {
+        let json_str = serde_json::to_string(self)?;
+        kv_store.set(SETTINGS_KEY, &json_str).await
+            .map_err(|e| TimerError::IoError(format!("KV storage error: {}", e)))
+    }
+
+    /// Apply `change` to the stored settings and save them
+    ///
+    /// Every mutation goes through here so concurrent updates serialize
+    /// instead of overwriting each other. Returns the saved settings.
+    pub async fn update<F>(kv_store: &dyn PluginKvStore, change: F) -> TimerResult<Self>
+    where
+        F: FnOnce(&mut Self),
+    {
+        let _guard = UPDATE_LOCK.lock().await;
+
+        let mut settings = Self::load(kv_store).await?;
+        change(&mut settings);
+        settings.save(kv_store).await?;
+
+        Ok(settings)
+    }
+
+    /// Cooldown between repeats of the same unit action
+    pub fn action_cooldown(&self) -> Duration {
+        Duration::from_millis(self.action_cooldown_ms)
+    }
+
+    /// Interval of the background reconciler; None when it is off
+    pub fn reconcile_interval(&self) -> Option<Duration> {
+        (self.reconcile_interval_secs > 0).then(|| Duration::from_secs(self.reconcile_interval_secs))
+    }
+
+    /// Fill the access lists from their legacy keys when `blob` predates them
+    ///
+    /// The lists joined the blob after it was introduced, so a blob saved
+    /// earlier lacks them while the legacy keys may still restrict units.
+    /// Returns true if the blob needs saving to record the lists.
+    async fn adopt_legacy_access_keys(&mut self, kv_store: &dyn PluginKvStore, blob: &str) -> TimerResult<bool> {
+        let stored: serde_json::Value = serde_json::from_str(blob)?;
+        let mut missing = false;
+
+        for (field, key) in [("allowed_units", ALLOWED_UNITS_KEY), ("denied_units", DENIED_UNITS_KEY)] {
+            if stored.get(field).is_some() {
+                continue;
+            }
+            missing = true;
+            if let Some(json_str) = kv_get(kv_store, key).await? {
+                let patterns = serde_json::from_str(&json_str)?;
+                match field {
+                    "allowed_units" => self.allowed_units = patterns,
+                    _ => self.denied_units = patterns,
+                }
+            }
+        }
+
+        Ok(missing)
+    }
+
+    /// Build settings from the per-setting keys, defaulting any that are unset
+    /// The flag tells whether any legacy key was set at all
+    async fn load_legacy(kv_store: &dyn PluginKvStore) -> TimerResult<(Self, bool)> {
+        let mut settings = Self::default();
+        let mut found = false;
+
+        if let Some(json_str) = kv_get(kv_store, WATCHED_TIMERS_KEY).await? {
+            settings.watched_timers = serde_json::from_str(&json_str)?;
+            found = true;
+        }
+        if let Some(json_str) = kv_get(kv_store, USE_24H_KEY).await? {
+            settings.use_24h = serde_json::from_str(&json_str)?;
+            found = true;
+        }
+        if let Some(json_str) = kv_get(kv_store, READ_ONLY_KEY).await? {
+            settings.read_only = serde_json::from_str(&json_str)?;
+            found = true;
+        }
+        if let Some(json_str) = kv_get(kv_store, ACTION_COOLDOWN_KEY).await? {
+            settings.action_cooldown_ms = serde_json::from_str(&json_str)?;
+            found = true;
+        }
+        if let Some(json_str) = kv_get(kv_store, ALLOWED_UNITS_KEY).await? {
+            settings.allowed_units = serde_json::from_str(&json_str)?;
+            found = true;
+        }
+        if let Some(json_str) = kv_get(kv_store, DENIED_UNITS_KEY).await? {
+            settings.denied_units = serde_json::from_str(&json_str)?;
+            found = true;
+        }
+
+        Ok((settings, found))
+    }
+}
+
+async fn kv_get(kv_store: &dyn PluginKvStore, key: &str) -> TimerResult<Option<String>> {
+    kv_store.get(key).await
+        .map_err(|e| TimerError::IoError(format!("KV storage error: {}", e)))
+}
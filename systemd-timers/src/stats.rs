@@ -0,0 +1,145 @@
+use crate::command::{CommandExecutor, CommandOutput};
+use crate::error::TimerResult;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
+
+/// Request and command counters kept by the plugin for `GET /debug/stats`
+///
+/// Shared by every request. Counters only go up, except `requests_in_flight`
+/// which a `RequestGuard` decrements when its request finishes.
+#[derive(Debug, Default)]
+pub struct PluginStats {
+    requests_total: AtomicU64,
+    requests_in_flight: AtomicU64,
+    commands_total: AtomicU64,
+    last_error: Mutex<Option<String>>,
+}
+
+/// Point-in-time copy of the counters
+///
+/// The plugin keeps no response cache, so unlike systemd-services there are
+/// no cache hit/miss counters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatsSnapshot {
+    pub requests_total: u64,
+    pub requests_in_flight: u64,
+    /// systemctl and journalctl invocations
+    pub commands_total: u64,
+    /// Most recent 5xx response or dispatch error
+    pub last_error: Option<String>,
+}
+
+/// Keep a request counted as in flight until dropped
+pub struct RequestGuard<'a> {
+    stats: &'a PluginStats,
+}
+
+impl Drop for RequestGuard<'_> {
+    fn drop(&mut self) {
+        self.stats.requests_in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl PluginStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a request as received and in flight until the guard is dropped
+    pub fn request_started(&self) -> RequestGuard<'_> {
+        self.requests_total.fetch_add(1, Ordering::Relaxed);
+        self.requests_in_flight.fetch_add(1, Ordering::Relaxed);
+        RequestGuard { stats: self }
+    }
+
+    /// Count one spawned command
+    pub fn command_started(&self) {
+        self.commands_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Remember the most recent server-side error
+    pub fn record_error(&self, error: &str) {
+        let mut last_error = self.last_error.lock().unwrap_or_else(|e| e.into_inner());
+        *last_error = Some(error.to_string());
+    }
+
+    pub fn snapshot(&self) -> StatsSnapshot {
+        StatsSnapshot {
+            requests_total: self.requests_total.load(Ordering::Relaxed),
+            requests_in_flight: self.requests_in_flight.load(Ordering::Relaxed),
+            commands_total: self.commands_total.load(Ordering::Relaxed),
+            last_error: self.last_error.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+        }
+    }
+}
+
+/// Executor wrapper counting every command in `PluginStats`
+pub struct CountingExecutor<E> {
+    inner: E,
+    stats: Arc<PluginStats>,
+}
+
+impl<E: CommandExecutor> CountingExecutor<E> {
+    pub fn new(inner: E, stats: Arc<PluginStats>) -> Self {
+        Self { inner, stats }
+    }
+}
+
+#[async_trait]
+impl<E: CommandExecutor> CommandExecutor for CountingExecutor<E> {
+    async fn execute(&self, program: &str, args: &[&str]) -> TimerResult<CommandOutput> {
+        self.stats.command_started();
+        self.inner.execute(program, args).await
+    }
+
+    async fn execute_cancellable(
+        &self,
+        program: &str,
+        args: &[&str],
+        token: CancellationToken,
+    ) -> TimerResult<CommandOutput> {
+        self.stats.command_started();
+        self.inner.execute_cancellable(program, args, token).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::mock::MockCommandExecutor;
+
+    #[tokio::test]
+    async fn test_counting_executor_counts_commands() {
+        let stats = Arc::new(PluginStats::new());
+        let mock = MockCommandExecutor::new();
+        mock.expect("systemctl is-system-running", CommandOutput {
+            stdout: "running\n".to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+        let executor = CountingExecutor::new(mock, stats.clone());
+
+        executor.execute("systemctl", &["is-system-running"]).await.unwrap();
+        // A failing command still counts as an invocation
+        assert!(executor.execute("systemctl", &["bogus"]).await.is_err());
+
+        assert_eq!(stats.snapshot().commands_total, 2);
+    }
+
+    #[test]
+    fn test_request_guard_tracks_in_flight() {
+        let stats = PluginStats::new();
+
+        let guard = stats.request_started();
+        let _second = stats.request_started();
+        assert_eq!(stats.snapshot().requests_in_flight, 2);
+
+        drop(guard);
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.requests_total, 2);
+        assert_eq!(snapshot.requests_in_flight, 1);
+    }
+}
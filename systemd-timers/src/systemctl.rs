@@ -1,35 +1,147 @@
-use crate::command::CommandExecutor;
+use crate::command::{CommandExecutor, CommandOutput};
 use crate::error::{TimerError, TimerResult};
 use crate::schedule::Schedule;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Information about a systemd timer
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimerInfo {
     pub name: String,
+    /// Unit `Description=`, empty when unknown
+    #[serde(default)]
+    pub description: String,
+    /// Deprecated: `enabled_at_boot && active`; kept for one release, use the
+    /// two flags instead
     pub enabled: bool,
+    /// Enabled in the unit file (`UnitFileState=enabled`), i.e. starts at boot
+    #[serde(default)]
+    pub enabled_at_boot: bool,
+    /// Currently running (`ActiveState=active`), i.e. will elapse on schedule
+    #[serde(default)]
+    pub active: bool,
     pub schedule: String,
+    /// Raw OnCalendar expressions the schedule was humanized from
+    #[serde(default)]
+    pub calendar: Vec<String>,
     pub next_run: Option<String>,
     pub last_trigger: Option<String>,
     pub service: String,
+    /// Unit file the timer was loaded from
+    #[serde(default)]
+    pub fragment_path: Option<String>,
+    /// Drop-in override files, in the order systemd applies them
+    #[serde(default)]
+    pub drop_in_paths: Vec<String>,
+    /// Whether the service's last start was skipped because a `Condition*=`
+    /// directive wasn't met; None if its conditions were never checked
+    #[serde(default)]
+    pub last_condition_failed: Option<bool>,
+    /// `LEFT` column of `list-timers` as printed ("45min left"); None for
+    /// `n/a` and for timers read with `get_timer_info`
+    #[serde(default)]
+    pub left: Option<String>,
+    /// `PASSED` column of `list-timers` as printed ("2h ago"); None for
+    /// `n/a` and for timers read with `get_timer_info`
+    #[serde(default)]
+    pub passed: Option<String>,
 }
 
+/// Minimal current state of a timer, read without touching execution logs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TimerState {
+    /// Enabled in the unit file
+    pub enabled: bool,
+    /// Timer unit is active (waiting to elapse)
+    pub active: bool,
+    pub next_run: Option<String>,
+    /// "success", "failed" or "running" from the service unit; None if it never ran
+    pub last_result: Option<String>,
+}
+
+/// Per-step outcome of enabling a timer (enable for boot, then start)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct EnableSteps {
+    pub enabled: bool,
+    pub started: bool,
+    /// False when the timer was already enabled and running
+    pub changed: bool,
+    /// True when the enable was undone after the start failed
+    pub rolled_back: bool,
+    /// Error of the failed step, if the sequence did not complete
+    pub error: Option<String>,
+}
+
+/// Per-step outcome of disabling a timer (stop, then disable for boot)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DisableSteps {
+    pub stopped: bool,
+    pub disabled: bool,
+    /// False when the timer was already stopped and disabled
+    pub changed: bool,
+    /// True when the timer was started again after the disable failed
+    pub rolled_back: bool,
+    /// Error of the failed step, if the sequence did not complete
+    pub error: Option<String>,
+}
+
+/// Boot-enablement and runtime state of a unit
+#[derive(Debug, Clone, Copy, Default)]
+struct UnitState {
+    enabled: bool,
+    active: bool,
+}
+
+/// Parse a numeric `systemctl show` property, mapping systemd's "no value"
+/// sentinels to None
+///
+/// Empty values, `[not set]`, `[no data]`, `infinity` and u64::MAX (how
+/// unbounded limits and unset counters are printed) all mean there is no
+/// number to report.
+pub fn parse_systemd_optional_u64(value: &str) -> Option<u64> {
+    match value.trim() {
+        "" | "[not set]" | "[no data]" | "infinity" => None,
+        value => value.parse::<u64>().ok().filter(|n| *n != u64::MAX),
+    }
+}
+
+/// How long the health probe waits for systemd before calling it unreachable
+pub const HEALTH_PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
 /// Systemctl wrapper for timer operations
 pub struct SystemctlClient<E: CommandExecutor> {
     executor: E,
+    /// Render humanized schedules on a 24-hour clock
+    use_24h: bool,
 }
 
 impl<E: CommandExecutor> SystemctlClient<E> {
     pub fn new(executor: E) -> Self {
-        Self { executor }
+        Self { executor, use_24h: false }
+    }
+
+    /// Render humanized schedules on a 24-hour clock ("08:00") instead of AM/PM
+    pub fn with_24h_clock(mut self, use_24h: bool) -> Self {
+        self.use_24h = use_24h;
+        self
     }
 
     /// List all systemd timers
+    ///
+    /// Asks for `--timestamp=unix` so NEXT/LAST are single `@seconds` tokens;
+    /// systemd older than v247 rejects the option, and the localized
+    /// multi-word datetimes are parsed instead.
     pub async fn list_timers(&self) -> TimerResult<Vec<TimerInfo>> {
-        let output = self.executor
-            .execute("systemctl", &["list-timers", "--all", "--no-pager", "--plain"])
+        let mut output = self.executor
+            .execute("systemctl", &["list-timers", "--all", "--no-pager", "--plain", "--timestamp=unix"])
             .await?;
 
+        if output.exit_code != 0 && Self::is_unknown_option_error(&output.stderr) {
+            output = self.executor
+                .execute("systemctl", &["list-timers", "--all", "--no-pager", "--plain"])
+                .await?;
+        }
+
         if output.exit_code != 0 {
             return Err(TimerError::CommandFailed {
                 command: "systemctl list-timers".to_string(),
@@ -45,23 +157,81 @@ impl<E: CommandExecutor> SystemctlClient<E> {
     pub async fn get_timer_info(&self, name: &str) -> TimerResult<TimerInfo> {
         Self::validate_timer_name(name)?;
 
+        // The service is shown too: its ConditionResult explains skipped runs
+        let service = Self::timer_to_service(name).ok();
+        let mut args = vec!["show", name];
+        args.extend(service.as_deref());
+        args.push("--property=Id,Description,LoadState,UnitFileState,ActiveState,NextElapseUSecRealtime,LastTriggerUSec,TimersCalendar,TimersMonotonic,FragmentPath,DropInPaths,ConditionResult,ConditionTimestamp");
+
+        let output = self.executor.execute("systemctl", &args).await?;
+
+        if output.exit_code != 0 {
+            return Err(TimerError::CommandFailed {
+                command: format!("systemctl {}", args[..args.len() - 1].join(" ")),
+                stderr: output.stderr,
+                exit_code: Some(output.exit_code),
+            });
+        }
+
+        self.parse_timer_info(&output.stdout, name)
+    }
+
+    /// Get a timer's current state with a single `systemctl show` of the timer and its service
+    pub async fn get_timer_state(&self, name: &str) -> TimerResult<TimerState> {
+        Self::validate_timer_name(name)?;
+        let service = Self::timer_to_service(name)?;
+
         let output = self.executor
             .execute("systemctl", &[
                 "show",
                 name,
-                "--property=Id,LoadState,UnitFileState,ActiveState,NextElapseUSecRealtime,LastTriggerUSec,TimersCalendar",
+                &service,
+                "--property=LoadState,UnitFileState,ActiveState,NextElapseUSecRealtime,Result,ExecMainStartTimestampMonotonic",
             ])
             .await?;
 
         if output.exit_code != 0 {
             return Err(TimerError::CommandFailed {
-                command: format!("systemctl show {}", name),
+                command: format!("systemctl show {} {}", name, service),
                 stderr: output.stderr,
                 exit_code: Some(output.exit_code),
             });
         }
 
-        self.parse_timer_info(&output.stdout, name)
+        // One block of properties per unit, separated by a blank line
+        let blocks: Vec<HashMap<&str, &str>> = output.stdout
+            .split("\n\n")
+            .map(|block| block.lines().filter_map(|line| line.split_once('=')).collect())
+            .collect();
+        let empty = HashMap::new();
+        let timer = blocks.first().unwrap_or(&empty);
+        let service_props = blocks.get(1).unwrap_or(&empty);
+
+        if timer.get("LoadState") == Some(&"not-found") {
+            return Err(TimerError::NotFound(name.to_string()));
+        }
+
+        let next_run = timer.get("NextElapseUSecRealtime")
+            .filter(|v| !v.is_empty() && **v != "0")
+            .map(|v| v.to_string());
+
+        let ever_started = service_props.get("ExecMainStartTimestampMonotonic")
+            .and_then(|v| parse_systemd_optional_u64(v))
+            .is_some_and(|usec| usec != 0);
+        let last_result = match (service_props.get("ActiveState"), service_props.get("Result")) {
+            (Some(&"activating"), _) => Some("running".to_string()),
+            _ if !ever_started => None,
+            (_, Some(&"success")) => Some("success".to_string()),
+            (_, Some(_)) => Some("failed".to_string()),
+            _ => None,
+        };
+
+        Ok(TimerState {
+            enabled: timer.get("UnitFileState") == Some(&"enabled"),
+            active: timer.get("ActiveState") == Some(&"active"),
+            next_run,
+            last_result,
+        })
     }
 
     /// Trigger a timer's associated service immediately
@@ -82,84 +252,259 @@ impl<E: CommandExecutor> SystemctlClient<E> {
         };
 
         if output.exit_code != 0 {
-            return Err(TimerError::CommandFailed {
-                command: format!("systemctl start {}", service),
-                stderr: output.stderr,
-                exit_code: Some(output.exit_code),
-            });
+            return Err(Self::command_error(format!("systemctl start {}", service), output));
         }
 
         Ok(())
     }
 
     /// Enable a timer (enable for boot + start now)
-    pub async fn enable_timer(&self, name: &str) -> TimerResult<()> {
+    ///
+    /// Steps whose target state is already reached are skipped, so repeated calls
+    /// are no-ops reported with `changed: false`. A failure of the first command
+    /// is returned as an error since nothing changed. If starting fails an enable
+    /// made by this call is rolled back on a best-effort basis and the partial
+    /// state is reported in the returned steps.
+    pub async fn enable_timer(&self, name: &str) -> TimerResult<EnableSteps> {
         Self::validate_timer_name(name)?;
 
-        // First enable for boot
-        let output = self.executor
-            .execute("systemctl", &["enable", name])
-            .await?;
-
-        if output.exit_code != 0 {
-            return Err(TimerError::CommandFailed {
-                command: format!("systemctl enable {}", name),
-                stderr: output.stderr,
-                exit_code: Some(output.exit_code),
+        // If the state can't be queried, fall back to issuing both commands
+        let current = self.unit_state(name).await;
+        let was_enabled = current.is_some_and(|s| s.enabled);
+        let was_active = current.is_some_and(|s| s.active);
+
+        if was_enabled && was_active {
+            return Ok(EnableSteps {
+                enabled: true,
+                started: true,
+                changed: false,
+                ..EnableSteps::default()
             });
         }
 
+        // First enable for boot
+        if !was_enabled {
+            self.run_systemctl(&["enable", name]).await?;
+        }
+
+        let mut steps = EnableSteps {
+            enabled: true,
+            changed: true,
+            ..EnableSteps::default()
+        };
+
         // Then start the timer now
-        let output = self.executor
-            .execute("systemctl", &["start", name])
-            .await?;
+        if !was_active {
+            if let Err(e) = self.run_systemctl(&["start", name]).await {
+                steps.error = Some(e.to_string());
+
+                // Don't leave the timer enabled for boot but not running
+                if !was_enabled && self.run_systemctl(&["disable", name]).await.is_ok() {
+                    steps.enabled = false;
+                    steps.rolled_back = true;
+                }
 
-        if output.exit_code != 0 {
-            return Err(TimerError::CommandFailed {
-                command: format!("systemctl start {}", name),
-                stderr: output.stderr,
-                exit_code: Some(output.exit_code),
-            });
+                return Ok(steps);
+            }
         }
 
-        Ok(())
+        steps.started = true;
+        Ok(steps)
     }
 
     /// Disable a timer (stop now + disable for boot)
-    pub async fn disable_timer(&self, name: &str) -> TimerResult<()> {
+    ///
+    /// Steps whose target state is already reached are skipped, so repeated calls
+    /// are no-ops reported with `changed: false`. A failure of the first command
+    /// is returned as an error since nothing changed. If disabling fails a timer
+    /// stopped by this call is started again on a best-effort basis and the
+    /// partial state is reported in the returned steps.
+    pub async fn disable_timer(&self, name: &str) -> TimerResult<DisableSteps> {
         Self::validate_timer_name(name)?;
 
+        // If the state can't be queried, fall back to issuing both commands
+        let current = self.unit_state(name).await;
+        let was_stopped = current.is_some_and(|s| !s.active);
+        let was_disabled = current.is_some_and(|s| !s.enabled);
+
+        if was_stopped && was_disabled {
+            return Ok(DisableSteps {
+                stopped: true,
+                disabled: true,
+                changed: false,
+                ..DisableSteps::default()
+            });
+        }
+
         // First stop the timer
-        let output = self.executor
-            .execute("systemctl", &["stop", name])
-            .await?;
+        if !was_stopped {
+            self.run_systemctl(&["stop", name]).await?;
+        }
 
-        if output.exit_code != 0 {
-            return Err(TimerError::CommandFailed {
-                command: format!("systemctl stop {}", name),
+        let mut steps = DisableSteps {
+            stopped: true,
+            changed: true,
+            ..DisableSteps::default()
+        };
+
+        // Then disable for boot
+        if !was_disabled {
+            if let Err(e) = self.run_systemctl(&["disable", name]).await {
+                steps.error = Some(e.to_string());
+
+                // Don't leave the timer stopped but still enabled for boot
+                if !was_stopped && self.run_systemctl(&["start", name]).await.is_ok() {
+                    steps.stopped = false;
+                    steps.rolled_back = true;
+                }
+
+                return Ok(steps);
+            }
+        }
+
+        steps.disabled = true;
+        Ok(steps)
+    }
+
+    /// Reload unit files so units written or changed on disk take effect
+    pub async fn daemon_reload(&self) -> TimerResult<()> {
+        self.run_systemctl(&["daemon-reload"]).await
+    }
+
+    /// Ask systemd for the overall system state (`running`, `degraded`, `offline`, ...)
+    ///
+    /// `is-system-running` exits non-zero for every state but `running`, so
+    /// only an empty answer is an error. Give up after `HEALTH_PROBE_TIMEOUT`.
+    pub async fn system_state(&self) -> TimerResult<String> {
+        let output = tokio::time::timeout(
+            HEALTH_PROBE_TIMEOUT,
+            self.executor.execute("systemctl", &["is-system-running"]),
+        )
+        .await
+        .map_err(|_| TimerError::CommandFailed {
+            command: "systemctl is-system-running".to_string(),
+            stderr: format!("timed out after {}s", HEALTH_PROBE_TIMEOUT.as_secs()),
+            exit_code: None,
+        })??;
+
+        match output.stdout.trim() {
+            "" => Err(TimerError::CommandFailed {
+                command: "systemctl is-system-running".to_string(),
                 stderr: output.stderr,
                 exit_code: Some(output.exit_code),
-            });
+            }),
+            state => Ok(state.to_string()),
         }
+    }
+
+    /// Restart a unit if it is running; a stopped unit stays stopped
+    pub async fn try_restart(&self, name: &str) -> TimerResult<()> {
+        Self::validate_timer_name(name)?;
+        self.run_systemctl(&["try-restart", name]).await
+    }
+
+    /// Check whether systemd knows a unit, from its `LoadState` alone
+    ///
+    /// Cheap enough to run before an action so a missing unit gets a clean
+    /// 404. `loaded` and `masked` units exist (the action reports the mask);
+    /// `not-found` and invalid names don't. If systemctl can't answer, assume
+    /// the unit exists and let the action report the real failure.
+    pub async fn unit_exists(&self, name: &str) -> bool {
+        if Self::validate_timer_name(name).is_err() {
+            return false;
+        }
+
+        match self.load_state(name).await {
+            Ok(load_state) => load_state != "not-found",
+            Err(_) => true,
+        }
+    }
+
+    /// Query a unit's `LoadState` (`loaded`, `not-found`, `masked`...)
+    ///
+    /// Units systemd loads from any unit directory count, not only the one
+    /// the plugin writes to.
+    pub async fn load_state(&self, name: &str) -> TimerResult<String> {
+        Self::validate_timer_name(name)?;
 
-        // Then disable for boot
         let output = self.executor
-            .execute("systemctl", &["disable", name])
+            .execute("systemctl", &["show", name, "--property=LoadState"])
             .await?;
+        if output.exit_code != 0 {
+            return Err(Self::command_error(format!("systemctl show {}", name), output));
+        }
+
+        output.stdout
+            .lines()
+            .find_map(|line| line.strip_prefix("LoadState="))
+            .map(|state| state.trim().to_string())
+            .ok_or_else(|| TimerError::ParseError {
+                source: format!("systemctl show {}", name),
+                reason: "No LoadState in output".to_string(),
+            })
+    }
+
+    /// Query whether a unit is enabled for boot and currently active
+    /// Returns None if systemctl can't answer
+    async fn unit_state(&self, name: &str) -> Option<UnitState> {
+        let output = self.executor
+            .execute("systemctl", &["show", name, "--property=UnitFileState,ActiveState"])
+            .await
+            .ok()?;
 
         if output.exit_code != 0 {
-            return Err(TimerError::CommandFailed {
-                command: format!("systemctl disable {}", name),
-                stderr: output.stderr,
-                exit_code: Some(output.exit_code),
-            });
+            return None;
+        }
+
+        let mut state = UnitState::default();
+        for line in output.stdout.lines() {
+            if let Some(value) = line.strip_prefix("UnitFileState=") {
+                state.enabled = matches!(value, "enabled" | "enabled-runtime");
+            } else if let Some(value) = line.strip_prefix("ActiveState=") {
+                state.active = value == "active";
+            }
+        }
+
+        Some(state)
+    }
+
+    /// Run a systemctl command, turning a non-zero exit into `CommandFailed`
+    async fn run_systemctl(&self, args: &[&str]) -> TimerResult<()> {
+        let output = self.executor.execute("systemctl", args).await?;
+
+        if output.exit_code != 0 {
+            return Err(Self::command_error(format!("systemctl {}", args.join(" ")), output));
         }
 
         Ok(())
     }
 
+    /// Turn a failed systemctl action into an error, recognizing masked units
+    /// and polkit authentication prompts from stderr
+    fn command_error(command: String, output: CommandOutput) -> TimerError {
+        let stderr = output.stderr.trim();
+
+        if let Some(unit) = stderr
+            .split_once("Unit ")
+            .and_then(|(_, rest)| rest.split_once(" is masked"))
+            .map(|(unit, _)| unit)
+        {
+            return TimerError::Masked(unit.to_string());
+        }
+
+        if stderr.contains("Interactive authentication required") {
+            return TimerError::AuthenticationRequired(stderr.to_string());
+        }
+
+        TimerError::CommandFailed {
+            command,
+            stderr: output.stderr,
+            exit_code: Some(output.exit_code),
+        }
+    }
+
     /// Validate timer name to prevent command injection
-    fn validate_timer_name(name: &str) -> TimerResult<()> {
+    pub(crate) fn validate_timer_name(name: &str) -> TimerResult<()> {
         if name.is_empty() {
             return Err(TimerError::InvalidInput("Timer name cannot be empty".to_string()));
         }
@@ -190,7 +535,34 @@ impl<E: CommandExecutor> SystemctlClient<E> {
         }
     }
 
+    /// Convert service name to timer name (foo.service -> foo.timer)
+    pub(crate) fn service_to_timer(service: &str) -> TimerResult<String> {
+        if let Some(base) = service.strip_suffix(".service") {
+            Ok(format!("{}.timer", base))
+        } else {
+            Err(TimerError::InvalidInput(
+                "Service name must end with .service".to_string()
+            ))
+        }
+    }
+
+    /// Find the timer named after a service (foo.service -> foo.timer)
+    /// Returns None if `list_timers` doesn't include that timer
+    pub async fn timer_for_service(&self, service: &str) -> TimerResult<Option<String>> {
+        Self::validate_timer_name(service)?;
+        let timer = Self::service_to_timer(service)?;
+
+        let timers = self.list_timers().await?;
+        Ok(timers.iter().any(|t| t.name == timer).then_some(timer))
+    }
+
     /// Parse output from systemctl list-timers
+    ///
+    /// Columns are NEXT LEFT LAST PASSED UNIT ACTIVATES, but their widths vary:
+    /// NEXT/LAST are either `n/a` or a datetime with an optional zone of any
+    /// length, and LEFT/PASSED are free-form spans. UNIT and ACTIVATES are
+    /// always the last two tokens, so they're taken from the end and the
+    /// rest is consumed from the front.
     fn parse_list_timers(&self, output: &str) -> TimerResult<Vec<TimerInfo>> {
         let mut timers = Vec::new();
 
@@ -200,46 +572,128 @@ impl<E: CommandExecutor> SystemctlClient<E> {
                 continue;
             }
 
+            // Example: Wed 2026-01-15 14:00:00 CET 45min left n/a n/a chfscraper-scrape-bcp.timer chfscraper-scrape-bcp.service
             let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() < 7 {
+            let [columns @ .., timer_name, service_name] = parts.as_slice() else {
+                continue;
+            };
+            // Skips the "N timers listed." footer and anything else unexpected
+            if !timer_name.ends_with(".timer") {
                 continue;
             }
 
-            // Format: NEXT LEFT LAST PASSED UNIT ACTIVATES
-            // Example: Wed 2026-01-15 14:00:00 CET 45min left n/a n/a chfscraper-scrape-bcp.timer chfscraper-scrape-bcp.service
-
-            let unit_idx = parts.len() - 2;
-            let activates_idx = parts.len() - 1;
-
-            let timer_name = parts[unit_idx].to_string();
-            let service_name = parts[activates_idx].to_string();
+            let (next_run, rest) = Self::take_list_timers_datetime(columns);
+            let (left, rest) = Self::take_list_timers_span(rest);
+            let (last_trigger, rest) = Self::take_list_timers_datetime(rest);
+            let (passed, _) = Self::take_list_timers_span(rest);
 
             timers.push(TimerInfo {
-                name: timer_name,
-                enabled: true, // We'll determine this more accurately in get_timer_info
+                name: timer_name.to_string(),
+                description: String::new(),
+                // We'll determine these more accurately in get_timer_info
+                enabled: true,
+                enabled_at_boot: true,
+                active: true,
                 schedule: "".to_string(), // Parsed separately
-                next_run: if parts[0] == "n/a" { None } else { Some(parts[0..5].join(" ")) },
-                last_trigger: if parts[5] == "n/a" { None } else { Some(parts[5].to_string()) },
-                service: service_name,
+                calendar: Vec::new(),
+                next_run,
+                last_trigger,
+                service: service_name.to_string(),
+                fragment_path: None,
+                drop_in_paths: Vec::new(),
+                last_condition_failed: None,
+                left,
+                passed,
             });
         }
 
         Ok(timers)
     }
 
+    /// Take a NEXT/LAST column off the front: `n/a` (or `-`), `@seconds` from
+    /// `--timestamp=unix`, or `[Weekday] YYYY-MM-DD HH:MM:SS [Zone]`
+    ///
+    /// Unix timestamps are returned as microseconds, like `systemctl show` prints them.
+    fn take_list_timers_datetime<'a>(tokens: &'a [&'a str]) -> (Option<String>, &'a [&'a str]) {
+        match tokens.first() {
+            None => return (None, tokens),
+            Some(&"n/a") | Some(&"-") => return (None, &tokens[1..]),
+            Some(token) => {
+                if let Some(secs) = token.strip_prefix('@').and_then(|s| s.parse::<i64>().ok()) {
+                    let next = (secs > 0).then(|| (secs * 1_000_000).to_string());
+                    return (next, &tokens[1..]);
+                }
+            }
+        }
+
+        let is_date = |t: &str| t.len() == 10 && t.as_bytes()[4] == b'-' && t.as_bytes()[7] == b'-';
+        let mut taken = 0;
+
+        if tokens.first().is_some_and(|t| t.chars().all(|c| c.is_ascii_alphabetic())) {
+            taken += 1;
+        }
+        if !tokens.get(taken).is_some_and(|t| is_date(t)) {
+            // Not a datetime; leave the tokens for the next column
+            return (None, tokens);
+        }
+        taken += 1;
+        if tokens.get(taken).is_some_and(|t| t.contains(':')) {
+            taken += 1;
+        }
+        // Zone: "CET", "AEST", "+0530"... but never a span, which starts with a digit
+        if tokens.get(taken).is_some_and(|t| *t != "n/a" && !t.starts_with(|c: char| c.is_ascii_digit())) {
+            taken += 1;
+        }
+
+        (Some(tokens[..taken].join(" ")), &tokens[taken..])
+    }
+
+    /// True if systemctl rejected an option it doesn't know (older systemd)
+    fn is_unknown_option_error(stderr: &str) -> bool {
+        stderr.contains("unrecognized option") || stderr.contains("Unknown option")
+            || stderr.contains("invalid option")
+    }
+
+    /// Take a LEFT/PASSED column off the front: `n/a` (None), or a span up to
+    /// and including "left"/"ago"
+    fn take_list_timers_span<'a>(tokens: &'a [&'a str]) -> (Option<String>, &'a [&'a str]) {
+        let end = match tokens.first() {
+            None => return (None, tokens),
+            Some(&"n/a") | Some(&"-") => return (None, &tokens[1..]),
+            _ => match tokens.iter().position(|t| *t == "left" || *t == "ago") {
+                Some(end) => end + 1,
+                // Older systemd may omit the suffix; a span is a single token then
+                None => 1,
+            },
+        };
+
+        (Some(tokens[..end].join(" ")), &tokens[end..])
+    }
+
     /// Parse output from systemctl show
     fn parse_timer_info(&self, output: &str, name: &str) -> TimerResult<TimerInfo> {
         let mut id = String::new();
+        let mut description = String::new();
         let mut load_state = String::new();
         let mut unit_file_state = String::new();
         let mut active_state = String::new();
         let mut next_elapse = None;
         let mut last_trigger = None;
         let mut calendar_entries: Vec<String> = Vec::new();
+        let mut monotonic_entries: Vec<(String, String)> = Vec::new();
+        let mut fragment_path = None;
+        let mut drop_in_paths = Vec::new();
 
-        for line in output.lines() {
+        // One block of properties per unit: the timer, then its service
+        let mut blocks = output.split("\n\n");
+        let timer_block = blocks.next().unwrap_or_default();
+        let last_condition_failed = blocks.next().and_then(Self::parse_condition_failed);
+
+        for line in timer_block.lines() {
             if let Some(value) = line.strip_prefix("Id=") {
                 id = value.to_string();
+            } else if let Some(value) = line.strip_prefix("Description=") {
+                description = value.to_string();
             } else if let Some(value) = line.strip_prefix("LoadState=") {
                 load_state = value.to_string();
             } else if let Some(value) = line.strip_prefix("UnitFileState=") {
@@ -254,11 +708,23 @@ impl<E: CommandExecutor> SystemctlClient<E> {
                 if value != "0" && !value.is_empty() {
                     last_trigger = Some(value.to_string());
                 }
+            } else if let Some(value) = line.strip_prefix("FragmentPath=") {
+                if !value.is_empty() {
+                    fragment_path = Some(value.to_string());
+                }
+            } else if let Some(value) = line.strip_prefix("DropInPaths=") {
+                // Space-separated list of absolute paths
+                drop_in_paths = value.split_whitespace().map(str::to_string).collect();
             } else if let Some(value) = line.strip_prefix("TimersCalendar=") {
                 // Format: { OnCalendar=Mon..Fri 07..21:00:00 Europe/Warsaw ; next_elapse=... }
                 if let Some(cal) = Self::extract_on_calendar(value) {
                     calendar_entries.push(cal);
                 }
+            } else if let Some(value) = line.strip_prefix("TimersMonotonic=") {
+                // Format: { OnBootUSec=5min ; next_elapse=... }
+                if let Some(entry) = Self::extract_monotonic(value) {
+                    monotonic_entries.push(entry);
+                }
             }
         }
 
@@ -266,27 +732,58 @@ impl<E: CommandExecutor> SystemctlClient<E> {
             return Err(TimerError::NotFound(name.to_string()));
         }
 
-        // Timer is considered "enabled" if it's both enabled in unit file AND actively running
-        let enabled = unit_file_state == "enabled" && active_state == "active";
+        let enabled_at_boot = unit_file_state == "enabled";
+        let active = active_state == "active";
         let service = Self::timer_to_service(name).unwrap_or_else(|_| name.to_string());
 
-        // Generate human-readable schedule from calendar entries
-        let schedule_human = if calendar_entries.is_empty() {
+        // Generate human-readable schedule from calendar and monotonic entries
+        let schedule_human = if calendar_entries.is_empty() && monotonic_entries.is_empty() {
             "Schedule not available".to_string()
         } else {
-            Self::humanize_schedules(&calendar_entries)
+            Self::humanize_schedules(&calendar_entries, &monotonic_entries, self.use_24h)
         };
 
         Ok(TimerInfo {
             name: id,
-            enabled,
+            description,
+            enabled: enabled_at_boot && active,
+            enabled_at_boot,
+            active,
             schedule: schedule_human,
+            calendar: calendar_entries,
             next_run: next_elapse,
             last_trigger,
             service,
+            fragment_path,
+            drop_in_paths,
+            last_condition_failed,
+            left: None,
+            passed: None,
         })
     }
 
+    /// Parse a unit's `ConditionResult`/`ConditionTimestamp` into whether its
+    /// last start was skipped on an unmet condition
+    /// An empty timestamp means the conditions were never evaluated.
+    fn parse_condition_failed(block: &str) -> Option<bool> {
+        let mut result = None;
+        let mut evaluated = false;
+
+        for line in block.lines() {
+            if let Some(value) = line.strip_prefix("ConditionResult=") {
+                result = Some(value.trim());
+            } else if let Some(value) = line.strip_prefix("ConditionTimestamp=") {
+                evaluated = !matches!(value.trim(), "" | "n/a" | "0");
+            }
+        }
+
+        match result {
+            Some("no") if evaluated => Some(true),
+            Some("yes") if evaluated => Some(false),
+            _ => None,
+        }
+    }
+
     /// Extract OnCalendar value from TimersCalendar property
     /// Input format: { OnCalendar=Mon..Fri 07..21:00:00 Europe/Warsaw ; next_elapse=... }
     fn extract_on_calendar(value: &str) -> Option<String> {
@@ -305,19 +802,48 @@ impl<E: CommandExecutor> SystemctlClient<E> {
         None
     }
 
-    /// Humanize multiple calendar entries
-    fn humanize_schedules(entries: &[String]) -> String {
-        entries.iter()
-            .map(|e| {
-                // Try to use Schedule parser, fall back to raw string
-                if let Ok(schedule) = Schedule::parse(Some(e), None, None) {
-                    schedule.humanize()
-                } else {
-                    e.clone()
-                }
-            })
-            .collect::<Vec<_>>()
-            .join(", ")
+    /// Extract the setting and span from a TimersMonotonic property
+    /// Input format: { OnBootUSec=5min ; next_elapse=... }
+    /// systemd reports the settings as `On*USec`; they're returned as the
+    /// unit-file names (`OnBootSec`) with the span as written, e.g. "5min".
+    fn extract_monotonic(value: &str) -> Option<(String, String)> {
+        let inner = value.trim().trim_start_matches('{').trim_end_matches('}');
+        let setting = inner.split(';').next()?.trim();
+        let (key, span) = setting.split_once('=')?;
+
+        let key = key.trim();
+        let base = key.strip_suffix("USec").or_else(|| key.strip_suffix("Sec"))?;
+        let span = span.trim();
+        if !base.starts_with("On") || span.is_empty() {
+            return None;
+        }
+
+        Some((format!("{}Sec", base), span.to_string()))
+    }
+
+    /// Humanize calendar entries followed by monotonic ones
+    fn humanize_schedules(calendar: &[String], monotonic: &[(String, String)], use_24h: bool) -> String {
+        let calendar = calendar.iter().map(|e| {
+            // Try to use Schedule parser, fall back to raw string
+            if let Ok(schedule) = Schedule::parse(Some(e), None, None) {
+                schedule.humanize_with_clock(use_24h)
+            } else {
+                e.clone()
+            }
+        });
+
+        let monotonic = monotonic.iter().map(|(setting, span)| {
+            let schedule = match setting.as_str() {
+                "OnBootSec" => Schedule::parse(None, Some(span), None).ok(),
+                "OnUnitActiveSec" => Schedule::parse(None, None, Some(span)).ok(),
+                _ => None,
+            };
+            schedule
+                .map(|s| s.humanize_with_clock(use_24h))
+                .unwrap_or_else(|| format!("{}={}", setting, span))
+        });
+
+        calendar.chain(monotonic).collect::<Vec<_>>().join(", ")
     }
 }
 
@@ -327,6 +853,17 @@ mod tests {
     use crate::command::mock::MockCommandExecutor;
     use crate::command::CommandOutput;
 
+    /// Make the mock behave like systemd without `--timestamp=`: the unix
+    /// listing fails and the plain one returns `output`
+    fn expect_legacy_list_timers(mock: &MockCommandExecutor, output: CommandOutput) {
+        mock.expect("systemctl list-timers --all --no-pager --plain --timestamp=unix", CommandOutput {
+            stdout: String::new(),
+            stderr: "systemctl: unrecognized option '--timestamp=unix'\n".to_string(),
+            exit_code: 1,
+        });
+        mock.expect("systemctl list-timers --all --no-pager --plain", output);
+    }
+
     #[tokio::test]
     async fn test_validate_timer_name_valid() {
         assert!(SystemctlClient::<MockCommandExecutor>::validate_timer_name("foo.timer").is_ok());
@@ -345,6 +882,18 @@ mod tests {
         assert!(SystemctlClient::<MockCommandExecutor>::validate_timer_name("foo$bar.timer").is_err());
     }
 
+    #[test]
+    fn test_parse_systemd_optional_u64() {
+        assert_eq!(parse_systemd_optional_u64("1704880800000000"), Some(1704880800000000));
+        assert_eq!(parse_systemd_optional_u64(" 0 "), Some(0));
+        assert_eq!(parse_systemd_optional_u64(""), None);
+        assert_eq!(parse_systemd_optional_u64("[not set]"), None);
+        assert_eq!(parse_systemd_optional_u64("[no data]"), None);
+        assert_eq!(parse_systemd_optional_u64("infinity"), None);
+        assert_eq!(parse_systemd_optional_u64("18446744073709551615"), None);
+        assert_eq!(parse_systemd_optional_u64("n/a"), None);
+    }
+
     #[tokio::test]
     async fn test_timer_to_service() {
         assert_eq!(
@@ -358,6 +907,31 @@ mod tests {
         assert!(SystemctlClient::<MockCommandExecutor>::timer_to_service("foo.service").is_err());
     }
 
+    #[tokio::test]
+    async fn test_service_to_timer() {
+        assert_eq!(
+            SystemctlClient::<MockCommandExecutor>::service_to_timer("foo.service").unwrap(),
+            "foo.timer"
+        );
+        assert!(SystemctlClient::<MockCommandExecutor>::service_to_timer("foo.timer").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_timer_for_service() {
+        let mock = MockCommandExecutor::new();
+        expect_legacy_list_timers(&mock, CommandOutput {
+            stdout: "Thu 2026-01-16 09:00:00 UTC 18h left n/a n/a backup.timer backup.service\n".to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+        let client = SystemctlClient::new(mock);
+
+        assert_eq!(client.timer_for_service("backup.service").await.unwrap().as_deref(), Some("backup.timer"));
+        // A service without a timer of the same name
+        assert_eq!(client.timer_for_service("nginx.service").await.unwrap(), None);
+        assert!(client.timer_for_service("foo;bar.service").await.is_err());
+    }
+
     #[tokio::test]
     async fn test_list_timers_success() {
         let mock = MockCommandExecutor::new();
@@ -368,7 +942,7 @@ mod tests {
             stderr: String::new(),
             exit_code: 0,
         };
-        mock.expect("systemctl list-timers --all --no-pager --plain", output);
+        expect_legacy_list_timers(&mock, output);
 
         let client = SystemctlClient::new(mock);
         let timers = client.list_timers().await.unwrap();
@@ -378,7 +952,108 @@ mod tests {
         assert_eq!(timers[0].service, "chfscraper-scrape-bcp.service");
         assert!(timers[0].next_run.is_some());
         assert_eq!(timers[1].name, "chfscraper-scrape-scc.timer");
-        assert!(timers[1].next_run.is_some());
+        assert_eq!(timers[1].next_run.as_deref(), Some("Wed 2026-01-15 13:30:00 CET"));
+        assert_eq!(timers[1].last_trigger.as_deref(), Some("Wed 2026-01-15 12:30:00 CET"));
+    }
+
+    async fn list_timers_from(stdout: &str) -> Vec<TimerInfo> {
+        let mock = MockCommandExecutor::new();
+        expect_legacy_list_timers(&mock, CommandOutput {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+
+        SystemctlClient::new(mock).list_timers().await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_list_timers_next_not_available() {
+        let timers = list_timers_from(
+            "NEXT LEFT LAST PASSED UNIT ACTIVATES\n\
+             n/a n/a Wed 2026-01-15 12:30:00 CET 45min ago idle.timer idle.service\n\
+             3 timers listed.\n",
+        ).await;
+
+        assert_eq!(timers.len(), 1);
+        assert_eq!(timers[0].name, "idle.timer");
+        assert_eq!(timers[0].service, "idle.service");
+        assert_eq!(timers[0].next_run, None);
+        assert_eq!(timers[0].last_trigger.as_deref(), Some("Wed 2026-01-15 12:30:00 CET"));
+    }
+
+    #[tokio::test]
+    async fn test_list_timers_last_not_available() {
+        let timers = list_timers_from(
+            "Wed 2026-01-15 14:00:00 CET 1h 15min left n/a n/a fresh.timer fresh.service\n",
+        ).await;
+
+        assert_eq!(timers[0].next_run.as_deref(), Some("Wed 2026-01-15 14:00:00 CET"));
+        assert_eq!(timers[0].last_trigger, None);
+        assert_eq!(timers[0].service, "fresh.service");
+    }
+
+    #[tokio::test]
+    async fn test_list_timers_four_letter_timezone() {
+        let timers = list_timers_from(
+            "Thu 2026-01-16 09:00:00 AEST 18h left Wed 2026-01-15 09:00:00 AEST 5h 2min ago backup.timer backup.service\n",
+        ).await;
+
+        assert_eq!(timers[0].next_run.as_deref(), Some("Thu 2026-01-16 09:00:00 AEST"));
+        assert_eq!(timers[0].last_trigger.as_deref(), Some("Wed 2026-01-15 09:00:00 AEST"));
+        assert_eq!(timers[0].name, "backup.timer");
+    }
+
+    #[tokio::test]
+    async fn test_list_timers_left_and_passed() {
+        let timers = list_timers_from(
+            "Wed 2026-01-15 14:00:00 CET 45min left Wed 2026-01-15 11:15:00 CET 2h ago backup.timer backup.service\n\
+             Thu 2026-01-16 09:00:00 AEST 1h 15min left Wed 2026-01-15 09:00:00 AEST 5h 2min ago sync.timer sync.service\n",
+        ).await;
+
+        assert_eq!(timers[0].left.as_deref(), Some("45min left"));
+        assert_eq!(timers[0].passed.as_deref(), Some("2h ago"));
+        assert_eq!(timers[1].left.as_deref(), Some("1h 15min left"));
+        assert_eq!(timers[1].passed.as_deref(), Some("5h 2min ago"));
+        assert_eq!(timers[1].service, "sync.service");
+    }
+
+    #[tokio::test]
+    async fn test_list_timers_left_and_passed_not_available() {
+        let timers = list_timers_from("n/a n/a n/a n/a idle.timer idle.service\n").await;
+
+        assert_eq!(timers.len(), 1);
+        assert_eq!(timers[0].name, "idle.timer");
+        assert_eq!(timers[0].next_run, None);
+        assert_eq!(timers[0].left, None);
+        assert_eq!(timers[0].last_trigger, None);
+        assert_eq!(timers[0].passed, None);
+    }
+
+    #[tokio::test]
+    async fn test_list_timers_unix_timestamps() {
+        let mock = MockCommandExecutor::new();
+        mock.expect("systemctl list-timers --all --no-pager --plain --timestamp=unix", CommandOutput {
+            stdout: "NEXT LEFT LAST PASSED UNIT ACTIVATES\n\
+                     @1705324800 45min left @1705320000 34min ago backup.timer backup.service\n\
+                     @1705327200 1h 25min left n/a n/a fresh.timer fresh.service\n\
+                     n/a n/a @1705233600 1 day 34min ago idle.timer idle.service\n\
+                     \n\
+                     3 timers listed.\n".to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+
+        let timers = SystemctlClient::new(mock).list_timers().await.unwrap();
+        assert_eq!(timers.len(), 3);
+
+        assert_eq!(timers[0].next_run.as_deref(), Some("1705324800000000"));
+        assert_eq!(timers[0].last_trigger.as_deref(), Some("1705320000000000"));
+        assert_eq!(timers[1].next_run.as_deref(), Some("1705327200000000"));
+        assert_eq!(timers[1].last_trigger, None);
+        assert_eq!(timers[2].next_run, None);
+        assert_eq!(timers[2].last_trigger.as_deref(), Some("1705233600000000"));
+        assert_eq!(timers[2].service, "idle.service");
     }
 
     #[tokio::test]
@@ -389,7 +1064,7 @@ mod tests {
             stderr: String::new(),
             exit_code: 0,
         };
-        mock.expect("systemctl list-timers --all --no-pager --plain", output);
+        expect_legacy_list_timers(&mock, output);
 
         let client = SystemctlClient::new(mock);
         let timers = client.list_timers().await.unwrap();
@@ -404,7 +1079,7 @@ mod tests {
             stderr: "Permission denied".to_string(),
             exit_code: 1,
         };
-        mock.expect("systemctl list-timers --all --no-pager --plain", output);
+        mock.expect("systemctl list-timers --all --no-pager --plain --timestamp=unix", output);
 
         let client = SystemctlClient::new(mock);
         let result = client.list_timers().await;
@@ -420,7 +1095,7 @@ mod tests {
             exit_code: 0,
         };
         mock.expect(
-            "systemctl show test.timer --property=Id,LoadState,UnitFileState,ActiveState,NextElapseUSecRealtime,LastTriggerUSec,TimersCalendar",
+            "systemctl show test.timer test.service --property=Id,Description,LoadState,UnitFileState,ActiveState,NextElapseUSecRealtime,LastTriggerUSec,TimersCalendar,TimersMonotonic,FragmentPath,DropInPaths,ConditionResult,ConditionTimestamp",
             output
         );
 
@@ -428,11 +1103,113 @@ mod tests {
         let info = client.get_timer_info("test.timer").await.unwrap();
         assert_eq!(info.name, "test.timer");
         assert!(info.enabled); // enabled + active = true
+        assert!(info.enabled_at_boot && info.active);
         assert!(info.next_run.is_some());
         assert!(info.last_trigger.is_some());
         assert_eq!(info.schedule, "Daily at midnight");
     }
 
+    #[tokio::test]
+    async fn test_get_timer_info_monotonic() {
+        let mock = MockCommandExecutor::new();
+        mock.expect(
+            "systemctl show boot.timer boot.service --property=Id,Description,LoadState,UnitFileState,ActiveState,NextElapseUSecRealtime,LastTriggerUSec,TimersCalendar,TimersMonotonic,FragmentPath,DropInPaths,ConditionResult,ConditionTimestamp",
+            CommandOutput {
+                stdout: "Id=boot.timer\nLoadState=loaded\nUnitFileState=enabled\nActiveState=active\nTimersMonotonic={ OnBootUSec=5min ; next_elapse=5min }\n".to_string(),
+                stderr: String::new(),
+                exit_code: 0,
+            },
+        );
+
+        let info = SystemctlClient::new(mock).get_timer_info("boot.timer").await.unwrap();
+        assert_eq!(info.schedule, "5min after boot");
+        assert!(info.calendar.is_empty());
+    }
+
+    #[test]
+    fn test_extract_monotonic_on_boot() {
+        assert_eq!(
+            SystemctlClient::<MockCommandExecutor>::extract_monotonic("{ OnBootUSec=5min ; next_elapse=5min }"),
+            Some(("OnBootSec".to_string(), "5min".to_string()))
+        );
+        // Unit-file spelling is accepted too
+        assert_eq!(
+            SystemctlClient::<MockCommandExecutor>::extract_monotonic("{ OnBootSec=15min }"),
+            Some(("OnBootSec".to_string(), "15min".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_extract_monotonic_on_unit_active() {
+        assert_eq!(
+            SystemctlClient::<MockCommandExecutor>::extract_monotonic("{ OnUnitActiveUSec=1h 30min ; next_elapse=2h 5min 1.250000s }"),
+            Some(("OnUnitActiveSec".to_string(), "1h 30min".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_extract_monotonic_invalid() {
+        assert_eq!(SystemctlClient::<MockCommandExecutor>::extract_monotonic(""), None);
+        assert_eq!(SystemctlClient::<MockCommandExecutor>::extract_monotonic("{ next_elapse=5min }"), None);
+        assert_eq!(SystemctlClient::<MockCommandExecutor>::extract_monotonic("{ OnBootUSec= ; next_elapse=0 }"), None);
+    }
+
+    #[test]
+    fn test_humanize_monotonic_schedules() {
+        let monotonic = vec![
+            ("OnBootSec".to_string(), "5min".to_string()),
+            ("OnUnitActiveSec".to_string(), "1d".to_string()),
+            ("OnStartupSec".to_string(), "10min".to_string()),
+        ];
+
+        assert_eq!(
+            SystemctlClient::<MockCommandExecutor>::humanize_schedules(&["daily".to_string()], &monotonic, false),
+            "Daily at midnight, 5min after boot, Every 1d, OnStartupSec=10min"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_timer_info_description() {
+        let mock = MockCommandExecutor::new();
+        let output = CommandOutput {
+            stdout: "Id=backup.timer\nDescription=Nightly database backup\nLoadState=loaded\nUnitFileState=enabled\nActiveState=active\n".to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        };
+        mock.expect(
+            "systemctl show backup.timer backup.service --property=Id,Description,LoadState,UnitFileState,ActiveState,NextElapseUSecRealtime,LastTriggerUSec,TimersCalendar,TimersMonotonic,FragmentPath,DropInPaths,ConditionResult,ConditionTimestamp",
+            output
+        );
+
+        let client = SystemctlClient::new(mock);
+        let info = client.get_timer_info("backup.timer").await.unwrap();
+        assert_eq!(info.description, "Nightly database backup");
+    }
+
+    #[tokio::test]
+    async fn test_get_timer_info_unit_files() {
+        let mock = MockCommandExecutor::new();
+        let output = CommandOutput {
+            stdout: "Id=backup.timer\nLoadState=loaded\nUnitFileState=enabled\nActiveState=active\n\
+                     FragmentPath=/etc/systemd/system/backup.timer\n\
+                     DropInPaths=/etc/systemd/system/backup.timer.d/10-schedule.conf /run/systemd/system/backup.timer.d/50-override.conf\n".to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        };
+        mock.expect(
+            "systemctl show backup.timer backup.service --property=Id,Description,LoadState,UnitFileState,ActiveState,NextElapseUSecRealtime,LastTriggerUSec,TimersCalendar,TimersMonotonic,FragmentPath,DropInPaths,ConditionResult,ConditionTimestamp",
+            output
+        );
+
+        let client = SystemctlClient::new(mock);
+        let info = client.get_timer_info("backup.timer").await.unwrap();
+        assert_eq!(info.fragment_path.as_deref(), Some("/etc/systemd/system/backup.timer"));
+        assert_eq!(info.drop_in_paths, vec![
+            "/etc/systemd/system/backup.timer.d/10-schedule.conf",
+            "/run/systemd/system/backup.timer.d/50-override.conf",
+        ]);
+    }
+
     #[tokio::test]
     async fn test_get_timer_info_disabled() {
         let mock = MockCommandExecutor::new();
@@ -442,16 +1219,36 @@ mod tests {
             exit_code: 0,
         };
         mock.expect(
-            "systemctl show test.timer --property=Id,LoadState,UnitFileState,ActiveState,NextElapseUSecRealtime,LastTriggerUSec,TimersCalendar",
+            "systemctl show test.timer test.service --property=Id,Description,LoadState,UnitFileState,ActiveState,NextElapseUSecRealtime,LastTriggerUSec,TimersCalendar,TimersMonotonic,FragmentPath,DropInPaths,ConditionResult,ConditionTimestamp",
             output
         );
 
         let client = SystemctlClient::new(mock);
         let info = client.get_timer_info("test.timer").await.unwrap();
         assert!(!info.enabled);
+        assert!(!info.enabled_at_boot && !info.active);
         assert!(info.next_run.is_none());
     }
 
+    #[tokio::test]
+    async fn test_get_timer_info_enabled_at_boot_but_stopped() {
+        let mock = MockCommandExecutor::new();
+        mock.expect(
+            "systemctl show test.timer test.service --property=Id,Description,LoadState,UnitFileState,ActiveState,NextElapseUSecRealtime,LastTriggerUSec,TimersCalendar,TimersMonotonic,FragmentPath,DropInPaths,ConditionResult,ConditionTimestamp",
+            CommandOutput {
+                stdout: "Id=test.timer\nLoadState=loaded\nUnitFileState=enabled\nActiveState=inactive\nNextElapseUSecRealtime=0\n".to_string(),
+                stderr: String::new(),
+                exit_code: 0,
+            },
+        );
+
+        let client = SystemctlClient::new(mock);
+        let info = client.get_timer_info("test.timer").await.unwrap();
+        assert!(info.enabled_at_boot);
+        assert!(!info.active);
+        assert!(!info.enabled); // derived: only true when both hold
+    }
+
     #[tokio::test]
     async fn test_get_timer_info_not_found() {
         let mock = MockCommandExecutor::new();
@@ -461,7 +1258,7 @@ mod tests {
             exit_code: 0,
         };
         mock.expect(
-            "systemctl show missing.timer --property=Id,LoadState,UnitFileState,ActiveState,NextElapseUSecRealtime,LastTriggerUSec,TimersCalendar",
+            "systemctl show missing.timer missing.service --property=Id,Description,LoadState,UnitFileState,ActiveState,NextElapseUSecRealtime,LastTriggerUSec,TimersCalendar,TimersMonotonic,FragmentPath,DropInPaths,ConditionResult,ConditionTimestamp",
             output
         );
 
@@ -471,6 +1268,59 @@ mod tests {
         assert!(matches!(result.unwrap_err(), TimerError::NotFound(_)));
     }
 
+    #[tokio::test]
+    async fn test_get_timer_info_condition_failed() {
+        let mock = MockCommandExecutor::new();
+        mock.expect(
+            "systemctl show backup.timer backup.service --property=Id,Description,LoadState,UnitFileState,ActiveState,NextElapseUSecRealtime,LastTriggerUSec,TimersCalendar,TimersMonotonic,FragmentPath,DropInPaths,ConditionResult,ConditionTimestamp",
+            CommandOutput {
+                // The timer's own conditions passed; the service's did not
+                stdout: "Id=backup.timer\nLoadState=loaded\nUnitFileState=enabled\nActiveState=active\n\
+                         ConditionResult=yes\nConditionTimestamp=Mon 2024-01-15 02:00:00 UTC\n\n\
+                         ConditionResult=no\nConditionTimestamp=Mon 2024-01-15 02:00:00 UTC\n".to_string(),
+                stderr: String::new(),
+                exit_code: 0,
+            },
+        );
+
+        let client = SystemctlClient::new(mock);
+        let info = client.get_timer_info("backup.timer").await.unwrap();
+        assert_eq!(info.last_condition_failed, Some(true));
+    }
+
+    #[test]
+    fn test_parse_condition_failed() {
+        let met = "ConditionResult=yes\nConditionTimestamp=Mon 2024-01-15 02:00:00 UTC\n";
+        assert_eq!(SystemctlClient::<MockCommandExecutor>::parse_condition_failed(met), Some(false));
+
+        // Never started: systemd reports "no" with an empty timestamp
+        let unchecked = "ConditionResult=no\nConditionTimestamp=\n";
+        assert_eq!(SystemctlClient::<MockCommandExecutor>::parse_condition_failed(unchecked), None);
+        assert_eq!(SystemctlClient::<MockCommandExecutor>::parse_condition_failed(""), None);
+    }
+
+    #[tokio::test]
+    async fn test_unit_exists() {
+        async fn exists_with(load_state: &str) -> bool {
+            let mock = MockCommandExecutor::new();
+            mock.expect("systemctl show backup.timer --property=LoadState", CommandOutput {
+                stdout: format!("LoadState={}\n", load_state),
+                stderr: String::new(),
+                exit_code: 0,
+            });
+            SystemctlClient::new(mock).unit_exists("backup.timer").await
+        }
+
+        assert!(exists_with("loaded").await);
+        assert!(!exists_with("not-found").await);
+        // Masked units have a unit file; starting one reports the mask instead
+        assert!(exists_with("masked").await);
+
+        // An unanswered query doesn't block the action
+        assert!(SystemctlClient::new(MockCommandExecutor::new()).unit_exists("backup.timer").await);
+        assert!(!SystemctlClient::new(MockCommandExecutor::new()).unit_exists("bad;name.timer").await);
+    }
+
     #[tokio::test]
     async fn test_run_timer_production() {
         let mock = MockCommandExecutor::new();
@@ -570,4 +1420,171 @@ mod tests {
         let result = client.enable_timer("test.timer").await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_enable_timer_start_fails_rolls_back() {
+        let mock = MockCommandExecutor::new();
+        mock.expect("systemctl enable test.timer", CommandOutput {
+            stdout: String::new(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+        mock.expect("systemctl start test.timer", CommandOutput {
+            stdout: String::new(),
+            stderr: "Job for test.timer failed".to_string(),
+            exit_code: 1,
+        });
+        mock.expect("systemctl disable test.timer", CommandOutput {
+            stdout: String::new(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+
+        let client = SystemctlClient::new(mock);
+        let steps = client.enable_timer("test.timer").await.unwrap();
+        assert!(!steps.started);
+        assert!(!steps.enabled);
+        assert!(steps.rolled_back);
+        assert!(steps.error.unwrap().contains("Job for test.timer failed"));
+    }
+
+    #[tokio::test]
+    async fn test_disable_timer_disable_fails_reports_partial_state() {
+        let mock = MockCommandExecutor::new();
+        mock.expect("systemctl stop test.timer", CommandOutput {
+            stdout: String::new(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+        mock.expect("systemctl disable test.timer", CommandOutput {
+            stdout: String::new(),
+            stderr: "Failed to disable unit".to_string(),
+            exit_code: 1,
+        });
+        // No response for the rollback start: it fails and the partial state stands
+
+        let client = SystemctlClient::new(mock);
+        let steps = client.disable_timer("test.timer").await.unwrap();
+        assert!(steps.stopped);
+        assert!(!steps.disabled);
+        assert!(!steps.rolled_back);
+        assert!(steps.error.unwrap().contains("Failed to disable unit"));
+    }
+
+    #[tokio::test]
+    async fn test_enable_timer_already_enabled_is_noop() {
+        let mock = MockCommandExecutor::new();
+        // No enable/start responses: issuing them would fail the test
+        mock.expect("systemctl show test.timer --property=UnitFileState,ActiveState", CommandOutput {
+            stdout: "UnitFileState=enabled\nActiveState=active\n".to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+
+        let client = SystemctlClient::new(mock);
+        let steps = client.enable_timer("test.timer").await.unwrap();
+        assert!(!steps.changed);
+        assert!(steps.enabled && steps.started);
+        assert!(steps.error.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_enable_timer_only_starts_when_already_enabled() {
+        let mock = MockCommandExecutor::new();
+        mock.expect("systemctl show test.timer --property=UnitFileState,ActiveState", CommandOutput {
+            stdout: "UnitFileState=enabled\nActiveState=inactive\n".to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+        mock.expect("systemctl start test.timer", CommandOutput {
+            stdout: String::new(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+
+        let client = SystemctlClient::new(mock);
+        let steps = client.enable_timer("test.timer").await.unwrap();
+        assert!(steps.changed);
+        assert!(steps.started);
+    }
+
+    #[tokio::test]
+    async fn test_disable_timer_already_disabled_is_noop() {
+        let mock = MockCommandExecutor::new();
+        mock.expect("systemctl show test.timer --property=UnitFileState,ActiveState", CommandOutput {
+            stdout: "UnitFileState=disabled\nActiveState=inactive\n".to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        });
+
+        let client = SystemctlClient::new(mock);
+        let steps = client.disable_timer("test.timer").await.unwrap();
+        assert!(!steps.changed);
+        assert!(steps.stopped && steps.disabled);
+    }
+
+    #[tokio::test]
+    async fn test_get_timer_info_24h_clock() {
+        let output = CommandOutput {
+            stdout: "Id=work.timer\nLoadState=loaded\nUnitFileState=enabled\nActiveState=active\nTimersCalendar={ OnCalendar=Mon-Fri 08-21:00 ; next_elapse=n/a }\n".to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+        };
+        let key = "systemctl show work.timer work.service --property=Id,Description,LoadState,UnitFileState,ActiveState,NextElapseUSecRealtime,LastTriggerUSec,TimersCalendar,TimersMonotonic,FragmentPath,DropInPaths,ConditionResult,ConditionTimestamp";
+
+        let mock = MockCommandExecutor::new();
+        mock.expect(key, output.clone());
+        let info = SystemctlClient::new(mock).get_timer_info("work.timer").await.unwrap();
+        assert_eq!(info.schedule, "Mon-Fri, 8 AM - 9 PM");
+
+        let mock = MockCommandExecutor::new();
+        mock.expect(key, output);
+        let info = SystemctlClient::new(mock)
+            .with_24h_clock(true)
+            .get_timer_info("work.timer")
+            .await
+            .unwrap();
+        assert_eq!(info.schedule, "Mon-Fri, 08:00 - 21:00");
+    }
+
+    #[tokio::test]
+    async fn test_get_timer_state() {
+        let mock = MockCommandExecutor::new();
+        mock.expect(
+            "systemctl show backup.timer backup.service --property=LoadState,UnitFileState,ActiveState,NextElapseUSecRealtime,Result,ExecMainStartTimestampMonotonic",
+            CommandOutput {
+                stdout: "LoadState=loaded\nUnitFileState=enabled\nActiveState=active\nNextElapseUSecRealtime=1705324800000000\nResult=success\nExecMainStartTimestampMonotonic=0\n\n\
+                         LoadState=loaded\nUnitFileState=static\nActiveState=inactive\nNextElapseUSecRealtime=\nResult=exit-code\nExecMainStartTimestampMonotonic=123456789\n".to_string(),
+                stderr: String::new(),
+                exit_code: 0,
+            },
+        );
+
+        let state = SystemctlClient::new(mock).get_timer_state("backup.timer").await.unwrap();
+        assert_eq!(state, TimerState {
+            enabled: true,
+            active: true,
+            next_run: Some("1705324800000000".to_string()),
+            last_result: Some("failed".to_string()),
+        });
+    }
+
+    #[tokio::test]
+    async fn test_get_timer_state_never_ran() {
+        let mock = MockCommandExecutor::new();
+        mock.expect(
+            "systemctl show new.timer new.service --property=LoadState,UnitFileState,ActiveState,NextElapseUSecRealtime,Result,ExecMainStartTimestampMonotonic",
+            CommandOutput {
+                stdout: "LoadState=loaded\nUnitFileState=disabled\nActiveState=inactive\nNextElapseUSecRealtime=0\nResult=success\n\n\
+                         LoadState=loaded\nActiveState=inactive\nResult=success\nExecMainStartTimestampMonotonic=0\n".to_string(),
+                stderr: String::new(),
+                exit_code: 0,
+            },
+        );
+
+        let state = SystemctlClient::new(mock).get_timer_state("new.timer").await.unwrap();
+        assert!(!state.enabled && !state.active);
+        assert_eq!(state.next_run, None);
+        assert_eq!(state.last_result, None);
+    }
 }
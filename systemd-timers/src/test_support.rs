@@ -0,0 +1,51 @@
+use crate::command::CommandOutput;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use toru_plugin_api::{PluginKvStore, PluginResult};
+
+/// In-memory KV store for unit tests
+pub struct TestKvStore {
+    data: Mutex<HashMap<String, String>>,
+}
+
+impl TestKvStore {
+    pub fn new() -> Self {
+        Self {
+            data: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with(entries: &[(&str, &str)]) -> Self {
+        let store = Self::new();
+        for (key, value) in entries {
+            store.data.lock().unwrap().insert(key.to_string(), value.to_string());
+        }
+        store
+    }
+}
+
+#[async_trait::async_trait]
+impl PluginKvStore for TestKvStore {
+    async fn get(&self, key: &str) -> PluginResult<Option<String>> {
+        Ok(self.data.lock().unwrap().get(key).cloned())
+    }
+
+    async fn set(&self, key: &str, value: &str) -> PluginResult<()> {
+        self.data.lock().unwrap().insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> PluginResult<()> {
+        self.data.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+/// Output of a command that succeeded silently
+pub fn ok_output() -> CommandOutput {
+    CommandOutput {
+        stdout: String::new(),
+        stderr: String::new(),
+        exit_code: 0,
+    }
+}
@@ -0,0 +1,137 @@
+use crate::clock::{Clock, SystemClock};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+
+/// Parse a timestamp as printed by `systemctl show`
+///
+/// Accepts both raw microseconds since the epoch (`1705323000000000`) and the
+/// formatted form (`Mon 2024-01-15 10:30:00 UTC`). Zero, `n/a` and empty
+/// values mean "never" and yield None. Zone abbreviations other than UTC/GMT
+/// are taken to be the system's local time zone.
+pub fn parse_systemd_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+
+    if value.is_empty() || value == "n/a" || value == "0" {
+        return None;
+    }
+
+    if value.chars().all(|c| c.is_ascii_digit()) {
+        let micros: i64 = value.parse().ok()?;
+        return DateTime::from_timestamp_micros(micros);
+    }
+
+    let mut parts: Vec<&str> = value.split_whitespace().collect();
+
+    // Leading weekday name ("Mon") is redundant with the date
+    if parts.first().is_some_and(|p| p.chars().all(|c| c.is_ascii_alphabetic())) {
+        parts.remove(0);
+    }
+
+    if parts.len() < 2 {
+        return None;
+    }
+
+    // Drop fractional seconds if present
+    let time = parts[1].split('.').next().unwrap_or(parts[1]);
+    let naive = NaiveDateTime::parse_from_str(
+        &format!("{} {}", parts[0], time),
+        "%Y-%m-%d %H:%M:%S",
+    )
+    .ok()?;
+
+    match parts.get(2) {
+        Some(&"UTC") | Some(&"GMT") | None => Some(Utc.from_utc_datetime(&naive)),
+        Some(_) => Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|dt| dt.with_timezone(&Utc)),
+    }
+}
+
+/// Describe a timestamp relative to now: "just now", "3 minutes ago", "in 2 days"
+pub fn humanize_relative_time(time: DateTime<Utc>) -> String {
+    humanize_relative_time_at(time, SystemClock.now())
+}
+
+/// Describe `time` relative to `now`
+///
+/// Anything within 45 seconds either way is "just now". Larger deltas are
+/// rounded down to the biggest whole unit (minutes, hours, days, months of 30
+/// days, years of 365 days), so 119 seconds is "1 minute ago".
+pub fn humanize_relative_time_at(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let delta = now.signed_duration_since(time).num_seconds();
+    let seconds = delta.unsigned_abs();
+
+    if seconds < 45 {
+        return "just now".to_string();
+    }
+
+    let (count, unit) = match seconds {
+        s if s < 60 * 60 => ((s / 60).max(1), "minute"),
+        s if s < 24 * 60 * 60 => (s / (60 * 60), "hour"),
+        s if s < 30 * 24 * 60 * 60 => (s / (24 * 60 * 60), "day"),
+        s if s < 365 * 24 * 60 * 60 => (s / (30 * 24 * 60 * 60), "month"),
+        s => (s / (365 * 24 * 60 * 60), "year"),
+    };
+    let plural = if count == 1 { "" } else { "s" };
+
+    if delta < 0 {
+        format!("in {} {}{}", count, unit, plural)
+    } else {
+        format!("{} {}{} ago", count, unit, plural)
+    }
+}
+
+/// Relative description of a systemd timestamp as of `now`, or None if it means "never"
+pub fn relative_systemd_timestamp_at(value: &str, now: DateTime<Utc>) -> Option<String> {
+    parse_systemd_timestamp(value).map(|time| humanize_relative_time_at(time, now))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_microseconds() {
+        let dt = parse_systemd_timestamp("1705323000000000").unwrap();
+        assert_eq!(dt.to_rfc3339(), "2024-01-15T12:50:00+00:00");
+    }
+
+    #[test]
+    fn test_parse_formatted_utc() {
+        let dt = parse_systemd_timestamp("Mon 2024-01-15 10:30:45 UTC").unwrap();
+        assert_eq!(dt.to_rfc3339(), "2024-01-15T10:30:45+00:00");
+    }
+
+    #[test]
+    fn test_parse_never() {
+        assert!(parse_systemd_timestamp("0").is_none());
+        assert!(parse_systemd_timestamp("n/a").is_none());
+        assert!(parse_systemd_timestamp("").is_none());
+        assert!(parse_systemd_timestamp("garbage").is_none());
+    }
+
+    #[test]
+    fn test_humanize_relative_time() {
+        let now = parse_systemd_timestamp("2024-01-15 12:00:00 UTC").unwrap();
+        let ago = |secs: i64| humanize_relative_time_at(now - chrono::Duration::seconds(secs), now);
+
+        assert_eq!(ago(0), "just now");
+        assert_eq!(ago(44), "just now");
+        assert_eq!(ago(45), "1 minute ago");
+        assert_eq!(ago(3 * 60 + 20), "3 minutes ago");
+        assert_eq!(ago(60 * 60), "1 hour ago");
+        assert_eq!(ago(2 * 24 * 60 * 60 + 5), "2 days ago");
+        assert_eq!(ago(90 * 24 * 60 * 60), "3 months ago");
+        assert_eq!(ago(800 * 24 * 60 * 60), "2 years ago");
+    }
+
+    #[test]
+    fn test_humanize_relative_time_future() {
+        let now = parse_systemd_timestamp("2024-01-15 12:00:00 UTC").unwrap();
+        let ahead = |secs: i64| humanize_relative_time_at(now + chrono::Duration::seconds(secs), now);
+
+        assert_eq!(ahead(10), "just now");
+        assert_eq!(ahead(5 * 60), "in 5 minutes");
+        assert_eq!(ahead(24 * 60 * 60), "in 1 day");
+    }
+}
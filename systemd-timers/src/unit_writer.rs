@@ -0,0 +1,527 @@
+//! Generation of `.service` + `.timer` unit pairs for scheduled jobs created
+//! from the UI
+//!
+//! Every value ends up on a `Key=value` line of a unit file, so anything that
+//! could break out of that line (newlines, a trailing line continuation) is
+//! rejected, and `%` is escaped so systemd doesn't expand it as a specifier.
+
+use crate::calendar::CalendarSpec;
+use crate::error::{TimerError, TimerResult};
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+/// Directory generated units are written to when none is configured
+pub const DEFAULT_UNIT_DIR: &str = "/etc/systemd/system";
+
+/// First line of every generated unit file
+pub const GENERATED_MARKER: &str = "# Generated by the systemd-timers plugin";
+
+/// Longest accepted job name, without the unit suffix
+const MAX_NAME_LEN: usize = 200;
+
+/// Longest accepted description
+const MAX_DESCRIPTION_LEN: usize = 256;
+
+/// A scheduled job: a oneshot service running `command` and a timer firing it
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimerTemplate {
+    /// Job name shared by both units (`backup` -> `backup.service` + `backup.timer`)
+    pub name: String,
+    /// Command line for `ExecStart=`; must start with an absolute path
+    pub command: String,
+    /// `OnCalendar=` expression
+    pub on_calendar: String,
+    pub description: String,
+}
+
+impl TimerTemplate {
+    /// Build and validate a template
+    ///
+    /// `name` may carry a `.timer` suffix; an empty description gets a generic one.
+    pub fn new(name: &str, command: &str, on_calendar: &str, description: Option<&str>) -> TimerResult<Self> {
+        let name = name.strip_suffix(".timer").unwrap_or(name);
+        validate_job_name(name)?;
+
+        let command = command.trim();
+        if command.is_empty() {
+            return Err(TimerError::InvalidInput("Command cannot be empty".to_string()));
+        }
+        validate_line_value("Command", command)?;
+        if !command.starts_with('/') {
+            return Err(TimerError::InvalidInput(
+                "Command must start with an absolute path".to_string()
+            ));
+        }
+
+        let on_calendar = validate_on_calendar(on_calendar)?;
+
+        let description = match description.map(str::trim) {
+            Some(description) if !description.is_empty() => description.to_string(),
+            _ => format!("Scheduled job {}", name),
+        };
+        validate_line_value("Description", &description)?;
+        if description.len() > MAX_DESCRIPTION_LEN {
+            return Err(TimerError::InvalidInput(format!(
+                "Description must be at most {} characters", MAX_DESCRIPTION_LEN
+            )));
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            command: command.to_string(),
+            on_calendar: on_calendar.to_string(),
+            description,
+        })
+    }
+
+    pub fn timer_unit(&self) -> String {
+        format!("{}.timer", self.name)
+    }
+
+    pub fn service_unit(&self) -> String {
+        format!("{}.service", self.name)
+    }
+
+    /// Contents of the `.service` unit
+    pub fn render_service(&self) -> String {
+        format!(
+            "{}\n[Unit]\nDescription={}\n\n[Service]\nType=oneshot\nExecStart={}\n",
+            GENERATED_MARKER,
+            escape_specifiers(&self.description),
+            escape_specifiers(&self.command),
+        )
+    }
+
+    /// Contents of the `.timer` unit
+    pub fn render_timer(&self) -> String {
+        format!(
+            "{}\n[Unit]\nDescription={}\n\n[Timer]\nOnCalendar={}\nPersistent=true\n\n[Install]\nWantedBy=timers.target\n",
+            GENERATED_MARKER,
+            escape_specifiers(&self.description),
+            self.on_calendar,
+        )
+    }
+}
+
+/// Paths of a written unit pair
+#[derive(Debug, Clone, PartialEq)]
+pub struct WrittenUnits {
+    pub service_path: PathBuf,
+    pub timer_path: PathBuf,
+}
+
+/// Writes generated units into one directory
+pub struct UnitWriter {
+    dir: PathBuf,
+}
+
+impl UnitWriter {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Path a unit of the given name has in this directory
+    pub fn unit_path(&self, unit: &str) -> PathBuf {
+        self.dir.join(unit)
+    }
+
+    /// Write both units of a template
+    ///
+    /// Existing files are never overwritten: if either unit already exists
+    /// nothing is written and `AlreadyExists` is returned. A service file
+    /// written before the timer file failed is removed again.
+    pub async fn write(&self, template: &TimerTemplate) -> TimerResult<WrittenUnits> {
+        let service_path = self.unit_path(&template.service_unit());
+        let timer_path = self.unit_path(&template.timer_unit());
+
+        for path in [&service_path, &timer_path] {
+            if tokio::fs::try_exists(path).await? {
+                return Err(TimerError::AlreadyExists(path.display().to_string()));
+            }
+        }
+
+        write_new_file(&service_path, &template.render_service()).await?;
+        if let Err(e) = write_new_file(&timer_path, &template.render_timer()).await {
+            let _ = tokio::fs::remove_file(&service_path).await;
+            return Err(e);
+        }
+
+        Ok(WrittenUnits { service_path, timer_path })
+    }
+
+    /// Remove the units of a generated job
+    ///
+    /// Both files must start with `GENERATED_MARKER`; otherwise nothing is
+    /// removed and `PermissionDenied` is returned, so a unit replaced by hand
+    /// or shipped by a package is never deleted. An already missing file is
+    /// skipped, but if neither exists `NotFound` is returned: there is
+    /// nothing to delete.
+    pub async fn remove_generated(&self, job_name: &str) -> TimerResult<WrittenUnits> {
+        validate_job_name(job_name)?;
+
+        let units = WrittenUnits {
+            service_path: self.unit_path(&format!("{}.service", job_name)),
+            timer_path: self.unit_path(&format!("{}.timer", job_name)),
+        };
+
+        let mut existing = Vec::new();
+        for path in [&units.service_path, &units.timer_path] {
+            let contents = match tokio::fs::read_to_string(path).await {
+                Ok(contents) => contents,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(TimerError::IoError(format!("Failed to read {}: {}", path.display(), e))),
+            };
+
+            if !contents.starts_with(GENERATED_MARKER) {
+                return Err(TimerError::PermissionDenied(format!(
+                    "{} was not generated by this plugin", path.display()
+                )));
+            }
+            existing.push(path);
+        }
+
+        if existing.is_empty() {
+            return Err(TimerError::NotFound(format!(
+                "{} has no unit files in {}", job_name, self.dir.display()
+            )));
+        }
+
+        for path in existing {
+            tokio::fs::remove_file(path).await?;
+        }
+
+        Ok(units)
+    }
+
+    /// Replace the `OnCalendar=` line of a generated job's timer unit
+    ///
+    /// The timer file must carry `GENERATED_MARKER` (`PermissionDenied`
+    /// otherwise). The new contents are written to a temporary file that is
+    /// then renamed over the unit, so a failed write leaves the old schedule.
+    pub async fn rewrite_on_calendar(&self, job_name: &str, on_calendar: &str) -> TimerResult<PathBuf> {
+        validate_job_name(job_name)?;
+        let on_calendar = validate_on_calendar(on_calendar)?;
+
+        let timer_path = self.unit_path(&format!("{}.timer", job_name));
+        let contents = match tokio::fs::read_to_string(&timer_path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(TimerError::NotFound(timer_path.display().to_string()));
+            }
+            Err(e) => return Err(TimerError::IoError(format!("Failed to read {}: {}", timer_path.display(), e))),
+        };
+
+        if !contents.starts_with(GENERATED_MARKER) {
+            return Err(TimerError::PermissionDenied(format!(
+                "{} was not generated by this plugin", timer_path.display()
+            )));
+        }
+
+        let mut replaced = false;
+        let mut rewritten = String::with_capacity(contents.len());
+        for line in contents.lines() {
+            if line.starts_with("OnCalendar=") {
+                // Generated timers have exactly one schedule; drop any extras
+                if !replaced {
+                    rewritten.push_str(&format!("OnCalendar={}\n", on_calendar));
+                    replaced = true;
+                }
+                continue;
+            }
+            rewritten.push_str(line);
+            rewritten.push('\n');
+        }
+
+        if !replaced {
+            return Err(TimerError::ParseError {
+                source: timer_path.display().to_string(),
+                reason: "no OnCalendar= line".to_string(),
+            });
+        }
+
+        let temp_path = self.unit_path(&format!(".{}.timer.tmp", job_name));
+        let _ = tokio::fs::remove_file(&temp_path).await;
+        write_new_file(&temp_path, &rewritten).await?;
+        if let Err(e) = tokio::fs::rename(&temp_path, &timer_path).await {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+            return Err(e.into());
+        }
+
+        Ok(timer_path)
+    }
+
+    /// Remove a written unit pair on a best-effort basis
+    pub async fn remove(&self, units: &WrittenUnits) {
+        for path in [&units.service_path, &units.timer_path] {
+            if let Err(e) = tokio::fs::remove_file(path).await {
+                eprintln!("Failed to remove {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// Create a file that must not exist yet and write `contents` to it
+async fn write_new_file(path: &Path, contents: &str) -> TimerResult<()> {
+    let mut file = tokio::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .await
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::AlreadyExists => TimerError::AlreadyExists(path.display().to_string()),
+            _ => TimerError::IoError(format!("Failed to create {}: {}", path.display(), e)),
+        })?;
+
+    file.write_all(contents.as_bytes()).await?;
+    file.flush().await?;
+    Ok(())
+}
+
+/// Validate a job name usable as a unit name prefix
+fn validate_job_name(name: &str) -> TimerResult<()> {
+    if name.is_empty() {
+        return Err(TimerError::InvalidInput("Timer name cannot be empty".to_string()));
+    }
+
+    if name.len() > MAX_NAME_LEN {
+        return Err(TimerError::InvalidInput(format!(
+            "Timer name must be at most {} characters", MAX_NAME_LEN
+        )));
+    }
+
+    if name.starts_with(['.', '-'])
+        || !name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'))
+    {
+        return Err(TimerError::InvalidInput(
+            "Timer name may only contain letters, digits, '-', '_', '.' and ':'".to_string()
+        ));
+    }
+
+    if name.ends_with(".service") {
+        return Err(TimerError::InvalidInput(
+            "Timer name must not end with .service".to_string()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validate an `OnCalendar=` expression with the calendar parser, returning it trimmed
+pub fn validate_on_calendar(on_calendar: &str) -> TimerResult<&str> {
+    let on_calendar = on_calendar.trim();
+    validate_line_value("OnCalendar", on_calendar)?;
+    CalendarSpec::parse(on_calendar)?;
+    Ok(on_calendar)
+}
+
+/// Reject values that would not stay on their own `Key=value` line
+fn validate_line_value(field: &str, value: &str) -> TimerResult<()> {
+    if value.contains(['\n', '\r', '\0']) || value.ends_with('\\') {
+        return Err(TimerError::InvalidInput(format!(
+            "{} must be a single line", field
+        )));
+    }
+
+    Ok(())
+}
+
+/// Escape `%` so systemd doesn't treat it as a specifier
+fn escape_specifiers(value: &str) -> String {
+    value.replace('%', "%%")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template() -> TimerTemplate {
+        TimerTemplate::new(
+            "backup-db",
+            "/usr/local/bin/backup --target /srv/backups",
+            "*-*-* 03:00:00",
+            Some("Nightly database backup"),
+        )
+        .unwrap()
+    }
+
+    /// A fresh, empty directory under the system temp dir
+    fn temp_unit_dir(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("unit-writer-{}-{}", test, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_render_service() {
+        assert_eq!(
+            template().render_service(),
+            "# Generated by the systemd-timers plugin\n\
+             [Unit]\n\
+             Description=Nightly database backup\n\
+             \n\
+             [Service]\n\
+             Type=oneshot\n\
+             ExecStart=/usr/local/bin/backup --target /srv/backups\n"
+        );
+    }
+
+    #[test]
+    fn test_render_timer() {
+        assert_eq!(
+            template().render_timer(),
+            "# Generated by the systemd-timers plugin\n\
+             [Unit]\n\
+             Description=Nightly database backup\n\
+             \n\
+             [Timer]\n\
+             OnCalendar=*-*-* 03:00:00\n\
+             Persistent=true\n\
+             \n\
+             [Install]\n\
+             WantedBy=timers.target\n"
+        );
+    }
+
+    #[test]
+    fn test_template_names_and_defaults() {
+        let template = TimerTemplate::new("report.timer", "/usr/bin/report", "daily", None).unwrap();
+        assert_eq!(template.timer_unit(), "report.timer");
+        assert_eq!(template.service_unit(), "report.service");
+        assert_eq!(template.description, "Scheduled job report");
+    }
+
+    #[test]
+    fn test_percent_is_escaped() {
+        let template = TimerTemplate::new("stamp", "/bin/date +%F", "hourly", Some("100% fresh")).unwrap();
+        assert!(template.render_service().contains("ExecStart=/bin/date +%%F\n"));
+        assert!(template.render_service().contains("Description=100%% fresh\n"));
+    }
+
+    #[test]
+    fn test_invalid_calendar_rejected() {
+        for on_calendar in ["every tuesday", "*-*-* 25:00:00", ""] {
+            assert!(
+                TimerTemplate::new("job", "/bin/true", on_calendar, None).is_err(),
+                "{:?} should be rejected", on_calendar
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_fields_rejected() {
+        assert!(TimerTemplate::new("", "/bin/true", "daily", None).is_err());
+        assert!(TimerTemplate::new("../etc/passwd", "/bin/true", "daily", None).is_err());
+        assert!(TimerTemplate::new("job.service", "/bin/true", "daily", None).is_err());
+        assert!(TimerTemplate::new("job", "", "daily", None).is_err());
+        assert!(TimerTemplate::new("job", "true", "daily", None).is_err());
+        assert!(TimerTemplate::new("job", "/bin/true\nExecStartPost=/bin/sh", "daily", None).is_err());
+        assert!(TimerTemplate::new("job", "/bin/true \\", "daily", None).is_err());
+        assert!(TimerTemplate::new("job", "/bin/true", "daily\n[Install]", None).is_err());
+        assert!(TimerTemplate::new("job", "/bin/true", "daily", Some("two\nlines")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_write_creates_both_units() {
+        let dir = temp_unit_dir("write");
+        let writer = UnitWriter::new(&dir);
+
+        let written = writer.write(&template()).await.unwrap();
+        assert_eq!(written.service_path, dir.join("backup-db.service"));
+        assert_eq!(written.timer_path, dir.join("backup-db.timer"));
+        assert_eq!(std::fs::read_to_string(&written.service_path).unwrap(), template().render_service());
+        assert_eq!(std::fs::read_to_string(&written.timer_path).unwrap(), template().render_timer());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_remove_generated_units() {
+        let dir = temp_unit_dir("remove");
+        let writer = UnitWriter::new(&dir);
+        writer.write(&template()).await.unwrap();
+
+        writer.remove_generated("backup-db").await.unwrap();
+        assert!(!dir.join("backup-db.service").exists());
+        assert!(!dir.join("backup-db.timer").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_remove_reports_missing_units() {
+        let dir = temp_unit_dir("remove-missing");
+        let writer = UnitWriter::new(&dir);
+
+        let result = writer.remove_generated("backup-db").await;
+        assert!(matches!(result, Err(TimerError::NotFound(_))));
+
+        // One file left behind is still cleaned up
+        writer.write(&template()).await.unwrap();
+        std::fs::remove_file(dir.join("backup-db.service")).unwrap();
+        writer.remove_generated("backup-db").await.unwrap();
+        assert!(!dir.join("backup-db.timer").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_remove_refuses_units_without_marker() {
+        let dir = temp_unit_dir("remove-foreign");
+        let writer = UnitWriter::new(&dir);
+        writer.write(&template()).await.unwrap();
+        std::fs::write(dir.join("backup-db.service"), "[Service]\nExecStart=/bin/true\n").unwrap();
+
+        let result = writer.remove_generated("backup-db").await;
+        assert!(matches!(result, Err(TimerError::PermissionDenied(_))));
+        assert!(dir.join("backup-db.service").exists());
+        assert!(dir.join("backup-db.timer").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rewrite_on_calendar() {
+        let dir = temp_unit_dir("rewrite");
+        let writer = UnitWriter::new(&dir);
+        writer.write(&template()).await.unwrap();
+
+        writer.rewrite_on_calendar("backup-db", " Mon *-*-* 04:30:00 ").await.unwrap();
+
+        let expected = template().render_timer().replace("*-*-* 03:00:00", "Mon *-*-* 04:30:00");
+        assert_eq!(std::fs::read_to_string(dir.join("backup-db.timer")).unwrap(), expected);
+        assert!(!dir.join(".backup-db.timer.tmp").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rewrite_on_calendar_rejects_invalid_and_foreign() {
+        let dir = temp_unit_dir("rewrite-invalid");
+        let writer = UnitWriter::new(&dir);
+        writer.write(&template()).await.unwrap();
+
+        assert!(writer.rewrite_on_calendar("backup-db", "whenever").await.is_err());
+        assert_eq!(std::fs::read_to_string(dir.join("backup-db.timer")).unwrap(), template().render_timer());
+
+        std::fs::write(dir.join("other.timer"), "[Timer]\nOnCalendar=daily\n").unwrap();
+        let result = writer.rewrite_on_calendar("other", "weekly").await;
+        assert!(matches!(result, Err(TimerError::PermissionDenied(_))));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_write_refuses_to_overwrite() {
+        let dir = temp_unit_dir("overwrite");
+        std::fs::write(dir.join("backup-db.timer"), "[Timer]\n").unwrap();
+        let writer = UnitWriter::new(&dir);
+
+        let result = writer.write(&template()).await;
+        assert!(matches!(result, Err(TimerError::AlreadyExists(_))));
+        assert!(!dir.join("backup-db.service").exists());
+        assert_eq!(std::fs::read_to_string(dir.join("backup-db.timer")).unwrap(), "[Timer]\n");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,127 @@
+// End-to-end harness: runs the plugin binary on a private socket and talks
+// to it through `PluginProtocol`, the way TSC does
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+use tokio::net::UnixStream;
+use tokio::process::{Child, Command};
+use toru_plugin_api::{HttpRequest, HttpResponse, InitPayload, Message, MessagePayload, PluginProtocol};
+
+/// How long to wait for the plugin to bind its socket or answer a message
+pub const HARNESS_TIMEOUT: Duration = Duration::from_secs(10);
+
+static NEXT_SOCKET: AtomicU32 = AtomicU32::new(0);
+
+/// A running plugin process and a connection to it
+pub struct PluginHarness {
+    _child: Child,
+    socket_path: PathBuf,
+    stream: UnixStream,
+    protocol: PluginProtocol,
+    next_request: u32,
+}
+
+impl PluginHarness {
+    /// Start the plugin binary listening on a fresh socket and connect to it
+    pub async fn start() -> Self {
+        let socket_path = std::env::temp_dir().join(format!(
+            "systemd-timers-test-{}-{}.sock",
+            std::process::id(),
+            NEXT_SOCKET.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let child = Command::new(env!("CARGO_BIN_EXE_systemd-timers"))
+            .env("TORU_PLUGIN_SOCKET", &socket_path)
+            .kill_on_drop(true)
+            .spawn()
+            .expect("Failed to start plugin binary");
+
+        // The plugin removes a stale socket before binding, so wait until connecting works
+        let stream = tokio::time::timeout(HARNESS_TIMEOUT, async {
+            loop {
+                match UnixStream::connect(&socket_path).await {
+                    Ok(stream) => break stream,
+                    Err(_) => tokio::time::sleep(Duration::from_millis(20)).await,
+                }
+            }
+        })
+        .await
+        .expect("Plugin did not start listening");
+
+        Self {
+            _child: child,
+            socket_path,
+            stream,
+            protocol: PluginProtocol::new(),
+            next_request: 0,
+        }
+    }
+
+    /// Send the init lifecycle message; the plugin doesn't answer it
+    pub async fn init(&mut self, instance_id: &str) {
+        let message = Message {
+            message_type: "lifecycle".to_string(),
+            payload: MessagePayload::Lifecycle {
+                action: "init".to_string(),
+                payload: Some(InitPayload { instance_id: instance_id.to_string() }),
+            },
+        };
+        self.protocol
+            .write_message(&mut self.stream, &message)
+            .await
+            .expect("Failed to send init");
+    }
+
+    /// Send an HTTP message and decode the response frame
+    ///
+    /// Returns the request id sent along with the response so callers can
+    /// check the plugin echoed the right one.
+    pub async fn request(&mut self, method: &str, path: &str) -> (String, String, HttpResponse) {
+        self.next_request += 1;
+        let request_id = format!("req-{}", self.next_request);
+
+        let message = Message::new_http(
+            request_id.clone(),
+            HttpRequest {
+                method: method.to_string(),
+                path: path.to_string(),
+                headers: HashMap::new(),
+                body: None,
+            },
+        );
+        self.protocol
+            .write_message(&mut self.stream, &message)
+            .await
+            .expect("Failed to send request");
+
+        let reply = tokio::time::timeout(HARNESS_TIMEOUT, self.protocol.read_message(&mut self.stream))
+            .await
+            .expect("Plugin did not answer")
+            .expect("Failed to read response frame");
+
+        let (reply_id, frame) = match reply.payload {
+            MessagePayload::Http { request_id, payload } => (request_id, payload),
+            other => panic!("Expected an HTTP response, got {:?}", other),
+        };
+        assert_eq!(frame.method, "RESPONSE");
+
+        let body: serde_json::Value =
+            serde_json::from_str(frame.body.as_deref().expect("Response frame without body"))
+                .expect("Response frame body is not JSON");
+        let response = HttpResponse {
+            status: body["status"].as_u64().expect("Response without status") as u16,
+            headers: serde_json::from_value(body["headers"].clone()).unwrap_or_default(),
+            body: body["body"].as_str().map(str::to_string),
+        };
+
+        (request_id, reply_id, response)
+    }
+}
+
+impl Drop for PluginHarness {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.socket_path);
+    }
+}
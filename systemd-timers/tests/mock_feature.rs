@@ -0,0 +1,26 @@
+// Checks the mock executor is usable from outside the crate with the `mock` feature
+#![cfg(feature = "mock")]
+
+use systemd_timers::command::mock::MockCommandExecutor;
+use systemd_timers::command::CommandOutput;
+use systemd_timers::systemctl::SystemctlClient;
+
+fn load_state(state: &str) -> CommandOutput {
+    CommandOutput {
+        stdout: format!("LoadState={}\n", state),
+        stderr: String::new(),
+        exit_code: 0,
+    }
+}
+
+#[tokio::test]
+async fn test_mock_executor_drives_client() {
+    let mock = std::sync::Arc::new(MockCommandExecutor::default());
+    mock.expect("systemctl show backup.timer --property=LoadState", load_state("loaded"));
+    mock.expect("systemctl show gone.timer --property=LoadState", load_state("not-found"));
+
+    let client = SystemctlClient::new(mock.clone());
+    assert!(client.unit_exists("backup.timer").await);
+    assert!(!client.unit_exists("gone.timer").await);
+    assert_eq!(mock.calls().len(), 2);
+}
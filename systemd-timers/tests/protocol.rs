@@ -0,0 +1,30 @@
+// Drives the plugin's message loop over its unix socket
+
+mod common;
+
+use common::PluginHarness;
+
+#[tokio::test]
+async fn test_info_request_over_socket() {
+    let mut plugin = PluginHarness::start().await;
+    plugin.init("protocol-test").await;
+
+    let (request_id, reply_id, response) = plugin.request("GET", "/").await;
+
+    assert_eq!(reply_id, request_id);
+    assert_eq!(response.status, 200);
+    assert_eq!(response.headers["Content-Type"], "application/json");
+
+    let info: serde_json::Value = serde_json::from_str(&response.body.unwrap()).unwrap();
+    assert_eq!(info["plugin"], "systemd-timers");
+    assert_eq!(info["version"], env!("CARGO_PKG_VERSION"));
+}
+
+#[tokio::test]
+async fn test_unknown_route_over_socket() {
+    let mut plugin = PluginHarness::start().await;
+    plugin.init("protocol-test").await;
+
+    let (_, _, response) = plugin.request("GET", "/no-such-route").await;
+    assert_eq!(response.status, 404);
+}